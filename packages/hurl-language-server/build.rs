@@ -3,10 +3,10 @@ use std::env::consts::{ARCH, OS};
 use std::process::Command;
 
 #[cfg(debug_assertions)]
-const BUILD_TYPE: &'static str = "debug";
+const BUILD_TYPE: &str = "debug";
 
 #[cfg(not(debug_assertions))]
-const BUILD_TYPE: &'static str = "release";
+const BUILD_TYPE: &str = "release";
 
 fn main() {
     //Create the version string
@@ -0,0 +1,225 @@
+use hurl_parser::parser::types::Ast;
+use ropey::Rope;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, NumberOrString};
+
+use crate::utils::{byte_span_to_range, PositionEncoding};
+use crate::variables::{definition, occurrences, Occurrence, OccurrenceKind};
+use crate::variables_file::ExternalVariables;
+
+/// The code of the diagnostic for a variable that has no value where it is used
+pub const UNDEFINED_VARIABLE: &str = "W0001";
+/// The code of the diagnostic for a capture whose value is never read
+pub const UNUSED_CAPTURE: &str = "W0002";
+
+/// The diagnostics of the variables of the document:
+/// - A variable used by a template or a `variable` query must be captured or set by a
+///   `variable` option before it is used, or come from the variables files or the
+///   environment.
+/// - A captured value should be read afterwards by a template or a `variable` query.
+///
+/// Undefined variables are warnings rather than errors since they can still be passed
+/// with `--variable` when running hurl.
+pub fn variable_diagnostics(
+    rope: &Rope,
+    ast: &Ast,
+    variables: &ExternalVariables,
+    encoding: PositionEncoding,
+) -> Vec<Diagnostic> {
+    let occurrences = occurrences(ast);
+    let mut diagnostics = vec![];
+    for occurrence in &occurrences {
+        let diagnostic = if occurrence.kind.is_definition() {
+            unused_capture(&occurrences, occurrence)
+        } else {
+            undefined_variable(&occurrences, occurrence, variables)
+        };
+        let Some((code, message)) = diagnostic else {
+            continue;
+        };
+        let Some(range) = byte_span_to_range(occurrence.span, rope, encoding) else {
+            continue;
+        };
+        let mut diagnostic = Diagnostic::new(
+            range,
+            Some(DiagnosticSeverity::WARNING),
+            Some(NumberOrString::String(code.to_string())),
+            Some("hurl".to_string()),
+            message,
+            None,
+            None,
+        );
+        if code == UNUSED_CAPTURE {
+            diagnostic.tags = Some(vec![DiagnosticTag::UNNECESSARY]);
+        }
+        diagnostics.push(diagnostic);
+    }
+    diagnostics
+}
+
+fn undefined_variable(
+    occurrences: &[Occurrence],
+    occurrence: &Occurrence,
+    variables: &ExternalVariables,
+) -> Option<(&'static str, String)> {
+    if definition(occurrences, occurrence).is_some() || variables.get(&occurrence.name).is_some() {
+        return None;
+    }
+    let defined_later = occurrences.iter().any(|candidate| {
+        candidate.kind.is_definition()
+            && candidate.name == occurrence.name
+            && candidate.scope > occurrence.scope
+    });
+    let message = if defined_later {
+        format!(
+            "Variable `{}` is used before it is defined",
+            occurrence.name
+        )
+    } else {
+        format!("Undefined variable `{}`", occurrence.name)
+    };
+    Some((UNDEFINED_VARIABLE, message))
+}
+
+/// A capture is unused when no template or `variable` query reads its value before the
+/// variable is captured again
+fn unused_capture(
+    occurrences: &[Occurrence],
+    capture: &Occurrence,
+) -> Option<(&'static str, String)> {
+    if capture.kind != OccurrenceKind::Capture {
+        return None;
+    }
+    let used = occurrences.iter().any(|occurrence| {
+        !occurrence.kind.is_definition() && definition(occurrences, occurrence) == Some(capture)
+    });
+    (!used).then(|| {
+        (
+            UNUSED_CAPTURE,
+            format!("Captured variable `{}` is never used", capture.name),
+        )
+    })
+}
+
+#[cfg(test)]
+mod analysis_tests {
+    use super::*;
+    use crate::test_utils::range;
+    use crate::variables_file::VariablesFile;
+    use hurl_parser::parser::parser::parse_ast;
+    use std::path::PathBuf;
+    use tower_lsp::lsp_types::Range;
+
+    fn diagnostics(test_str: &str, variables: &ExternalVariables) -> Vec<(Range, String)> {
+        let (ast, _) = parse_ast(test_str);
+        variable_diagnostics(
+            &Rope::from_str(test_str),
+            &ast.unwrap(),
+            variables,
+            PositionEncoding::Utf16,
+        )
+        .into_iter()
+        .map(|diagnostic| (diagnostic.range, diagnostic.message))
+        .collect()
+    }
+
+    #[test]
+    fn it_reports_undefined_variables() {
+        let test_str = r#"GET https://{{host}}/{{token}}/{{newUuid}}
+[Options]
+variable: page=1
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+[Asserts]
+variable "page" == 1
+variable "user" exists
+
+GET https://{{host}}/{{token}}?page={{page}}
+"#;
+        let variables = ExternalVariables {
+            environment: vec![],
+            files: vec![VariablesFile::parse(
+                PathBuf::from("vars.env"),
+                "user=bob\n",
+            )],
+        };
+        assert_eq!(
+            diagnostics(test_str, &variables),
+            vec![
+                (range(0, 14, 18), "Undefined variable `host`".to_string()),
+                (
+                    range(0, 23, 28),
+                    "Variable `token` is used before it is defined".to_string()
+                ),
+                (range(10, 14, 18), "Undefined variable `host`".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_variables_used_before_their_capture() {
+        let test_str = r#"GET https://example.org/{{id}}
+
+GET https://example.org
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+[Asserts]
+variable "id" exists
+"#;
+        assert_eq!(
+            diagnostics(test_str, &ExternalVariables::default()),
+            vec![(
+                range(0, 26, 28),
+                "Variable `id` is used before it is defined".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn it_reports_unused_captures() {
+        let test_str = r#"GET https://example.org
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+token: jsonpath "$.token"
+csrf: xpath "string(//input/@value)"
+
+GET https://example.org/{{token}}
+HTTP 200
+[Captures]
+id: jsonpath "$.next"
+[Asserts]
+variable "id" exists
+"#;
+        let (ast, _) = parse_ast(test_str);
+        let diagnostics = variable_diagnostics(
+            &Rope::from_str(test_str),
+            &ast.unwrap(),
+            &ExternalVariables::default(),
+            PositionEncoding::Utf16,
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (
+                    diagnostic.range,
+                    diagnostic.message.as_str(),
+                    diagnostic.tags.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    range(3, 0, 2),
+                    "Captured variable `id` is never used",
+                    Some(vec![DiagnosticTag::UNNECESSARY])
+                ),
+                (
+                    range(5, 0, 4),
+                    "Captured variable `csrf` is never used",
+                    Some(vec![DiagnosticTag::UNNECESSARY])
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use hurl_parser::parser::types::Span;
+use hurl_parser::ParseError;
+use ropey::Rope;
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, NumberOrString, Range, TextEdit,
+    Url, WorkspaceEdit,
+};
+
+use crate::sections::{suggest_section, SectionSpelling, SECTION_SPELLING, UNKNOWN_SECTION};
+use crate::utils::{byte_span_to_range, position_to_byte_offset, PositionEncoding};
+
+/// The quick fixes of the diagnostics the client sends with the code action request
+pub fn code_actions(
+    uri: &Url,
+    rope: &Rope,
+    diagnostics: &[Diagnostic],
+    spelling: Option<SectionSpelling>,
+    encoding: PositionEncoding,
+) -> Vec<CodeActionOrCommand> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.source.as_deref() == Some("hurl"))
+        .filter_map(|diagnostic| quick_fix(uri, rope, diagnostic, spelling, encoding))
+        .map(CodeActionOrCommand::CodeAction)
+        .collect()
+}
+
+fn quick_fix(
+    uri: &Url,
+    rope: &Rope,
+    diagnostic: &Diagnostic,
+    spelling: Option<SectionSpelling>,
+    encoding: PositionEncoding,
+) -> Option<CodeAction> {
+    let Some(NumberOrString::String(code)) = &diagnostic.code else {
+        return None;
+    };
+    let start = position_to_byte_offset(diagnostic.range.start, rope, encoding)?;
+    let end = position_to_byte_offset(diagnostic.range.end, rope, encoding)?;
+    let text = rope.get_byte_slice(start..end)?.to_string();
+    match code.as_str() {
+        ParseError::INVALID_METHOD_CASE => {
+            let method = text.to_ascii_uppercase();
+            Some(replace(
+                uri,
+                diagnostic,
+                format!("Convert `{}` to `{}`", text, method),
+                diagnostic.range,
+                method,
+            ))
+        }
+        ParseError::MISSING_STATUS => {
+            let spaced = start > 0 && rope.byte(start - 1) == b' ';
+            let status = if spaced { "200" } else { " 200" };
+            Some(replace(
+                uri,
+                diagnostic,
+                "Add status `200`".to_string(),
+                diagnostic.range,
+                status.to_string(),
+            ))
+        }
+        UNKNOWN_SECTION => {
+            let name = text.strip_prefix('[')?.strip_suffix(']')?;
+            let replacement = format!("[{}]", suggest_section(name)?);
+            Some(replace(
+                uri,
+                diagnostic,
+                format!("Replace with `{}`", replacement),
+                diagnostic.range,
+                replacement,
+            ))
+        }
+        SECTION_SPELLING => {
+            let name = text.strip_prefix('[')?.strip_suffix(']')?;
+            let replacement = format!("[{}]", spelling?.respell(name)?);
+            Some(replace(
+                uri,
+                diagnostic,
+                format!("Rename to `{}`", replacement),
+                diagnostic.range,
+                replacement,
+            ))
+        }
+        //A value that does not parse in an unquoted variable value is fixed by making it a
+        //string
+        ParseError::UNEXPECTED | ParseError::UNEXPECTED_CHAR | ParseError::INVALID_FLOAT => {
+            let line = rope.byte_to_line(start);
+            let line_start = rope.line_to_byte(line);
+            let text = rope.line(line).to_string();
+            let (name, value, quoted) = quote_variable_value(&text)?;
+            let value = Span::new(line_start + value.start, line_start + value.end);
+            if start < value.start || value.end < start {
+                return None;
+            }
+            Some(replace(
+                uri,
+                diagnostic,
+                format!("Wrap the value of `{}` in quotes", name),
+                byte_span_to_range(value, rope, encoding)?,
+                quoted,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The name of the variable of a `variable: name=value` option line with the span of its
+/// unquoted value in the line and the value as a quoted string
+fn quote_variable_value(line: &str) -> Option<(&str, Span, String)> {
+    let option = line.trim_start_matches([' ', '\t']);
+    let rest = option
+        .strip_prefix("variable")?
+        .trim_start_matches([' ', '\t']);
+    let rest = rest.strip_prefix(':')?.trim_start_matches([' ', '\t']);
+    let (name, value) = rest.split_once('=')?;
+    let value = value.trim_start_matches([' ', '\t']);
+    let start = line.len() - value.len();
+    //The value ends at a comment or the end of the line
+    let value = value.split('#').next()?.trim_end();
+    if value.is_empty() || value.starts_with('"') {
+        return None;
+    }
+    let quoted = format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    Some((
+        name.trim_end(),
+        Span::new(start, start + value.len()),
+        quoted,
+    ))
+}
+
+/// A quick fix replacing a range of the document
+fn replace(
+    uri: &Url,
+    diagnostic: &Diagnostic,
+    title: String,
+    range: Range,
+    new_text: String,
+) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(
+                uri.clone(),
+                vec![TextEdit::new(range, new_text)],
+            )])),
+            ..WorkspaceEdit::default()
+        }),
+        is_preferred: Some(true),
+        ..CodeAction::default()
+    }
+}
+
+#[cfg(test)]
+mod code_actions_tests {
+    use super::*;
+    use crate::analysis::UNDEFINED_VARIABLE;
+    use crate::sections::section_diagnostics;
+    use crate::test_utils::range;
+    use crate::type_check::TYPE_MISMATCH;
+    use hurl_parser::parser::parser::parse_ast;
+    use tower_lsp::lsp_types::{DiagnosticSeverity, Position};
+
+    /// The diagnostics of the parse errors like the server publishes them
+    fn parse_diagnostics(test_str: &str, rope: &Rope) -> Vec<Diagnostic> {
+        let (_, errs) = parse_ast(test_str);
+        errs.iter()
+            .filter_map(|err| {
+                Some(Diagnostic::new(
+                    byte_span_to_range(err.span(), rope, PositionEncoding::Utf16)?,
+                    Some(DiagnosticSeverity::ERROR),
+                    Some(NumberOrString::String(err.code().to_string())),
+                    Some("hurl".to_string()),
+                    err.message(),
+                    None,
+                    None,
+                ))
+            })
+            .collect()
+    }
+
+    /// The title and edit of the quick fixes of every diagnostic of the document
+    fn quick_fixes(test_str: &str, spelling: Option<SectionSpelling>) -> Vec<(String, TextEdit)> {
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        let rope = Rope::from_str(test_str);
+        let mut diagnostics = parse_diagnostics(test_str, &rope);
+        if let (Some(ast), _) = parse_ast(test_str) {
+            diagnostics.extend(section_diagnostics(
+                &rope,
+                &ast,
+                spelling,
+                PositionEncoding::Utf16,
+            ));
+        }
+        code_actions(&uri, &rope, &diagnostics, spelling, PositionEncoding::Utf16)
+            .into_iter()
+            .map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => {
+                    let mut edits = action.edit.unwrap().changes.unwrap().remove(&uri).unwrap();
+                    assert_eq!(edits.len(), 1);
+                    (action.title, edits.remove(0))
+                }
+                CodeActionOrCommand::Command(command) => panic!("unexpected {:?}", command),
+            })
+            .collect()
+    }
+
+    fn edit(line: u32, start: u32, end: u32, new_text: &str) -> TextEdit {
+        TextEdit::new(
+            Range::new(Position::new(line, start), Position::new(line, end)),
+            new_text.to_string(),
+        )
+    }
+
+    #[test]
+    fn it_uppercases_a_method() {
+        assert_eq!(
+            quick_fixes("get https://example.org\n", None),
+            vec![("Convert `get` to `GET`".to_string(), edit(0, 0, 3, "GET"))]
+        );
+    }
+
+    #[test]
+    fn it_adds_a_missing_status() {
+        assert_eq!(
+            quick_fixes("GET https://example.org\nHTTP\n", None),
+            vec![("Add status `200`".to_string(), edit(1, 4, 4, " 200"))]
+        );
+    }
+
+    #[test]
+    fn it_quotes_an_invalid_variable_value() {
+        let test_str = r#"GET https://example.org
+[Options]
+variable: bad_float=6.{{fraction}} # comment
+"#;
+        assert_eq!(
+            quick_fixes(test_str, None),
+            vec![(
+                "Wrap the value of `bad_float` in quotes".to_string(),
+                edit(2, 20, 34, r#""6.{{fraction}}""#)
+            )]
+        );
+    }
+
+    #[test]
+    fn it_does_not_quote_a_variable_value_for_other_diagnostics() {
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        let rope = Rope::from_str("GET https://example.org\n[Options]\nvariable: n={{count}}\n");
+        let diagnostics: Vec<_> = [UNDEFINED_VARIABLE, TYPE_MISMATCH]
+            .into_iter()
+            .map(|code| Diagnostic {
+                range: range(2, 14, 19),
+                code: Some(NumberOrString::String(code.to_string())),
+                source: Some("hurl".to_string()),
+                ..Diagnostic::default()
+            })
+            .collect();
+        assert_eq!(
+            code_actions(&uri, &rope, &diagnostics, None, PositionEncoding::Utf16),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn it_finds_the_value_of_a_variable_option() {
+        assert_eq!(
+            quote_variable_value("  variable:  name = a \"b\"\\c\n"),
+            Some(("name", Span::new(20, 27), r#""a \"b\"\\c""#.to_string()))
+        );
+        assert_eq!(quote_variable_value(r#"variable: name="a""#), None);
+        assert_eq!(quote_variable_value("verbose: true"), None);
+    }
+
+    #[test]
+    fn it_replaces_an_unknown_section_with_the_suggestion() {
+        let test_str = r#"GET https://example.org
+HTTP 200
+[Capture]
+id: jsonpath "$.id"
+[Whatever]
+foo: bar
+"#;
+        assert_eq!(
+            quick_fixes(test_str, None),
+            vec![(
+                "Replace with `[Captures]`".to_string(),
+                edit(2, 0, 9, "[Captures]")
+            )]
+        );
+    }
+
+    #[test]
+    fn it_renames_a_section_to_the_preferred_spelling() {
+        let test_str = "GET https://example.org\n[Query]\npage: 1\n";
+        assert_eq!(
+            quick_fixes(test_str, Some(SectionSpelling::Long)),
+            vec![(
+                "Rename to `[QueryStringParams]`".to_string(),
+                edit(1, 0, 7, "[QueryStringParams]")
+            )]
+        );
+    }
+}
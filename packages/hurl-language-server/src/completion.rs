@@ -1,8 +1,17 @@
 use std::collections::HashMap;
 
+use hurl_parser::parser::parser::{parse_assert_start, parse_ast};
+use hurl_parser::parser::types::{Ast, Body, RequestSection, ResponseSection, Span};
+
+use crate::docs::{docs_of_kind, Doc, DocKind};
+use crate::variables::occurrences;
+use crate::variables_file::ExternalVariables;
+
 pub enum ImCompleteCompletionItem {
-    Keyword(String),
+    Keyword(String, Option<&'static Doc>),
     Snippet(String, String),
+    /// A variable name with the value it gets from outside of the document if known
+    Variable(String, Option<String>),
 }
 
 pub enum RequestMethods {
@@ -72,75 +81,153 @@ const BYTE_SNIPPETS: [(&str, &str); 3] = [
     ("hex", r#"hex, $1;"#),
 ];
 
-const SECTION_HEADER: [&str; 11] = [
-    "[Asserts]\n",
-    "[Query]\n",
-    "[QueryStringParams]\n",
-    "[BasicAuth]\n",
-    "[Form]\n",
-    "[FormParams]\n",
-    "[Cookies]\n",
-    "[Captures]\n",
-    "[Multipart]\n",
-    "[MultipartFormData]\n",
-    "[Options]\n",
-];
+const HTTP_SNIPPET: (&str, &str) = ("HTTP", "HTTP ${1:200}");
 
-const OPTIONS: [&str; 35] = [
-    "aws-sigv4",
-    "cacert",
-    "cert",
-    "key",
-    "compressed",
-    "connect-to",
-    "connect-timeout",
-    "delay",
-    "location",
-    "location-trusted",
-    "http1.0",
-    "http1.1",
-    "http2",
-    "http3",
-    "insecure",
-    "ipv4",
-    "ipv6",
-    "limit-rate",
-    "max-redirs",
-    "netrc",
-    "netrc-file",
-    "netrc-optional",
-    "output",
-    "path-as-is",
-    "proxy",
-    "repeat",
-    "resolve",
-    "retry",
-    "retry-interval",
-    "skip",
-    "unix-socket",
-    "user",
-    "variable",
-    "verbose",
-    "very-verbose",
-];
+const RESPONSE_SECTIONS: [&str; 2] = ["Captures", "Asserts"];
+
+/// The part of the entry the cursor is in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Area {
+    /// Before the first entry
+    Document,
+    Request,
+    Response,
+    /// Inside the body of a request or a response
+    Body,
+}
 
-pub fn completion() -> HashMap<String, ImCompleteCompletionItem> {
+/// The section the cursor is in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Section {
+    Options,
+    Captures,
+    Asserts,
+    Other,
+}
+
+/// What can be written next on an assert or capture line
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Expect {
+    Query,
+    FilterOrPredicate,
+    Filter,
+    Predicate,
+    Nothing,
+}
+
+/// The completion items that are valid at the offset of the document
+pub fn completion(
+    document: &str,
+    offset: usize,
+    ast: Option<&Ast>,
+    variables: &ExternalVariables,
+) -> HashMap<String, ImCompleteCompletionItem> {
     let mut map = HashMap::new();
+    let line_start = document[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let prefix = &document[line_start..offset];
 
-    for keyword in SECTION_HEADER {
-        map.insert(
-            keyword.to_owned(),
-            ImCompleteCompletionItem::Keyword(keyword.to_owned()),
-        );
+    //The line being written usually breaks the document so fall back to the entries above it
+    let parsed;
+    let ast = match ast {
+        Some(ast) => ast,
+        None => {
+            parsed = parse_ast(&document[..line_start])
+                .0
+                .unwrap_or(Ast { entries: vec![] });
+            &parsed
+        }
+    };
+
+    if in_template(prefix) {
+        let defined_variables = defined_variables(ast, offset);
+        for name in variables.names() {
+            let value = variables
+                .get(name)
+                .map(|variable| variable.value().to_string());
+            map.insert(
+                name.to_string(),
+                ImCompleteCompletionItem::Variable(name.to_string(), value),
+            );
+        }
+        for variable in defined_variables {
+            map.insert(
+                variable.clone(),
+                ImCompleteCompletionItem::Variable(variable, None),
+            );
+        }
+        return map;
+    }
+
+    let (area, section) = context(ast, line_start);
+    let line = prefix.trim_start();
+    if area == Area::Body || line.starts_with('#') {
+        return map;
+    }
+
+    //Asserts and captures are completed word by word
+    let expect = match section {
+        Some(Section::Asserts) => Some(expect_next(line, false)),
+        Some(Section::Captures) => line
+            .split_once(':')
+            .map(|(_, line)| expect_next(line, true)),
+        _ => None,
+    };
+    let at_line_start = !line.contains(char::is_whitespace) && !line.contains(':');
+    match expect {
+        Some(Expect::Query) if !at_line_start || section == Some(Section::Asserts) => {
+            insert_docs(&mut map, DocKind::Query, |_| true)
+        }
+        Some(Expect::FilterOrPredicate) => {
+            insert_docs(&mut map, DocKind::Filter, |_| true);
+            insert_docs(&mut map, DocKind::Predicate, |_| true);
+        }
+        Some(Expect::Filter) => insert_docs(&mut map, DocKind::Filter, |_| true),
+        Some(Expect::Predicate) => {
+            insert_docs(&mut map, DocKind::Predicate, |doc| doc.name != "not")
+        }
+        _ => {}
+    }
+    if !at_line_start {
+        return map;
     }
 
-    for option in OPTIONS {
+    //Only a section header can start with '['
+    if let Some(name) = line.strip_prefix('[') {
+        if name.contains(']') {
+            return map;
+        }
+        let sections = docs_of_kind(DocKind::Section).filter(|doc| match area {
+            Area::Document | Area::Body => false,
+            Area::Request => !RESPONSE_SECTIONS.contains(&doc.name),
+            Area::Response => RESPONSE_SECTIONS.contains(&doc.name),
+        });
+        for doc in sections {
+            let keyword = format!("{}]", doc.name);
+            map.insert(
+                keyword.clone(),
+                ImCompleteCompletionItem::Keyword(keyword, Some(doc)),
+            );
+        }
+        return map;
+    }
+
+    if section == Some(Section::Options) {
+        insert_docs(&mut map, DocKind::Option, |_| true);
+    }
+    if area == Area::Request {
+        let (key, snippet) = HTTP_SNIPPET;
         map.insert(
-            option.to_owned(),
-            ImCompleteCompletionItem::Keyword(option.to_owned()),
+            key.to_owned(),
+            ImCompleteCompletionItem::Snippet(key.to_owned(), snippet.to_owned()),
         );
+        for byte_snippet_tuple in BYTE_SNIPPETS {
+            let (key, snippet) = byte_snippet_tuple;
+            map.insert(
+                key.to_owned(),
+                ImCompleteCompletionItem::Snippet(key.to_owned(), snippet.to_owned()),
+            );
+        }
     }
-
     for method_snippet_tuple in METHOD_SNIPPETS {
         let (method, snippet) = method_snippet_tuple;
         map.insert(
@@ -149,13 +236,281 @@ pub fn completion() -> HashMap<String, ImCompleteCompletionItem> {
         );
     }
 
-    for byte_snippet_tuple in BYTE_SNIPPETS {
-        let (key, snippet) = byte_snippet_tuple;
+    map
+}
+
+fn insert_docs(
+    map: &mut HashMap<String, ImCompleteCompletionItem>,
+    kind: DocKind,
+    filter: impl Fn(&Doc) -> bool,
+) {
+    for doc in docs_of_kind(kind).filter(|doc| filter(doc)) {
         map.insert(
-            key.to_owned(),
-            ImCompleteCompletionItem::Snippet(key.to_owned(), snippet.to_owned()),
+            doc.name.to_owned(),
+            ImCompleteCompletionItem::Keyword(doc.name.to_owned(), Some(doc)),
         );
     }
+}
 
-    map
+/// Whether the text before the cursor opened a template that isn't closed yet
+fn in_template(prefix: &str) -> bool {
+    match (prefix.rfind("{{"), prefix.rfind("}}")) {
+        (Some(open), Some(close)) => open > close,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// Finds the part of the entry and the section the line starting at the offset is in,
+/// from the nodes of the AST that start before it.
+fn context(ast: &Ast, line_start: usize) -> (Area, Option<Section>) {
+    let before = |span: Span| span.start < line_start;
+    let in_body = |body: &Option<Body>| {
+        body.as_ref()
+            .is_some_and(|body| before(body.span) && line_start < body.span.end)
+    };
+    let Some(entry) = ast.entries.iter().rev().find(|entry| before(entry.span)) else {
+        return (Area::Document, None);
+    };
+    if let Some(response) = entry
+        .response
+        .as_ref()
+        .filter(|response| before(response.span))
+    {
+        if in_body(&response.body) {
+            return (Area::Body, None);
+        }
+        let section = response
+            .response_sections
+            .iter()
+            .rev()
+            .find(|section| before(section.span()))
+            .map(|section| match section {
+                ResponseSection::CapturesSection(_) => Section::Captures,
+                ResponseSection::AssertsSection(_) => Section::Asserts,
+                _ => Section::Other,
+            });
+        return (Area::Response, section);
+    }
+    if in_body(&entry.request.body) {
+        return (Area::Body, None);
+    }
+    let section = entry
+        .request
+        .request_sections
+        .iter()
+        .rev()
+        .find(|section| before(section.span()))
+        .map(|section| match section {
+            RequestSection::OptionsSection(_) => Section::Options,
+            _ => Section::Other,
+        });
+    (Area::Request, section)
+}
+
+/// Parses the complete words of an assert or capture to find what can be written next
+fn expect_next(line: &str, capture: bool) -> Expect {
+    //The last word is still being written unless it is followed by a space
+    let complete = &line[..line.rfind(char::is_whitespace).map_or(0, |i| i + 1)];
+    if complete.trim().is_empty() {
+        return Expect::Query;
+    }
+    match parse_assert_start(complete) {
+        Some(start) if start.not && !capture => Expect::Predicate,
+        Some(start) if start.not => Expect::Nothing,
+        Some(_) if capture => Expect::Filter,
+        Some(_) => Expect::FilterOrPredicate,
+        None => Expect::Nothing,
+    }
+}
+
+/// The names of the variables captured or defined with the `variable` option that are
+/// visible at the offset
+fn defined_variables(ast: &Ast, offset: usize) -> Vec<String> {
+    occurrences(ast)
+        .into_iter()
+        .filter(|occurrence| occurrence.kind.is_definition() && occurrence.scope <= offset)
+        .map(|occurrence| occurrence.name)
+        .collect()
+}
+
+#[cfg(test)]
+mod completion_tests {
+    use super::*;
+    use crate::variables_file::{environment_variables, VariablesFile};
+    use std::path::PathBuf;
+
+    /// The sorted labels of the completion items at the `|` of the document
+    fn labels(document: &str) -> Vec<String> {
+        let offset = document.find('|').unwrap();
+        let document = document.replacen('|', "", 1);
+        let (ast, _) = parse_ast(&document);
+        let mut labels = completion(
+            &document,
+            offset,
+            ast.as_ref(),
+            &ExternalVariables::default(),
+        )
+        .into_keys()
+        .collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
+
+    #[test]
+    fn it_completes_methods_at_the_start_of_the_document() {
+        assert_eq!(
+            labels("|"),
+            vec!["CONNECT", "DELETE", "GET", "HEAD", "OPTIONS", "PATCH", "POST", "PUT", "TRACE"]
+        );
+    }
+
+    #[test]
+    fn it_completes_response_sections_after_http_line() {
+        assert_eq!(
+            labels("GET https://example.org\nHTTP 200\n[|"),
+            vec!["Asserts]", "Captures]"]
+        );
+    }
+
+    #[test]
+    fn it_completes_request_sections_after_method_line() {
+        let labels = labels("GET https://example.org\n[|");
+        assert!(labels.contains(&"Options]".to_string()));
+        assert!(labels.contains(&"QueryStringParams]".to_string()));
+        assert!(!labels.contains(&"Asserts]".to_string()));
+    }
+
+    #[test]
+    fn it_completes_options() {
+        let labels = labels("GET https://example.org\n[Options]\nret|");
+        assert!(labels.contains(&"retry-interval".to_string()));
+        assert!(labels.contains(&"HTTP".to_string()));
+        assert!(!labels.contains(&"jsonpath".to_string()));
+    }
+
+    #[test]
+    fn it_completes_queries_at_the_start_of_an_assert() {
+        let labels = labels("GET https://example.org\nHTTP 200\n[Asserts]\njs|");
+        assert!(labels.contains(&"jsonpath".to_string()));
+        assert!(labels.contains(&"GET".to_string()));
+        assert!(!labels.contains(&"nth".to_string()));
+    }
+
+    #[test]
+    fn it_completes_filters_and_predicates_after_a_query() {
+        let labels =
+            labels("GET https://example.org\nHTTP 200\n[Asserts]\njsonpath \"$.a b\" nth 0 |");
+        assert!(labels.contains(&"count".to_string()));
+        assert!(labels.contains(&"==".to_string()));
+        assert!(labels.contains(&"not".to_string()));
+        assert!(!labels.contains(&"GET".to_string()));
+    }
+
+    #[test]
+    fn it_completes_predicates_after_not() {
+        let labels = labels("GET https://example.org\nHTTP 200\n[Asserts]\nbody not |");
+        assert!(labels.contains(&"contains".to_string()));
+        assert!(!labels.contains(&"not".to_string()));
+        assert!(!labels.contains(&"count".to_string()));
+    }
+
+    #[test]
+    fn it_completes_nothing_after_a_predicate() {
+        assert_eq!(
+            labels("GET https://example.org\nHTTP 200\n[Asserts]\nstatus == |"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn it_completes_queries_and_filters_in_captures() {
+        let document = "GET https://example.org\nHTTP 200\n[Captures]\nid: |";
+        assert!(labels(document).contains(&"jsonpath".to_string()));
+        let document = "GET https://example.org\nHTTP 200\n[Captures]\nid: header \"Id\" |";
+        let labels = labels(document);
+        assert!(labels.contains(&"toInt".to_string()));
+        assert!(!labels.contains(&"==".to_string()));
+    }
+
+    #[test]
+    fn it_completes_variables_in_templates() {
+        let document = r#"GET https://example.org
+[Options]
+variable: host=example.net
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+
+GET https://{{|
+HTTP 200
+"#;
+        assert_eq!(labels(document), vec!["host", "token"]);
+    }
+
+    #[test]
+    fn it_only_completes_variables_defined_before_the_cursor() {
+        let document = r#"GET https://{{|}}
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+
+GET https://example.org
+[Options]
+variable: host=example.net
+HTTP 200
+"#;
+        assert_eq!(labels(document), Vec::<String>::new());
+        let document = r#"GET https://example.org
+[Options]
+variable: host=example.net
+HTTP 200
+
+GET https://{{|}}
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+"#;
+        assert_eq!(labels(document), vec!["host"]);
+    }
+
+    #[test]
+    fn it_completes_external_variables_with_their_value() {
+        let document = "GET https://{{\n";
+        let variables = ExternalVariables {
+            environment: environment_variables([("HURL_token".to_string(), "abc".to_string())]),
+            files: vec![VariablesFile::parse(
+                PathBuf::from("vars.env"),
+                "host=localhost\n",
+            )],
+        };
+        let completions = completion(document, document.len() - 1, None, &variables);
+        let mut items: Vec<_> = completions
+            .into_values()
+            .filter_map(|item| match item {
+                ImCompleteCompletionItem::Variable(name, value) => Some((name, value)),
+                _ => None,
+            })
+            .collect();
+        items.sort();
+        assert_eq!(
+            items,
+            vec![
+                ("host".to_string(), Some("localhost".to_string())),
+                ("token".to_string(), Some("abc".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_completes_nothing_in_a_body() {
+        let document = "POST https://example.org\n```\nNOTE uppercase words\n|\n```\nHTTP 200\n";
+        assert_eq!(labels(document), Vec::<String>::new());
+    }
+
+    #[test]
+    fn it_completes_response_sections_after_a_response_body() {
+        let document = "GET https://example.org\nHTTP 200\n```\nNOTE uppercase words\n```\n[|";
+        assert_eq!(labels(document), vec!["Asserts]", "Captures]"]);
+    }
 }
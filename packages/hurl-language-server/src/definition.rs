@@ -0,0 +1,146 @@
+use hurl_parser::parser::types::Ast;
+use ropey::Rope;
+use tower_lsp::lsp_types::{Location, Position, Url};
+
+use crate::utils::{byte_span_to_range, position_to_byte_offset, PositionEncoding};
+use crate::variables::{definition as variable_definition, occurrence_at, occurrences};
+use crate::variables_file::{ExternalVariable, ExternalVariables};
+
+/// The definition of the variable at the position. Variables that are not captured or
+/// defined with a `variable` option in the document are looked up in the variables files,
+/// the last file defining the variable winning like with hurl. Variables of the environment
+/// have no location.
+pub fn definition(
+    uri: &Url,
+    rope: &Rope,
+    ast: &Ast,
+    position: Position,
+    variables: &ExternalVariables,
+    encoding: PositionEncoding,
+) -> Option<Location> {
+    let offset = position_to_byte_offset(position, rope, encoding)?;
+    let occurrences = occurrences(ast);
+    let occurrence = occurrence_at(&occurrences, offset)?;
+    if let Some(definition) = variable_definition(&occurrences, occurrence) {
+        let range = byte_span_to_range(definition.span, rope, encoding)?;
+        return Some(Location::new(uri.clone(), range));
+    }
+    let ExternalVariable::File(file, variable) = variables.get(&occurrence.name)? else {
+        return None;
+    };
+    let uri = Url::from_file_path(&file.path).ok()?;
+    let range = byte_span_to_range(variable.span, &Rope::from_str(&file.text), encoding)?;
+    Some(Location::new(uri, range))
+}
+
+#[cfg(test)]
+mod definition_tests {
+    use super::*;
+    use crate::utils::byte_offset_to_position;
+    use crate::variables_file::VariablesFile;
+    use hurl_parser::parser::parser::parse_ast;
+    use std::path::PathBuf;
+    use tower_lsp::lsp_types::Range;
+
+    fn definition_at(test_str: &str, files: &[VariablesFile]) -> Option<Location> {
+        definition_in(test_str, files, PositionEncoding::Utf16)
+    }
+
+    fn definition_in(
+        test_str: &str,
+        files: &[VariablesFile],
+        encoding: PositionEncoding,
+    ) -> Option<Location> {
+        let offset = test_str.find('|').unwrap();
+        let document = test_str.replacen('|', "", 1);
+        let rope = Rope::from_str(&document);
+        let (ast, _) = parse_ast(&document);
+        let uri = Url::parse("file:///project/test.hurl").unwrap();
+        let position = byte_offset_to_position(offset, &rope, encoding).unwrap();
+        definition(
+            &uri,
+            &rope,
+            &ast.unwrap(),
+            position,
+            &ExternalVariables {
+                environment: vec![],
+                files: files.to_vec(),
+            },
+            encoding,
+        )
+    }
+
+    #[test]
+    fn it_goes_to_the_capture() {
+        let test_str = r#"GET https://example.org
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+
+DELETE https://example.org/{{i|d}}
+"#;
+        assert_eq!(
+            definition_at(test_str, &[]),
+            Some(Location::new(
+                Url::parse("file:///project/test.hurl").unwrap(),
+                Range::new(Position::new(3, 0), Position::new(3, 2))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_goes_to_the_variable_option() {
+        let test_str = r#"GET https://{{|host}}
+[Options]
+variable: host=localhost
+"#;
+        assert_eq!(
+            definition_at(test_str, &[]),
+            Some(Location::new(
+                Url::parse("file:///project/test.hurl").unwrap(),
+                Range::new(Position::new(2, 10), Position::new(2, 14))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_goes_to_the_variables_file() {
+        let test_str = "GET https://{{host|}}\n";
+        let files = [
+            VariablesFile::parse(PathBuf::from("/project/a.env"), "host=a\n"),
+            VariablesFile::parse(PathBuf::from("/project/b.env"), "port=80\nhost=b\n"),
+        ];
+        assert_eq!(
+            definition_at(test_str, &files),
+            Some(Location::new(
+                Url::parse("file:///project/b.env").unwrap(),
+                Range::new(Position::new(1, 0), Position::new(1, 4))
+            ))
+        );
+    }
+
+    #[test]
+    fn it_counts_the_columns_of_the_variables_file_in_the_position_encoding() {
+        let test_str = "GET https://{{host|}}\n";
+        //The ideographic space is 3 UTF-8 bytes and 1 UTF-16 unit
+        let files = [VariablesFile::parse(
+            PathBuf::from("/project/vars.env"),
+            "\u{3000}host=a\n",
+        )];
+        let range = |encoding| definition_in(test_str, &files, encoding).unwrap().range;
+        assert_eq!(
+            range(PositionEncoding::Utf8),
+            Range::new(Position::new(0, 3), Position::new(0, 7))
+        );
+        assert_eq!(
+            range(PositionEncoding::Utf16),
+            Range::new(Position::new(0, 1), Position::new(0, 5))
+        );
+    }
+
+    #[test]
+    fn it_does_not_go_to_functions() {
+        let test_str = "GET https://example.org/{{new|Uuid}}\n";
+        assert_eq!(definition_at(test_str, &[]), None);
+    }
+}
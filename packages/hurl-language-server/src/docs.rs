@@ -0,0 +1,756 @@
+//! Documentation for the hurl keywords shown on hover and in completion items.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DocKind {
+    Section,
+    Query,
+    Filter,
+    Predicate,
+    Option,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Doc {
+    pub kind: DocKind,
+    pub name: &'static str,
+    pub syntax: &'static str,
+    pub description: &'static str,
+    pub example: &'static str,
+}
+
+impl Doc {
+    /// The documentation formatted as markdown
+    pub fn markdown(&self) -> String {
+        format!(
+            "```hurl\n{}\n```\n\n{}\n\nExample:\n\n```hurl\n{}\n```",
+            self.syntax, self.description, self.example
+        )
+    }
+}
+
+/// Finds the documentation of a keyword
+pub fn find_doc(kind: DocKind, name: &str) -> Option<&'static Doc> {
+    DOCS.iter().find(|doc| doc.kind == kind && doc.name == name)
+}
+
+/// All the documented keywords of a kind
+pub fn docs_of_kind(kind: DocKind) -> impl Iterator<Item = &'static Doc> {
+    DOCS.iter().filter(move |doc| doc.kind == kind)
+}
+
+const fn doc(
+    kind: DocKind,
+    name: &'static str,
+    syntax: &'static str,
+    description: &'static str,
+    example: &'static str,
+) -> Doc {
+    Doc {
+        kind,
+        name,
+        syntax,
+        description,
+        example,
+    }
+}
+
+pub const DOCS: &[Doc] = &[
+    //Sections
+    doc(
+        DocKind::Section,
+        "Asserts",
+        "[Asserts]\n<query> <filters>* <predicate>",
+        "Checks the response. Each line is a query, optional filters and a predicate. The entry fails if any assert fails.",
+        "HTTP 200\n[Asserts]\njsonpath \"$.users\" count == 2\nheader \"Content-Type\" contains \"json\"",
+    ),
+    doc(
+        DocKind::Section,
+        "Captures",
+        "[Captures]\n<name>: <query> <filters>*",
+        "Captures values from the response into variables that can be used by the following entries with `{{name}}`.",
+        "HTTP 200\n[Captures]\ncsrf_token: xpath \"string(//meta[@name='_csrf_token']/@content)\"\nuser_id: jsonpath \"$.user.id\"",
+    ),
+    doc(
+        DocKind::Section,
+        "QueryStringParams",
+        "[QueryStringParams]\n<key>: <value>",
+        "Adds query string parameters to the request url. The values are url encoded.",
+        "GET https://example.org/news\n[QueryStringParams]\norder: newest\nsearch: {{custom-search}}",
+    ),
+    doc(
+        DocKind::Section,
+        "Query",
+        "[Query]\n<key>: <value>",
+        "Short alias of `[QueryStringParams]`. Adds query string parameters to the request url.",
+        "GET https://example.org/news\n[Query]\norder: newest",
+    ),
+    doc(
+        DocKind::Section,
+        "FormParams",
+        "[FormParams]\n<key>: <value>",
+        "Sends the request body as url encoded form data with the `application/x-www-form-urlencoded` content type.",
+        "POST https://example.org/contact\n[FormParams]\ndefault: false\ntoken: {{token}}",
+    ),
+    doc(
+        DocKind::Section,
+        "Form",
+        "[Form]\n<key>: <value>",
+        "Short alias of `[FormParams]`. Sends the request body as url encoded form data.",
+        "POST https://example.org/contact\n[Form]\ndefault: false",
+    ),
+    doc(
+        DocKind::Section,
+        "MultipartFormData",
+        "[MultipartFormData]\n<key>: <value>\n<key>: file,<filename>; <content-type>?",
+        "Sends the request body as multipart form data. Files are read relative to the hurl file.",
+        "POST https://example.org/upload\n[MultipartFormData]\nfield1: value1\nfile1: file,data.txt;\nfile2: file,data.html; text/html",
+    ),
+    doc(
+        DocKind::Section,
+        "Multipart",
+        "[Multipart]\n<key>: <value>\n<key>: file,<filename>; <content-type>?",
+        "Short alias of `[MultipartFormData]`. Sends the request body as multipart form data.",
+        "POST https://example.org/upload\n[Multipart]\nfile1: file,data.txt;",
+    ),
+    doc(
+        DocKind::Section,
+        "Cookies",
+        "[Cookies]\n<name>: <value>",
+        "Adds cookies to the request. The cookies are only sent with this request.",
+        "GET https://example.org/index.html\n[Cookies]\ntheme: light\nsessionToken: abc123",
+    ),
+    doc(
+        DocKind::Section,
+        "BasicAuth",
+        "[BasicAuth]\n<user>: <password>",
+        "Adds an `Authorization` header using basic authentication. Only one user can be defined.",
+        "GET https://example.org/protected\n[BasicAuth]\nbob: secret",
+    ),
+    doc(
+        DocKind::Section,
+        "Options",
+        "[Options]\n<option>: <value>",
+        "Sets options that only apply to this entry. They override the options given on the command line.",
+        "GET https://example.org\n[Options]\nlocation: true\nretry: 3\nvariable: host=example.net",
+    ),
+    //Queries
+    doc(
+        DocKind::Query,
+        "status",
+        "status",
+        "The HTTP status code of the response.",
+        "[Asserts]\nstatus < 300",
+    ),
+    doc(
+        DocKind::Query,
+        "url",
+        "url",
+        "The last url fetched. It differs from the request url when redirects are followed.",
+        "[Asserts]\nurl == \"https://example.org/redirected\"",
+    ),
+    doc(
+        DocKind::Query,
+        "header",
+        "header \"<name>\"",
+        "The value of a response header. The name is case insensitive. Returns a list if the header is sent more than once.",
+        "[Asserts]\nheader \"Content-Type\" contains \"utf-8\"",
+    ),
+    doc(
+        DocKind::Query,
+        "certificate",
+        "certificate \"Subject\" | \"Issuer\" | \"Start-Date\" | \"Expire-Date\" | \"Serial-Number\"",
+        "A field of the SSL certificate of the server.",
+        "[Asserts]\ncertificate \"Expire-Date\" daysAfterNow > 15",
+    ),
+    doc(
+        DocKind::Query,
+        "cookie",
+        "cookie \"<name>[<attribute>]\"",
+        "A cookie set by the `Set-Cookie` response header or one of its attributes (`Value`, `Expires`, `Max-Age`, `Domain`, `Path`, `Secure`, `HttpOnly`, `SameSite`).",
+        "[Asserts]\ncookie \"LSID\" == \"DQAAAKEaem_vYg\"\ncookie \"LSID[Max-Age]\" not exists",
+    ),
+    doc(
+        DocKind::Query,
+        "body",
+        "body",
+        "The response body decoded as a string using the charset of the `Content-Type` header.",
+        "[Asserts]\nbody contains \"<h1>Welcome</h1>\"",
+    ),
+    doc(
+        DocKind::Query,
+        "xpath",
+        "xpath \"<expression>\"",
+        "Evaluates an XPath expression on an HTML or XML response body.",
+        "[Asserts]\nxpath \"normalize-space(//head/title)\" == \"Hello world!\"",
+    ),
+    doc(
+        DocKind::Query,
+        "jsonpath",
+        "jsonpath \"<expression>\"",
+        "Evaluates a JSONPath expression on a JSON response body.",
+        "[Captures]\nuser_id: jsonpath \"$.users[0].id\"",
+    ),
+    doc(
+        DocKind::Query,
+        "regex",
+        "regex \"<pattern>\" | regex /<pattern>/",
+        "Matches a regular expression on the response body and returns the first capture group.",
+        "[Asserts]\nregex /^(\\d{4}-\\d{2}-\\d{2})/ == \"2024-01-01\"",
+    ),
+    doc(
+        DocKind::Query,
+        "variable",
+        "variable \"<name>\"",
+        "The value of a variable captured or defined earlier.",
+        "[Asserts]\nvariable \"user_id\" isInteger",
+    ),
+    doc(
+        DocKind::Query,
+        "duration",
+        "duration",
+        "The total time of the request and response in milliseconds.",
+        "[Asserts]\nduration < 1000",
+    ),
+    doc(
+        DocKind::Query,
+        "bytes",
+        "bytes",
+        "The raw bytes of the response body.",
+        "[Asserts]\nbytes count == 120",
+    ),
+    doc(
+        DocKind::Query,
+        "sha256",
+        "sha256",
+        "The SHA-256 hash of the response body bytes.",
+        "[Asserts]\nsha256 == hex,039058c6f2c0cb492c533b0a4d14ef77cc0f78abccced5287d84a1a2011cfb81;",
+    ),
+    doc(
+        DocKind::Query,
+        "md5",
+        "md5",
+        "The MD5 hash of the response body bytes.",
+        "[Asserts]\nmd5 == hex,ed076287532e86365e841e92bfc50d8c;",
+    ),
+    //Filters
+    doc(
+        DocKind::Filter,
+        "count",
+        "count",
+        "The number of items in a collection.",
+        "[Asserts]\njsonpath \"$.books\" count == 12",
+    ),
+    doc(
+        DocKind::Filter,
+        "daysAfterNow",
+        "daysAfterNow",
+        "The number of days between now and a date in the future.",
+        "[Asserts]\ncertificate \"Expire-Date\" daysAfterNow > 15",
+    ),
+    doc(
+        DocKind::Filter,
+        "daysBeforeNow",
+        "daysBeforeNow",
+        "The number of days between a date in the past and now.",
+        "[Asserts]\ncertificate \"Start-Date\" daysBeforeNow < 100",
+    ),
+    doc(
+        DocKind::Filter,
+        "decode",
+        "decode \"<encoding>\"",
+        "Decodes bytes to a string using the encoding.",
+        "[Asserts]\nbytes decode \"gb2312\" xpath \"string(//body)\" == \"你好世界\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "format",
+        "format \"<format>\"",
+        "Formats a date to a string using a strftime format.",
+        "[Asserts]\ncookie \"LSID[Expires]\" format \"%a, %d %b %Y %H:%M:%S\" == \"Wed, 13 Jan 2021 22:23:01\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "htmlEscape",
+        "htmlEscape",
+        "Converts the characters `&`, `<` and `>` to HTML-safe sequences.",
+        "[Asserts]\njsonpath \"$.text\" htmlEscape == \"a &gt; b\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "htmlUnescape",
+        "htmlUnescape",
+        "Converts named and numeric HTML character references to the characters they represent.",
+        "[Asserts]\njsonpath \"$.escaped_html[1]\" htmlUnescape == \"Foo Bar\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "jsonpath",
+        "jsonpath \"<expression>\"",
+        "Evaluates a JSONPath expression on a JSON string.",
+        "[Captures]\nbook: xpath \"string(//body/@data-store)\" jsonpath \"$.book\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "nth",
+        "nth <index>",
+        "The item at a zero based index of a collection.",
+        "[Asserts]\njsonpath \"$.users\" nth 2 == \"Bob\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "regex",
+        "regex \"<pattern>\" | regex /<pattern>/",
+        "The first capture group of a regular expression.",
+        "[Captures]\nparam1: header \"header1\" regex \"Hello (.*)!\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "replace",
+        "replace \"<old value>\" \"<new value>\" | replace /<pattern>/ \"<new value>\"",
+        "Replaces all the occurrences of a string or regular expression.",
+        "[Asserts]\njsonpath \"$.ips\" replace \", \" \"|\" == \"192.168.2.1|10.0.0.20\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "split",
+        "split \"<separator>\"",
+        "Splits a string into a list of strings.",
+        "[Asserts]\njsonpath \"$.ips\" split \", \" count == 3",
+    ),
+    doc(
+        DocKind::Filter,
+        "toDate",
+        "toDate \"<format>\"",
+        "Parses a string into a date using a strftime format.",
+        "[Asserts]\nheader \"Expires\" toDate \"%a, %d %b %Y %H:%M:%S GMT\" daysBeforeNow > 1000",
+    ),
+    doc(
+        DocKind::Filter,
+        "toFloat",
+        "toFloat",
+        "Converts a string to a float.",
+        "[Asserts]\njsonpath \"$.pi\" toFloat == 3.14",
+    ),
+    doc(
+        DocKind::Filter,
+        "toInt",
+        "toInt",
+        "Converts a string to an integer.",
+        "[Asserts]\njsonpath \"$.id\" toInt == 123",
+    ),
+    doc(
+        DocKind::Filter,
+        "urlDecode",
+        "urlDecode",
+        "Replaces the `%xx` escapes of a string with the characters they represent.",
+        "[Asserts]\njsonpath \"$.encoded_url\" urlDecode == \"https://mozilla.org/?x=шеллы\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "urlEncode",
+        "urlEncode",
+        "Percent encodes the characters of a string that aren't allowed in a url.",
+        "[Asserts]\njsonpath \"$.url\" urlEncode == \"https%3A//mozilla.org/%3Fx%3D%D1%88%D0%B5%D0%BB%D0%BB%D1%8B\"",
+    ),
+    doc(
+        DocKind::Filter,
+        "xpath",
+        "xpath \"<expression>\"",
+        "Evaluates an XPath expression on an HTML or XML string.",
+        "[Asserts]\njsonpath \"$.html\" xpath \"string(//h1)\" == \"Hello\"",
+    ),
+    //Predicates
+    doc(
+        DocKind::Predicate,
+        "not",
+        "not <predicate>",
+        "Negates the predicate that follows.",
+        "[Asserts]\nheader \"Location\" not exists",
+    ),
+    doc(
+        DocKind::Predicate,
+        "==",
+        "== <value>",
+        "Checks that the value is equal to the expected value.",
+        "[Asserts]\njsonpath \"$.book\" == \"Dune\"",
+    ),
+    doc(
+        DocKind::Predicate,
+        "!=",
+        "!= <value>",
+        "Checks that the value is not equal to the expected value.",
+        "[Asserts]\njsonpath \"$.color\" != \"red\"",
+    ),
+    doc(
+        DocKind::Predicate,
+        ">",
+        "> <number | string | date>",
+        "Checks that the value is greater than the expected value.",
+        "[Asserts]\njsonpath \"$.year\" > 1978",
+    ),
+    doc(
+        DocKind::Predicate,
+        ">=",
+        ">= <number | string | date>",
+        "Checks that the value is greater than or equal to the expected value.",
+        "[Asserts]\njsonpath \"$.year\" >= 1978",
+    ),
+    doc(
+        DocKind::Predicate,
+        "<",
+        "< <number | string | date>",
+        "Checks that the value is less than the expected value.",
+        "[Asserts]\njsonpath \"$.year\" < 1978",
+    ),
+    doc(
+        DocKind::Predicate,
+        "<=",
+        "<= <number | string | date>",
+        "Checks that the value is less than or equal to the expected value.",
+        "[Asserts]\njsonpath \"$.year\" <= 1978",
+    ),
+    doc(
+        DocKind::Predicate,
+        "startsWith",
+        "startsWith <string | bytes>",
+        "Checks that the value starts with the expected prefix.",
+        "[Asserts]\njsonpath \"$.movie\" startsWith \"The\"",
+    ),
+    doc(
+        DocKind::Predicate,
+        "endsWith",
+        "endsWith <string | bytes>",
+        "Checks that the value ends with the expected suffix.",
+        "[Asserts]\njsonpath \"$.movie\" endsWith \"Back\"",
+    ),
+    doc(
+        DocKind::Predicate,
+        "contains",
+        "contains <string | bytes>",
+        "Checks that the value contains the expected string or bytes.",
+        "[Asserts]\nbody contains \"Hello\"",
+    ),
+    doc(
+        DocKind::Predicate,
+        "includes",
+        "includes <value>",
+        "Checks that a collection includes the expected value.",
+        "[Asserts]\njsonpath \"$.books\" includes \"Dune\"",
+    ),
+    doc(
+        DocKind::Predicate,
+        "matches",
+        "matches \"<pattern>\" | matches /<pattern>/",
+        "Checks that the value matches a regular expression.",
+        "[Asserts]\njsonpath \"$.date\" matches /^\\d{4}-\\d{2}-\\d{2}$/",
+    ),
+    doc(
+        DocKind::Predicate,
+        "exists",
+        "exists",
+        "Checks that the query returned a value.",
+        "[Asserts]\njsonpath \"$.book\" exists",
+    ),
+    doc(
+        DocKind::Predicate,
+        "isEmpty",
+        "isEmpty",
+        "Checks that a collection or string is empty.",
+        "[Asserts]\njsonpath \"$.movies\" isEmpty",
+    ),
+    doc(
+        DocKind::Predicate,
+        "isInteger",
+        "isInteger",
+        "Checks that the value is an integer.",
+        "[Asserts]\njsonpath \"$.count\" isInteger",
+    ),
+    doc(
+        DocKind::Predicate,
+        "isFloat",
+        "isFloat",
+        "Checks that the value is a float.",
+        "[Asserts]\njsonpath \"$.height\" isFloat",
+    ),
+    doc(
+        DocKind::Predicate,
+        "isBoolean",
+        "isBoolean",
+        "Checks that the value is a boolean.",
+        "[Asserts]\njsonpath \"$.visible\" isBoolean",
+    ),
+    doc(
+        DocKind::Predicate,
+        "isString",
+        "isString",
+        "Checks that the value is a string.",
+        "[Asserts]\njsonpath \"$.name\" isString",
+    ),
+    doc(
+        DocKind::Predicate,
+        "isCollection",
+        "isCollection",
+        "Checks that the value is a collection (a list, an object, bytes or a node set).",
+        "[Asserts]\njsonpath \"$.books\" isCollection",
+    ),
+    doc(
+        DocKind::Predicate,
+        "isDate",
+        "isDate",
+        "Checks that the value is a date. Dates are returned by the `toDate` filter and some queries.",
+        "[Asserts]\ncookie \"LSID[Expires]\" isDate",
+    ),
+    doc(
+        DocKind::Predicate,
+        "isIsoDate",
+        "isIsoDate",
+        "Checks that the value is a string formatted as an RFC 3339 date.",
+        "[Asserts]\njsonpath \"$.published\" isIsoDate",
+    ),
+    //Options
+    doc(
+        DocKind::Option,
+        "aws-sigv4",
+        "aws-sigv4: <provider1[:provider2[:region[:service]]]>",
+        "Generates an `Authorization` header with an AWS SigV4 signature.",
+        "[Options]\naws-sigv4: aws:amz:eu-central-1:sts",
+    ),
+    doc(
+        DocKind::Option,
+        "cacert",
+        "cacert: <file>",
+        "Uses the certificate file in PEM format to verify the peer.",
+        "[Options]\ncacert: /etc/cert.pem",
+    ),
+    doc(
+        DocKind::Option,
+        "cert",
+        "cert: <certificate[:password]>",
+        "Uses the client certificate file and optional password.",
+        "[Options]\ncert: /etc/client-cert.pem",
+    ),
+    doc(
+        DocKind::Option,
+        "key",
+        "key: <file>",
+        "Uses the private key file for the client certificate.",
+        "[Options]\nkey: /etc/client-cert.key",
+    ),
+    doc(
+        DocKind::Option,
+        "compressed",
+        "compressed: <boolean>",
+        "Requests a compressed response using one of the algorithms br, gzip or deflate and decompresses the body.",
+        "[Options]\ncompressed: true",
+    ),
+    doc(
+        DocKind::Option,
+        "connect-to",
+        "connect-to: <host1:port1:host2:port2>",
+        "Connects to `host2:port2` instead of `host1:port1`.",
+        "[Options]\nconnect-to: example.com:443:localhost:8443",
+    ),
+    doc(
+        DocKind::Option,
+        "connect-timeout",
+        "connect-timeout: <duration>",
+        "The maximum time allowed for the connection. The unit defaults to seconds and can be set with `ms`, `s` or `m`.",
+        "[Options]\nconnect-timeout: 5s",
+    ),
+    doc(
+        DocKind::Option,
+        "delay",
+        "delay: <duration>",
+        "Waits before sending the request. The unit defaults to milliseconds and can be set with `ms`, `s` or `m`.",
+        "[Options]\ndelay: 2s",
+    ),
+    doc(
+        DocKind::Option,
+        "location",
+        "location: <boolean>",
+        "Follows redirects.",
+        "[Options]\nlocation: true",
+    ),
+    doc(
+        DocKind::Option,
+        "location-trusted",
+        "location-trusted: <boolean>",
+        "Follows redirects like `location` and also sends the credentials to the other hosts.",
+        "[Options]\nlocation-trusted: true",
+    ),
+    doc(
+        DocKind::Option,
+        "http1.0",
+        "http1.0: <boolean>",
+        "Uses HTTP version 1.0.",
+        "[Options]\nhttp1.0: true",
+    ),
+    doc(
+        DocKind::Option,
+        "http1.1",
+        "http1.1: <boolean>",
+        "Uses HTTP version 1.1.",
+        "[Options]\nhttp1.1: true",
+    ),
+    doc(
+        DocKind::Option,
+        "http2",
+        "http2: <boolean>",
+        "Uses HTTP version 2.",
+        "[Options]\nhttp2: true",
+    ),
+    doc(
+        DocKind::Option,
+        "http3",
+        "http3: <boolean>",
+        "Uses HTTP version 3.",
+        "[Options]\nhttp3: true",
+    ),
+    doc(
+        DocKind::Option,
+        "insecure",
+        "insecure: <boolean>",
+        "Allows insecure SSL connections and transfers.",
+        "[Options]\ninsecure: true",
+    ),
+    doc(
+        DocKind::Option,
+        "ipv4",
+        "ipv4: <boolean>",
+        "Resolves host names to IPv4 addresses only.",
+        "[Options]\nipv4: true",
+    ),
+    doc(
+        DocKind::Option,
+        "ipv6",
+        "ipv6: <boolean>",
+        "Resolves host names to IPv6 addresses only.",
+        "[Options]\nipv6: true",
+    ),
+    doc(
+        DocKind::Option,
+        "limit-rate",
+        "limit-rate: <integer>",
+        "The maximum transfer rate in bytes per second.",
+        "[Options]\nlimit-rate: 32000",
+    ),
+    doc(
+        DocKind::Option,
+        "max-redirs",
+        "max-redirs: <integer>",
+        "The maximum number of redirects to follow. Use -1 for no limit.",
+        "[Options]\nmax-redirs: 5",
+    ),
+    doc(
+        DocKind::Option,
+        "netrc",
+        "netrc: <boolean>",
+        "Reads the user name and password from the `~/.netrc` file.",
+        "[Options]\nnetrc: true",
+    ),
+    doc(
+        DocKind::Option,
+        "netrc-file",
+        "netrc-file: <file>",
+        "Reads the user name and password from the netrc file.",
+        "[Options]\nnetrc-file: /home/bob/.netrc",
+    ),
+    doc(
+        DocKind::Option,
+        "netrc-optional",
+        "netrc-optional: <boolean>",
+        "Like `netrc` but doesn't fail when the `~/.netrc` file is missing.",
+        "[Options]\nnetrc-optional: true",
+    ),
+    doc(
+        DocKind::Option,
+        "output",
+        "output: <file>",
+        "Writes the response body to the file. Use `-` for standard output.",
+        "[Options]\noutput: response.json",
+    ),
+    doc(
+        DocKind::Option,
+        "path-as-is",
+        "path-as-is: <boolean>",
+        "Sends the url path without squashing the `/../` and `/./` sequences.",
+        "[Options]\npath-as-is: true",
+    ),
+    doc(
+        DocKind::Option,
+        "proxy",
+        "proxy: <[protocol://]host[:port]>",
+        "Uses the proxy for the request.",
+        "[Options]\nproxy: http://localhost:3128",
+    ),
+    doc(
+        DocKind::Option,
+        "repeat",
+        "repeat: <integer>",
+        "Repeats the entry. Use -1 to repeat forever.",
+        "[Options]\nrepeat: 3",
+    ),
+    doc(
+        DocKind::Option,
+        "resolve",
+        "resolve: <host:port:address>",
+        "Uses a custom address for the host and port.",
+        "[Options]\nresolve: example.com:443:127.0.0.1",
+    ),
+    doc(
+        DocKind::Option,
+        "retry",
+        "retry: <integer>",
+        "The maximum number of retries when the asserts fail. Use -1 to retry forever.",
+        "[Options]\nretry: 10",
+    ),
+    doc(
+        DocKind::Option,
+        "retry-interval",
+        "retry-interval: <duration>",
+        "The time to wait between retries. The unit defaults to milliseconds and can be set with `ms`, `s` or `m`.",
+        "[Options]\nretry-interval: 500ms",
+    ),
+    doc(
+        DocKind::Option,
+        "skip",
+        "skip: <boolean>",
+        "Skips the entry.",
+        "[Options]\nskip: true",
+    ),
+    doc(
+        DocKind::Option,
+        "unix-socket",
+        "unix-socket: <path>",
+        "Connects through the Unix domain socket instead of the network.",
+        "[Options]\nunix-socket: /var/run/docker.sock",
+    ),
+    doc(
+        DocKind::Option,
+        "user",
+        "user: <user:password>",
+        "Adds basic authentication with the user and password.",
+        "[Options]\nuser: bob:secret",
+    ),
+    doc(
+        DocKind::Option,
+        "variable",
+        "variable: <name>=<value>",
+        "Defines a variable for this entry and the following ones.",
+        "[Options]\nvariable: host=example.net",
+    ),
+    doc(
+        DocKind::Option,
+        "verbose",
+        "verbose: <boolean>",
+        "Prints the request and response details of the entry.",
+        "[Options]\nverbose: true",
+    ),
+    doc(
+        DocKind::Option,
+        "very-verbose",
+        "very-verbose: <boolean>",
+        "Prints the request and response details of the entry including the bodies.",
+        "[Options]\nvery-verbose: true",
+    ),
+];
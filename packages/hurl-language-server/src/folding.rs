@@ -0,0 +1,207 @@
+use hurl_parser::parser::cst::{build_cst, SyntaxKind, SyntaxNode};
+use hurl_parser::parser::types::{Ast, Body, Bytes, Json, Span};
+use ropey::Rope;
+use tower_lsp::lsp_types::{FoldingRange, FoldingRangeKind};
+
+/// Folds every entry, section, multiline JSON object or array, multiline string and block of
+/// consecutive comment lines
+pub fn folding_ranges(rope: &Rope, ast: &Ast) -> Vec<FoldingRange> {
+    let cst = build_cst(&rope.to_string(), ast);
+    let mut folder = Folder {
+        rope,
+        ranges: vec![],
+    };
+    folder.node(&cst);
+    folder.comments(&cst);
+    folder.ast(ast);
+    folder
+        .ranges
+        .sort_by_key(|range| (range.start_line, range.end_line));
+    folder.ranges
+}
+
+struct Folder<'a> {
+    rope: &'a Rope,
+    ranges: Vec<FoldingRange>,
+}
+
+impl Folder<'_> {
+    fn fold(&mut self, span: Span, kind: Option<FoldingRangeKind>) {
+        if span.end <= span.start {
+            return;
+        }
+        let (Ok(start_line), Ok(end_line)) = (
+            self.rope.try_byte_to_line(span.start),
+            self.rope.try_byte_to_line(span.end - 1),
+        ) else {
+            return;
+        };
+        if end_line > start_line {
+            self.ranges.push(FoldingRange {
+                start_line: start_line as u32,
+                end_line: end_line as u32,
+                kind,
+                ..Default::default()
+            });
+        }
+    }
+
+    fn node(&mut self, node: &SyntaxNode) {
+        if matches!(node.kind, SyntaxKind::Entry | SyntaxKind::Section) {
+            //The span of a node ends with the blank lines that follow it
+            let end = node
+                .tokens()
+                .into_iter()
+                .rev()
+                .find(|token| !matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Newline))
+                .map_or(node.span.end, |token| token.span.end);
+            self.fold(
+                Span::new(node.span.start, end),
+                Some(FoldingRangeKind::Region),
+            );
+        }
+        for child in node.child_nodes() {
+            self.node(child);
+        }
+    }
+
+    /// Folds the blocks of lines that only hold a comment
+    fn comments(&mut self, cst: &SyntaxNode) {
+        let mut block: Option<(usize, usize, Span)> = None;
+        for token in cst.comments() {
+            let line = self.rope.byte_to_line(token.span.start);
+            let line_start = self.rope.line_to_byte(line);
+            let on_own_line = self
+                .rope
+                .byte_slice(line_start..token.span.start)
+                .chars()
+                .all(|c| c == ' ' || c == '\t');
+            if !on_own_line {
+                continue;
+            }
+            block = match block {
+                Some((first, last, span)) if last + 1 == line => {
+                    Some((first, line, Span::new(span.start, token.span.end)))
+                }
+                _ => {
+                    if let Some((_, _, span)) = block {
+                        self.fold(span, Some(FoldingRangeKind::Comment));
+                    }
+                    Some((line, line, token.span))
+                }
+            };
+        }
+        if let Some((_, _, span)) = block {
+            self.fold(span, Some(FoldingRangeKind::Comment));
+        }
+    }
+
+    fn ast(&mut self, ast: &Ast) {
+        for entry in &ast.entries {
+            if let Some(body) = &entry.request.body {
+                self.body(body);
+            }
+            if let Some(body) = entry.response.as_ref().and_then(|r| r.body.as_ref()) {
+                self.body(body);
+            }
+        }
+    }
+
+    fn body(&mut self, body: &Body) {
+        match &body.bytes {
+            Bytes::JsonValue(json) => self.json(json, body.span),
+            Bytes::MultilineString(_) => self.fold(body.span, None),
+            _ => {}
+        }
+    }
+
+    fn json(&mut self, json: &Json, span: Span) {
+        match json {
+            Json::Object(key_values) => {
+                self.fold(span, None);
+                for key_value in key_values {
+                    self.json(&key_value.value.0, key_value.value.1);
+                }
+            }
+            Json::Array(values) => {
+                self.fold(span, None);
+                for (value, span) in values {
+                    self.json(value, *span);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod folding_tests {
+    use super::*;
+    use hurl_parser::parser::parser::parse_ast;
+
+    fn folds(test_str: &str) -> Vec<(u32, u32, Option<FoldingRangeKind>)> {
+        let (ast, _) = parse_ast(test_str);
+        folding_ranges(&Rope::from_str(test_str), &ast.unwrap())
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line, range.kind))
+            .collect()
+    }
+
+    #[test]
+    fn it_folds_entries_and_sections() {
+        let test_str = r#"# Create a user
+# and check it
+POST https://example.org/users
+[Options]
+retry: 3
+verbose: true
+
+HTTP 201
+[Asserts]
+status == 201
+header "Location" exists
+
+GET https://example.org
+"#;
+        assert_eq!(
+            folds(test_str),
+            vec![
+                (0, 1, Some(FoldingRangeKind::Comment)),
+                (2, 10, Some(FoldingRangeKind::Region)),
+                (3, 5, Some(FoldingRangeKind::Region)),
+                (8, 10, Some(FoldingRangeKind::Region)),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_folds_bodies() {
+        let test_str = r#"POST https://example.org
+{
+  "user": {
+    "name": "bob",
+    "tags": ["a", "b"]
+  },
+  "ids": [
+    1,
+    2
+  ]
+}
+HTTP 200
+```
+some
+text
+```
+"#;
+        assert_eq!(
+            folds(test_str),
+            vec![
+                (0, 15, Some(FoldingRangeKind::Region)),
+                (1, 10, None),
+                (2, 5, None),
+                (6, 9, None),
+                (12, 15, None),
+            ]
+        );
+    }
+}
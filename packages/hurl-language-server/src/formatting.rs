@@ -0,0 +1,76 @@
+use hurl_parser::parser::formatter::{format, format_range};
+use hurl_parser::parser::types::Span;
+use ropey::Rope;
+use tower_lsp::lsp_types::{Position, Range, TextEdit};
+
+use crate::utils::{
+    byte_offset_to_position, byte_span_to_range, position_to_byte_offset, PositionEncoding,
+};
+
+/// Formats the whole document. Returns `None` if the document can't be formatted
+/// because it has errors.
+pub fn formatting(rope: &Rope, encoding: PositionEncoding) -> Option<Vec<TextEdit>> {
+    let document = rope.to_string();
+    let formatted = format(&document)?;
+    if formatted == document {
+        return Some(vec![]);
+    }
+    let end = byte_offset_to_position(document.len(), rope, encoding)?;
+    Some(vec![TextEdit::new(
+        Range::new(Position::new(0, 0), end),
+        formatted,
+    )])
+}
+
+/// Formats the entries that overlap the range
+pub fn range_formatting(
+    rope: &Rope,
+    range: Range,
+    encoding: PositionEncoding,
+) -> Option<Vec<TextEdit>> {
+    let document = rope.to_string();
+    let start = position_to_byte_offset(range.start, rope, encoding)?;
+    let end = position_to_byte_offset(range.end, rope, encoding)?;
+    let (span, formatted) = format_range(&document, Span::new(start, end))?;
+    if document[span.start..span.end] == formatted {
+        return Some(vec![]);
+    }
+    let range = byte_span_to_range(span, rope, encoding)?;
+    Some(vec![TextEdit::new(range, formatted)])
+}
+
+#[cfg(test)]
+mod formatting_tests {
+    use super::*;
+
+    #[test]
+    fn it_replaces_the_whole_document() {
+        let rope = Rope::from_str("GET   https://example.org\n\n\n\nHTTP   200\n");
+        assert_eq!(
+            formatting(&rope, PositionEncoding::Utf16),
+            Some(vec![TextEdit::new(
+                Range::new(Position::new(0, 0), Position::new(5, 0)),
+                "GET https://example.org\n\nHTTP 200\n".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn it_returns_no_edits_for_formatted_documents() {
+        let rope = Rope::from_str("GET https://example.org\nHTTP 200\n");
+        assert_eq!(formatting(&rope, PositionEncoding::Utf16), Some(vec![]));
+    }
+
+    #[test]
+    fn it_replaces_the_entries_in_the_range() {
+        let rope = Rope::from_str("GET https://example.org/a\n\nGET   https://example.org/b\n");
+        let range = Range::new(Position::new(2, 0), Position::new(2, 3));
+        assert_eq!(
+            range_formatting(&rope, range, PositionEncoding::Utf16),
+            Some(vec![TextEdit::new(
+                Range::new(Position::new(2, 0), Position::new(2, 27)),
+                "GET https://example.org/b".to_string()
+            )])
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use hurl_parser::parser::cst::{build_cst, SyntaxElement, SyntaxKind, SyntaxNode};
+use hurl_parser::parser::types::{Ast, Span};
+use ropey::Rope;
+use tower_lsp::lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+
+use crate::docs::{find_doc, Doc, DocKind};
+use crate::utils::{byte_span_to_range, position_to_byte_offset, PositionEncoding};
+use crate::variables::{definition, occurrence_at, occurrences};
+use crate::variables_file::{ExternalVariable, ExternalVariables};
+
+/// The value of the variable or the documentation of the keyword at the position
+pub fn hover(
+    rope: &Rope,
+    ast: Option<&Ast>,
+    position: Position,
+    encoding: PositionEncoding,
+    variables: &ExternalVariables,
+) -> Option<Hover> {
+    let document = rope.to_string();
+    let offset = position_to_byte_offset(position, rope, encoding)?;
+    let ast = ast?;
+    let (span, markdown) = variable_at(&document, ast, offset, variables).or_else(|| {
+        keyword_doc_at(&document, ast, offset).map(|(span, doc)| (span, doc.markdown()))
+    })?;
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: markdown,
+        }),
+        range: Some(byte_span_to_range(span, rope, encoding)?),
+    })
+}
+
+/// Describes where the variable used at the offset gets its value from. A capture or a
+/// `variable` option of the document is shown as written while the variables files and
+/// the environment give the resolved value.
+pub fn variable_at(
+    document: &str,
+    ast: &Ast,
+    offset: usize,
+    variables: &ExternalVariables,
+) -> Option<(Span, String)> {
+    let occurrences = occurrences(ast);
+    let occurrence = occurrence_at(&occurrences, offset)?;
+    if occurrence.kind.is_definition() {
+        return None;
+    }
+    let name = &occurrence.name;
+    let markdown = match definition(&occurrences, occurrence) {
+        Some(definition) => {
+            let line_start = document[..definition.span.start]
+                .rfind('\n')
+                .map_or(0, |i| i + 1);
+            let line = document[line_start..].lines().next().unwrap_or_default();
+            format!("**{name}**\n```hurl\n{}\n```", line.trim())
+        }
+        None => match variables.get(name)? {
+            ExternalVariable::File(file, variable) => format!(
+                "**{name}** = `{}`\n\nFrom the variables file `{}`",
+                variable.value,
+                file.path.display()
+            ),
+            ExternalVariable::Environment(variable) => format!(
+                "**{name}** = `{}`\n\nFrom the `{}` environment variable",
+                variable.value, variable.key
+            ),
+        },
+    };
+    Some((occurrence.span, markdown))
+}
+
+/// Finds the documented keyword at the offset along with its span
+pub fn keyword_doc_at(document: &str, ast: &Ast, offset: usize) -> Option<(Span, &'static Doc)> {
+    build_cst(document, ast)
+        .nodes_at(offset)
+        .into_iter()
+        .rev()
+        .find_map(|node| keyword(node, offset))
+}
+
+fn keyword(node: &SyntaxNode, offset: usize) -> Option<(Span, &'static Doc)> {
+    let text = node.text();
+    let (kind, start, name) = match node.kind {
+        SyntaxKind::Query => (DocKind::Query, 0, first_word(&text)),
+        SyntaxKind::Filter => (DocKind::Filter, 0, first_word(&text)),
+        SyntaxKind::Predicate => {
+            let prefix = first_word(&text);
+            let rest = text[prefix.len()..].trim_start();
+            let start = text.len() - rest.len();
+            if prefix == "not" && offset > node.span.start + prefix.len() && !rest.is_empty() {
+                (DocKind::Predicate, start, first_word(rest))
+            } else {
+                (DocKind::Predicate, 0, prefix)
+            }
+        }
+        SyntaxKind::Option => {
+            let name = text.split(':').next().unwrap_or_default().trim_end();
+            (DocKind::Option, 0, name)
+        }
+        SyntaxKind::Section => return section_header(node, offset),
+        _ => return None,
+    };
+    let span = Span::new(
+        node.span.start + start,
+        node.span.start + start + name.len(),
+    );
+    if offset < span.start || offset > span.end {
+        return None;
+    }
+    find_doc(kind, name).map(|doc| (span, doc))
+}
+
+/// The section header is the first text of the section like `[Asserts]`
+fn section_header(node: &SyntaxNode, offset: usize) -> Option<(Span, &'static Doc)> {
+    let header = node.children.iter().find_map(|child| match child {
+        SyntaxElement::Token(token) if token.kind == SyntaxKind::Text => Some(token),
+        _ => None,
+    })?;
+    if offset < header.span.start || offset > header.span.end {
+        return None;
+    }
+    let name = header.text.strip_prefix('[')?.strip_suffix(']')?;
+    find_doc(DocKind::Section, name).map(|doc| (header.span, doc))
+}
+
+fn first_word(text: &str) -> &str {
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    &text[..end]
+}
+
+#[cfg(test)]
+mod hover_tests {
+    use super::*;
+    use crate::variables_file::VariablesFile;
+    use hurl_parser::parser::parser::parse_ast;
+    use std::path::PathBuf;
+
+    fn keyword_at(document: &str, needle: &str) -> Option<(&'static str, Span)> {
+        let offset = document.find(needle).unwrap();
+        let (ast, _) = parse_ast(document);
+        keyword_doc_at(document, &ast.unwrap(), offset).map(|(span, doc)| (doc.name, span))
+    }
+
+    #[test]
+    fn it_finds_keywords() {
+        let document = r#"GET https://example.org
+[Options]
+retry-interval: 500ms
+HTTP 200
+[Captures]
+id: jsonpath "$.id" nth 0
+[Asserts]
+header "Date" toDate "%a" not isIsoDate
+"#;
+        assert_eq!(
+            keyword_at(document, "retry-interval"),
+            Some(("retry-interval", Span::new(34, 48)))
+        );
+        assert_eq!(
+            keyword_at(document, "Captures]"),
+            Some(("Captures", Span::new(65, 75)))
+        );
+        assert_eq!(
+            keyword_at(document, "jsonpath"),
+            Some(("jsonpath", Span::new(80, 88)))
+        );
+        assert_eq!(
+            keyword_at(document, "nth"),
+            Some(("nth", Span::new(96, 99)))
+        );
+        assert_eq!(
+            keyword_at(document, "toDate"),
+            Some(("toDate", Span::new(126, 132)))
+        );
+        assert_eq!(
+            keyword_at(document, "not"),
+            Some(("not", Span::new(138, 141)))
+        );
+        assert_eq!(
+            keyword_at(document, "isIsoDate"),
+            Some(("isIsoDate", Span::new(142, 151)))
+        );
+    }
+
+    #[test]
+    fn it_ignores_values() {
+        let document = "GET https://example.org\nHTTP 200\n[Captures]\nid: jsonpath \"$.id\"\n";
+        assert_eq!(keyword_at(document, "$.id"), None);
+        assert_eq!(keyword_at(document, "example"), None);
+    }
+
+    #[test]
+    fn it_shows_where_variables_come_from() {
+        let document = r#"GET https://{{host}}/{{id}}/{{token}}
+[Options]
+variable: id=42
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+
+GET https://{{host}}/{{token}}
+"#;
+        let variables = ExternalVariables {
+            environment: vec![],
+            files: vec![VariablesFile::parse(
+                PathBuf::from("vars.env"),
+                "host=localhost:8080\n",
+            )],
+        };
+        let (ast, _) = parse_ast(document);
+        let ast = ast.unwrap();
+        let markdown = |offset: Option<usize>| {
+            variable_at(document, &ast, offset.unwrap(), &variables).map(|(_, markdown)| markdown)
+        };
+        assert_eq!(
+            markdown(document.find("host")),
+            Some("**host** = `localhost:8080`\n\nFrom the variables file `vars.env`".to_string())
+        );
+        assert_eq!(
+            markdown(document.find("id}}")),
+            Some("**id**\n```hurl\nvariable: id=42\n```".to_string())
+        );
+        assert_eq!(
+            markdown(document.find("token")),
+            None,
+            "the token is only captured after the first request"
+        );
+        assert_eq!(
+            markdown(document.rfind("token")),
+            Some("**token**\n```hurl\ntoken: jsonpath \"$.token\"\n```".to_string())
+        );
+    }
+}
@@ -1,2 +1,22 @@
+pub mod analysis;
+pub mod code_actions;
 pub mod completion;
+pub mod definition;
+pub mod docs;
+pub mod folding;
+pub mod formatting;
+pub mod hover;
+pub mod refactor;
+pub mod references;
+pub mod rename;
+pub mod sections;
+pub mod semantic_tokens;
+pub mod symbols;
+#[cfg(test)]
+mod test_utils;
+pub mod text_sync;
+pub mod type_check;
 pub mod utils;
+pub mod variables;
+pub mod variables_file;
+pub mod workspace;
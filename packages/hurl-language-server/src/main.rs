@@ -1,12 +1,37 @@
 use dashmap::DashMap;
 use env_logger::Env;
+use hurl_language_server::analysis::variable_diagnostics;
+use hurl_language_server::code_actions::code_actions;
 use hurl_language_server::completion::{completion, ImCompleteCompletionItem};
-use hurl_language_server::utils::offset_to_position;
-use hurl_parser::parser::types::{Ast, Rich};
+use hurl_language_server::definition::definition;
+use hurl_language_server::folding::folding_ranges;
+use hurl_language_server::formatting::{formatting, range_formatting};
+use hurl_language_server::hover::hover;
+use hurl_language_server::refactor::{refactors, WorkspaceEditSupport};
+use hurl_language_server::references::{document_highlights, references};
+use hurl_language_server::rename::{prepare_rename, rename};
+use hurl_language_server::sections::{section_diagnostics, SectionSpelling};
+use hurl_language_server::semantic_tokens::{legend, semantic_tokens, semantic_tokens_edits};
+use hurl_language_server::symbols::document_symbols;
+use hurl_language_server::text_sync::apply_change;
+use hurl_language_server::type_check::type_diagnostics;
+use hurl_language_server::utils::{byte_span_to_range, position_to_byte_offset, PositionEncoding};
+use hurl_language_server::variables_file::{
+    configured_variables_files, discover_variables_files, ExternalVariables,
+};
+use hurl_language_server::workspace::{
+    find_hurl_files, is_hurl_file, matches_query, workspace_file_symbols,
+};
+use hurl_parser::parser::incremental::reparse_ast;
+use hurl_parser::parser::parser::parse_ast;
+use hurl_parser::parser::types::Ast;
+use hurl_parser::ParseError;
 use log::debug;
 use ropey::Rope;
-use serde_json::Value;
-use tower_lsp::jsonrpc::Result;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use tower_lsp::jsonrpc::{Error, Result};
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
@@ -24,20 +49,74 @@ OPTIONS:
 struct Backend {
     client: Client,
     ast_map: DashMap<String, Option<hurl_parser::parser::types::Ast>>,
+    /// The parse errors of the documents of `ast_map` for the incremental parser
+    parse_errors_map: DashMap<String, Vec<ParseError>>,
     document_map: DashMap<String, Rope>,
+    variables_files: RwLock<Vec<PathBuf>>,
+    external_variables: RwLock<ExternalVariables>,
+    semantic_tokens_map: DashMap<String, (String, Vec<SemanticToken>)>,
+    semantic_tokens_result_id: AtomicU64,
+    workspace_folders: RwLock<Vec<PathBuf>>,
+    workspace_symbols_map: DashMap<String, Vec<SymbolInformation>>,
+    position_encoding: RwLock<PositionEncoding>,
+    section_spelling: RwLock<Option<SectionSpelling>>,
+    client_capabilities: RwLock<ClientCapabilities>,
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        #[allow(deprecated)]
+        let folders: Vec<PathBuf> = match &params.workspace_folders {
+            Some(folders) => folders
+                .iter()
+                .filter_map(|folder| folder.uri.to_file_path().ok())
+                .collect(),
+            None => params
+                .root_uri
+                .iter()
+                .filter_map(|uri| uri.to_file_path().ok())
+                .collect(),
+        };
+        let root = folders.first().cloned();
+        let encoding = PositionEncoding::negotiate(
+            params
+                .capabilities
+                .general
+                .as_ref()
+                .and_then(|general| general.position_encodings.as_deref()),
+        );
+        if let Ok(mut position_encoding) = self.position_encoding.write() {
+            *position_encoding = encoding;
+        }
+        let mut configured_files =
+            configured_variables_files(params.initialization_options.as_ref(), root.as_deref());
+        if configured_files.is_empty() {
+            configured_files = discover_variables_files(&folders);
+        }
+        if let Ok(mut variables_files) = self.variables_files.write() {
+            *variables_files = configured_files;
+        }
+        self.reload_external_variables();
+        if let Ok(mut workspace_folders) = self.workspace_folders.write() {
+            *workspace_folders = folders;
+        }
+        if let Ok(mut section_spelling) = self.section_spelling.write() {
+            *section_spelling = SectionSpelling::configured(params.initialization_options.as_ref());
+        }
+        if let Ok(mut client_capabilities) = self.client_capabilities.write() {
+            *client_capabilities = params.capabilities;
+        }
         Ok(InitializeResult {
             server_info: None,
-            offset_encoding: Some("utf-8".to_string()),
+            //Clients predating positionEncoding read the encoding from this clangd extension
+            offset_encoding: Some(encoding.kind().as_str().to_string()),
             capabilities: ServerCapabilities {
+                position_encoding: Some(encoding.kind()),
                 text_document_sync: Some(TextDocumentSyncCapability::Options(
                     TextDocumentSyncOptions {
                         open_close: Some(true),
-                        change: Some(TextDocumentSyncKind::FULL),
+                        change: Some(TextDocumentSyncKind::INCREMENTAL),
                         save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
                             include_text: Some(true),
                         })),
@@ -46,11 +125,50 @@ impl LanguageServer for Backend {
                 )),
                 completion_provider: Some(CompletionOptions {
                     resolve_provider: Some(false),
-                    trigger_characters: Some(vec![".".to_string(), "[".to_string()]),
+                    trigger_characters: Some(vec![
+                        ".".to_string(),
+                        "[".to_string(),
+                        "{".to_string(),
+                        " ".to_string(),
+                    ]),
                     work_done_progress_options: Default::default(),
                     all_commit_characters: None,
                     completion_item: None,
                 }),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                document_highlight_provider: Some(OneOf::Left(true)),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        SemanticTokensOptions {
+                            legend: legend(),
+                            full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                            range: None,
+                            work_done_progress_options: Default::default(),
+                        },
+                    ),
+                ),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                document_range_formatting_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR_EXTRACT,
+                            CodeActionKind::REFACTOR_INLINE,
+                            CodeActionKind::REFACTOR_REWRITE,
+                        ]),
+                        ..CodeActionOptions::default()
+                    },
+                )),
                 workspace: Some(WorkspaceServerCapabilities {
                     workspace_folders: Some(WorkspaceFoldersServerCapabilities {
                         supported: Some(true),
@@ -64,6 +182,29 @@ impl LanguageServer for Backend {
     }
     async fn initialized(&self, _: InitializedParams) {
         debug!("initialized!");
+        let dynamic_watchers = self
+            .client_capabilities
+            .read()
+            .ok()
+            .and_then(|capabilities| {
+                capabilities
+                    .workspace
+                    .as_ref()?
+                    .did_change_watched_files?
+                    .dynamic_registration
+            })
+            .unwrap_or(false);
+        if dynamic_watchers {
+            self.register_file_watchers().await;
+        }
+        let folders = self
+            .workspace_folders
+            .read()
+            .map(|folders| folders.clone())
+            .unwrap_or_default();
+        for folder in folders {
+            self.index_folder(folder).await;
+        }
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -72,37 +213,78 @@ impl LanguageServer for Backend {
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
         debug!("file opened");
-        self.on_change(TextDocumentItem {
-            uri: params.text_document.uri,
-            text: &params.text_document.text,
-            version: Some(params.text_document.version),
-        })
+        let parsed = parse_ast(&params.text_document.text);
+        self.on_change(
+            TextDocumentItem {
+                uri: params.text_document.uri,
+                rope: Rope::from_str(&params.text_document.text),
+                version: Some(params.text_document.version),
+            },
+            parsed,
+        )
         .await
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        self.on_change(TextDocumentItem {
-            text: &params.content_changes[0].text,
-            uri: params.text_document.uri,
-            version: Some(params.text_document.version),
-        })
+        let encoding = self.position_encoding();
+        let uri = params.text_document.uri.to_string();
+        let mut rope = self
+            .document_map
+            .get(&uri)
+            .map(|rope| rope.clone())
+            .unwrap_or_default();
+        let mut ast = self.ast_map.get(&uri).and_then(|ast| ast.clone());
+        let mut errs = self
+            .parse_errors_map
+            .get(&uri)
+            .map(|errs| errs.clone())
+            .unwrap_or_default();
+        for change in &params.content_changes {
+            let edit = apply_change(&mut rope, change, encoding);
+            (ast, errs) = reparse_ast(ast.as_ref(), &errs, &edit, &rope.to_string());
+        }
+        self.on_change(
+            TextDocumentItem {
+                uri: params.text_document.uri,
+                rope,
+                version: Some(params.text_document.version),
+            },
+            (ast, errs),
+        )
         .await
     }
 
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
         dbg!(&params.text);
-        if let Some(text) = params.text {
+        if self.is_variables_file(&params.text_document.uri) {
+            self.reload_external_variables();
+            self.refresh_diagnostics().await;
+        } else if let Some(text) = params.text {
+            let parsed = parse_ast(&text);
             let item = TextDocumentItem {
                 uri: params.text_document.uri,
-                text: &text,
+                rope: Rope::from_str(&text),
                 version: None,
             };
-            self.on_change(item).await;
+            self.on_change(item, parsed).await;
             _ = self.client.semantic_tokens_refresh().await;
         }
         debug!("file saved!");
     }
-    async fn did_close(&self, _: DidCloseTextDocumentParams) {
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.document_map.remove(&uri.to_string());
+        self.ast_map.remove(&uri.to_string());
+        self.parse_errors_map.remove(&uri.to_string());
+        self.semantic_tokens_map.remove(&uri.to_string());
+        //The symbols of the unsaved text are replaced by the ones of the file on disk, if it
+        //still exists
+        self.workspace_symbols_map.remove(&uri.to_string());
+        if let Ok(path) = uri.to_file_path() {
+            if is_hurl_file(&path) {
+                self.index_files(vec![path]).await;
+            }
+        }
         debug!("file closed!");
     }
 
@@ -121,17 +303,31 @@ impl LanguageServer for Backend {
             )
             .await;
 
-        let completions = {
-            let completions = completion();
+        let completions = self.document_map.get(&uri.to_string()).and_then(|rope| {
+            let document = rope.to_string();
+            let offset = position_to_byte_offset(position, &rope, self.position_encoding())?;
+            let ast = self.ast_map.get(&uri.to_string());
+            let completions = completion(
+                &document,
+                offset,
+                ast.as_deref().and_then(Option::as_ref),
+                &self.external_variables(),
+            );
             let mut ret = Vec::with_capacity(completions.len());
             for (_, item) in completions {
                 match item {
-                    ImCompleteCompletionItem::Keyword(var) => {
+                    ImCompleteCompletionItem::Keyword(var, doc) => {
                         ret.push(CompletionItem {
                             label: var.clone(),
                             insert_text: Some(var.clone()),
                             kind: Some(CompletionItemKind::KEYWORD),
                             detail: Some(var),
+                            documentation: doc.map(|doc| {
+                                Documentation::MarkupContent(MarkupContent {
+                                    kind: MarkupKind::Markdown,
+                                    value: doc.markdown(),
+                                })
+                            }),
                             ..Default::default()
                         });
                     }
@@ -145,69 +341,567 @@ impl LanguageServer for Backend {
                             ..Default::default()
                         });
                     }
+                    ImCompleteCompletionItem::Variable(var, value) => {
+                        ret.push(CompletionItem {
+                            label: var.clone(),
+                            kind: Some(CompletionItemKind::VARIABLE),
+                            insert_text: Some(var),
+                            detail: value,
+                            ..Default::default()
+                        });
+                    }
                 }
             }
             Some(ret)
-        };
+        });
 
         Ok(completions.map(CompletionResponse::Array))
     }
 
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let uri = params
+            .text_document_position_params
+            .text_document
+            .uri
+            .to_string();
+        let position = params.text_document_position_params.position;
+        let variables = self.external_variables();
+        Ok(self.document_map.get(&uri).and_then(|rope| {
+            let ast = self.ast_map.get(&uri);
+            hover(
+                &rope,
+                ast.as_deref().and_then(Option::as_ref),
+                position,
+                self.position_encoding(),
+                &variables,
+            )
+        }))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        let variables = self.external_variables();
+        let location = self.document_map.get(&uri.to_string()).and_then(|rope| {
+            let ast = self.ast_map.get(&uri.to_string())?;
+            definition(
+                &uri,
+                &rope,
+                ast.as_ref()?,
+                position,
+                &variables,
+                self.position_encoding(),
+            )
+        });
+        Ok(location.map(GotoDefinitionResponse::Scalar))
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let include_declaration = params.context.include_declaration;
+        Ok(self.document_map.get(&uri.to_string()).and_then(|rope| {
+            let ast = self.ast_map.get(&uri.to_string())?;
+            references(
+                &uri,
+                &rope,
+                ast.as_ref()?,
+                position,
+                include_declaration,
+                self.position_encoding(),
+            )
+        }))
+    }
+
+    async fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>> {
+        let uri = params
+            .text_document_position_params
+            .text_document
+            .uri
+            .to_string();
+        let position = params.text_document_position_params.position;
+        Ok(self.document_map.get(&uri).and_then(|rope| {
+            let ast = self.ast_map.get(&uri)?;
+            document_highlights(&rope, ast.as_ref()?, position, self.position_encoding())
+        }))
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let uri = params.text_document.uri.to_string();
+        let (Some(rope), Some(ast)) = (self.document_map.get(&uri), self.ast_map.get(&uri)) else {
+            return Ok(None);
+        };
+        let Some(ast) = ast.as_ref() else {
+            return Ok(None);
+        };
+        prepare_rename(&rope, ast, params.position, self.position_encoding())
+            .map(|range| range.map(PrepareRenameResponse::Range))
+            .map_err(|err| Error::invalid_params(err.to_string()))
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let (Some(rope), Some(ast)) = (
+            self.document_map.get(&uri.to_string()),
+            self.ast_map.get(&uri.to_string()),
+        ) else {
+            return Ok(None);
+        };
+        let Some(ast) = ast.as_ref() else {
+            return Ok(None);
+        };
+        rename(
+            &uri,
+            &rope,
+            ast,
+            position,
+            &params.new_name,
+            self.position_encoding(),
+        )
+        .map_err(|err| Error::invalid_params(err.to_string()))
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        let uri = params.text_document.uri.to_string();
+        let Some(document) = self.document_map.get(&uri).map(|rope| rope.to_string()) else {
+            return Ok(None);
+        };
+        let Some(ast) = self.ast_map.get(&uri) else {
+            return Ok(None);
+        };
+        let Some(ast) = ast.as_ref() else {
+            return Ok(None);
+        };
+        let data = semantic_tokens(&document, ast, self.position_encoding());
+        let result_id = self.next_semantic_tokens_result_id();
+        self.semantic_tokens_map
+            .insert(uri, (result_id.clone(), data.clone()));
+        Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: Some(result_id),
+            data,
+        })))
+    }
+
+    async fn semantic_tokens_full_delta(
+        &self,
+        params: SemanticTokensDeltaParams,
+    ) -> Result<Option<SemanticTokensFullDeltaResult>> {
+        let uri = params.text_document.uri.to_string();
+        let Some(document) = self.document_map.get(&uri).map(|rope| rope.to_string()) else {
+            return Ok(None);
+        };
+        let Some(ast) = self.ast_map.get(&uri) else {
+            return Ok(None);
+        };
+        let Some(ast) = ast.as_ref() else {
+            return Ok(None);
+        };
+        let data = semantic_tokens(&document, ast, self.position_encoding());
+        let result_id = self.next_semantic_tokens_result_id();
+        let previous = self
+            .semantic_tokens_map
+            .insert(uri, (result_id.clone(), data.clone()));
+        Ok(Some(match previous {
+            Some((previous_result_id, previous))
+                if previous_result_id == params.previous_result_id =>
+            {
+                SemanticTokensFullDeltaResult::TokensDelta(SemanticTokensDelta {
+                    result_id: Some(result_id),
+                    edits: semantic_tokens_edits(&previous, &data),
+                })
+            }
+            _ => SemanticTokensFullDeltaResult::Tokens(SemanticTokens {
+                result_id: Some(result_id),
+                data,
+            }),
+        }))
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let uri = params.text_document.uri.to_string();
+        let (Some(rope), Some(ast)) = (self.document_map.get(&uri), self.ast_map.get(&uri)) else {
+            return Ok(None);
+        };
+        Ok(ast.as_ref().map(|ast| {
+            DocumentSymbolResponse::Nested(document_symbols(&rope, ast, self.position_encoding()))
+        }))
+    }
+
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let uri = params.text_document.uri.to_string();
+        let (Some(rope), Some(ast)) = (self.document_map.get(&uri), self.ast_map.get(&uri)) else {
+            return Ok(None);
+        };
+        Ok(ast.as_ref().map(|ast| folding_ranges(&rope, ast)))
+    }
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let uri = params.text_document.uri.to_string();
+        Ok(self
+            .document_map
+            .get(&uri)
+            .and_then(|rope| formatting(&rope, self.position_encoding())))
+    }
+
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let uri = params.text_document.uri.to_string();
+        Ok(self
+            .document_map
+            .get(&uri)
+            .and_then(|rope| range_formatting(&rope, params.range, self.position_encoding())))
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let Some(rope) = self.document_map.get(&uri.to_string()) else {
+            return Ok(None);
+        };
+        let mut actions = code_actions(
+            &uri,
+            &rope,
+            &params.context.diagnostics,
+            self.section_spelling(),
+            self.position_encoding(),
+        );
+        if let Some(ast) = self.ast_map.get(&uri.to_string()) {
+            if let Some(ast) = ast.as_ref() {
+                actions.extend(refactors(
+                    &uri,
+                    &rope,
+                    ast,
+                    params.range,
+                    &self.variables_file_texts(),
+                    self.workspace_edit_support(),
+                    self.position_encoding(),
+                ));
+            }
+        }
+        Ok(Some(actions))
+    }
+
     async fn did_change_configuration(&self, _: DidChangeConfigurationParams) {
         debug!("configuration changed!");
     }
 
-    async fn did_change_workspace_folders(&self, _: DidChangeWorkspaceFoldersParams) {
+    async fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
         debug!("workspace folders changed!");
+        for folder in params.event.removed {
+            let prefix = format!("{}/", folder.uri.as_str().trim_end_matches('/'));
+            self.workspace_symbols_map
+                .retain(|uri, _| !uri.starts_with(&prefix));
+        }
+        for folder in params.event.added {
+            if let Ok(path) = folder.uri.to_file_path() {
+                self.index_folder(path).await;
+            }
+        }
     }
 
-    async fn did_change_watched_files(&self, _: DidChangeWatchedFilesParams) {
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
         debug!("watched files have changed!");
-    }
-
-    async fn execute_command(&self, _: ExecuteCommandParams) -> Result<Option<Value>> {
-        debug!("command executed!");
-
-        match self.client.apply_edit(WorkspaceEdit::default()).await {
-            Ok(res) if res.applied => self.client.log_message(MessageType::INFO, "applied").await,
-            Ok(_) => self.client.log_message(MessageType::INFO, "rejected").await,
-            Err(err) => self.client.log_message(MessageType::ERROR, err).await,
+        let mut paths = vec![];
+        let mut variables_changed = false;
+        for change in params.changes {
+            let Ok(path) = change.uri.to_file_path() else {
+                continue;
+            };
+            if self.is_variables_file(&change.uri) {
+                variables_changed = true;
+                continue;
+            }
+            if !is_hurl_file(&path) {
+                continue;
+            }
+            if change.typ == FileChangeType::DELETED {
+                self.workspace_symbols_map.remove(&change.uri.to_string());
+            } else {
+                paths.push(path);
+            }
+        }
+        self.index_files(paths).await;
+        if variables_changed {
+            self.reload_external_variables();
+            self.refresh_diagnostics().await;
         }
+    }
 
-        Ok(None)
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        let symbols = self
+            .workspace_symbols_map
+            .iter()
+            .flat_map(|file| {
+                file.value()
+                    .iter()
+                    .filter(|symbol| matches_query(symbol, &params.query))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Ok(Some(symbols))
     }
 }
 
 #[allow(unused)]
-struct TextDocumentItem<'a> {
+struct TextDocumentItem {
     uri: Url,
-    text: &'a str,
+    rope: Rope,
     version: Option<i32>,
 }
 
 impl Backend {
-    async fn on_change<'a>(&self, params: TextDocumentItem<'a>) {
+    /// Asks the client to report the hurl files and the variables files changed outside of
+    /// the editor
+    async fn register_file_watchers(&self) {
+        let variables_files = self
+            .variables_files
+            .read()
+            .map(|paths| paths.clone())
+            .unwrap_or_default();
+        let globs = std::iter::once("**/*.hurl".to_string()).chain(
+            variables_files
+                .iter()
+                .map(|path| path.to_string_lossy().into_owned()),
+        );
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: globs
+                .map(|glob| FileSystemWatcher {
+                    glob_pattern: GlobPattern::String(glob),
+                    kind: None,
+                })
+                .collect(),
+        };
+        let registration = Registration {
+            id: "hurl-files".to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options: serde_json::to_value(options).ok(),
+        };
+        if let Err(err) = self.client.register_capability(vec![registration]).await {
+            debug!("could not watch the hurl files: {}", err);
+        }
+    }
+
+    /// Indexes the symbols of every hurl file of the folder for `workspace/symbol`
+    async fn index_folder(&self, folder: PathBuf) {
+        let paths = tokio::task::spawn_blocking(move || find_hurl_files(&folder))
+            .await
+            .unwrap_or_default();
+        self.index_files(paths).await;
+    }
+
+    /// Reads and parses the files on the blocking thread pool so that a large workspace
+    /// doesn't hold up the other requests. The open documents are indexed from their
+    /// unsaved text instead.
+    async fn index_files(&self, paths: Vec<PathBuf>) {
+        let encoding = self.position_encoding();
+        let indexed = tokio::task::spawn_blocking(move || {
+            paths
+                .iter()
+                .filter_map(|path| {
+                    let uri = Url::from_file_path(path).ok()?;
+                    let text = std::fs::read_to_string(path).ok()?;
+                    let (ast, _) = parse_ast(&text);
+                    let symbols =
+                        workspace_file_symbols(&uri, &Rope::from_str(&text), &ast?, encoding);
+                    Some((uri, symbols))
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+        for (uri, symbols) in indexed {
+            if !self.document_map.contains_key(&uri.to_string()) {
+                self.workspace_symbols_map.insert(uri.to_string(), symbols);
+            }
+        }
+    }
+
+    /// The variables of the variables files and the environment
+    fn external_variables(&self) -> ExternalVariables {
+        self.external_variables
+            .read()
+            .map(|variables| variables.clone())
+            .unwrap_or_default()
+    }
+
+    /// Reads the variables files again, when the server starts and when one of them changes
+    fn reload_external_variables(&self) {
+        let paths = self
+            .variables_files
+            .read()
+            .map(|paths| paths.clone())
+            .unwrap_or_default();
+        let variables = ExternalVariables::read(&paths, std::env::vars());
+        if let Ok(mut external_variables) = self.external_variables.write() {
+            *external_variables = variables;
+        }
+    }
+
+    fn is_variables_file(&self, uri: &Url) -> bool {
+        let Ok(path) = uri.to_file_path() else {
+            return false;
+        };
+        self.variables_files
+            .read()
+            .is_ok_and(|paths| paths.contains(&path))
+    }
+
+    /// The uri and text of the variables files that can be read, with the text of the
+    /// editor for the ones that are open
+    fn variables_file_texts(&self) -> Vec<(Url, String)> {
+        self.external_variables()
+            .files
+            .into_iter()
+            .filter_map(|file| {
+                let uri = Url::from_file_path(&file.path).ok()?;
+                let text = match self.document_map.get(&uri.to_string()) {
+                    Some(rope) => rope.to_string(),
+                    None => file.text,
+                };
+                Some((uri, text))
+            })
+            .collect()
+    }
+
+    fn workspace_edit_support(&self) -> WorkspaceEditSupport {
+        self.client_capabilities
+            .read()
+            .map(|capabilities| WorkspaceEditSupport::of(&capabilities))
+            .unwrap_or_default()
+    }
+
+    /// Publishes the diagnostics of the documents again with their current AST, for
+    /// instance when the variables they use changed
+    async fn refresh_diagnostics(&self) {
+        let documents: Vec<(String, Rope)> = self
+            .document_map
+            .iter()
+            .map(|document| (document.key().clone(), document.value().clone()))
+            .collect();
+        for (uri, rope) in documents {
+            let Ok(url) = Url::parse(&uri) else {
+                continue;
+            };
+            let ast = self.ast_map.get(&uri).and_then(|ast| ast.clone());
+            let errs = self
+                .parse_errors_map
+                .get(&uri)
+                .map(|errs| errs.clone())
+                .unwrap_or_default();
+            let item = TextDocumentItem {
+                uri: url,
+                rope,
+                version: None,
+            };
+            self.on_change(item, (ast, errs)).await;
+        }
+    }
+
+    fn position_encoding(&self) -> PositionEncoding {
+        self.position_encoding
+            .read()
+            .map(|encoding| *encoding)
+            .unwrap_or_default()
+    }
+
+    fn section_spelling(&self) -> Option<SectionSpelling> {
+        self.section_spelling
+            .read()
+            .map(|spelling| *spelling)
+            .unwrap_or_default()
+    }
+
+    fn next_semantic_tokens_result_id(&self) -> String {
+        self.semantic_tokens_result_id
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string()
+    }
+
+    /// Stores the document with its AST and publishes its diagnostics
+    async fn on_change(
+        &self,
+        params: TextDocumentItem,
+        (ast, errs): (Option<Ast>, Vec<ParseError>),
+    ) {
         dbg!(&params.version);
-        let rope = ropey::Rope::from_str(params.text);
+        let rope = params.rope;
         self.document_map
             .insert(params.uri.to_string(), rope.clone());
-        debug!("about to parse document");
-        debug!("document: {}", params.text);
+        debug!("document: {}", rope);
 
         let mut diagnostics: Vec<Diagnostic> = vec![];
-        let (ast, errs): (Option<Ast>, Vec<Rich<'_, char>>) =
-            hurl_parser::parser::parser::parse_ast(params.text);
 
+        let encoding = self.position_encoding();
+        if let Some(ast) = &ast {
+            diagnostics.extend(variable_diagnostics(
+                &rope,
+                ast,
+                &self.external_variables(),
+                encoding,
+            ));
+            diagnostics.extend(type_diagnostics(&rope, ast, encoding));
+            diagnostics.extend(section_diagnostics(
+                &rope,
+                ast,
+                self.section_spelling(),
+                encoding,
+            ));
+        }
+        let symbols = ast
+            .as_ref()
+            .map(|ast| workspace_file_symbols(&params.uri, &rope, ast, encoding))
+            .unwrap_or_default();
+        self.workspace_symbols_map
+            .insert(params.uri.to_string(), symbols);
         self.ast_map.insert(params.uri.to_string(), ast);
+        self.parse_errors_map
+            .insert(params.uri.to_string(), errs.clone());
         for err in errs {
             let span = err.span();
-            let start_position = offset_to_position(span.start, &rope);
-            let end_position = offset_to_position(span.end, &rope);
-            let diag = start_position
-                .and_then(|start| end_position.map(|end| (start, end)))
-                .map(|(start, end)| {
-                    Diagnostic::new_simple(Range::new(start, end), format!("{:?}", err))
-                });
+            let related_information: Vec<DiagnosticRelatedInformation> = err
+                .labels()
+                .iter()
+                .filter_map(|label| {
+                    byte_span_to_range(label.span, &rope, encoding).map(|range| {
+                        DiagnosticRelatedInformation {
+                            location: Location::new(params.uri.clone(), range),
+                            message: label.message.clone(),
+                        }
+                    })
+                })
+                .collect();
+            let diag = byte_span_to_range(span, &rope, encoding).map(|range| {
+                Diagnostic::new(
+                    range,
+                    Some(DiagnosticSeverity::ERROR),
+                    Some(NumberOrString::String(err.code().to_string())),
+                    Some("hurl".to_string()),
+                    err.message(),
+                    (!related_information.is_empty()).then_some(related_information),
+                    None,
+                )
+            });
             if let Some(diag) = diag {
                 diagnostics.push(diag);
             }
@@ -218,7 +912,7 @@ impl Backend {
     }
 }
 
-const VERSION_STRING: &'static str = env!("VERSION_STRING");
+const VERSION_STRING: &str = env!("VERSION_STRING");
 
 #[tokio::main]
 async fn main() {
@@ -242,7 +936,17 @@ async fn main() {
     let (service, socket) = LspService::build(|client| Backend {
         client,
         ast_map: DashMap::new(),
+        parse_errors_map: DashMap::new(),
         document_map: DashMap::new(),
+        variables_files: RwLock::new(vec![]),
+        external_variables: RwLock::new(ExternalVariables::default()),
+        semantic_tokens_map: DashMap::new(),
+        semantic_tokens_result_id: AtomicU64::new(0),
+        workspace_folders: RwLock::new(vec![]),
+        workspace_symbols_map: DashMap::new(),
+        position_encoding: RwLock::new(PositionEncoding::default()),
+        section_spelling: RwLock::new(None),
+        client_capabilities: RwLock::new(ClientCapabilities::default()),
     })
     .finish();
 
@@ -0,0 +1,1134 @@
+use std::collections::HashMap;
+
+use hurl_parser::parser::expr::is_variable_name;
+use hurl_parser::parser::types::{
+    self, Ast, Bytes, Entry, InterpolatedString, InterpolatedStringPart, Json, MultipartFormParam,
+    PredicateFunc, PredicateValue, RequestOption, RequestOptionsSection, RequestSection,
+    ResponseSection, Span, VariableDefinitionOption, VariableValue,
+};
+use hurl_parser::parser::visit::templates;
+use ropey::Rope;
+use tower_lsp::lsp_types::{
+    AnnotatedTextEdit, ChangeAnnotation, ClientCapabilities, CodeAction, CodeActionKind,
+    CodeActionOrCommand, DocumentChanges, OneOf, OptionalVersionedTextDocumentIdentifier, Range,
+    TextDocumentEdit, TextEdit, Url, WorkspaceEdit,
+};
+
+use crate::sections::section_header;
+use crate::type_check::{literal_type, ValueType};
+use crate::utils::{byte_span_to_range, position_to_byte_offset, PositionEncoding};
+use crate::variables::{definition, occurrences, OccurrenceKind};
+use crate::variables_file::VariablesFile;
+
+/// The refactorings available for the selected range:
+/// - Extract to variable replaces a literal selected in a value of a request with a
+///   template and defines the variable with a `variable` option of the entry.
+/// - Inline variable replaces the templates reading the `variable` option under the cursor
+///   with its value and removes the option.
+/// - Move to variables file removes the `variable` option under the cursor and writes it
+///   to the last of the variables files, given with their text, which overrides the others.
+pub fn refactors(
+    uri: &Url,
+    rope: &Rope,
+    ast: &Ast,
+    range: Range,
+    variables_files: &[(Url, String)],
+    edit_support: WorkspaceEditSupport,
+    encoding: PositionEncoding,
+) -> Vec<CodeActionOrCommand> {
+    let (Some(start), Some(end)) = (
+        position_to_byte_offset(range.start, rope, encoding),
+        position_to_byte_offset(range.end, rope, encoding),
+    ) else {
+        return vec![];
+    };
+    let document = rope.to_string();
+    let selection = Span::new(start, end);
+    [
+        extract_variable(uri, &document, rope, ast, selection, encoding),
+        inline_variable(uri, &document, rope, ast, selection, encoding),
+        move_to_variables_file(
+            uri,
+            &document,
+            rope,
+            ast,
+            selection,
+            variables_files,
+            encoding,
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|action| edit_support.restrict(action))
+    .map(CodeActionOrCommand::CodeAction)
+    .collect()
+}
+
+/// The workspace edits the client can apply, from its `workspace.workspaceEdit` capabilities
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkspaceEditSupport {
+    /// Edits given as `documentChanges` rather than as `changes`
+    pub document_changes: bool,
+    /// Edits annotated with a change the user confirms
+    pub change_annotations: bool,
+}
+
+impl WorkspaceEditSupport {
+    /// The support announced by the client when it initialized the server
+    pub fn of(capabilities: &ClientCapabilities) -> WorkspaceEditSupport {
+        let workspace_edit = capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.workspace_edit.as_ref());
+        WorkspaceEditSupport {
+            document_changes: workspace_edit
+                .and_then(|workspace_edit| workspace_edit.document_changes)
+                .unwrap_or(false),
+            change_annotations: workspace_edit
+                .is_some_and(|workspace_edit| workspace_edit.change_annotation_support.is_some()),
+        }
+    }
+
+    /// The action with its edit given in a form the client applies. Without change
+    /// annotations the edits are applied without a confirmation, so the title of the action
+    /// has to warn about them.
+    fn restrict(self, mut action: CodeAction) -> CodeAction {
+        let Some(edit) = &mut action.edit else {
+            return action;
+        };
+        if !self.change_annotations || !self.document_changes {
+            edit.change_annotations = None;
+        }
+        if let Some(DocumentChanges::Edits(document_edits)) = &edit.document_changes {
+            let text_edits = document_edits.iter().map(|document_edit| {
+                let edits = document_edit.edits.iter().map(|edit| match edit {
+                    OneOf::Left(edit) => edit.clone(),
+                    OneOf::Right(edit) => edit.text_edit.clone(),
+                });
+                (document_edit.text_document.uri.clone(), edits)
+            });
+            if !self.document_changes {
+                let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+                for (uri, edits) in text_edits {
+                    changes.entry(uri).or_default().extend(edits);
+                }
+                edit.changes = Some(changes);
+                edit.document_changes = None;
+            } else if !self.change_annotations {
+                let document_edits = text_edits
+                    .map(|(uri, edits)| TextDocumentEdit {
+                        text_document: OptionalVersionedTextDocumentIdentifier {
+                            uri,
+                            version: None,
+                        },
+                        edits: edits.map(OneOf::Left).collect(),
+                    })
+                    .collect();
+                edit.document_changes = Some(DocumentChanges::Edits(document_edits));
+            }
+        }
+        action
+    }
+}
+
+/// A value of a request a literal can be extracted from
+struct Literal<'a> {
+    /// The key of the header, key-value or JSON member holding the value
+    key: Option<&'a InterpolatedString>,
+    /// The span of the content of the value, without the quotes of a JSON string
+    span: Span,
+    value: &'a InterpolatedString,
+}
+
+/// The values of the request of the entry a literal can be extracted from
+fn literals(entry: &Entry) -> Vec<Literal<'_>> {
+    let request = &entry.request;
+    let mut literals = vec![];
+    if let types::Url::Url(url) = &request.url.0 {
+        literals.push(Literal {
+            key: None,
+            span: url.span,
+            value: url,
+        });
+    }
+    let mut key_values: Vec<_> = request.headers.iter().collect();
+    for section in &request.request_sections {
+        match section {
+            RequestSection::BasicAuthSection(section) => key_values.extend(&section.key_values),
+            RequestSection::QueryStringParamsSection(section) => {
+                key_values.extend(&section.queries)
+            }
+            RequestSection::FormParamsSection(section) => key_values.extend(&section.params),
+            RequestSection::CookiesSection(section) => key_values.extend(&section.cookies),
+            RequestSection::MultipartFormDataSection(section) => {
+                key_values.extend(section.params.iter().filter_map(|param| match param {
+                    MultipartFormParam::KeyValueParam(key_value) => Some(key_value),
+                    MultipartFormParam::FileParam(_) => None,
+                }))
+            }
+            RequestSection::OptionsSection(_) | RequestSection::UnknownSection(_) => {}
+        }
+    }
+    literals.extend(key_values.into_iter().map(|key_value| Literal {
+        key: Some(&key_value.key),
+        span: key_value.value.span,
+        value: &key_value.value,
+    }));
+    if let Some(Bytes::JsonValue(json)) = request.body.as_ref().map(|body| &body.bytes) {
+        json_literals(None, json, &mut literals);
+    }
+    literals
+}
+
+fn json_literals<'a>(
+    key: Option<&'a InterpolatedString>,
+    json: &'a Json,
+    literals: &mut Vec<Literal<'a>>,
+) {
+    match json {
+        Json::Object(key_values) => {
+            for key_value in key_values {
+                json_literals(Some(&key_value.key), &key_value.value.0, literals);
+            }
+        }
+        Json::Array(values) => {
+            for (value, _) in values {
+                json_literals(key, value, literals);
+            }
+        }
+        Json::InterpolatedString(value) => literals.push(Literal {
+            key,
+            span: Span::new(value.span.start + 1, value.span.end - 1),
+            value,
+        }),
+        Json::Invalid
+        | Json::Str(_)
+        | Json::Num(_)
+        | Json::Bool(_)
+        | Json::Null
+        | Json::Template(_) => {}
+    }
+}
+
+/// The name of the extracted variable derived from the key of the value, like `user_agent`
+/// for a `User-Agent` header, which is not already used in the document
+fn variable_name(key: Option<&InterpolatedString>, used: &[String]) -> String {
+    let key = match key.map(|key| &key.parts[..]) {
+        Some([InterpolatedStringPart::Str(key)]) => key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>(),
+        _ => String::new(),
+    };
+    let key = key.trim_matches('_');
+    let name = if is_variable_name(key) { key } else { "value" };
+    let mut candidate = name.to_string();
+    let mut suffix = 1;
+    while used.contains(&candidate) {
+        suffix += 1;
+        candidate = format!("{}_{}", name, suffix);
+    }
+    candidate
+}
+
+/// The value of a `variable` option for a literal which is left unquoted when it can't be
+/// read as a number, a boolean or null
+fn variable_value(literal: &str) -> String {
+    let unquoted = literal.starts_with(|c: char| c.is_ascii_alphabetic())
+        && literal
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c))
+        && !["true", "false", "null"]
+            .iter()
+            .any(|keyword| literal.starts_with(keyword));
+    if unquoted {
+        literal.to_string()
+    } else {
+        format!("\"{}\"", literal)
+    }
+}
+
+/// The offset of the start of the line after the one of the offset, with the newline to
+/// write first when the offset is on the last line of a document without a final newline
+fn next_line(rope: &Rope, offset: usize) -> (usize, &'static str) {
+    let line = rope.byte_to_line(offset);
+    let start = rope.line_to_byte(line + 1);
+    if start == rope.len_bytes() && rope.len_bytes() > 0 && rope.byte(start - 1) != b'\n' {
+        (start, "\n")
+    } else {
+        (start, "")
+    }
+}
+
+fn extract_variable(
+    uri: &Url,
+    document: &str,
+    rope: &Rope,
+    ast: &Ast,
+    selection: Span,
+    encoding: PositionEncoding,
+) -> Option<CodeAction> {
+    if selection.start == selection.end {
+        return None;
+    }
+    let text = document.get(selection.start..selection.end)?;
+    //Escaped characters would have to be written differently in the variable value
+    if text.contains(['\\', '"', '\n']) {
+        return None;
+    }
+    let entry = ast
+        .entries
+        .iter()
+        .find(|entry| entry.span.start <= selection.start && selection.end <= entry.span.end)?;
+    let literal = literals(entry).into_iter().find(|literal| {
+        literal.span.start <= selection.start && selection.end <= literal.span.end
+    })?;
+    let overlaps_template = literal.value.parts.iter().any(|part| match part {
+        InterpolatedStringPart::Template(template) => {
+            template.span.start < selection.end && selection.start < template.span.end
+        }
+        InterpolatedStringPart::Str(_) => false,
+    });
+    if overlaps_template {
+        return None;
+    }
+
+    let used: Vec<String> = occurrences(ast)
+        .into_iter()
+        .map(|occurrence| occurrence.name)
+        .collect();
+    let name = variable_name(literal.key, &used);
+    let option = format!("variable: {}={}\n", name, variable_value(text));
+    let options = entry
+        .request
+        .request_sections
+        .iter()
+        .find_map(|section| match section {
+            RequestSection::OptionsSection(section) => Some(section),
+            _ => None,
+        });
+    let (offset, new_text) = match options {
+        Some(section) => {
+            let (offset, newline) = next_line(rope, section_header(document, section.span).start);
+            (offset, format!("{}{}", newline, option))
+        }
+        None => {
+            let last_line = entry
+                .request
+                .headers
+                .last()
+                .map_or(entry.request.method.span.start, |header| header.span.start);
+            let (offset, newline) = next_line(rope, last_line);
+            (offset, format!("{}[Options]\n{}", newline, option))
+        }
+    };
+    let mut edits = vec![
+        TextEdit::new(
+            byte_span_to_range(selection, rope, encoding)?,
+            format!("{{{{{}}}}}", name),
+        ),
+        TextEdit::new(
+            byte_span_to_range(Span::new(offset, offset), rope, encoding)?,
+            new_text,
+        ),
+    ];
+    edits.sort_by_key(|edit| edit.range.start);
+    Some(refactor(
+        uri,
+        format!("Extract to variable `{}`", name),
+        CodeActionKind::REFACTOR_EXTRACT,
+        edits,
+    ))
+}
+
+/// The spans of the templates that are a whole JSON or predicate value rather than a part
+/// of a string, where a string has to be written with quotes
+fn standalone_templates(ast: &Ast) -> Vec<Span> {
+    fn json_templates(json: &Json, spans: &mut Vec<Span>) {
+        match json {
+            Json::Object(key_values) => {
+                for key_value in key_values {
+                    json_templates(&key_value.value.0, spans);
+                }
+            }
+            Json::Array(values) => {
+                for (value, _) in values {
+                    json_templates(value, spans);
+                }
+            }
+            Json::Template(template) => spans.push(template.span),
+            Json::Invalid
+            | Json::Str(_)
+            | Json::InterpolatedString(_)
+            | Json::Num(_)
+            | Json::Bool(_)
+            | Json::Null => {}
+        }
+    }
+
+    let mut spans = vec![];
+    for entry in &ast.entries {
+        let response = entry.response.as_deref();
+        let bodies = [
+            entry.request.body.as_ref(),
+            response.and_then(|response| response.body.as_ref()),
+        ];
+        for body in bodies.into_iter().flatten() {
+            if let Bytes::JsonValue(json) = &body.bytes {
+                json_templates(json, &mut spans);
+            }
+        }
+        let Some(response) = response else {
+            continue;
+        };
+        for section in &response.response_sections {
+            let ResponseSection::AssertsSection(section) = section else {
+                continue;
+            };
+            for assert in &section.asserts {
+                match &assert.predicate.function.0 {
+                    PredicateFunc::Equal { value }
+                    | PredicateFunc::NotEqual { value }
+                    | PredicateFunc::Greater { value }
+                    | PredicateFunc::GreaterOrEqual { value }
+                    | PredicateFunc::Less { value }
+                    | PredicateFunc::LessOrEqual { value }
+                    | PredicateFunc::StartWith { value }
+                    | PredicateFunc::EndWith { value }
+                    | PredicateFunc::Contain { value }
+                    | PredicateFunc::Match { value }
+                    | PredicateFunc::Include { value } => {
+                        if let PredicateValue::Template(template) = &value.0 {
+                            spans.push(template.span);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    spans
+}
+
+/// A `variable` option of an `[Options]` section
+struct VariableOption<'a> {
+    variable: &'a VariableDefinitionOption,
+    span: Span,
+    section: &'a RequestOptionsSection,
+}
+
+impl VariableOption<'_> {
+    /// The text of the value, without the quotes of a string, and whether it is a string.
+    /// Values with escaped characters are left out since they would have to be written
+    /// differently elsewhere.
+    fn value<'a>(&self, document: &'a str) -> Option<(&'a str, bool)> {
+        let (value, span) = &self.variable.value;
+        let text = document.get(span.start..span.end)?;
+        let (text, is_string) = match value {
+            VariableValue::String(_) => (
+                text.strip_prefix('"')
+                    .and_then(|text| text.strip_suffix('"'))
+                    .unwrap_or(text),
+                true,
+            ),
+            VariableValue::Invalid => return None,
+            _ => (text, false),
+        };
+        (!text.contains(['\\', '"'])).then_some((text, is_string))
+    }
+
+    /// The edit deleting the lines of the option, along with the section header when it is
+    /// the only option of the section
+    fn removal(&self, document: &str, rope: &Rope, encoding: PositionEncoding) -> Option<TextEdit> {
+        let first_line = if self.section.options.len() == 1 {
+            section_header(document, self.section.span).start
+        } else {
+            self.span.start
+        };
+        let start = rope.line_to_byte(rope.byte_to_line(first_line));
+        let (end, _) = next_line(rope, self.span.start);
+        Some(TextEdit::new(
+            byte_span_to_range(Span::new(start, end), rope, encoding)?,
+            String::new(),
+        ))
+    }
+}
+
+/// The `variable` option under the offset
+fn variable_option_at(ast: &Ast, offset: usize) -> Option<VariableOption<'_>> {
+    ast.entries
+        .iter()
+        .flat_map(|entry| &entry.request.request_sections)
+        .find_map(|section| {
+            let RequestSection::OptionsSection(section) = section else {
+                return None;
+            };
+            section
+                .options
+                .iter()
+                .find_map(|(option, span)| match option {
+                    RequestOption::Variable(variable)
+                        if span.start <= offset && offset <= span.end =>
+                    {
+                        Some(VariableOption {
+                            variable,
+                            span: *span,
+                            section,
+                        })
+                    }
+                    _ => None,
+                })
+        })
+}
+
+fn inline_variable(
+    uri: &Url,
+    document: &str,
+    rope: &Rope,
+    ast: &Ast,
+    selection: Span,
+    encoding: PositionEncoding,
+) -> Option<CodeAction> {
+    let option = variable_option_at(ast, selection.start)?;
+    let variable = option.variable;
+    let (text, is_string) = option.value(document)?;
+
+    let occurrences = occurrences(ast);
+    let definition_occurrence = occurrences.iter().find(|occurrence| {
+        occurrence.kind == OccurrenceKind::Option && occurrence.span == variable.name.1
+    })?;
+    let uses: Vec<_> = occurrences
+        .iter()
+        .filter(|occurrence| {
+            !occurrence.kind.is_definition()
+                && definition(&occurrences, occurrence) == Some(definition_occurrence)
+        })
+        .collect();
+    let templates = templates(ast);
+    let standalone = standalone_templates(ast);
+    let mut edits = vec![];
+    for occurrence in uses {
+        //A `variable` query or a filter can't be applied to a literal
+        let template = templates
+            .iter()
+            .find(|template| template.expr.variable.1 == occurrence.span)
+            .filter(|template| template.expr.filters.is_empty())?;
+        let new_text = if is_string && standalone.contains(&template.span) {
+            format!("\"{}\"", text)
+        } else {
+            text.to_string()
+        };
+        edits.push(TextEdit::new(
+            byte_span_to_range(template.span, rope, encoding)?,
+            new_text,
+        ));
+    }
+
+    edits.push(option.removal(document, rope, encoding)?);
+    edits.sort_by_key(|edit| edit.range.start);
+    Some(refactor(
+        uri,
+        format!("Inline variable `{}`", variable.name.0),
+        CodeActionKind::REFACTOR_INLINE,
+        edits,
+    ))
+}
+
+/// The id of the change annotation of a variable moved over a different value
+const CONFLICTING_VALUE: &str = "conflicting-value";
+
+fn move_to_variables_file(
+    uri: &Url,
+    document: &str,
+    rope: &Rope,
+    ast: &Ast,
+    selection: Span,
+    variables_files: &[(Url, String)],
+    encoding: PositionEncoding,
+) -> Option<CodeAction> {
+    let option = variable_option_at(ast, selection.start)?;
+    let name = &option.variable.name.0;
+    //A variables file has no templates
+    if let VariableValue::String(value) = &option.variable.value.0 {
+        let templated = value
+            .parts
+            .iter()
+            .any(|part| matches!(part, InterpolatedStringPart::Template(_)));
+        if templated {
+            return None;
+        }
+    }
+    //A variables file has no quotes either, so a string must still read as a string
+    let (value, is_string) = option.value(document)?;
+    if is_string && literal_type(value) != ValueType::String {
+        return None;
+    }
+    let (file_uri, file_text) = variables_files.last()?;
+    let file_name = file_uri
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or_else(|| file_uri.as_str());
+
+    let files: Vec<VariablesFile> = variables_files
+        .iter()
+        .map(|(uri, text)| VariablesFile::parse(uri.to_file_path().unwrap_or_default(), text))
+        .collect();
+    //The variable is only written when the files don't already give it the same value
+    let written = files
+        .iter()
+        .rev()
+        .find_map(|file| file.get(name))
+        .is_none_or(|variable| variable.value != value);
+    let (target, others) = files.split_last()?;
+    //The line of the target file defining the variable is replaced rather than left dead
+    let replaced = target.get(name).filter(|_| written);
+    let conflict = others.iter().filter(|_| written).find_map(|file| {
+        let variable = file.get(name).filter(|variable| variable.value != value)?;
+        Some((file, variable))
+    });
+
+    let mut document_changes = vec![TextDocumentEdit {
+        text_document: OptionalVersionedTextDocumentIdentifier {
+            uri: uri.clone(),
+            version: None,
+        },
+        edits: vec![OneOf::Left(option.removal(document, rope, encoding)?)],
+    }];
+    if written {
+        let file_rope = Rope::from_str(file_text);
+        let text_edit = match replaced {
+            Some(variable) => {
+                let start = variable.span.start;
+                let line = file_text[start..].lines().next().unwrap_or_default();
+                TextEdit::new(
+                    byte_span_to_range(Span::new(start, start + line.len()), &file_rope, encoding)?,
+                    format!("{}={}", name, value),
+                )
+            }
+            None => {
+                let end = file_rope.len_bytes();
+                let newline = if file_text.is_empty() || file_text.ends_with('\n') {
+                    ""
+                } else {
+                    "\n"
+                };
+                TextEdit::new(
+                    byte_span_to_range(Span::new(end, end), &file_rope, encoding)?,
+                    format!("{}{}={}\n", newline, name, value),
+                )
+            }
+        };
+        let edit = match conflict {
+            Some(_) => OneOf::Right(AnnotatedTextEdit {
+                text_edit,
+                annotation_id: CONFLICTING_VALUE.to_string(),
+            }),
+            None => OneOf::Left(text_edit),
+        };
+        document_changes.push(TextDocumentEdit {
+            text_document: OptionalVersionedTextDocumentIdentifier {
+                uri: file_uri.clone(),
+                version: None,
+            },
+            edits: vec![edit],
+        });
+    }
+
+    let (title, change_annotations) = match conflict {
+        Some((file, variable)) => {
+            let description = format!(
+                "{} already defines `{}={}`, the moved value overrides it",
+                file.path.display(),
+                name,
+                variable.value
+            );
+            (
+                format!(
+                    "Move `{}` to {} (overrides `{}={}`)",
+                    name, file_name, name, variable.value
+                ),
+                Some(HashMap::from([(
+                    CONFLICTING_VALUE.to_string(),
+                    ChangeAnnotation {
+                        label: "Conflicting variable value".to_string(),
+                        needs_confirmation: Some(true),
+                        description: Some(description),
+                    },
+                )])),
+            )
+        }
+        None => match replaced {
+            Some(variable) => (
+                format!(
+                    "Move `{}` to {} (replaces `{}={}`)",
+                    name, file_name, name, variable.value
+                ),
+                None,
+            ),
+            None => (format!("Move `{}` to {}", name, file_name), None),
+        },
+    };
+    Some(CodeAction {
+        title,
+        kind: Some(CodeActionKind::REFACTOR_REWRITE),
+        edit: Some(WorkspaceEdit {
+            document_changes: Some(DocumentChanges::Edits(document_changes)),
+            change_annotations,
+            ..WorkspaceEdit::default()
+        }),
+        ..CodeAction::default()
+    })
+}
+
+/// A refactoring editing the document
+fn refactor(uri: &Url, title: String, kind: CodeActionKind, edits: Vec<TextEdit>) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some(kind),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), edits)])),
+            ..WorkspaceEdit::default()
+        }),
+        ..CodeAction::default()
+    }
+}
+
+#[cfg(test)]
+mod refactor_tests {
+    use super::*;
+    use crate::test_utils::range;
+    use hurl_parser::parser::parser::parse_ast;
+
+    /// The text with the edits applied
+    fn apply(text: &str, mut edits: Vec<TextEdit>) -> String {
+        let mut rope = Rope::from_str(text);
+        edits.reverse();
+        for edit in edits {
+            let start =
+                position_to_byte_offset(edit.range.start, &rope, PositionEncoding::Utf16).unwrap();
+            let end =
+                position_to_byte_offset(edit.range.end, &rope, PositionEncoding::Utf16).unwrap();
+            rope.remove(rope.byte_to_char(start)..rope.byte_to_char(end));
+            rope.insert(rope.byte_to_char(start), &edit.new_text);
+        }
+        rope.to_string()
+    }
+
+    /// A client applying every kind of workspace edit
+    const FULL_SUPPORT: WorkspaceEditSupport = WorkspaceEditSupport {
+        document_changes: true,
+        change_annotations: true,
+    };
+
+    fn actions(
+        test_str: &str,
+        range: Range,
+        variables_files: &[(Url, String)],
+        edit_support: WorkspaceEditSupport,
+    ) -> Vec<CodeAction> {
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        let (ast, errs) = parse_ast(test_str);
+        assert_eq!(errs, vec![]);
+        refactors(
+            &uri,
+            &Rope::from_str(test_str),
+            &ast.unwrap(),
+            range,
+            variables_files,
+            edit_support,
+            PositionEncoding::Utf16,
+        )
+        .into_iter()
+        .map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) => action,
+            CodeActionOrCommand::Command(command) => panic!("unexpected {:?}", command),
+        })
+        .collect()
+    }
+
+    /// The title of each refactoring of the range with the document it produces
+    fn refactored(test_str: &str, range: Range) -> Vec<(String, String)> {
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        actions(test_str, range, &[], FULL_SUPPORT)
+            .into_iter()
+            .map(|action| {
+                let edits = action.edit.unwrap().changes.unwrap().remove(&uri).unwrap();
+                (action.title, apply(test_str, edits))
+            })
+            .collect()
+    }
+
+    /// The title of the move to the variables file with the text of each edited file and
+    /// the label of the annotation asking for a confirmation
+    type Moved = (String, Vec<(String, String)>, Option<String>);
+
+    fn moved(test_str: &str, range: Range, variables_file: &str) -> Option<Moved> {
+        moved_with(
+            test_str,
+            range,
+            &[("vars.env", variables_file)],
+            FULL_SUPPORT,
+        )
+    }
+
+    /// The move with the variables files given by their name and text
+    fn moved_with(
+        test_str: &str,
+        range: Range,
+        variables_files: &[(&str, &str)],
+        edit_support: WorkspaceEditSupport,
+    ) -> Option<Moved> {
+        let variables_files: Vec<(Url, String)> = variables_files
+            .iter()
+            .map(|(name, text)| {
+                let uri = Url::parse(&format!("file:///{}", name)).unwrap();
+                (uri, text.to_string())
+            })
+            .collect();
+        let action = actions(test_str, range, &variables_files, edit_support)
+            .into_iter()
+            .find(|action| action.kind == Some(CodeActionKind::REFACTOR_REWRITE))?;
+        let edit = action.edit.unwrap();
+        let mut document_edits: Vec<(Url, Vec<TextEdit>)> = match edit.document_changes {
+            Some(DocumentChanges::Edits(document_edits)) => document_edits
+                .into_iter()
+                .map(|document_edit| {
+                    let edits = document_edit
+                        .edits
+                        .into_iter()
+                        .map(|edit| match edit {
+                            OneOf::Left(edit) => edit,
+                            OneOf::Right(edit) => edit.text_edit,
+                        })
+                        .collect();
+                    (document_edit.text_document.uri, edits)
+                })
+                .collect(),
+            Some(DocumentChanges::Operations(_)) => panic!("expected document edits"),
+            None => edit.changes.unwrap().into_iter().collect(),
+        };
+        document_edits.sort_by_key(|(uri, _)| uri.to_string());
+        let files = document_edits
+            .into_iter()
+            .map(|(uri, edits)| {
+                let text = variables_files
+                    .iter()
+                    .find(|(file_uri, _)| *file_uri == uri)
+                    .map_or(test_str, |(_, text)| text);
+                (uri.to_string(), apply(text, edits))
+            })
+            .collect();
+        let label = edit.change_annotations.and_then(|annotations| {
+            let annotation = annotations.get(CONFLICTING_VALUE)?;
+            assert_eq!(annotation.needs_confirmation, Some(true));
+            Some(annotation.label.clone())
+        });
+        Some((action.title, files, label))
+    }
+
+    #[test]
+    fn it_extracts_a_header_value_to_a_new_options_section() {
+        let test_str = r#"GET https://example.org
+User-Agent: hurl-tests
+[Query]
+page: 1
+"#;
+        assert_eq!(
+            refactored(test_str, range(1, 12, 22)),
+            vec![(
+                "Extract to variable `user_agent`".to_string(),
+                r#"GET https://example.org
+User-Agent: {{user_agent}}
+[Options]
+variable: user_agent=hurl-tests
+[Query]
+page: 1
+"#
+                .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn it_extracts_a_json_string_to_the_options_section() {
+        let test_str = r#"POST https://example.org/users
+[Options]
+variable: name=alice
+{"name": "{{name}} smith", "role": "admin"}
+"#;
+        assert_eq!(
+            refactored(test_str, range(3, 18, 24)),
+            vec![(
+                "Extract to variable `name_2`".to_string(),
+                r#"POST https://example.org/users
+[Options]
+variable: name_2=" smith"
+variable: name=alice
+{"name": "{{name}}{{name_2}}", "role": "admin"}
+"#
+                .to_string()
+            )]
+        );
+        assert_eq!(refactored(test_str, range(3, 10, 20)), vec![]);
+    }
+
+    #[test]
+    fn it_extracts_a_part_of_the_url() {
+        let test_str = "GET https://example.org/users";
+        assert_eq!(
+            refactored(test_str, range(0, 4, 23)),
+            vec![(
+                "Extract to variable `value`".to_string(),
+                "GET {{value}}/users\n[Options]\nvariable: value=\"https://example.org\"\n"
+                    .to_string()
+            )]
+        );
+        assert_eq!(refactored(test_str, range(0, 4, 4)), vec![]);
+    }
+
+    #[test]
+    fn it_inlines_a_variable_option() {
+        let test_str = r#"POST https://{{host}}/users
+[Options]
+variable: host=example.org
+{"host": {{host}}, "url": "https://{{host}}"}
+HTTP 200
+[Asserts]
+header "Host" == {{host}}
+
+GET https://{{host}}/users
+"#;
+        assert_eq!(
+            refactored(test_str, range(2, 12, 12)),
+            vec![(
+                "Inline variable `host`".to_string(),
+                r#"POST https://example.org/users
+{"host": "example.org", "url": "https://example.org"}
+HTTP 200
+[Asserts]
+header "Host" == "example.org"
+
+GET https://example.org/users
+"#
+                .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn it_inlines_a_number_and_keeps_the_other_options() {
+        let test_str = r#"GET https://example.org/users?page={{page}}
+[Options]
+verbose: true
+variable: page=2
+HTTP 200
+[Asserts]
+jsonpath "$.page" == {{page}}
+"#;
+        assert_eq!(
+            refactored(test_str, range(3, 0, 0)),
+            vec![(
+                "Inline variable `page`".to_string(),
+                r#"GET https://example.org/users?page=2
+[Options]
+verbose: true
+HTTP 200
+[Asserts]
+jsonpath "$.page" == 2
+"#
+                .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn it_does_not_inline_a_variable_read_by_a_query_or_a_filter() {
+        let test_str = r#"GET https://example.org/{{id urlEncode}}
+[Options]
+variable: id=a-b
+"#;
+        assert_eq!(refactored(test_str, range(2, 10, 10)), vec![]);
+        let test_str = r#"GET https://example.org
+[Options]
+variable: id=a-b
+HTTP 200
+[Asserts]
+variable "id" exists
+"#;
+        assert_eq!(refactored(test_str, range(2, 10, 10)), vec![]);
+    }
+
+    #[test]
+    fn it_moves_a_variable_option_to_the_variables_file() {
+        let test_str = r#"GET https://{{host}}/users
+[Options]
+variable: host="example.org"
+"#;
+        assert_eq!(
+            moved(test_str, range(2, 10, 10), "# shared\ntoken=abc"),
+            Some((
+                "Move `host` to vars.env".to_string(),
+                vec![
+                    (
+                        "file:///test.hurl".to_string(),
+                        "GET https://{{host}}/users\n".to_string()
+                    ),
+                    (
+                        "file:///vars.env".to_string(),
+                        "# shared\ntoken=abc\nhost=example.org\n".to_string()
+                    ),
+                ],
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_replaces_the_value_the_variables_file_already_defines() {
+        let test_str = r#"GET https://{{host}}/users
+[Options]
+verbose: true
+variable: host=staging.example.org
+"#;
+        assert_eq!(
+            moved(
+                test_str,
+                range(3, 0, 0),
+                "host = example.org\r\ntoken=abc\r\n"
+            ),
+            Some((
+                "Move `host` to vars.env (replaces `host=example.org`)".to_string(),
+                vec![
+                    (
+                        "file:///test.hurl".to_string(),
+                        "GET https://{{host}}/users\n[Options]\nverbose: true\n".to_string()
+                    ),
+                    (
+                        "file:///vars.env".to_string(),
+                        "host=staging.example.org\r\ntoken=abc\r\n".to_string()
+                    ),
+                ],
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn it_warns_when_moving_over_a_value_of_another_variables_file() {
+        let test_str = r#"GET https://{{host}}/users
+[Options]
+variable: host=staging.example.org
+"#;
+        let expected = Some((
+            "Move `host` to vars.env (overrides `host=example.org`)".to_string(),
+            vec![
+                (
+                    "file:///test.hurl".to_string(),
+                    "GET https://{{host}}/users\n".to_string(),
+                ),
+                (
+                    "file:///vars.env".to_string(),
+                    "token=abc\nhost=staging.example.org\n".to_string(),
+                ),
+            ],
+            Some("Conflicting variable value".to_string()),
+        ));
+        let files = [
+            ("common.env", "host=example.org\n"),
+            ("vars.env", "token=abc\n"),
+        ];
+        assert_eq!(
+            moved_with(test_str, range(2, 0, 0), &files, FULL_SUPPORT),
+            expected
+        );
+    }
+
+    #[test]
+    fn it_only_warns_in_the_title_when_the_client_has_no_change_annotations() {
+        let test_str = r#"GET https://{{host}}/users
+[Options]
+variable: host=staging.example.org
+"#;
+        let expected = Some((
+            "Move `host` to vars.env (overrides `host=example.org`)".to_string(),
+            vec![
+                (
+                    "file:///test.hurl".to_string(),
+                    "GET https://{{host}}/users\n".to_string(),
+                ),
+                (
+                    "file:///vars.env".to_string(),
+                    "host=staging.example.org\n".to_string(),
+                ),
+            ],
+            None,
+        ));
+        let files = [("common.env", "host=example.org\n"), ("vars.env", "")];
+        let document_changes = WorkspaceEditSupport {
+            document_changes: true,
+            change_annotations: false,
+        };
+        assert_eq!(
+            moved_with(test_str, range(2, 0, 0), &files, document_changes),
+            expected
+        );
+        let changes = WorkspaceEditSupport::default();
+        assert_eq!(
+            moved_with(test_str, range(2, 0, 0), &files, changes),
+            expected
+        );
+    }
+
+    #[test]
+    fn it_does_not_move_a_string_that_would_read_as_another_type() {
+        let test_str = r#"GET https://example.org:{{port}}
+[Options]
+variable: port="8080"
+"#;
+        assert_eq!(moved(test_str, range(2, 0, 0), ""), None);
+        let test_str = r#"GET https://example.org:{{port}}
+[Options]
+variable: port=8080
+"#;
+        assert_eq!(
+            moved(test_str, range(2, 0, 0), "").map(|(_, files, _)| files),
+            Some(vec![
+                (
+                    "file:///test.hurl".to_string(),
+                    "GET https://example.org:{{port}}\n".to_string()
+                ),
+                ("file:///vars.env".to_string(), "port=8080\n".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_only_removes_a_variable_option_the_variables_file_already_defines() {
+        let test_str = r#"GET https://{{host}}/users
+[Options]
+variable: host=example.org
+"#;
+        assert_eq!(
+            moved(test_str, range(2, 0, 0), "host=example.org\n"),
+            Some((
+                "Move `host` to vars.env".to_string(),
+                vec![(
+                    "file:///test.hurl".to_string(),
+                    "GET https://{{host}}/users\n".to_string()
+                )],
+                None
+            ))
+        );
+        let test_str = r#"GET https://{{host}}/users
+[Options]
+variable: host="{{domain}}.org"
+"#;
+        assert_eq!(moved(test_str, range(2, 0, 0), ""), None);
+    }
+}
@@ -0,0 +1,185 @@
+use hurl_parser::parser::types::Ast;
+use ropey::Rope;
+use tower_lsp::lsp_types::{DocumentHighlight, DocumentHighlightKind, Location, Position, Url};
+
+use crate::utils::{byte_span_to_range, position_to_byte_offset, PositionEncoding};
+use crate::variables::{occurrence_at, occurrences, Occurrence};
+
+/// Every occurrence of the variable at the position. The captures and `variable` options
+/// defining it are only included with `include_declaration`.
+pub fn references(
+    uri: &Url,
+    rope: &Rope,
+    ast: &Ast,
+    position: Position,
+    include_declaration: bool,
+    encoding: PositionEncoding,
+) -> Option<Vec<Location>> {
+    let locations = same_variable(rope, ast, position, encoding)?
+        .into_iter()
+        .filter(|occurrence| include_declaration || !occurrence.kind.is_definition())
+        .filter_map(|occurrence| byte_span_to_range(occurrence.span, rope, encoding))
+        .map(|range| Location::new(uri.clone(), range))
+        .collect();
+    Some(locations)
+}
+
+/// Highlights every occurrence of the variable at the position. Definitions are writes and
+/// uses are reads.
+pub fn document_highlights(
+    rope: &Rope,
+    ast: &Ast,
+    position: Position,
+    encoding: PositionEncoding,
+) -> Option<Vec<DocumentHighlight>> {
+    let highlights = same_variable(rope, ast, position, encoding)?
+        .into_iter()
+        .filter_map(|occurrence| {
+            Some(DocumentHighlight {
+                range: byte_span_to_range(occurrence.span, rope, encoding)?,
+                kind: Some(if occurrence.kind.is_definition() {
+                    DocumentHighlightKind::WRITE
+                } else {
+                    DocumentHighlightKind::READ
+                }),
+            })
+        })
+        .collect();
+    Some(highlights)
+}
+
+fn same_variable(
+    rope: &Rope,
+    ast: &Ast,
+    position: Position,
+    encoding: PositionEncoding,
+) -> Option<Vec<Occurrence>> {
+    let offset = position_to_byte_offset(position, rope, encoding)?;
+    let occurrences = occurrences(ast);
+    let name = occurrence_at(&occurrences, offset)?.name.clone();
+    Some(
+        occurrences
+            .into_iter()
+            .filter(|occurrence| occurrence.name == name)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod references_tests {
+    use super::*;
+    use crate::test_utils::range;
+    use crate::utils::byte_offset_to_position;
+    use hurl_parser::parser::parser::parse_ast;
+    use tower_lsp::lsp_types::Range;
+
+    const TEST_STR: &str = r#"POST https://{{host}}/users
+Authorization: Bearer {{token}}
+{"host": "{{host}}"}
+HTTP 200
+[Captures]
+host: header "Host"
+[Asserts]
+variable "host" == "{{host}}"
+body contains "{{host}}"
+"#;
+
+    fn ranges(locations: Vec<Location>) -> Vec<Range> {
+        locations
+            .into_iter()
+            .map(|location| location.range)
+            .collect()
+    }
+
+    #[test]
+    fn it_finds_the_references_of_a_variable() {
+        let rope = Rope::from_str(TEST_STR);
+        let (ast, errs) = parse_ast(TEST_STR);
+        assert_eq!(errs, vec![]);
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        let position = byte_offset_to_position(
+            TEST_STR.find("host").unwrap(),
+            &rope,
+            PositionEncoding::Utf16,
+        )
+        .unwrap();
+        let ast = ast.unwrap();
+        assert_eq!(
+            ranges(references(&uri, &rope, &ast, position, true, PositionEncoding::Utf16).unwrap()),
+            vec![
+                range(0, 15, 19),
+                range(2, 12, 16),
+                range(5, 0, 4),
+                range(7, 10, 14),
+                range(7, 22, 26),
+                range(8, 17, 21),
+            ]
+        );
+        assert_eq!(
+            ranges(
+                references(&uri, &rope, &ast, position, false, PositionEncoding::Utf16).unwrap()
+            ),
+            vec![
+                range(0, 15, 19),
+                range(2, 12, 16),
+                range(7, 10, 14),
+                range(7, 22, 26),
+                range(8, 17, 21),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_highlights_reads_and_writes() {
+        let test_str = r#"GET https://example.org
+[Options]
+variable: id=1
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+[Asserts]
+jsonpath "$.id" == {{id}}
+"#;
+        let rope = Rope::from_str(test_str);
+        let (ast, _) = parse_ast(test_str);
+        let highlights = document_highlights(
+            &rope,
+            &ast.unwrap(),
+            Position::new(7, 22),
+            PositionEncoding::Utf16,
+        )
+        .unwrap();
+        assert_eq!(
+            highlights,
+            vec![
+                DocumentHighlight {
+                    range: range(2, 10, 12),
+                    kind: Some(DocumentHighlightKind::WRITE),
+                },
+                DocumentHighlight {
+                    range: range(5, 0, 2),
+                    kind: Some(DocumentHighlightKind::WRITE),
+                },
+                DocumentHighlight {
+                    range: range(7, 21, 23),
+                    kind: Some(DocumentHighlightKind::READ),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_finds_nothing_outside_of_variables() {
+        let rope = Rope::from_str(TEST_STR);
+        let (ast, _) = parse_ast(TEST_STR);
+        assert_eq!(
+            document_highlights(
+                &rope,
+                &ast.unwrap(),
+                Position::new(0, 2),
+                PositionEncoding::Utf16
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use hurl_parser::parser::expr::is_variable_name;
+use hurl_parser::parser::types::{Ast, ExprValue};
+use hurl_parser::parser::visit::templates;
+use ropey::Rope;
+use tower_lsp::lsp_types::{Position, Range, TextEdit, Url, WorkspaceEdit};
+
+use crate::utils::{byte_span_to_range, position_to_byte_offset, PositionEncoding};
+use crate::variables::{definition, occurrence_at, occurrences, Occurrence};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RenameError {
+    BuiltinFunction(String),
+    UndefinedVariable(String),
+    InvalidName(String),
+    NameCollision(String),
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenameError::BuiltinFunction(name) => {
+                write!(f, "cannot rename the built-in function {name}")
+            }
+            RenameError::UndefinedVariable(name) => write!(
+                f,
+                "cannot rename {name} because it is not captured or defined in this file"
+            ),
+            RenameError::InvalidName(name) => write!(
+                f,
+                "{name:?} is not a valid variable name, it must start with an ascii letter followed by ascii letters, digits, underscores or dashes"
+            ),
+            RenameError::NameCollision(name) => write!(
+                f,
+                "cannot rename to {name} because another capture or variable option named {name} would be read instead"
+            ),
+        }
+    }
+}
+
+/// The range of the variable name at the position if it can be renamed
+pub fn prepare_rename(
+    rope: &Rope,
+    ast: &Ast,
+    position: Position,
+    encoding: PositionEncoding,
+) -> Result<Option<Range>, RenameError> {
+    let Some(offset) = position_to_byte_offset(position, rope, encoding) else {
+        return Ok(None);
+    };
+    let occurrences = occurrences(ast);
+    let Some(occurrence) = renamed_occurrence(ast, &occurrences, offset)? else {
+        return Ok(None);
+    };
+    Ok(byte_span_to_range(occurrence.span, rope, encoding))
+}
+
+/// Renames the capture or `variable` option defining the variable at the position along
+/// with every template and `variable` query reading the value of that definition. The
+/// rename is refused when it would make an occurrence read another definition.
+pub fn rename(
+    uri: &Url,
+    rope: &Rope,
+    ast: &Ast,
+    position: Position,
+    new_name: &str,
+    encoding: PositionEncoding,
+) -> Result<Option<WorkspaceEdit>, RenameError> {
+    if !is_variable_name(new_name) {
+        return Err(RenameError::InvalidName(new_name.to_string()));
+    }
+    let Some(offset) = position_to_byte_offset(position, rope, encoding) else {
+        return Ok(None);
+    };
+    let occurrences = occurrences(ast);
+    let Some(occurrence) = renamed_occurrence(ast, &occurrences, offset)? else {
+        return Ok(None);
+    };
+    let renamed = definition(&occurrences, occurrence);
+    let is_renamed = |candidate: &Occurrence| {
+        candidate.name == occurrence.name && definition(&occurrences, candidate) == renamed
+    };
+    let renamed_occurrences: Vec<Occurrence> = occurrences
+        .iter()
+        .map(|candidate| Occurrence {
+            name: if is_renamed(candidate) {
+                new_name.to_string()
+            } else {
+                candidate.name.clone()
+            },
+            ..candidate.clone()
+        })
+        .collect();
+    let collides = occurrences
+        .iter()
+        .zip(&renamed_occurrences)
+        .any(|(before, after)| {
+            definition(&occurrences, before).map(|definition| definition.span)
+                != definition(&renamed_occurrences, after).map(|definition| definition.span)
+        });
+    if collides {
+        return Err(RenameError::NameCollision(new_name.to_string()));
+    }
+    let edits = occurrences
+        .iter()
+        .filter(|candidate| is_renamed(candidate))
+        .filter_map(|candidate| {
+            Some(TextEdit::new(
+                byte_span_to_range(candidate.span, rope, encoding)?,
+                new_name.to_string(),
+            ))
+        })
+        .collect();
+    Ok(Some(WorkspaceEdit {
+        changes: Some(HashMap::from([(uri.clone(), edits)])),
+        ..Default::default()
+    }))
+}
+
+fn renamed_occurrence<'a>(
+    ast: &Ast,
+    occurrences: &'a [Occurrence],
+    offset: usize,
+) -> Result<Option<&'a Occurrence>, RenameError> {
+    for template in templates(ast) {
+        if let (ExprValue::FunctionName(name), span) = &template.expr.variable {
+            if span.start <= offset && offset <= span.end {
+                return Err(RenameError::BuiltinFunction(name.clone()));
+            }
+        }
+    }
+    let Some(occurrence) = occurrence_at(occurrences, offset) else {
+        return Ok(None);
+    };
+    if definition(occurrences, occurrence).is_none() {
+        return Err(RenameError::UndefinedVariable(occurrence.name.clone()));
+    }
+    Ok(Some(occurrence))
+}
+
+#[cfg(test)]
+mod rename_tests {
+    use super::*;
+    use crate::test_utils::range;
+    use hurl_parser::parser::parser::parse_ast;
+
+    const TEST_STR: &str = r#"GET https://example.org
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+
+GET https://example.org/{{id}}/{{newUuid}}
+HTTP 200
+[Captures]
+id: jsonpath "$.next"
+[Asserts]
+variable "id" exists
+
+GET https://example.org/{{id}}
+"#;
+
+    fn edited_ranges(edit: WorkspaceEdit) -> Vec<Range> {
+        edit.changes
+            .unwrap()
+            .into_values()
+            .flatten()
+            .map(|edit| edit.range)
+            .collect()
+    }
+
+    #[test]
+    fn it_renames_a_capture_and_its_uses() {
+        let rope = Rope::from_str(TEST_STR);
+        let (ast, _) = parse_ast(TEST_STR);
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        let edit = rename(
+            &uri,
+            &rope,
+            &ast.unwrap(),
+            Position::new(3, 1),
+            "user_id",
+            PositionEncoding::Utf16,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(edited_ranges(edit), vec![range(3, 0, 2), range(5, 26, 28)]);
+    }
+
+    #[test]
+    fn it_renames_from_a_use() {
+        let rope = Rope::from_str(TEST_STR);
+        let (ast, _) = parse_ast(TEST_STR);
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        let edit = rename(
+            &uri,
+            &rope,
+            &ast.unwrap(),
+            Position::new(12, 27),
+            "next",
+            PositionEncoding::Utf16,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            edited_ranges(edit),
+            vec![range(8, 0, 2), range(10, 10, 12), range(12, 26, 28)]
+        );
+    }
+
+    #[test]
+    fn it_prepares_the_rename_of_a_variable() {
+        let rope = Rope::from_str(TEST_STR);
+        let (ast, _) = parse_ast(TEST_STR);
+        let ast = ast.unwrap();
+        assert_eq!(
+            prepare_rename(&rope, &ast, Position::new(5, 27), PositionEncoding::Utf16),
+            Ok(Some(range(5, 26, 28)))
+        );
+        assert_eq!(
+            prepare_rename(&rope, &ast, Position::new(0, 1), PositionEncoding::Utf16),
+            Ok(None)
+        );
+        assert_eq!(
+            prepare_rename(&rope, &ast, Position::new(5, 35), PositionEncoding::Utf16),
+            Err(RenameError::BuiltinFunction("newUuid".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_refuses_invalid_names_and_undefined_variables() {
+        let test_str = "GET https://{{host}}\n";
+        let rope = Rope::from_str(test_str);
+        let (ast, _) = parse_ast(test_str);
+        let ast = ast.unwrap();
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        assert_eq!(
+            rename(
+                &uri,
+                &rope,
+                &ast,
+                Position::new(0, 15),
+                "new host",
+                PositionEncoding::Utf16
+            ),
+            Err(RenameError::InvalidName("new host".to_string()))
+        );
+        assert_eq!(
+            rename(
+                &uri,
+                &rope,
+                &ast,
+                Position::new(0, 15),
+                "newUuid",
+                PositionEncoding::Utf16
+            ),
+            Err(RenameError::InvalidName("newUuid".to_string()))
+        );
+        assert_eq!(
+            rename(
+                &uri,
+                &rope,
+                &ast,
+                Position::new(0, 15),
+                "server",
+                PositionEncoding::Utf16
+            ),
+            Err(RenameError::UndefinedVariable("host".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_refuses_names_read_instead_of_another_definition() {
+        let test_str = r#"GET https://example.org
+[Options]
+variable: host=example.org
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+
+GET https://{{host}}/{{token}}
+"#;
+        let rope = Rope::from_str(test_str);
+        let (ast, _) = parse_ast(test_str);
+        let ast = ast.unwrap();
+        let uri = Url::parse("file:///test.hurl").unwrap();
+        assert_eq!(
+            rename(
+                &uri,
+                &rope,
+                &ast,
+                Position::new(2, 11),
+                "token",
+                PositionEncoding::Utf16
+            ),
+            Err(RenameError::NameCollision("token".to_string()))
+        );
+        assert_eq!(
+            rename(
+                &uri,
+                &rope,
+                &ast,
+                Position::new(5, 1),
+                "host",
+                PositionEncoding::Utf16
+            ),
+            Err(RenameError::NameCollision("host".to_string()))
+        );
+    }
+}
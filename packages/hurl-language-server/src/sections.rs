@@ -0,0 +1,532 @@
+use hurl_parser::parser::types::{Ast, Entry, RequestSection, ResponseSection, Span};
+use ropey::Rope;
+use serde_json::Value;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+use crate::utils::{byte_span_to_range, PositionEncoding};
+
+/// The code of the diagnostic for a section written twice in a request or a response
+pub const DUPLICATE_SECTION: &str = "E0801";
+/// The code of the diagnostic for sections that give the request a body more than once
+pub const CONFLICTING_SECTIONS: &str = "E0802";
+/// The code of the diagnostic for a section on the wrong side of the status line
+pub const MISPLACED_SECTION: &str = "E0803";
+/// The code of the diagnostic for a section whose name hurl does not know
+pub const UNKNOWN_SECTION: &str = "E0804";
+/// The code of the hint for a section not written with the spelling preferred by the project
+pub const SECTION_SPELLING: &str = "W0801";
+
+/// The names of the sections with their aliases
+pub const SECTION_NAMES: [&str; 11] = [
+    "BasicAuth",
+    "QueryStringParams",
+    "Query",
+    "FormParams",
+    "Form",
+    "MultipartFormData",
+    "Multipart",
+    "Cookies",
+    "Options",
+    "Captures",
+    "Asserts",
+];
+
+/// The sections that have a short alias, with their full name first
+const SECTION_ALIASES: [(&str, &str); 3] = [
+    ("QueryStringParams", "Query"),
+    ("FormParams", "Form"),
+    ("MultipartFormData", "Multipart"),
+];
+
+/// How the project writes the sections that have an alias, set by the `sectionSpelling`
+/// initialization option to `"long"` for `[QueryStringParams]` or `"short"` for `[Query]`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SectionSpelling {
+    Long,
+    Short,
+}
+
+impl SectionSpelling {
+    pub fn configured(options: Option<&Value>) -> Option<SectionSpelling> {
+        match options?.get("sectionSpelling")?.as_str()? {
+            "long" => Some(SectionSpelling::Long),
+            "short" => Some(SectionSpelling::Short),
+            _ => None,
+        }
+    }
+
+    /// The preferred spelling of the section name when it is written otherwise
+    pub fn respell(self, name: &str) -> Option<&'static str> {
+        SECTION_ALIASES
+            .iter()
+            .find_map(|&(long, short)| match self {
+                SectionSpelling::Long if name == short => Some(long),
+                SectionSpelling::Short if name == long => Some(short),
+                _ => None,
+            })
+    }
+}
+
+/// The kind of a section whatever the alias used for its header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SectionKind {
+    BasicAuth,
+    QueryStringParams,
+    FormParams,
+    MultipartFormData,
+    Cookies,
+    Options,
+    Captures,
+    Asserts,
+}
+
+impl SectionKind {
+    /// The kind of the section or `None` for an unknown section
+    pub fn of_request_section(section: &RequestSection) -> Option<SectionKind> {
+        match section {
+            RequestSection::BasicAuthSection(_) => Some(SectionKind::BasicAuth),
+            RequestSection::QueryStringParamsSection(_) => Some(SectionKind::QueryStringParams),
+            RequestSection::FormParamsSection(_) => Some(SectionKind::FormParams),
+            RequestSection::MultipartFormDataSection(_) => Some(SectionKind::MultipartFormData),
+            RequestSection::CookiesSection(_) => Some(SectionKind::Cookies),
+            RequestSection::OptionsSection(_) => Some(SectionKind::Options),
+            RequestSection::UnknownSection(_) => None,
+        }
+    }
+
+    /// The kind of the section or `None` for an unknown section
+    pub fn of_response_section(section: &ResponseSection) -> Option<SectionKind> {
+        match section {
+            ResponseSection::CapturesSection(_) => Some(SectionKind::Captures),
+            ResponseSection::AssertsSection(_) => Some(SectionKind::Asserts),
+            ResponseSection::UnknownSection(_) => None,
+        }
+    }
+
+    /// Whether the section belongs after the status line
+    pub fn is_response_section(self) -> bool {
+        matches!(self, SectionKind::Captures | SectionKind::Asserts)
+    }
+
+    /// Whether the section gives the request its body
+    fn is_body(self) -> bool {
+        matches!(
+            self,
+            SectionKind::FormParams | SectionKind::MultipartFormData
+        )
+    }
+}
+
+/// The span of the `[Name]` header of a section, without the spaces before it
+pub fn section_header(document: &str, span: Span) -> Span {
+    let text = &document[span.start..span.end];
+    let start = text.len() - text.trim_start_matches([' ', '\t']).len();
+    let end = text[start..].find(']').map_or_else(
+        || text.lines().next().unwrap_or_default().len(),
+        |end| start + end + 1,
+    );
+    Span::new(span.start + start, span.start + end)
+}
+
+/// The number of single character insertions, deletions or substitutions to turn one word
+/// into the other, ignoring the case
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The known section closest to an unknown section name, if it is close enough to be a typo
+pub fn suggest_section(name: &str) -> Option<&'static str> {
+    SECTION_NAMES
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), *candidate))
+        .filter(|(distance, candidate)| distance * 3 <= candidate.len())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// An invalid section found by the validation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SectionError {
+    pub code: &'static str,
+    /// The span of the header of the section
+    pub span: Span,
+    pub message: String,
+}
+
+/// The errors of the sections of every entry of the document:
+/// - A section can only be written once in a request or a response.
+/// - A request has only one body so `[FormParams]`, `[MultipartFormData]` and a body
+///   exclude each other.
+/// - `[Captures]` and `[Asserts]` come after the status line and the other sections before.
+/// - Unknown sections are reported with the known section they are closest to.
+/// - Sections with an alias are hinted when the project prefers the other spelling.
+pub fn section_errors(
+    document: &str,
+    ast: &Ast,
+    spelling: Option<SectionSpelling>,
+) -> Vec<SectionError> {
+    ast.entries
+        .iter()
+        .flat_map(|entry| entry_errors(document, entry, spelling))
+        .collect()
+}
+
+fn entry_errors(
+    document: &str,
+    entry: &Entry,
+    spelling: Option<SectionSpelling>,
+) -> Vec<SectionError> {
+    let request = &entry.request;
+    //The sections on each side of the status line, whichever part of the entry they belong to
+    let before_status = request
+        .request_sections
+        .iter()
+        .map(|section| (SectionKind::of_request_section(section), section.span()))
+        .chain(
+            request
+                .misplaced_sections
+                .iter()
+                .map(|section| (SectionKind::of_response_section(section), section.span())),
+        );
+    let after_status = entry.response.iter().flat_map(|response| {
+        response
+            .response_sections
+            .iter()
+            .map(|section| (SectionKind::of_response_section(section), section.span()))
+            .chain(
+                response
+                    .misplaced_sections
+                    .iter()
+                    .map(|section| (SectionKind::of_request_section(section), section.span())),
+            )
+    });
+
+    let mut errors = vec![];
+    for (in_response, mut sections) in [
+        (false, before_status.collect::<Vec<_>>()),
+        (true, after_status.collect::<Vec<_>>()),
+    ] {
+        sections.sort_by_key(|(_, span)| span.start);
+        let part = if in_response { "response" } else { "request" };
+        //The sections in the right part of the entry with their header
+        let mut seen: Vec<(SectionKind, &str)> = vec![];
+        for (kind, span) in sections {
+            let header = section_header(document, span);
+            let name = &document[header.start..header.end];
+            let Some(kind) = kind else {
+                let message = match suggest_section(&name[1..name.len() - 1]) {
+                    Some(suggestion) => format!(
+                        "Unknown section `{}`, did you mean `[{}]`?",
+                        name, suggestion
+                    ),
+                    None => format!("Unknown section `{}`", name),
+                };
+                errors.push(SectionError {
+                    code: UNKNOWN_SECTION,
+                    span: header,
+                    message,
+                });
+                continue;
+            };
+            let preferred =
+                spelling.and_then(|spelling| spelling.respell(&name[1..name.len() - 1]));
+            if let Some(preferred) = preferred {
+                errors.push(SectionError {
+                    code: SECTION_SPELLING,
+                    span: header,
+                    message: format!(
+                        "Section `{}` is written `[{}]` in this project",
+                        name, preferred
+                    ),
+                });
+            }
+            if kind.is_response_section() != in_response {
+                let side = if in_response { "before" } else { "after" };
+                errors.push(SectionError {
+                    code: MISPLACED_SECTION,
+                    span: header,
+                    message: format!("Section `{}` must be {} the status line", name, side),
+                });
+                continue;
+            }
+            if seen.iter().any(|(seen, _)| *seen == kind) {
+                errors.push(SectionError {
+                    code: DUPLICATE_SECTION,
+                    span: header,
+                    message: format!("Section `{}` is already defined in this {}", name, part),
+                });
+                continue;
+            }
+            if kind.is_body() {
+                if let Some((_, other)) = seen.iter().find(|(seen, _)| seen.is_body()) {
+                    errors.push(SectionError {
+                        code: CONFLICTING_SECTIONS,
+                        span: header,
+                        message: format!("Section `{}` cannot be used with `{}`", name, other),
+                    });
+                } else if entry.request.body.is_some() {
+                    errors.push(SectionError {
+                        code: CONFLICTING_SECTIONS,
+                        span: header,
+                        message: format!("Section `{}` cannot be used with a request body", name),
+                    });
+                }
+            }
+            seen.push((kind, name));
+        }
+    }
+    errors
+}
+
+/// The section errors as error diagnostics, except for the spelling of the sections which is
+/// only a hint
+pub fn section_diagnostics(
+    rope: &Rope,
+    ast: &Ast,
+    spelling: Option<SectionSpelling>,
+    encoding: PositionEncoding,
+) -> Vec<Diagnostic> {
+    section_errors(&rope.to_string(), ast, spelling)
+        .into_iter()
+        .filter_map(|error| {
+            let severity = if error.code == SECTION_SPELLING {
+                DiagnosticSeverity::HINT
+            } else {
+                DiagnosticSeverity::ERROR
+            };
+            Some(Diagnostic::new(
+                byte_span_to_range(error.span, rope, encoding)?,
+                Some(severity),
+                Some(NumberOrString::String(error.code.to_string())),
+                Some("hurl".to_string()),
+                error.message,
+                None,
+                None,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod sections_tests {
+    use super::*;
+    use hurl_parser::parser::parser::parse_ast;
+
+    /// The header of each invalid section with the code and message of the error
+    fn errors(test_str: &str) -> Vec<(&str, &str, String)> {
+        let (ast, errs) = parse_ast(test_str);
+        assert_eq!(errs, vec![]);
+        section_errors(test_str, &ast.unwrap(), None)
+            .into_iter()
+            .map(|error| {
+                (
+                    &test_str[error.span.start..error.span.end],
+                    error.code,
+                    error.message,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_sections_in_their_place() {
+        let test_str = r#"POST https://example.org
+[Query]
+page: 1
+[Form]
+user: bob
+[Options]
+verbose: true
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+[Asserts]
+status == 200
+"#;
+        assert_eq!(errors(test_str), vec![]);
+    }
+
+    #[test]
+    fn it_reports_duplicated_sections() {
+        let test_str = r#"GET https://example.org
+[QueryStringParams]
+page: 1
+  [Query]
+size: 10
+HTTP 200
+[Asserts]
+status == 200
+[Asserts]
+body contains "ok"
+"#;
+        assert_eq!(
+            errors(test_str),
+            vec![
+                (
+                    "[Query]",
+                    DUPLICATE_SECTION,
+                    "Section `[Query]` is already defined in this request".to_string()
+                ),
+                (
+                    "[Asserts]",
+                    DUPLICATE_SECTION,
+                    "Section `[Asserts]` is already defined in this response".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_sections_giving_a_second_body() {
+        let test_str = r#"POST https://example.org
+[FormParams]
+user: bob
+[Multipart]
+file: file,data.txt;
+
+POST https://example.org
+[Form]
+user: bob
+{"user": "bob"}
+"#;
+        assert_eq!(
+            errors(test_str),
+            vec![
+                (
+                    "[Multipart]",
+                    CONFLICTING_SECTIONS,
+                    "Section `[Multipart]` cannot be used with `[FormParams]`".to_string()
+                ),
+                (
+                    "[Form]",
+                    CONFLICTING_SECTIONS,
+                    "Section `[Form]` cannot be used with a request body".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_suggests_a_known_section_for_an_unknown_section() {
+        assert_eq!(suggest_section("Capture"), Some("Captures"));
+        assert_eq!(suggest_section("asserts"), Some("Asserts"));
+        assert_eq!(suggest_section("FormParam"), Some("FormParams"));
+        assert_eq!(suggest_section("Foo"), None);
+        assert_eq!(suggest_section("Whatever"), None);
+    }
+
+    #[test]
+    fn it_reports_unknown_sections() {
+        let test_str = r#"GET https://example.org
+[Option]
+verbose: true
+HTTP 200
+[Capture]
+id: jsonpath "$.id"
+[Whatever]
+foo: bar
+"#;
+        assert_eq!(
+            errors(test_str),
+            vec![
+                (
+                    "[Option]",
+                    UNKNOWN_SECTION,
+                    "Unknown section `[Option]`, did you mean `[Options]`?".to_string()
+                ),
+                (
+                    "[Capture]",
+                    UNKNOWN_SECTION,
+                    "Unknown section `[Capture]`, did you mean `[Captures]`?".to_string()
+                ),
+                (
+                    "[Whatever]",
+                    UNKNOWN_SECTION,
+                    "Unknown section `[Whatever]`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_sections_on_the_wrong_side_of_the_status_line() {
+        let test_str = r#"GET https://example.org
+[Asserts]
+status == 200
+HTTP 200
+[Options]
+verbose: true
+"#;
+        assert_eq!(
+            errors(test_str),
+            vec![
+                (
+                    "[Asserts]",
+                    MISPLACED_SECTION,
+                    "Section `[Asserts]` must be after the status line".to_string()
+                ),
+                (
+                    "[Options]",
+                    MISPLACED_SECTION,
+                    "Section `[Options]` must be before the status line".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_hints_the_preferred_spelling_of_sections() {
+        let test_str = r#"GET https://example.org
+[Query]
+page: 1
+
+POST https://example.org
+[QueryStringParams]
+page: 1
+[Multipart]
+file: file,data.txt;
+"#;
+        let (ast, _) = parse_ast(test_str);
+        let ast = ast.unwrap();
+        let hints = |spelling| {
+            section_errors(test_str, &ast, Some(spelling))
+                .into_iter()
+                .map(|error| (&test_str[error.span.start..error.span.end], error.message))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            hints(SectionSpelling::Long),
+            vec![
+                (
+                    "[Query]",
+                    "Section `[Query]` is written `[QueryStringParams]` in this project"
+                        .to_string()
+                ),
+                (
+                    "[Multipart]",
+                    "Section `[Multipart]` is written `[MultipartFormData]` in this project"
+                        .to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            hints(SectionSpelling::Short),
+            vec![(
+                "[QueryStringParams]",
+                "Section `[QueryStringParams]` is written `[Query]` in this project".to_string()
+            )]
+        );
+        assert_eq!(section_errors(test_str, &ast, None), vec![]);
+    }
+}
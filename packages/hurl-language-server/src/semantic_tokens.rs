@@ -0,0 +1,542 @@
+use hurl_parser::parser::cst::{build_cst, SyntaxElement, SyntaxKind, SyntaxNode};
+use hurl_parser::parser::types::{Ast, Body, Bytes, ExprValue, Json, Span};
+use hurl_parser::parser::visit::templates;
+use tower_lsp::lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensEdit,
+    SemanticTokensLegend,
+};
+
+use crate::utils::PositionEncoding;
+
+/// The token types in the order of their index in the legend
+pub const TOKEN_TYPES: [SemanticTokenType; 11] = [
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::STRING,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::OPERATOR,
+    SemanticTokenType::REGEXP,
+    SemanticTokenType::COMMENT,
+    SemanticTokenType::TYPE,
+];
+
+/// The token modifiers in the order of their bit in the legend
+pub const TOKEN_MODIFIERS: [SemanticTokenModifier; 2] = [
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::DEFAULT_LIBRARY,
+];
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+const KEYWORD: Style = Style(0, 0);
+const STRING: Style = Style(1, 0);
+const NUMBER: Style = Style(2, 0);
+const PROPERTY: Style = Style(3, 0);
+const NAMESPACE: Style = Style(4, 0);
+const VARIABLE: Style = Style(5, 0);
+const VARIABLE_DECLARATION: Style = Style(5, 1);
+const FUNCTION: Style = Style(6, 0);
+const BUILTIN_FUNCTION: Style = Style(6, 2);
+const OPERATOR: Style = Style(7, 0);
+const REGEXP: Style = Style(8, 0);
+const COMMENT: Style = Style(9, 0);
+const TYPE: Style = Style(10, 0);
+
+/// A token type index and a token modifiers bitset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Style(u32, u32);
+
+/// The semantic tokens of the whole document with their columns and lengths counted in the
+/// units of the encoding
+pub fn semantic_tokens(
+    document: &str,
+    ast: &Ast,
+    encoding: PositionEncoding,
+) -> Vec<SemanticToken> {
+    let mut painter = Painter {
+        document,
+        styles: vec![None; document.len()],
+    };
+    painter.node(&build_cst(document, ast));
+    painter.ast(ast);
+    painter.tokens(encoding)
+}
+
+/// The edits turning the previous tokens into the current ones. Only the changed run of
+/// tokens between the common prefix and suffix is sent.
+pub fn semantic_tokens_edits(
+    previous: &[SemanticToken],
+    current: &[SemanticToken],
+) -> Vec<SemanticTokensEdit> {
+    let prefix = previous
+        .iter()
+        .zip(current)
+        .take_while(|(previous, current)| previous == current)
+        .count();
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(current[prefix..].iter().rev())
+        .take_while(|(previous, current)| previous == current)
+        .count();
+    if prefix == previous.len() && prefix == current.len() {
+        return vec![];
+    }
+    vec![SemanticTokensEdit {
+        start: (prefix * 5) as u32,
+        delete_count: ((previous.len() - prefix - suffix) * 5) as u32,
+        data: Some(current[prefix..current.len() - suffix].to_vec()),
+    }]
+}
+
+/// Paints the style of every byte of the document. Outer syntax is painted first so that
+/// the syntax nested in it (ex: a template in a header value) paints over it.
+struct Painter<'a> {
+    document: &'a str,
+    styles: Vec<Option<Style>>,
+}
+
+impl Painter<'_> {
+    fn paint(&mut self, start: usize, end: usize, style: Option<Style>) {
+        let end = end.min(self.styles.len());
+        if start < end {
+            self.styles[start..end].fill(style);
+        }
+    }
+
+    fn node(&mut self, node: &SyntaxNode) {
+        let span = node.span;
+        let text = &self.document[span.start..span.end];
+        match node.kind {
+            SyntaxKind::Method | SyntaxKind::Version => {
+                self.paint(span.start, span.end, Some(KEYWORD))
+            }
+            SyntaxKind::Status => self.paint(span.start, span.end, Some(NUMBER)),
+            SyntaxKind::Url | SyntaxKind::Value => self.paint(span.start, span.end, Some(STRING)),
+            SyntaxKind::Key => self.paint(span.start, span.end, Some(PROPERTY)),
+            SyntaxKind::Query => self.words(text, span.start, KEYWORD, KEYWORD),
+            SyntaxKind::Filter => self.words(text, span.start, FUNCTION, FUNCTION),
+            SyntaxKind::Predicate => self.words(text, span.start, OPERATOR, OPERATOR),
+            SyntaxKind::Option => {
+                let name_end = text.find(':').unwrap_or(text.len());
+                self.paint(span.start, span.start + name_end, Some(PROPERTY));
+                //The value of a variable option is a child value node
+                if name_end < text.len() && node.child_nodes().next().is_none() {
+                    self.words(
+                        &text[name_end + 1..],
+                        span.start + name_end + 1,
+                        STRING,
+                        STRING,
+                    );
+                }
+            }
+            SyntaxKind::Section => {
+                let header = node.children.iter().find_map(|child| match child {
+                    SyntaxElement::Token(token) if token.kind == SyntaxKind::Text => Some(token),
+                    _ => None,
+                });
+                if let Some(header) = header {
+                    self.paint(header.span.start, header.span.end, Some(NAMESPACE));
+                }
+            }
+            _ => {}
+        }
+        for child in &node.children {
+            match child {
+                SyntaxElement::Node(child) => {
+                    self.node(child);
+                    //Capture keys and variable option names declare variables
+                    if child.kind == SyntaxKind::Key
+                        && matches!(node.kind, SyntaxKind::Capture | SyntaxKind::Option)
+                    {
+                        self.paint(child.span.start, child.span.end, Some(VARIABLE_DECLARATION));
+                    }
+                }
+                SyntaxElement::Token(token) if token.kind == SyntaxKind::Comment => {
+                    self.paint(token.span.start, token.span.end, Some(COMMENT))
+                }
+                SyntaxElement::Token(_) => {}
+            }
+        }
+    }
+
+    /// Paints the words of a query, filter, predicate or option value. Quoted strings,
+    /// regexes, numbers and literals are recognised, the first other word is painted with
+    /// `first` and the rest with `rest`. Templates are left for the AST to paint.
+    fn words(&mut self, text: &str, start: usize, first: Style, rest: Style) {
+        let bytes = text.as_bytes();
+        let mut offset = 0;
+        let mut is_first = true;
+        while offset < bytes.len() {
+            let c = bytes[offset];
+            if c.is_ascii_whitespace() {
+                offset += 1;
+                continue;
+            }
+            let (len, style) = match c {
+                b'"' | b'`' | b'/' => {
+                    let len = delimited_len(&text[offset..], c);
+                    (len, if c == b'/' { REGEXP } else { STRING })
+                }
+                b'{' if text[offset..].starts_with("{{") => {
+                    let len = text[offset..]
+                        .find("}}")
+                        .map_or(bytes.len() - offset, |i| i + 2);
+                    offset += len;
+                    continue;
+                }
+                _ => {
+                    let len = text[offset..]
+                        .find(|c: char| c.is_ascii_whitespace())
+                        .unwrap_or(bytes.len() - offset);
+                    let word = &text[offset..offset + len];
+                    let style = if word.parse::<f64>().is_ok() {
+                        NUMBER
+                    } else if matches!(word, "true" | "false" | "null") {
+                        KEYWORD
+                    } else if is_first {
+                        first
+                    } else {
+                        rest
+                    };
+                    is_first = false;
+                    (len, style)
+                }
+            };
+            self.paint(start + offset, start + offset + len, Some(style));
+            offset += len;
+        }
+    }
+
+    fn ast(&mut self, ast: &Ast) {
+        for entry in &ast.entries {
+            if let Some(body) = &entry.request.body {
+                self.body(body);
+            }
+            if let Some(body) = entry.response.as_ref().and_then(|r| r.body.as_ref()) {
+                self.body(body);
+            }
+        }
+        for template in templates(ast) {
+            let span = template.span;
+            self.paint(span.start, span.end, None);
+            let (variable, span) = &template.expr.variable;
+            let style = match variable {
+                ExprValue::VariableName(_) => VARIABLE,
+                ExprValue::FunctionName(_) => BUILTIN_FUNCTION,
+            };
+            self.paint(span.start, span.end, Some(style));
+            for (_, span) in &template.expr.filters {
+                let text = &self.document[span.start..span.end];
+                self.words(text, span.start, FUNCTION, FUNCTION);
+            }
+        }
+    }
+
+    fn body(&mut self, body: &Body) {
+        let span = body.span;
+        match &body.bytes {
+            Bytes::JsonValue(json) => self.json(json, span),
+            Bytes::MultilineString(multiline_string) => {
+                self.paint(span.start, span.end, Some(STRING));
+                //The type of a multiline string like json in ```json,escape
+                if multiline_string.r#type.is_some() {
+                    let text = &self.document[span.start..span.end];
+                    let header = text.lines().next().unwrap_or_default();
+                    let start = header.len() - header.trim_start_matches('`').len();
+                    let end = header.find(',').unwrap_or(header.trim_end().len());
+                    self.paint(span.start + start, span.start + end, Some(TYPE));
+                }
+            }
+            _ => self.paint(span.start, span.end, Some(STRING)),
+        }
+    }
+
+    fn json(&mut self, json: &Json, span: Span) {
+        match json {
+            Json::Object(key_values) => {
+                for key_value in key_values {
+                    let key = key_value.key.span;
+                    self.paint(key.start, key.end, Some(PROPERTY));
+                    self.json(&key_value.value.0, key_value.value.1);
+                }
+            }
+            Json::Array(values) => {
+                for (value, span) in values {
+                    self.json(value, *span);
+                }
+            }
+            Json::Str(_) | Json::InterpolatedString(_) => {
+                self.paint(span.start, span.end, Some(STRING))
+            }
+            Json::Num(_) => self.paint(span.start, span.end, Some(NUMBER)),
+            Json::Bool(_) | Json::Null => self.paint(span.start, span.end, Some(KEYWORD)),
+            Json::Template(_) | Json::Invalid => {}
+        }
+    }
+
+    /// Joins the painted bytes into tokens. Tokens never span several lines and never
+    /// start or end with whitespace.
+    fn tokens(&self, encoding: PositionEncoding) -> Vec<SemanticToken> {
+        let mut tokens = vec![];
+        let mut previous = (0, 0);
+        let mut push = |line: u32, (style, start, end): (Style, u32, u32)| {
+            let delta_line = line - previous.0;
+            let delta_start = if delta_line == 0 {
+                start - previous.1
+            } else {
+                start
+            };
+            tokens.push(SemanticToken {
+                delta_line,
+                delta_start,
+                length: end - start,
+                token_type: style.0,
+                token_modifiers_bitset: style.1,
+            });
+            previous = (line, start);
+        };
+        let mut line = 0;
+        let mut column = 0;
+        let mut run: Option<(Style, u32, u32)> = None;
+        for (offset, c) in self.document.char_indices() {
+            if c == '\n' {
+                if let Some(run) = run.take() {
+                    push(line, run);
+                }
+                line += 1;
+                column = 0;
+                continue;
+            }
+            let style = self.styles[offset];
+            let len = encoding.len(c) as u32;
+            match (&mut run, style) {
+                (Some((current, _, end)), Some(style)) if *current == style => {
+                    if !c.is_whitespace() {
+                        *end = column + len;
+                    }
+                }
+                _ => {
+                    if let Some(run) = run.take() {
+                        push(line, run);
+                    }
+                    if let Some(style) = style.filter(|_| !c.is_whitespace()) {
+                        run = Some((style, column, column + len));
+                    }
+                }
+            }
+            column += len;
+        }
+        if let Some(run) = run {
+            push(line, run);
+        }
+        tokens
+    }
+}
+
+/// The length of the text up to and including the closing delimiter, skipping escaped
+/// delimiters.
+fn delimited_len(text: &str, delimiter: u8) -> usize {
+    let bytes = text.as_bytes();
+    let mut offset = 1;
+    while offset < bytes.len() {
+        match bytes[offset] {
+            b'\\' => offset += 2,
+            c if c == delimiter => return offset + 1,
+            _ => offset += 1,
+        }
+    }
+    bytes.len()
+}
+
+#[cfg(test)]
+mod semantic_tokens_tests {
+    use super::*;
+    use hurl_parser::parser::parser::parse_ast;
+
+    fn tokens(document: &str, encoding: PositionEncoding) -> Vec<SemanticToken> {
+        let (ast, _) = parse_ast(document);
+        semantic_tokens(document, &ast.unwrap(), encoding)
+    }
+
+    /// Decodes the tokens into their line, text and type for readable assertions
+    fn describe(document: &str) -> Vec<String> {
+        let lines: Vec<Vec<char>> = document.lines().map(|l| l.chars().collect()).collect();
+        let mut line = 0;
+        let mut start = 0;
+        tokens(document, PositionEncoding::Utf32)
+            .into_iter()
+            .map(|token| {
+                line += token.delta_line;
+                start = if token.delta_line == 0 {
+                    start + token.delta_start
+                } else {
+                    token.delta_start
+                };
+                let text: String = lines[line as usize]
+                    [start as usize..(start + token.length) as usize]
+                    .iter()
+                    .collect();
+                let mut kind = TOKEN_TYPES[token.token_type as usize].as_str().to_string();
+                for (bit, modifier) in TOKEN_MODIFIERS.iter().enumerate() {
+                    if token.token_modifiers_bitset & (1 << bit) != 0 {
+                        kind = format!("{kind}.{}", modifier.as_str());
+                    }
+                }
+                format!("{line}:{text}:{kind}")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_classifies_a_request() {
+        let test_str = r#"# get the user
+GET https://{{host}}/users/{{newUuid}}
+Accept: application/json
+[Options]
+retry: 3
+variable: name=bob
+"#;
+        assert_eq!(
+            describe(test_str),
+            vec![
+                "0:# get the user:comment",
+                "1:GET:keyword",
+                "1:https://:string",
+                "1:host:variable",
+                "1:/users/:string",
+                "1:newUuid:function.defaultLibrary",
+                "2:Accept:property",
+                "2:application/json:string",
+                "3:[Options]:namespace",
+                "4:retry:property",
+                "4:3:number",
+                "5:variable:property",
+                "5:name:variable.declaration",
+                "5:bob:string",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_classifies_a_response() {
+        let test_str = r#"GET https://example.org
+HTTP 200
+[Captures]
+id: jsonpath "$.id" toInt
+[Asserts]
+header "Location" regex /users-([0-9]+)/ == "1"
+jsonpath "$.name" not == {{name}}
+"#;
+        assert_eq!(
+            describe(test_str),
+            vec![
+                "0:GET:keyword",
+                "0:https://example.org:string",
+                "1:HTTP:keyword",
+                "1:200:number",
+                "2:[Captures]:namespace",
+                "3:id:variable.declaration",
+                "3:jsonpath:keyword",
+                "3:\"$.id\":string",
+                "3:toInt:function",
+                "4:[Asserts]:namespace",
+                "5:header:keyword",
+                "5:\"Location\":string",
+                "5:regex:function",
+                "5:/users-([0-9]+)/:regexp",
+                "5:==:operator",
+                "5:\"1\":string",
+                "6:jsonpath:keyword",
+                "6:\"$.name\":string",
+                "6:not:operator",
+                "6:==:operator",
+                "6:name:variable",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_classifies_bodies() {
+        let test_str = r#"POST https://example.org
+{"id": 1, "tags": ["a", true, {{tag}}]}
+HTTP 200
+```json
+{}
+```
+"#;
+        assert_eq!(
+            describe(test_str),
+            vec![
+                "0:POST:keyword",
+                "0:https://example.org:string",
+                "1:\"id\":property",
+                "1:1:number",
+                "1:\"tags\":property",
+                "1:\"a\":string",
+                "1:true:keyword",
+                "1:tag:variable",
+                "2:HTTP:keyword",
+                "2:200:number",
+                "3:```:string",
+                "3:json:type",
+                "4:{}:string",
+                "5:```:string",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_counts_columns_in_the_units_of_the_encoding() {
+        let test_str = "GET https://example.org/😀/{{id}}\n";
+        let columns = |encoding| {
+            tokens(test_str, encoding)
+                .iter()
+                .map(|token| (token.delta_start, token.length))
+                .collect::<Vec<_>>()
+        };
+        //The emoji is 4 UTF-8 bytes, 2 UTF-16 code units and 1 char
+        assert_eq!(
+            columns(PositionEncoding::Utf8),
+            vec![(0, 3), (4, 25), (27, 2)]
+        );
+        assert_eq!(
+            columns(PositionEncoding::Utf16),
+            vec![(0, 3), (4, 23), (25, 2)]
+        );
+        assert_eq!(
+            columns(PositionEncoding::Utf32),
+            vec![(0, 3), (4, 22), (24, 2)]
+        );
+    }
+
+    #[test]
+    fn it_computes_the_edits_between_tokens() {
+        let previous = tokens(
+            "GET https://example.org\nHTTP 200\n",
+            PositionEncoding::Utf16,
+        );
+        let current = tokens(
+            "GET https://example.org\nX-Id: 1\nHTTP 200\n",
+            PositionEncoding::Utf16,
+        );
+        let edits = semantic_tokens_edits(&previous, &current);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].start, 10);
+        assert_eq!(edits[0].delete_count, 0);
+        let mut tokens = previous.clone();
+        let start = edits[0].start as usize / 5;
+        let end = start + edits[0].delete_count as usize / 5;
+        tokens.splice(start..end, edits[0].data.clone().unwrap());
+        assert_eq!(tokens, current);
+        assert_eq!(semantic_tokens_edits(&previous, &previous), vec![]);
+    }
+}
@@ -0,0 +1,243 @@
+use hurl_parser::parser::types::{Ast, Entry, InterpolatedStringPart, ResponseSection, Span, Url};
+use ropey::Rope;
+use tower_lsp::lsp_types::{DocumentSymbol, SymbolKind};
+
+use crate::utils::{byte_span_to_range, PositionEncoding};
+
+/// The outline of the document. Every entry holds its request sections, its response status
+/// line, its captures with one child per captured variable and its asserts.
+pub fn document_symbols(rope: &Rope, ast: &Ast, encoding: PositionEncoding) -> Vec<DocumentSymbol> {
+    let document = rope.to_string();
+    ast.entries
+        .iter()
+        .filter_map(|entry| entry_symbol(&document, rope, entry, encoding))
+        .collect()
+}
+
+/// The name of an entry as `METHOD url` with the templates of the url written as `{{var}}`
+pub fn entry_name(document: &str, entry: &Entry) -> String {
+    let request = &entry.request;
+    let url = match &request.url.0 {
+        Url::Url(url) => url
+            .parts
+            .iter()
+            .map(|part| match part {
+                InterpolatedStringPart::Str(text) => text.clone(),
+                InterpolatedStringPart::Template(template) => {
+                    format!(
+                        "{{{{{}}}}}",
+                        &document[template.expr.span.start..template.expr.span.end]
+                    )
+                }
+            })
+            .collect(),
+        Url::Invalid | Url::Missing => String::new(),
+    };
+    format!("{} {}", request.method.value, url)
+        .trim_end()
+        .to_string()
+}
+
+fn entry_symbol(
+    document: &str,
+    rope: &Rope,
+    entry: &Entry,
+    encoding: PositionEncoding,
+) -> Option<DocumentSymbol> {
+    let request = &entry.request;
+    let mut children = vec![];
+    for section in &request.request_sections {
+        children.extend(section_symbol(
+            document,
+            rope,
+            section.span(),
+            vec![],
+            encoding,
+        ));
+    }
+    for section in &request.misplaced_sections {
+        children.extend(response_section_symbol(document, rope, section, encoding));
+    }
+    if let Some(response) = &entry.response {
+        let status_line = Span::new(response.version.1.start, response.status.1.end);
+        children.extend(symbol(
+            &document[status_line.start..status_line.end],
+            SymbolKind::EVENT,
+            rope,
+            status_line,
+            status_line,
+            vec![],
+            encoding,
+        ));
+        for section in &response.response_sections {
+            children.extend(response_section_symbol(document, rope, section, encoding));
+        }
+        for section in &response.misplaced_sections {
+            children.extend(section_symbol(
+                document,
+                rope,
+                section.span(),
+                vec![],
+                encoding,
+            ));
+        }
+    }
+    //Misplaced sections are kept apart in the AST but are listed where they are written
+    children.sort_by_key(|child| child.range.start);
+    let selection = Span::new(request.method.span.start, request.url.1.end);
+    symbol(
+        &entry_name(document, entry),
+        SymbolKind::FUNCTION,
+        rope,
+        trim_end(document, entry.span),
+        selection,
+        children,
+        encoding,
+    )
+}
+
+/// The symbol of a response section, with the captures it defines as children
+fn response_section_symbol(
+    document: &str,
+    rope: &Rope,
+    section: &ResponseSection,
+    encoding: PositionEncoding,
+) -> Option<DocumentSymbol> {
+    match section {
+        ResponseSection::CapturesSection(section) => {
+            let captures = section
+                .captures
+                .iter()
+                .filter_map(|capture| {
+                    let key = capture.key.span;
+                    symbol(
+                        &document[key.start..key.end],
+                        SymbolKind::VARIABLE,
+                        rope,
+                        trim_end(document, capture.span),
+                        key,
+                        vec![],
+                        encoding,
+                    )
+                })
+                .collect();
+            section_symbol(document, rope, section.span, captures, encoding)
+        }
+        ResponseSection::AssertsSection(_) | ResponseSection::UnknownSection(_) => {
+            section_symbol(document, rope, section.span(), vec![], encoding)
+        }
+    }
+}
+
+/// A section named after its header as written in the document
+fn section_symbol(
+    document: &str,
+    rope: &Rope,
+    span: Span,
+    children: Vec<DocumentSymbol>,
+    encoding: PositionEncoding,
+) -> Option<DocumentSymbol> {
+    let text = &document[span.start..span.end];
+    let header = text.lines().next().unwrap_or_default().trim_end();
+    let selection = Span::new(span.start, span.start + header.len());
+    symbol(
+        header,
+        SymbolKind::NAMESPACE,
+        rope,
+        trim_end(document, span),
+        selection,
+        children,
+        encoding,
+    )
+}
+
+/// The span without the newlines and blank lines that follow the syntax
+fn trim_end(document: &str, span: Span) -> Span {
+    let text = document[span.start..span.end].trim_end();
+    Span::new(span.start, span.start + text.len())
+}
+
+fn symbol(
+    name: &str,
+    kind: SymbolKind,
+    rope: &Rope,
+    span: Span,
+    selection: Span,
+    children: Vec<DocumentSymbol>,
+    encoding: PositionEncoding,
+) -> Option<DocumentSymbol> {
+    #[allow(deprecated)]
+    Some(DocumentSymbol {
+        name: name.to_string(),
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: byte_span_to_range(span, rope, encoding)?,
+        selection_range: byte_span_to_range(selection, rope, encoding)?,
+        children: (!children.is_empty()).then_some(children),
+    })
+}
+
+#[cfg(test)]
+mod symbols_tests {
+    use super::*;
+    use hurl_parser::parser::parser::parse_ast;
+
+    fn outline(symbols: &[DocumentSymbol], depth: usize) -> Vec<String> {
+        symbols
+            .iter()
+            .flat_map(|symbol| {
+                let mut lines = vec![format!(
+                    "{}{} {:?} {}-{}",
+                    "  ".repeat(depth),
+                    symbol.name,
+                    symbol.kind,
+                    symbol.range.start.line,
+                    symbol.range.end.line
+                )];
+                lines.extend(outline(
+                    symbol.children.as_deref().unwrap_or(&[]),
+                    depth + 1,
+                ));
+                lines
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_outlines_the_entries() {
+        let test_str = r#"POST https://{{ host }}/login
+[FormParams]
+user: bob
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+id: jsonpath "$.id"
+[Asserts]
+status == 200
+
+GET https://example.org/users/{{id}}
+"#;
+        assert_eq!(
+            outline(
+                &document_symbols(
+                    &Rope::from_str(test_str),
+                    &parse_ast(test_str).0.unwrap(),
+                    PositionEncoding::Utf16
+                ),
+                0
+            ),
+            vec![
+                "POST https://{{host}}/login Function 0-8",
+                "  [FormParams] Namespace 1-2",
+                "  HTTP 200 Event 3-3",
+                "  [Captures] Namespace 4-6",
+                "    token Variable 5-5",
+                "    id Variable 6-6",
+                "  [Asserts] Namespace 7-8",
+                "GET https://example.org/users/{{id}} Function 10-10",
+            ]
+        );
+    }
+}
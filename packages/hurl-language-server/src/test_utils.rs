@@ -0,0 +1,6 @@
+use tower_lsp::lsp_types::{Position, Range};
+
+/// The range of the columns `start..end` of the line
+pub fn range(line: u32, start: u32, end: u32) -> Range {
+    Range::new(Position::new(line, start), Position::new(line, end))
+}
@@ -0,0 +1,141 @@
+use hurl_parser::parser::incremental::Edit;
+use hurl_parser::parser::types::Span;
+use ropey::Rope;
+use tower_lsp::lsp_types::TextDocumentContentChangeEvent;
+
+use crate::utils::{position_to_char_index, PositionEncoding};
+
+/// Applies a change of an incremental `textDocument/didChange` notification to the rope. A
+/// change without a range replaces the whole document. The change is returned as an edit of
+/// the bytes of the previous document for the incremental parser.
+pub fn apply_change(
+    rope: &mut Rope,
+    change: &TextDocumentContentChangeEvent,
+    encoding: PositionEncoding,
+) -> Edit {
+    let span = match change.range {
+        Some(range) => {
+            let start = position_to_char_index(range.start, rope, encoding);
+            let end = position_to_char_index(range.end, rope, encoding).max(start);
+            let span = Span::new(rope.char_to_byte(start), rope.char_to_byte(end));
+            rope.remove(start..end);
+            rope.insert(start, &change.text);
+            span
+        }
+        None => {
+            let span = Span::new(0, rope.len_bytes());
+            *rope = Rope::from_str(&change.text);
+            span
+        }
+    };
+    Edit {
+        span,
+        text: change.text.clone(),
+    }
+}
+
+#[cfg(test)]
+mod text_sync_tests {
+    use super::*;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    fn change(
+        range: Option<((u32, u32), (u32, u32))>,
+        text: &str,
+    ) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: range.map(|((start_line, start), (end_line, end))| {
+                Range::new(
+                    Position::new(start_line, start),
+                    Position::new(end_line, end),
+                )
+            }),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn it_applies_changes_in_order() {
+        let mut rope = Rope::from_str("GET https://example.org\nHTTP 200\n");
+        let changes = [
+            change(Some(((0, 0), (0, 3))), "POST"),
+            change(Some(((1, 5), (1, 8))), "201"),
+            change(Some(((2, 0), (2, 0))), "[Asserts]\nstatus == 201\n"),
+        ];
+        for change in &changes {
+            apply_change(&mut rope, change, PositionEncoding::Utf16);
+        }
+        assert_eq!(
+            rope.to_string(),
+            "POST https://example.org\nHTTP 201\n[Asserts]\nstatus == 201\n"
+        );
+        apply_change(
+            &mut rope,
+            &change(None, "GET https://example.org\n"),
+            PositionEncoding::Utf16,
+        );
+        assert_eq!(rope.to_string(), "GET https://example.org\n");
+    }
+
+    #[test]
+    fn it_counts_columns_in_the_position_encoding() {
+        //'é' is 2 UTF-8 bytes and 1 UTF-16 unit, '😀' is 4 UTF-8 bytes and 2 UTF-16 units
+        let text = "GET https://example.org/é😀/x\n";
+        let column_of_x = |encoding: PositionEncoding| {
+            text[..text.rfind('x').unwrap()]
+                .chars()
+                .map(|c| encoding.len(c) as u32)
+                .sum::<u32>()
+        };
+        for encoding in [
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32,
+        ] {
+            let mut rope = Rope::from_str(text);
+            let column = column_of_x(encoding);
+            apply_change(
+                &mut rope,
+                &change(Some(((0, column), (0, column + 1))), "y"),
+                encoding,
+            );
+            assert_eq!(rope.to_string(), "GET https://example.org/é😀/y\n");
+        }
+    }
+
+    #[test]
+    fn it_returns_the_bytes_replaced_by_the_change() {
+        let mut rope = Rope::from_str("GET https://example.org/é\nHTTP 200\n");
+        let edit = apply_change(
+            &mut rope,
+            &change(Some(((1, 5), (1, 8))), "201"),
+            PositionEncoding::Utf16,
+        );
+        assert_eq!(
+            edit,
+            Edit {
+                span: Span::new(32, 35),
+                text: "201".to_string(),
+            }
+        );
+        let edit = apply_change(&mut rope, &change(None, "GET\n"), PositionEncoding::Utf16);
+        assert_eq!(edit.span, Span::new(0, 36));
+    }
+
+    #[test]
+    fn it_clamps_positions_past_the_end() {
+        let mut rope = Rope::from_str("GET https://example.org\n");
+        apply_change(
+            &mut rope,
+            &change(Some(((0, 100), (0, 100))), "/x"),
+            PositionEncoding::Utf16,
+        );
+        apply_change(
+            &mut rope,
+            &change(Some(((5, 0), (5, 0))), "HTTP 200\n"),
+            PositionEncoding::Utf16,
+        );
+        assert_eq!(rope.to_string(), "GET https://example.org/x\nHTTP 200\n");
+    }
+}
@@ -0,0 +1,833 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use hurl_parser::parser::types::{
+    Ast, BooleanOption, CertificateFieldSelector, DurationOption, ExprValue, FilterFunction,
+    IntegerOption, InterpolatedStringPart, PredicateFunc, PredicateValue, Query, RequestOption,
+    RequestSection, ResponseSection, Span, Spanned, Template, VariableValue,
+};
+use hurl_parser::parser::visit;
+use ropey::Rope;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+use crate::utils::{byte_span_to_range, PositionEncoding};
+use crate::variables::{definition, occurrences, Occurrence, OccurrenceKind};
+
+/// The code of the diagnostic for a filter, predicate or option given a value of the wrong type
+pub const TYPE_MISMATCH: &str = "E0701";
+
+/// The type of a value produced by a query or a filter, or given to a predicate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueType {
+    Boolean,
+    Bytes,
+    Date,
+    Float,
+    Integer,
+    List,
+    Nodeset,
+    Null,
+    Object,
+    Regex,
+    String,
+    /// The type is only known when running hurl, like the result of a jsonpath query
+    Unknown,
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ValueType::Boolean => "boolean",
+            ValueType::Bytes => "bytes",
+            ValueType::Date => "date",
+            ValueType::Float => "float",
+            ValueType::Integer => "integer",
+            ValueType::List => "list",
+            ValueType::Nodeset => "nodeset",
+            ValueType::Null => "null",
+            ValueType::Object => "object",
+            ValueType::Regex => "regex",
+            ValueType::String => "string",
+            ValueType::Unknown => "unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl ValueType {
+    fn is_number(self) -> bool {
+        matches!(self, ValueType::Integer | ValueType::Float)
+    }
+
+    /// Whether values of the two types can be compared by `==` or ordered by `>` and co.
+    /// Null can be compared with anything and strings are compared with bytes and dates
+    /// like hurl does.
+    pub fn is_comparable_with(self, other: ValueType) -> bool {
+        use ValueType::*;
+        self == other
+            || self == Unknown
+            || other == Unknown
+            || self == Null
+            || other == Null
+            || (self.is_number() && other.is_number())
+            || matches!(
+                (self, other),
+                (String, Bytes) | (Bytes, String) | (String, Date) | (Date, String)
+            )
+    }
+}
+
+/// The types a filter or predicate accepts as input
+struct Expected {
+    types: &'static [ValueType],
+    description: &'static str,
+}
+
+const NUMBER_OR_STRING: Expected = Expected {
+    types: &[ValueType::Integer, ValueType::Float, ValueType::String],
+    description: "a number or a string",
+};
+const COLLECTION: Expected = Expected {
+    types: &[
+        ValueType::List,
+        ValueType::Nodeset,
+        ValueType::Object,
+        ValueType::Bytes,
+    ],
+    description: "a collection",
+};
+const LIST: Expected = Expected {
+    types: &[ValueType::List, ValueType::Nodeset],
+    description: "a list",
+};
+const DATE: Expected = Expected {
+    types: &[ValueType::Date],
+    description: "a date",
+};
+const BYTES: Expected = Expected {
+    types: &[ValueType::Bytes],
+    description: "bytes",
+};
+const STRING: Expected = Expected {
+    types: &[ValueType::String],
+    description: "a string",
+};
+const STRING_OR_BYTES: Expected = Expected {
+    types: &[ValueType::String, ValueType::Bytes],
+    description: "a string or bytes",
+};
+const ORDERED: Expected = Expected {
+    types: &[
+        ValueType::Integer,
+        ValueType::Float,
+        ValueType::Date,
+        ValueType::String,
+    ],
+    description: "a number, a date or a string",
+};
+const CONTAINER: Expected = Expected {
+    types: &[
+        ValueType::String,
+        ValueType::Bytes,
+        ValueType::List,
+        ValueType::Nodeset,
+        ValueType::Object,
+    ],
+    description: "a string, bytes or a collection",
+};
+
+impl Expected {
+    fn accepts(&self, value_type: ValueType) -> bool {
+        value_type == ValueType::Unknown || self.types.contains(&value_type)
+    }
+}
+
+/// The type of the value returned by the query
+pub fn query_type(query: &Query) -> ValueType {
+    match query {
+        Query::Status | Query::Duration => ValueType::Integer,
+        Query::Url | Query::Body | Query::Regex(_) => ValueType::String,
+        //A header sent several times is read as the list of its values
+        Query::Header(_) => ValueType::Unknown,
+        Query::Certificate(
+            CertificateFieldSelector::StartDate | CertificateFieldSelector::ExpireDate,
+        ) => ValueType::Date,
+        Query::Certificate(_) => ValueType::String,
+        //The attributes of a cookie like `cookie "id[Expires]"` have their own types
+        Query::Cookie(name) => match &name.parts[..] {
+            [InterpolatedStringPart::Str(name)] if !name.contains('[') => ValueType::String,
+            _ => ValueType::Unknown,
+        },
+        Query::Bytes | Query::Sha256 | Query::Md5 => ValueType::Bytes,
+        Query::Xpath(_) | Query::JsonPath(_) | Query::Variable(_) => ValueType::Unknown,
+    }
+}
+
+/// The name of the filter as written in a hurl file
+pub fn filter_name(filter: &FilterFunction) -> &'static str {
+    match filter {
+        FilterFunction::Count => "count",
+        FilterFunction::DaysAfterNow => "daysAfterNow",
+        FilterFunction::DaysBeforeNow => "daysBeforeNow",
+        FilterFunction::Decode { .. } => "decode",
+        FilterFunction::Format { .. } => "format",
+        FilterFunction::HtmlEscape => "htmlEscape",
+        FilterFunction::HtmlUnescape => "htmlUnescape",
+        FilterFunction::JsonPath { .. } => "jsonpath",
+        FilterFunction::Nth { .. } => "nth",
+        FilterFunction::Regex { .. } => "regex",
+        FilterFunction::Replace { .. } => "replace",
+        FilterFunction::Split { .. } => "split",
+        FilterFunction::ToDate { .. } => "toDate",
+        FilterFunction::ToFloat => "toFloat",
+        FilterFunction::ToInt => "toInt",
+        FilterFunction::UrlDecode => "urlDecode",
+        FilterFunction::UrlEncode => "urlEncode",
+        FilterFunction::XPath { .. } => "xpath",
+    }
+}
+
+/// The input the filter accepts and the type of its output
+fn filter_signature(filter: &FilterFunction) -> (&'static Expected, ValueType) {
+    match filter {
+        FilterFunction::Count => (&COLLECTION, ValueType::Integer),
+        FilterFunction::DaysAfterNow | FilterFunction::DaysBeforeNow => (&DATE, ValueType::Integer),
+        FilterFunction::Decode { .. } => (&BYTES, ValueType::String),
+        FilterFunction::Format { .. } => (&DATE, ValueType::String),
+        FilterFunction::HtmlEscape
+        | FilterFunction::HtmlUnescape
+        | FilterFunction::Regex { .. }
+        | FilterFunction::Replace { .. }
+        | FilterFunction::UrlDecode
+        | FilterFunction::UrlEncode => (&STRING, ValueType::String),
+        FilterFunction::JsonPath { .. } => (&STRING, ValueType::Unknown),
+        FilterFunction::Nth { .. } => (&LIST, ValueType::Unknown),
+        FilterFunction::Split { .. } => (&STRING, ValueType::List),
+        FilterFunction::ToDate { .. } => (&STRING, ValueType::Date),
+        FilterFunction::ToFloat => (&NUMBER_OR_STRING, ValueType::Float),
+        FilterFunction::ToInt => (&NUMBER_OR_STRING, ValueType::Integer),
+        FilterFunction::XPath { .. } => (&STRING_OR_BYTES, ValueType::Unknown),
+    }
+}
+
+/// The name of the predicate as written in a hurl file
+pub fn predicate_name(predicate: &PredicateFunc) -> &'static str {
+    match predicate {
+        PredicateFunc::Equal { .. } => "==",
+        PredicateFunc::NotEqual { .. } => "!=",
+        PredicateFunc::Greater { .. } => ">",
+        PredicateFunc::GreaterOrEqual { .. } => ">=",
+        PredicateFunc::Less { .. } => "<",
+        PredicateFunc::LessOrEqual { .. } => "<=",
+        PredicateFunc::StartWith { .. } => "startsWith",
+        PredicateFunc::EndWith { .. } => "endsWith",
+        PredicateFunc::Contain { .. } => "contains",
+        PredicateFunc::Match { .. } => "matches",
+        PredicateFunc::Exists => "exists",
+        PredicateFunc::IsEmpty => "isEmpty",
+        PredicateFunc::Include { .. } => "includes",
+        PredicateFunc::IsInteger => "isInteger",
+        PredicateFunc::IsFloat => "isFloat",
+        PredicateFunc::IsBoolean => "isBoolean",
+        PredicateFunc::IsString => "isString",
+        PredicateFunc::IsCollection => "isCollection",
+        PredicateFunc::IsDate => "isDate",
+        PredicateFunc::IsIsoDate => "isIsoDate",
+        PredicateFunc::Invalid => "",
+    }
+}
+
+/// The type of a value written after a predicate
+pub fn predicate_value_type(value: &PredicateValue) -> ValueType {
+    match value {
+        PredicateValue::Boolean(_) => ValueType::Boolean,
+        PredicateValue::Null => ValueType::Null,
+        PredicateValue::Integer(_) | PredicateValue::BigInteger(_) => ValueType::Integer,
+        PredicateValue::Float(_) => ValueType::Float,
+        PredicateValue::OneLineBase64(_)
+        | PredicateValue::OneLineHex(_)
+        | PredicateValue::OneLineFile(_) => ValueType::Bytes,
+        PredicateValue::OneLineString(_)
+        | PredicateValue::QuotedString(_)
+        | PredicateValue::MultilineString(_) => ValueType::String,
+        PredicateValue::Regex(_) => ValueType::Regex,
+        PredicateValue::Template(_) | PredicateValue::Invalid => ValueType::Unknown,
+    }
+}
+
+/// A value of the wrong type found by the type checker
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeMismatch {
+    pub span: Span,
+    pub message: String,
+}
+
+/// Applies the filters to the value of the query, reporting the filters that can't take the
+/// value they are given. The type of the value is unknown after a mismatch so that one
+/// mistake is only reported once.
+pub fn check_filters(
+    input: ValueType,
+    filters: &[Spanned<FilterFunction>],
+    mismatches: &mut Vec<TypeMismatch>,
+) -> ValueType {
+    filters.iter().fold(input, |input, (filter, span)| {
+        let (expected, output) = filter_signature(filter);
+        if expected.accepts(input) {
+            output
+        } else {
+            mismatches.push(TypeMismatch {
+                span: *span,
+                message: format!(
+                    "Filter `{}` expects {}, found {}",
+                    filter_name(filter),
+                    expected.description,
+                    input
+                ),
+            });
+            ValueType::Unknown
+        }
+    })
+}
+
+/// Checks that the predicate can be applied to a value of the input type and that its
+/// value can be compared with the input
+pub fn check_predicate(
+    input: ValueType,
+    (predicate, span): &Spanned<PredicateFunc>,
+    value_type: impl Fn(&PredicateValue) -> ValueType,
+    mismatches: &mut Vec<TypeMismatch>,
+) {
+    let (expected, value) = match predicate {
+        PredicateFunc::Equal { value } | PredicateFunc::NotEqual { value } => (None, Some(value)),
+        PredicateFunc::Greater { value }
+        | PredicateFunc::GreaterOrEqual { value }
+        | PredicateFunc::Less { value }
+        | PredicateFunc::LessOrEqual { value } => (Some(&ORDERED), Some(value)),
+        PredicateFunc::StartWith { value } | PredicateFunc::EndWith { value } => {
+            (Some(&STRING_OR_BYTES), Some(value))
+        }
+        PredicateFunc::Contain { .. } => (Some(&CONTAINER), None),
+        PredicateFunc::Match { .. } => (Some(&STRING), None),
+        PredicateFunc::Include { .. } => (Some(&COLLECTION), None),
+        PredicateFunc::IsEmpty => (Some(&CONTAINER), None),
+        PredicateFunc::IsIsoDate => (Some(&STRING), None),
+        PredicateFunc::Exists
+        | PredicateFunc::IsInteger
+        | PredicateFunc::IsFloat
+        | PredicateFunc::IsBoolean
+        | PredicateFunc::IsString
+        | PredicateFunc::IsCollection
+        | PredicateFunc::IsDate
+        | PredicateFunc::Invalid => (None, None),
+    };
+    if let Some(expected) = expected.filter(|expected| !expected.accepts(input)) {
+        mismatches.push(TypeMismatch {
+            span: *span,
+            message: format!(
+                "Predicate `{}` expects {}, found {}",
+                predicate_name(predicate),
+                expected.description,
+                input
+            ),
+        });
+        return;
+    }
+    if let Some((value, value_span)) = value {
+        let value_type = value_type(value);
+        if !input.is_comparable_with(value_type) {
+            mismatches.push(TypeMismatch {
+                span: *value_span,
+                message: format!(
+                    "Predicate `{}` compares {} with {}",
+                    predicate_name(predicate),
+                    input,
+                    value_type
+                ),
+            });
+        }
+    }
+}
+
+/// The type of the value of a `variable` option. A string with templates is only known once
+/// rendered so its type is unknown.
+pub fn variable_value_type(value: &VariableValue) -> ValueType {
+    match value {
+        VariableValue::Null => ValueType::Null,
+        VariableValue::Boolean(_) => ValueType::Boolean,
+        VariableValue::Integer(_) | VariableValue::BigInteger(_) => ValueType::Integer,
+        VariableValue::Float(_) => ValueType::Float,
+        VariableValue::String(value) => {
+            let templated = value
+                .parts
+                .iter()
+                .any(|part| matches!(part, InterpolatedStringPart::Template(_)));
+            if templated {
+                ValueType::Unknown
+            } else {
+                ValueType::String
+            }
+        }
+        VariableValue::Invalid => ValueType::Unknown,
+    }
+}
+
+/// The type hurl parses the rendered value of an option, or a value of a variables file, as
+pub(crate) fn literal_type(literal: &str) -> ValueType {
+    if literal == "true" || literal == "false" {
+        ValueType::Boolean
+    } else if literal.parse::<i64>().is_ok() {
+        ValueType::Integer
+    } else if literal.parse::<f64>().is_ok() {
+        ValueType::Float
+    } else {
+        ValueType::String
+    }
+}
+
+/// The type returned by a function of a template like `{{newUuid}}`
+fn function_type(name: &str) -> ValueType {
+    match name {
+        "newUuid" => ValueType::String,
+        "newDate" => ValueType::Date,
+        _ => ValueType::Unknown,
+    }
+}
+
+const BOOLEAN_OPTION: Expected = Expected {
+    types: &[ValueType::Boolean],
+    description: "a boolean",
+};
+const INTEGER_OPTION: Expected = Expected {
+    types: &[ValueType::Integer],
+    description: "an integer",
+};
+
+/// The name and the accepted input of an option taking a boolean, a duration or an integer
+/// with the template giving its value
+fn templated_option(
+    option: &RequestOption,
+) -> Option<(&'static str, &'static Expected, &Template)> {
+    let (name, value) = match option {
+        RequestOption::Compressed(value) => ("compressed", value),
+        RequestOption::Location(value) => ("location", value),
+        RequestOption::LocationTrusted(value) => ("location-trusted", value),
+        RequestOption::Http10(value) => ("http1.0", value),
+        RequestOption::Http11(value) => ("http1.1", value),
+        RequestOption::Http2(value) => ("http2", value),
+        RequestOption::Http3(value) => ("http3", value),
+        RequestOption::Insecure(value) => ("insecure", value),
+        RequestOption::Ipv4(value) => ("ipv4", value),
+        RequestOption::Ipv6(value) => ("ipv6", value),
+        RequestOption::Netrc(value) => ("netrc", value),
+        RequestOption::NetrcOptional(value) => ("netrc-optional", value),
+        RequestOption::PathAsIs(value) => ("path-as-is", value),
+        RequestOption::Skip(value) => ("skip", value),
+        RequestOption::Verbose(value) => ("verbose", value),
+        RequestOption::VeryVerbose(value) => ("very-verbose", value),
+        RequestOption::ConnectTimeout(DurationOption::Template(template)) => {
+            return Some(("connect-timeout", &INTEGER_OPTION, template))
+        }
+        RequestOption::Delay(DurationOption::Template(template)) => {
+            return Some(("delay", &INTEGER_OPTION, template))
+        }
+        RequestOption::RetryInterval(DurationOption::Template(template)) => {
+            return Some(("retry-interval", &INTEGER_OPTION, template))
+        }
+        RequestOption::LimitRate(IntegerOption::Template(template)) => {
+            return Some(("limit-rate", &INTEGER_OPTION, template))
+        }
+        RequestOption::MaxRedirs(IntegerOption::Template(template)) => {
+            return Some(("max-redirs", &INTEGER_OPTION, template))
+        }
+        RequestOption::Repeat(IntegerOption::Template(template)) => {
+            return Some(("repeat", &INTEGER_OPTION, template))
+        }
+        RequestOption::Retry(IntegerOption::Template(template)) => {
+            return Some(("retry", &INTEGER_OPTION, template))
+        }
+        _ => return None,
+    };
+    match value {
+        BooleanOption::Template(template) => Some((name, &BOOLEAN_OPTION, template)),
+        BooleanOption::Literal(_) => None,
+    }
+}
+
+/// The occurrence of the given kind whose name is inside the span
+fn occurrence_in(
+    occurrences: &[Occurrence],
+    kind: OccurrenceKind,
+    span: Span,
+) -> Option<&Occurrence> {
+    occurrences.iter().find(|occurrence| {
+        occurrence.kind == kind
+            && span.start <= occurrence.span.start
+            && occurrence.span.end <= span.end
+    })
+}
+
+/// The type mismatches of the document. The type of a variable is inferred from the value
+/// of its `variable` option or from the query and filters of its capture, and is then used
+/// for the templates and `variable` queries that read it. Variables from the variables
+/// files or the environment have an unknown type.
+pub fn type_mismatches(ast: &Ast) -> Vec<TypeMismatch> {
+    let occurrences = occurrences(ast);
+    let mut mismatches = vec![];
+
+    //The type of each definition by the start of its name
+    let mut definitions = HashMap::new();
+    //The type of the string literal of a `variable` option once parsed by an option
+    let mut literals = HashMap::new();
+    for entry in &ast.entries {
+        for section in entry.request_sections() {
+            let RequestSection::OptionsSection(section) = section else {
+                continue;
+            };
+            for (option, _) in &section.options {
+                if let RequestOption::Variable(variable) = option {
+                    let value_type = variable_value_type(&variable.value.0);
+                    definitions.insert(variable.name.1.start, value_type);
+                    if let (VariableValue::String(value), ValueType::String) =
+                        (&variable.value.0, value_type)
+                    {
+                        let literal = value
+                            .parts
+                            .iter()
+                            .filter_map(|part| match part {
+                                InterpolatedStringPart::Str(s) => Some(s.as_str()),
+                                InterpolatedStringPart::Template(_) => None,
+                            })
+                            .collect::<String>();
+                        literals.insert(variable.name.1.start, literal_type(&literal));
+                    }
+                }
+            }
+        }
+        for section in entry.response_sections() {
+            let ResponseSection::CapturesSection(section) = section else {
+                continue;
+            };
+            for capture in &section.captures {
+                let value_type = check_filters(
+                    query_type(&capture.query.0),
+                    &capture.filters,
+                    &mut mismatches,
+                );
+                if let Some(occurrence) =
+                    occurrence_in(&occurrences, OccurrenceKind::Capture, capture.key.span)
+                {
+                    definitions.insert(occurrence.span.start, value_type);
+                }
+            }
+        }
+    }
+    let variable_type = |kind: OccurrenceKind, span: Span| {
+        occurrence_in(&occurrences, kind, span)
+            .and_then(|occurrence| definition(&occurrences, occurrence))
+            .and_then(|definition| definitions.get(&definition.span.start))
+            .copied()
+            .unwrap_or(ValueType::Unknown)
+    };
+
+    //The type of each template by its start once its filters are applied
+    let mut templates = HashMap::new();
+    for template in visit::templates(ast) {
+        let input = match &template.expr.variable {
+            (ExprValue::VariableName(_), span) => variable_type(OccurrenceKind::Template, *span),
+            (ExprValue::FunctionName(name), _) => function_type(name),
+        };
+        let value_type = check_filters(input, &template.expr.filters, &mut mismatches);
+        templates.insert(template.span.start, value_type);
+    }
+    let template_type = |template: &Template| {
+        templates
+            .get(&template.span.start)
+            .copied()
+            .unwrap_or(ValueType::Unknown)
+    };
+    //Hurl parses the rendered template of an option so a string literal given as is is read
+    //as the value it spells
+    let option_value_type = |template: &Template| {
+        let value_type = template_type(template);
+        match &template.expr.variable {
+            (ExprValue::VariableName(_), span)
+                if value_type == ValueType::String && template.expr.filters.is_empty() =>
+            {
+                occurrence_in(&occurrences, OccurrenceKind::Template, *span)
+                    .and_then(|occurrence| definition(&occurrences, occurrence))
+                    .and_then(|definition| literals.get(&definition.span.start))
+                    .copied()
+                    .unwrap_or(value_type)
+            }
+            _ => value_type,
+        }
+    };
+
+    for entry in &ast.entries {
+        for section in entry.request_sections() {
+            let RequestSection::OptionsSection(section) = section else {
+                continue;
+            };
+            for (option, _) in &section.options {
+                let Some((name, expected, template)) = templated_option(option) else {
+                    continue;
+                };
+                let value_type = option_value_type(template);
+                if !expected.accepts(value_type) {
+                    mismatches.push(TypeMismatch {
+                        span: template.span,
+                        message: format!(
+                            "Option `{}` expects {}, found {}",
+                            name, expected.description, value_type
+                        ),
+                    });
+                }
+            }
+        }
+        for section in entry.response_sections() {
+            let ResponseSection::AssertsSection(section) = section else {
+                continue;
+            };
+            for assert in &section.asserts {
+                let (query, span) = &assert.query;
+                let input = match query {
+                    Query::Variable(_) => variable_type(OccurrenceKind::Query, *span),
+                    query => query_type(query),
+                };
+                let value_type = check_filters(input, &assert.filters, &mut mismatches);
+                let predicate_value_type = |value: &PredicateValue| match value {
+                    PredicateValue::Template(template) => template_type(template),
+                    value => predicate_value_type(value),
+                };
+                check_predicate(
+                    value_type,
+                    &assert.predicate.function,
+                    predicate_value_type,
+                    &mut mismatches,
+                );
+            }
+        }
+    }
+    mismatches.sort_by_key(|mismatch| mismatch.span.start);
+    mismatches
+}
+
+/// The type mismatches as error diagnostics
+pub fn type_diagnostics(rope: &Rope, ast: &Ast, encoding: PositionEncoding) -> Vec<Diagnostic> {
+    type_mismatches(ast)
+        .into_iter()
+        .filter_map(|mismatch| {
+            Some(Diagnostic::new(
+                byte_span_to_range(mismatch.span, rope, encoding)?,
+                Some(DiagnosticSeverity::ERROR),
+                Some(NumberOrString::String(TYPE_MISMATCH.to_string())),
+                Some("hurl".to_string()),
+                mismatch.message,
+                None,
+                None,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod type_check_tests {
+    use super::*;
+    use hurl_parser::parser::parser::parse_ast;
+
+    /// The text of each mismatch with its message
+    fn mismatches(asserts: &str) -> Vec<(String, String)> {
+        let test_str = format!("GET https://example.org\nHTTP 200\n{asserts}");
+        let (ast, errs) = parse_ast(&test_str);
+        assert_eq!(errs, vec![]);
+        type_mismatches(&ast.unwrap())
+            .into_iter()
+            .map(|mismatch| {
+                (
+                    test_str[mismatch.span.start..mismatch.span.end].to_string(),
+                    mismatch.message,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_well_typed_asserts() {
+        let asserts = r#"[Asserts]
+status == 200
+status >= 200
+duration < 1000
+header "Content-Type" contains "json"
+header "Date" toDate "%a, %d %b %Y %H:%M:%S GMT" daysBeforeNow < 1
+header "X-Count" toInt == 2
+header "Set-Cookie" count == 2
+header "Vary" includes "x"
+body matches "[a-z]+"
+bytes count > 10
+jsonpath "$.users" count == 2
+jsonpath "$.name" contains "bob"
+xpath "//li" count == 3
+sha256 == hex,2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824;
+certificate "Expire-Date" daysAfterNow > 15
+url split "/" nth 0 == "https:"
+"#;
+        assert_eq!(mismatches(asserts), vec![]);
+    }
+
+    #[test]
+    fn it_reports_predicates_of_the_wrong_type() {
+        let asserts = r#"[Asserts]
+status contains "ok"
+status == "ok"
+body count == 1
+url isIsoDate
+status startsWith "2"
+"#;
+        assert_eq!(
+            mismatches(asserts),
+            vec![
+                (
+                    "contains \"ok\"".to_string(),
+                    "Predicate `contains` expects a string, bytes or a collection, found integer"
+                        .to_string()
+                ),
+                (
+                    "\"ok\"".to_string(),
+                    "Predicate `==` compares integer with string".to_string()
+                ),
+                (
+                    "count".to_string(),
+                    "Filter `count` expects a collection, found string".to_string()
+                ),
+                (
+                    "startsWith \"2\"".to_string(),
+                    "Predicate `startsWith` expects a string or bytes, found integer".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_filters_of_the_wrong_type() {
+        let asserts = r#"[Captures]
+id: status toInt daysAfterNow
+[Asserts]
+bytes toInt == 1
+url format "%Y" == "2024"
+certificate "Start-Date" toDate "%Y" isDate
+"#;
+        assert_eq!(
+            mismatches(asserts),
+            vec![
+                (
+                    "daysAfterNow".to_string(),
+                    "Filter `daysAfterNow` expects a date, found integer".to_string()
+                ),
+                (
+                    "toInt".to_string(),
+                    "Filter `toInt` expects a number or a string, found bytes".to_string()
+                ),
+                (
+                    "format \"%Y\"".to_string(),
+                    "Filter `format` expects a date, found string".to_string()
+                ),
+                (
+                    "toDate \"%Y\"".to_string(),
+                    "Filter `toDate` expects a string, found date".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_checks_misplaced_sections() {
+        let sections = r#"[Options]
+variable: host=localhost
+delay: {{host}}
+[Captures]
+id: status toInt daysAfterNow
+"#;
+        assert_eq!(
+            mismatches(sections),
+            vec![
+                (
+                    "{{host}}".to_string(),
+                    "Option `delay` expects an integer, found string".to_string()
+                ),
+                (
+                    "daysAfterNow".to_string(),
+                    "Filter `daysAfterNow` expects a date, found integer".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_infers_the_types_of_variables() {
+        let test_str = r#"GET https://example.org/{{id toInt}}
+[Options]
+variable: verbose=1
+variable: retries=2
+variable: limit=1.5
+variable: host=localhost
+variable: repeats="3"
+variable: interval="{{retries}}00"
+verbose: {{verbose}}
+retry: {{retries}}
+max-redirs: {{limit}}
+delay: {{host}}
+repeat: {{repeats}}
+retry-interval: {{interval}}
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+count: jsonpath "$.users" count
+date: header "Date" toDate "%a, %d %b %Y"
+[Asserts]
+status == {{retries}}
+status == {{host}}
+variable "count" contains "x"
+variable "date" daysBeforeNow < 1
+variable "id" > {{date}}
+variable "date" == {{retries}}
+"#;
+        let (ast, errs) = parse_ast(test_str);
+        assert_eq!(errs, vec![]);
+        assert_eq!(
+            type_mismatches(&ast.unwrap())
+                .into_iter()
+                .map(|mismatch| (
+                    &test_str[mismatch.span.start..mismatch.span.end],
+                    mismatch.message
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "{{verbose}}",
+                    "Option `verbose` expects a boolean, found integer".to_string()
+                ),
+                (
+                    "{{limit}}",
+                    "Option `max-redirs` expects an integer, found float".to_string()
+                ),
+                (
+                    "{{host}}",
+                    "Option `delay` expects an integer, found string".to_string()
+                ),
+                (
+                    "{{host}}",
+                    "Predicate `==` compares integer with string".to_string()
+                ),
+                (
+                    "contains \"x\"",
+                    "Predicate `contains` expects a string, bytes or a collection, found integer"
+                        .to_string()
+                ),
+                (
+                    "{{retries}}",
+                    "Predicate `==` compares date with integer".to_string()
+                ),
+            ]
+        );
+    }
+}
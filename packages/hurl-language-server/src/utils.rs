@@ -1,13 +1,188 @@
+use hurl_parser::parser::types::Span;
 use ropey::Rope;
-use tower_lsp::lsp_types::Position;
+use tower_lsp::lsp_types::{Position, PositionEncodingKind, Range};
 
 pub fn usize_to_u32(x: usize) -> u32 {
     x as u32 //TODO handle this better
 }
 
-pub fn offset_to_position(offset: usize, rope: &Rope) -> Option<Position> {
-    let line = rope.try_char_to_line(offset).ok()?;
-    let first_char_of_line = rope.try_line_to_char(line).ok()?;
-    let column = offset - first_char_of_line;
-    Some(Position::new(line as u32, column as u32))
+/// How the characters of a line are counted in the columns of LSP positions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PositionEncoding {
+    /// Bytes of the UTF-8 encoded line
+    Utf8,
+    /// UTF-16 code units, the default of the LSP specification
+    #[default]
+    Utf16,
+    /// Unicode scalar values
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Picks UTF-8 when the client lists it anywhere in its `general.positionEncodings`
+    /// since the documents and the spans of the parser are UTF-8. Otherwise falls back to
+    /// UTF-16 which every client supports.
+    pub fn negotiate(client_encodings: Option<&[PositionEncodingKind]>) -> PositionEncoding {
+        let utf8 = client_encodings
+            .into_iter()
+            .flatten()
+            .any(|kind| *kind == PositionEncodingKind::UTF8);
+        if utf8 {
+            PositionEncoding::Utf8
+        } else {
+            PositionEncoding::Utf16
+        }
+    }
+
+    /// The kind announced to the client in the server capabilities
+    pub fn kind(self) -> PositionEncodingKind {
+        match self {
+            PositionEncoding::Utf8 => PositionEncodingKind::UTF8,
+            PositionEncoding::Utf16 => PositionEncodingKind::UTF16,
+            PositionEncoding::Utf32 => PositionEncodingKind::UTF32,
+        }
+    }
+
+    /// The number of units the char takes in a column
+    pub fn len(self, c: char) -> usize {
+        match self {
+            PositionEncoding::Utf8 => c.len_utf8(),
+            PositionEncoding::Utf16 => c.len_utf16(),
+            PositionEncoding::Utf32 => 1,
+        }
+    }
+}
+
+/// The char index of the position. Like the LSP specification asks, a column past the end
+/// of its line is the end of the line and a line past the end of the document is the end
+/// of the document.
+pub fn position_to_char_index(
+    position: Position,
+    rope: &Rope,
+    encoding: PositionEncoding,
+) -> usize {
+    let line = position.line as usize;
+    if line >= rope.len_lines() {
+        return rope.len_chars();
+    }
+    let line_start = rope.line_to_char(line);
+    let mut column = 0;
+    let mut index = line_start;
+    for c in rope.line(line).chars() {
+        if column >= position.character as usize || c == '\n' || c == '\r' {
+            break;
+        }
+        column += encoding.len(c);
+        index += 1;
+    }
+    index
+}
+
+/// The position of a byte offset of the document, like the offsets of the spans of the parser
+pub fn byte_offset_to_position(
+    offset: usize,
+    rope: &Rope,
+    encoding: PositionEncoding,
+) -> Option<Position> {
+    let line = rope.try_byte_to_line(offset).ok()?;
+    let line_start = rope.try_line_to_byte(line).ok()?;
+    let column = rope
+        .get_byte_slice(line_start..offset)?
+        .chars()
+        .map(|c| encoding.len(c))
+        .sum::<usize>();
+    Some(Position::new(usize_to_u32(line), usize_to_u32(column)))
+}
+
+/// The byte offset of a position, or `None` when the line is past the end of the document
+pub fn position_to_byte_offset(
+    position: Position,
+    rope: &Rope,
+    encoding: PositionEncoding,
+) -> Option<usize> {
+    if position.line as usize > rope.len_lines() {
+        return None;
+    }
+    rope.try_char_to_byte(position_to_char_index(position, rope, encoding))
+        .ok()
+}
+
+pub fn byte_span_to_range(span: Span, rope: &Rope, encoding: PositionEncoding) -> Option<Range> {
+    Some(Range::new(
+        byte_offset_to_position(span.start, rope, encoding)?,
+        byte_offset_to_position(span.end, rope, encoding)?,
+    ))
+}
+
+#[cfg(test)]
+mod utils_tests {
+    use super::*;
+
+    #[test]
+    fn it_prefers_utf8_and_falls_back_to_utf16() {
+        assert_eq!(PositionEncoding::negotiate(None), PositionEncoding::Utf16);
+        assert_eq!(
+            PositionEncoding::negotiate(Some(&[
+                PositionEncodingKind::new("utf-7"),
+                PositionEncodingKind::UTF32,
+                PositionEncodingKind::UTF8,
+            ])),
+            PositionEncoding::Utf8
+        );
+        assert_eq!(
+            PositionEncoding::negotiate(Some(&[
+                PositionEncodingKind::UTF32,
+                PositionEncodingKind::UTF16,
+            ])),
+            PositionEncoding::Utf16
+        );
+        assert_eq!(
+            PositionEncoding::negotiate(Some(&[PositionEncodingKind::UTF8])),
+            PositionEncoding::Utf8
+        );
+        assert_eq!(
+            PositionEncoding::negotiate(Some(&[])),
+            PositionEncoding::Utf16
+        );
+    }
+
+    #[test]
+    fn it_converts_byte_offsets_to_positions() {
+        let document = "GET https://example.org\nX-Name: 日本 😀 {{name}}\n";
+        let rope = Rope::from_str(document);
+        let offset = document.find("{{name}}").unwrap();
+        let column = |encoding| {
+            byte_offset_to_position(offset, &rope, encoding)
+                .unwrap()
+                .character
+        };
+        assert_eq!(column(PositionEncoding::Utf8), 20);
+        assert_eq!(column(PositionEncoding::Utf16), 14);
+        assert_eq!(column(PositionEncoding::Utf32), 13);
+        for encoding in [
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32,
+        ] {
+            let position = byte_offset_to_position(offset, &rope, encoding).unwrap();
+            assert_eq!(position.line, 1);
+            assert_eq!(
+                position_to_byte_offset(position, &rope, encoding),
+                Some(offset)
+            );
+        }
+    }
+
+    #[test]
+    fn it_clamps_positions_past_the_end_of_a_line() {
+        let rope = Rope::from_str("GET https://example.org\nHTTP 200\n");
+        assert_eq!(
+            position_to_byte_offset(Position::new(0, 99), &rope, PositionEncoding::Utf16),
+            Some(23)
+        );
+        assert_eq!(
+            position_to_byte_offset(Position::new(9, 0), &rope, PositionEncoding::Utf16),
+            None
+        );
+    }
 }
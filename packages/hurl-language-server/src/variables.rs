@@ -0,0 +1,265 @@
+use hurl_parser::parser::types::{
+    Ast, ExprValue, InterpolatedString, InterpolatedStringPart, Query, RequestOption,
+    RequestSection, ResponseSection, Span,
+};
+use hurl_parser::parser::visit::templates;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OccurrenceKind {
+    /// The key of a `[Captures]` entry
+    Capture,
+    /// The name of a `variable: name=value` option
+    Option,
+    /// A `{{name}}` template
+    Template,
+    /// A `variable "name"` query
+    Query,
+}
+
+impl OccurrenceKind {
+    pub fn is_definition(self) -> bool {
+        matches!(self, OccurrenceKind::Capture | OccurrenceKind::Option)
+    }
+}
+
+/// A variable name that is defined or used in a document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Occurrence {
+    pub name: String,
+    pub kind: OccurrenceKind,
+    /// The span of the name only
+    pub span: Span,
+    /// The offset from which a definition is visible or at which a use reads the variable.
+    /// The `variable` options are evaluated before the request they belong to so they are
+    /// visible from the start of the entry.
+    pub scope: usize,
+}
+
+/// Every definition and use of a variable in the document ordered by position
+pub fn occurrences(ast: &Ast) -> Vec<Occurrence> {
+    let mut occurrences = vec![];
+    for entry in &ast.entries {
+        for section in entry.request_sections() {
+            let RequestSection::OptionsSection(section) = section else {
+                continue;
+            };
+            for (option, _) in &section.options {
+                if let RequestOption::Variable(variable) = option {
+                    occurrences.push(Occurrence {
+                        name: variable.name.0.clone(),
+                        kind: OccurrenceKind::Option,
+                        span: variable.name.1,
+                        scope: entry.span.start,
+                    });
+                }
+            }
+        }
+        for section in entry.response_sections() {
+            let (captures, asserts) = match section {
+                ResponseSection::CapturesSection(section) => (&section.captures[..], &[][..]),
+                ResponseSection::AssertsSection(section) => (&[][..], &section.asserts[..]),
+                ResponseSection::UnknownSection(_) => continue,
+            };
+            for capture in captures {
+                if let Some((name, span)) = plain_name(&capture.key) {
+                    occurrences.push(Occurrence {
+                        name,
+                        kind: OccurrenceKind::Capture,
+                        span,
+                        scope: capture.span.end,
+                    });
+                }
+            }
+            let queries = captures
+                .iter()
+                .map(|capture| &capture.query)
+                .chain(asserts.iter().map(|assert| &assert.query));
+            for (query, _) in queries {
+                if let Query::Variable(value) = query {
+                    if let Some((name, span)) = plain_name(value) {
+                        occurrences.push(Occurrence {
+                            name,
+                            kind: OccurrenceKind::Query,
+                            span,
+                            scope: span.start,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    for template in templates(ast) {
+        if let (ExprValue::VariableName(name), span) = &template.expr.variable {
+            occurrences.push(Occurrence {
+                name: name.clone(),
+                kind: OccurrenceKind::Template,
+                span: *span,
+                scope: span.start,
+            });
+        }
+    }
+    occurrences.sort_by_key(|occurrence| occurrence.span.start);
+    occurrences
+}
+
+/// The occurrence under the offset. The end of a name counts as being on it so that a
+/// cursor right after the name still finds it.
+pub fn occurrence_at(occurrences: &[Occurrence], offset: usize) -> Option<&Occurrence> {
+    occurrences
+        .iter()
+        .find(|occurrence| occurrence.span.start <= offset && offset <= occurrence.span.end)
+}
+
+/// The definition that gives the occurrence its value which is the most recent definition
+/// visible where the variable is read. A definition is its own definition.
+pub fn definition<'a>(
+    occurrences: &'a [Occurrence],
+    occurrence: &'a Occurrence,
+) -> Option<&'a Occurrence> {
+    if occurrence.kind.is_definition() {
+        return Some(occurrence);
+    }
+    occurrences
+        .iter()
+        .filter(|candidate| {
+            candidate.kind.is_definition()
+                && candidate.name == occurrence.name
+                && candidate.scope <= occurrence.scope
+        })
+        .max_by_key(|candidate| (candidate.scope, candidate.span.start))
+}
+
+/// The name of an interpolated string without templates with the span of the name only,
+/// without the surrounding quotes.
+fn plain_name(value: &InterpolatedString) -> Option<(String, Span)> {
+    let name = match &value.parts[..] {
+        [InterpolatedStringPart::Str(name)] => name.clone(),
+        _ => return None,
+    };
+    let span = value.span;
+    if span.end - span.start == name.len() {
+        Some((name, span))
+    } else if span.end - span.start == name.len() + 2 {
+        Some((name, Span::new(span.start + 1, span.end - 1)))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod variables_tests {
+    use super::*;
+    use hurl_parser::parser::parser::parse_ast;
+
+    fn describe(test_str: &str) -> Vec<String> {
+        let (ast, _) = parse_ast(test_str);
+        occurrences(&ast.unwrap())
+            .iter()
+            .map(|occurrence| {
+                format!(
+                    "{:?} {} {}",
+                    occurrence.kind,
+                    occurrence.name,
+                    &test_str[occurrence.span.start..occurrence.span.end]
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_finds_the_occurrences_of_variables() {
+        let test_str = r#"POST https://{{host}}/users/{{newUuid}}
+[Options]
+variable: host=localhost
+{"id": {{id}}}
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+[Asserts]
+variable "id" == {{id}}
+"#;
+        assert_eq!(
+            describe(test_str),
+            vec![
+                "Template host host",
+                "Option host host",
+                "Template id id",
+                "Capture id id",
+                "Query id id",
+                "Template id id",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_finds_the_occurrences_in_misplaced_sections() {
+        let test_str = r#"GET https://example.org
+[Captures]
+id: jsonpath "$.id"
+HTTP 200
+[Options]
+variable: host=localhost
+"#;
+        assert_eq!(
+            describe(test_str),
+            vec!["Capture id id", "Option host host"]
+        );
+    }
+
+    #[test]
+    fn it_finds_the_most_recent_definition() {
+        let test_str = r#"GET https://example.org
+HTTP 200
+[Captures]
+token: header "token"
+
+GET https://example.org
+[Options]
+variable: token=abc
+HTTP 200
+[Captures]
+token: header "other"
+
+GET https://example.org/{{token}}
+"#;
+        let (ast, _) = parse_ast(test_str);
+        let occurrences = occurrences(&ast.unwrap());
+        let offset = test_str.find("{{token}}").unwrap() + 2;
+        let occurrence = occurrence_at(&occurrences, offset).unwrap();
+        let found = definition(&occurrences, occurrence).unwrap();
+        assert_eq!(found.kind, OccurrenceKind::Capture);
+        assert_eq!(
+            found.span.start,
+            test_str.find("token: header \"other\"").unwrap()
+        );
+    }
+
+    #[test]
+    fn it_finds_an_option_defined_later_in_the_same_entry() {
+        let test_str = r#"GET https://{{host}}
+[Options]
+variable: host=localhost
+"#;
+        let (ast, _) = parse_ast(test_str);
+        let occurrences = occurrences(&ast.unwrap());
+        let occurrence = occurrence_at(&occurrences, 14).unwrap();
+        let found = definition(&occurrences, occurrence).unwrap();
+        assert_eq!(found.kind, OccurrenceKind::Option);
+    }
+
+    #[test]
+    fn it_does_not_find_a_definition_after_the_use() {
+        let test_str = r#"GET https://{{id}}
+HTTP 200
+
+GET https://example.org
+HTTP 200
+[Captures]
+id: jsonpath "$.id"
+"#;
+        let (ast, _) = parse_ast(test_str);
+        let occurrences = occurrences(&ast.unwrap());
+        let occurrence = occurrence_at(&occurrences, 14).unwrap();
+        assert_eq!(definition(&occurrences, occurrence), None);
+    }
+}
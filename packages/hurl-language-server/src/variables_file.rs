@@ -0,0 +1,301 @@
+use std::path::{Path, PathBuf};
+
+use hurl_parser::parser::types::Span;
+use serde_json::Value;
+
+use crate::workspace::find_files;
+
+/// The name of the variables files found in the workspace when none are configured
+pub const DEFAULT_VARIABLES_FILE: &str = "vars.env";
+
+/// A variable of a variables file as passed to `hurl --variables-file`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileVariable {
+    pub name: String,
+    pub value: String,
+    /// The byte span of the name in the file, like the spans of the parser
+    pub span: Span,
+}
+
+/// A file with one `name=value` variable per line. The spaces around the name and the value
+/// are ignored, so are blank lines and lines starting with `#`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariablesFile {
+    pub path: PathBuf,
+    pub text: String,
+    pub variables: Vec<FileVariable>,
+}
+
+impl VariablesFile {
+    pub fn parse(path: PathBuf, text: &str) -> VariablesFile {
+        let mut variables = vec![];
+        let mut line_start = 0;
+        for line in text.split_inclusive('\n') {
+            let offset = line_start;
+            line_start += line.len();
+            let line = line.strip_suffix('\n').unwrap_or(line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let Some((name, value)) = trimmed.split_once('=') else {
+                continue;
+            };
+            let name = name.trim_end();
+            let start = offset + line.len() - trimmed.len();
+            variables.push(FileVariable {
+                name: name.to_string(),
+                value: value.trim().to_string(),
+                span: Span::new(start, start + name.len()),
+            });
+        }
+        VariablesFile {
+            path,
+            text: text.to_string(),
+            variables,
+        }
+    }
+
+    pub fn read(path: &Path) -> std::io::Result<VariablesFile> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(VariablesFile::parse(path.to_path_buf(), &text))
+    }
+
+    /// The variable with the name. A variable defined several times takes the last value
+    /// like hurl does.
+    pub fn get(&self, name: &str) -> Option<&FileVariable> {
+        self.variables
+            .iter()
+            .rev()
+            .find(|variable| variable.name == name)
+    }
+}
+
+/// The variables files listed in the `variablesFiles` initialization option. Relative paths
+/// are resolved from the workspace root.
+pub fn configured_variables_files(options: Option<&Value>, root: Option<&Path>) -> Vec<PathBuf> {
+    let paths = match options.and_then(|options| options.get("variablesFiles")) {
+        Some(Value::String(path)) => vec![path.as_str()],
+        Some(Value::Array(paths)) => paths.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+    paths
+        .into_iter()
+        .map(|path| match root {
+            Some(root) if Path::new(path).is_relative() => root.join(path),
+            _ => PathBuf::from(path),
+        })
+        .collect()
+}
+
+/// The `vars.env` files of the workspace folders, used when no variables file is configured
+pub fn discover_variables_files(folders: &[PathBuf]) -> Vec<PathBuf> {
+    folders
+        .iter()
+        .flat_map(|folder| {
+            find_files(folder, |path| {
+                path.file_name()
+                    .is_some_and(|name| name == DEFAULT_VARIABLES_FILE)
+            })
+        })
+        .collect()
+}
+
+/// A variable injected with a `HURL_VARIABLE_name` or the older `HURL_name` environment
+/// variable
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvironmentVariable {
+    /// The name of the environment variable
+    pub key: String,
+    pub name: String,
+    pub value: String,
+}
+
+/// The hurl variables of the environment. `HURL_VARIABLE_name` wins over `HURL_name`.
+pub fn environment_variables(
+    environment: impl IntoIterator<Item = (String, String)>,
+) -> Vec<EnvironmentVariable> {
+    let mut variables: Vec<EnvironmentVariable> = environment
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let name = key
+                .strip_prefix("HURL_VARIABLE_")
+                .or_else(|| key.strip_prefix("HURL_"))?
+                .to_string();
+            (!name.is_empty()).then_some(EnvironmentVariable { key, name, value })
+        })
+        .collect();
+    variables.sort_by_key(|variable| {
+        (
+            variable.name.clone(),
+            variable.key.starts_with("HURL_VARIABLE_"),
+        )
+    });
+    variables
+}
+
+/// The variables defined outside of hurl files. Like with hurl, the variables files
+/// override the environment and a later file overrides an earlier one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExternalVariables {
+    pub environment: Vec<EnvironmentVariable>,
+    pub files: Vec<VariablesFile>,
+}
+
+/// Where an external variable gets its value from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalVariable<'a> {
+    File(&'a VariablesFile, &'a FileVariable),
+    Environment(&'a EnvironmentVariable),
+}
+
+impl<'a> ExternalVariable<'a> {
+    pub fn value(&self) -> &'a str {
+        match self {
+            ExternalVariable::File(_, variable) => &variable.value,
+            ExternalVariable::Environment(variable) => &variable.value,
+        }
+    }
+}
+
+impl ExternalVariables {
+    /// Reads the variables files, skipping the ones that can't be read
+    pub fn read(
+        paths: &[PathBuf],
+        environment: impl IntoIterator<Item = (String, String)>,
+    ) -> ExternalVariables {
+        ExternalVariables {
+            environment: environment_variables(environment),
+            files: paths
+                .iter()
+                .filter_map(|path| VariablesFile::read(path).ok())
+                .collect(),
+        }
+    }
+
+    /// The definition giving the variable its value
+    pub fn get(&self, name: &str) -> Option<ExternalVariable<'_>> {
+        self.files
+            .iter()
+            .rev()
+            .find_map(|file| Some(ExternalVariable::File(file, file.get(name)?)))
+            .or_else(|| {
+                self.environment
+                    .iter()
+                    .rfind(|variable| variable.name == name)
+                    .map(ExternalVariable::Environment)
+            })
+    }
+
+    /// The names of every external variable, sorted and without duplicates
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .environment
+            .iter()
+            .map(|variable| variable.name.as_str())
+            .chain(
+                self.files
+                    .iter()
+                    .flat_map(|file| file.variables.iter().map(|variable| variable.name.as_str())),
+            )
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+#[cfg(test)]
+mod variables_file_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_parses_a_variables_file() {
+        let file = VariablesFile::parse(
+            PathBuf::from("vars.env"),
+            "# the server\nhost=localhost\n\n  port = 8080\ntoken=a=b\n",
+        );
+        assert_eq!(
+            file.variables,
+            vec![
+                FileVariable {
+                    name: "host".to_string(),
+                    value: "localhost".to_string(),
+                    span: Span::new(13, 17),
+                },
+                FileVariable {
+                    name: "port".to_string(),
+                    value: "8080".to_string(),
+                    span: Span::new(31, 35),
+                },
+                FileVariable {
+                    name: "token".to_string(),
+                    value: "a=b".to_string(),
+                    span: Span::new(43, 48),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reads_the_configured_variables_files() {
+        let options = json!({ "variablesFiles": ["vars.env", "/etc/hurl/vars.env"] });
+        assert_eq!(
+            configured_variables_files(Some(&options), Some(Path::new("/project"))),
+            vec![
+                PathBuf::from("/project/vars.env"),
+                PathBuf::from("/etc/hurl/vars.env")
+            ]
+        );
+        let options = json!({ "variablesFiles": "vars.env" });
+        assert_eq!(
+            configured_variables_files(Some(&options), None),
+            vec![PathBuf::from("vars.env")]
+        );
+    }
+
+    #[test]
+    fn it_reads_hurl_environment_variables() {
+        let environment = [
+            ("HURL_VARIABLE_host", "example.net"),
+            ("HURL_host", "example.org"),
+            ("HURL_token", "abc"),
+            ("HURL_", "nothing"),
+            ("HOME", "/root"),
+        ]
+        .map(|(key, value)| (key.to_string(), value.to_string()));
+        let variables = ExternalVariables::read(&[], environment);
+        assert_eq!(variables.names(), vec!["host", "token"]);
+        assert_eq!(
+            variables.get("host").map(|variable| variable.value()),
+            Some("example.net")
+        );
+        assert_eq!(variables.get("HOME"), None);
+    }
+
+    #[test]
+    fn it_lets_variables_files_override_the_environment() {
+        let file = VariablesFile::parse(PathBuf::from("vars.env"), "host=localhost\n");
+        let variables = ExternalVariables {
+            environment: environment_variables([("HURL_host".to_string(), "env".to_string())]),
+            files: vec![file.clone()],
+        };
+        assert_eq!(
+            variables.get("host"),
+            Some(ExternalVariable::File(&file, &file.variables[0]))
+        );
+    }
+
+    #[test]
+    fn it_discovers_variables_files() {
+        let root = std::env::temp_dir().join(format!("hurl-ls-vars-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("api")).unwrap();
+        std::fs::write(root.join("api/vars.env"), "host=localhost\n").unwrap();
+        std::fs::write(root.join("api/other.env"), "").unwrap();
+        let files = discover_variables_files(std::slice::from_ref(&root));
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(files, vec![root.join("api/vars.env")]);
+    }
+}
@@ -0,0 +1,190 @@
+use std::path::{Path, PathBuf};
+
+use hurl_parser::parser::types::Ast;
+use ropey::Rope;
+use tower_lsp::lsp_types::{DocumentSymbol, Location, SymbolInformation, Url};
+
+use crate::symbols::document_symbols;
+use crate::utils::PositionEncoding;
+
+/// The `.hurl` files under the directory. Hidden directories like `.git` are skipped.
+pub fn find_hurl_files(directory: &Path) -> Vec<PathBuf> {
+    find_files(directory, is_hurl_file)
+}
+
+/// The files under the directory matching the predicate, skipping hidden directories
+pub fn find_files(directory: &Path, predicate: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let mut directories = vec![directory.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with('.'));
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() && !hidden => directories.push(path),
+                Ok(file_type) if file_type.is_file() && predicate(&path) => files.push(path),
+                _ => {}
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+pub fn is_hurl_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "hurl")
+}
+
+/// The symbols of a file searchable from the whole workspace which are its entries and
+/// captured variables
+pub fn workspace_file_symbols(
+    uri: &Url,
+    rope: &Rope,
+    ast: &Ast,
+    encoding: PositionEncoding,
+) -> Vec<SymbolInformation> {
+    let mut symbols = vec![];
+    for entry in document_symbols(rope, ast, encoding) {
+        for section in entry.children.iter().flatten() {
+            for capture in section.children.iter().flatten() {
+                symbols.push(symbol_information(uri, capture, Some(&entry.name)));
+            }
+        }
+        symbols.push(symbol_information(uri, &entry, None));
+    }
+    symbols
+}
+
+fn symbol_information(
+    uri: &Url,
+    symbol: &DocumentSymbol,
+    container_name: Option<&str>,
+) -> SymbolInformation {
+    #[allow(deprecated)]
+    SymbolInformation {
+        name: symbol.name.clone(),
+        kind: symbol.kind,
+        tags: None,
+        deprecated: None,
+        location: Location::new(uri.clone(), symbol.selection_range),
+        container_name: container_name.map(ToString::to_string),
+    }
+}
+
+/// Whether the symbol matches the query of a `workspace/symbol` request. The characters of
+/// the query must appear in order in the name, ignoring case and the spaces of the query,
+/// so `POST /api/login` finds `POST https://example.org/api/login`.
+pub fn matches_query(symbol: &SymbolInformation, query: &str) -> bool {
+    let name = symbol.name.to_lowercase();
+    let mut chars = name.chars();
+    query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| chars.any(|name_char| name_char == c))
+}
+
+#[cfg(test)]
+mod workspace_tests {
+    use super::*;
+    use hurl_parser::parser::parser::parse_ast;
+    use tower_lsp::lsp_types::SymbolKind;
+
+    const TEST_STR: &str = r#"POST https://example.org/api/login
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+
+GET https://example.org/api/users
+"#;
+
+    fn symbols() -> Vec<SymbolInformation> {
+        let uri = Url::parse("file:///project/login.hurl").unwrap();
+        let (ast, _) = parse_ast(TEST_STR);
+        workspace_file_symbols(
+            &uri,
+            &Rope::from_str(TEST_STR),
+            &ast.unwrap(),
+            PositionEncoding::Utf16,
+        )
+    }
+
+    #[test]
+    fn it_lists_the_entries_and_captures_of_a_file() {
+        let names: Vec<_> = symbols()
+            .into_iter()
+            .map(|symbol| (symbol.name, symbol.kind, symbol.container_name))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (
+                    "token".to_string(),
+                    SymbolKind::VARIABLE,
+                    Some("POST https://example.org/api/login".to_string())
+                ),
+                (
+                    "POST https://example.org/api/login".to_string(),
+                    SymbolKind::FUNCTION,
+                    None
+                ),
+                (
+                    "GET https://example.org/api/users".to_string(),
+                    SymbolKind::FUNCTION,
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_matches_queries() {
+        let found: Vec<_> = symbols()
+            .into_iter()
+            .filter(|symbol| matches_query(symbol, "POST /api/login"))
+            .map(|symbol| symbol.name)
+            .collect();
+        assert_eq!(found, vec!["POST https://example.org/api/login"]);
+        assert_eq!(
+            symbols()
+                .iter()
+                .filter(|symbol| matches_query(symbol, "tok"))
+                .count(),
+            1
+        );
+        assert_eq!(
+            symbols()
+                .iter()
+                .filter(|symbol| matches_query(symbol, ""))
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn it_finds_hurl_files() {
+        let root = std::env::temp_dir().join(format!("hurl-ls-workspace-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("api/users")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("api/login.hurl"), "").unwrap();
+        std::fs::write(root.join("api/users/list.hurl"), "").unwrap();
+        std::fs::write(root.join("api/notes.txt"), "").unwrap();
+        std::fs::write(root.join(".git/ignored.hurl"), "").unwrap();
+        let files = find_hurl_files(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                root.join("api/login.hurl"),
+                root.join("api/users/list.hurl")
+            ]
+        );
+    }
+}
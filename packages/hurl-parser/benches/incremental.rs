@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hurl_parser::parser::incremental::{reparse_ast, Edit};
+use hurl_parser::parser::parser::parse_ast;
+use hurl_parser::parser::types::Span;
+
+/// A document of a few thousand lines made of the same entry repeated
+fn document(entries: usize) -> String {
+    (0..entries)
+        .map(|i| {
+            format!(
+                r#"# Update user {i}
+PUT https://example.org/users/{i}
+Authorization: Bearer {{{{token}}}}
+[Options]
+retry: 3
+{{
+  "name": "user {i}",
+  "tags": ["a", "b"]
+}}
+HTTP 200
+[Captures]
+user_{i}: jsonpath "$.id"
+[Asserts]
+jsonpath "$.name" == "user {i}"
+
+"#
+            )
+        })
+        .collect()
+}
+
+fn bench_reparse(c: &mut Criterion) {
+    let document = document(300);
+    let (ast, errors) = parse_ast(&document);
+    assert!(errors.is_empty());
+
+    //Types a digit into the retry option of the entry in the middle of the document
+    let offset = document.find("users/150").unwrap();
+    let offset = offset + document[offset..].find("retry: 3").unwrap() + "retry: 3".len();
+    let edit = Edit {
+        span: Span::new(offset, offset),
+        text: "0".to_string(),
+    };
+    let mut edited = document.clone();
+    edited.insert_str(offset, &edit.text);
+    assert_eq!(
+        reparse_ast(ast.as_ref(), &errors, &edit, &edited),
+        parse_ast(&edited)
+    );
+
+    let mut group = c.benchmark_group(format!("{} lines", edited.lines().count()));
+    group.bench_function("parse_ast", |b| b.iter(|| parse_ast(black_box(&edited))));
+    group.bench_function("reparse_ast", |b| {
+        b.iter(|| reparse_ast(ast.as_ref(), &errors, black_box(&edit), black_box(&edited)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_reparse);
+criterion_main!(benches);
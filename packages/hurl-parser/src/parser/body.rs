@@ -1,7 +1,9 @@
 use chumsky::prelude::*;
 
+use super::error::ParseError;
 use super::{
     json::json_value_parser,
+    method::method_line_parser,
     multiline_string::multiline_string_parser,
     oneline_base64::oneline_base64_parser,
     oneline_file::oneline_file_parser,
@@ -20,7 +22,7 @@ use super::{
 //
 // and then parse that section of text into the body
 
-pub fn bytes_parser<'a>() -> impl Parser<'a, &'a str, Bytes, extra::Err<Rich<'a, char>>> + Clone {
+pub fn bytes_parser<'a>() -> impl Parser<'a, &'a str, Bytes, extra::Err<ParseError>> + Clone {
     choice((
         //xml_parser().map(Bytes::Xml), //TODO when hurl implements syntax for xml bytes
         multiline_string_parser().map(Bytes::MultilineString),
@@ -28,16 +30,30 @@ pub fn bytes_parser<'a>() -> impl Parser<'a, &'a str, Bytes, extra::Err<Rich<'a,
         oneline_base64_parser().map(Bytes::OneLineBase64),
         oneline_file_parser().map(Bytes::OneLineFile),
         oneline_hex_parser().map(Bytes::OneLineHex),
-        json_value_parser().map(Bytes::JsonValue),
+        json_value_parser().map(|(json, _)| Bytes::JsonValue(json)),
     ))
     .labelled("bytes")
     .boxed()
 }
 
-pub fn body_parser<'a>() -> impl Parser<'a, &'a str, Body, extra::Err<Rich<'a, char>>> + Clone {
-    bytes_parser()
+pub fn body_parser<'a>() -> impl Parser<'a, &'a str, Body, extra::Err<ParseError>> + Clone {
+    //A body can never start with a method line or an HTTP line. Those begin the next entry or
+    //the response and should not be consumed by the error recovery of the body's bytes.
+    let entry_or_response_start = text::whitespace()
+        .then(choice((
+            just("HTTP").ignored(),
+            method_line_parser(true).ignored(),
+        )))
+        .boxed();
+
+    entry_or_response_start
+        .not()
+        .ignore_then(bytes_parser())
+        .map_with(|bytes, e| Body {
+            bytes,
+            span: e.span(),
+        })
         .then_ignore(lt_parser())
-        .map(|bytes| Body { bytes })
         .labelled("body")
         .boxed()
 }
@@ -61,6 +77,7 @@ mod body_tests {
                     bytes: OneLineHex(
                         "2AFA",
                     ),
+                    span: 0..10,
                 },
             ),
             errs: [],
@@ -81,6 +98,7 @@ mod body_tests {
                     bytes: OneLineBase64(
                         "VGhpcyBpcyBhIHRlc3Q=",
                     ),
+                    span: 0..29,
                 },
             ),
             errs: [],
@@ -105,8 +123,10 @@ mod body_tests {
                                     "test",
                                 ),
                             ],
+                            span: 0..6,
                         },
                     ),
+                    span: 0..6,
                 },
             ),
             errs: [],
@@ -138,9 +158,12 @@ mod body_tests {
                                         "    this is some text\n    another line\n    hello world\n                ",
                                     ),
                                 ],
+                                span: 4..75,
                             },
+                            span: 0..78,
                         },
                     ),
+                    span: 0..78,
                 },
             ),
             errs: [],
@@ -165,8 +188,10 @@ mod body_tests {
                                     "example.txt",
                                 ),
                             ],
+                            span: 5..16,
                         },
                     ),
+                    span: 0..17,
                 },
             ),
             errs: [],
@@ -196,14 +221,20 @@ mod body_tests {
                                                 "pet_count",
                                             ),
                                         ],
+                                        span: 14..25,
                                     },
-                                    value: Num(
-                                        "3",
+                                    value: (
+                                        Num(
+                                            "3",
+                                        ),
+                                        27..28,
                                     ),
+                                    span: 14..28,
                                 },
                             ],
                         ),
                     ),
+                    span: 0..42,
                 },
             ),
             errs: [],
@@ -226,18 +257,28 @@ mod body_tests {
                     bytes: JsonValue(
                         Array(
                             [
-                                Num(
-                                    "1",
+                                (
+                                    Num(
+                                        "1",
+                                    ),
+                                    14..15,
                                 ),
-                                Num(
-                                    "2",
+                                (
+                                    Num(
+                                        "2",
+                                    ),
+                                    17..18,
                                 ),
-                                Num(
-                                    "3",
+                                (
+                                    Num(
+                                        "3",
+                                    ),
+                                    20..21,
                                 ),
                             ],
                         ),
                     ),
+                    span: 0..35,
                 },
             ),
             errs: [],
@@ -260,6 +301,7 @@ mod body_tests {
                             "12345",
                         ),
                     ),
+                    span: 0..5,
                 },
             ),
             errs: [],
@@ -285,9 +327,11 @@ mod body_tests {
                                         "testing",
                                     ),
                                 ],
+                                span: 0..9,
                             },
                         ),
                     ),
+                    span: 0..9,
                 },
             ),
             errs: [],
@@ -309,14 +353,20 @@ mod body_tests {
                         Template(
                             Template {
                                 expr: Expr {
-                                    variable: VariableName(
-                                        "body",
+                                    variable: (
+                                        VariableName(
+                                            "body",
+                                        ),
+                                        2..6,
                                     ),
                                     filters: [],
+                                    span: 2..6,
                                 },
+                                span: 0..8,
                             },
                         ),
                     ),
+                    span: 0..8,
                 },
             ),
             errs: [],
@@ -324,4 +374,15 @@ mod body_tests {
         "#,
         );
     }
+
+    #[test]
+    fn it_does_not_parse_a_method_or_http_line_as_a_body() {
+        assert!(body_parser()
+            .parse("GET https://example.org\n")
+            .has_errors());
+        assert!(body_parser().parse("  HTTP 200\n").has_errors());
+        assert!(!body_parser()
+            .parse("`GET https://example.org`\n")
+            .has_errors());
+    }
 }
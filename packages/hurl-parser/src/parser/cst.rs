@@ -0,0 +1,703 @@
+//! A lossless concrete syntax tree.
+//!
+//! The typed [`Ast`] throws away comments, blank lines and spacing. The CST is
+//! built on top of the AST's spans and keeps every byte of the document as a
+//! token so tools like the formatter can keep comments where the user wrote
+//! them. Concatenating the text of every token gives back the original document.
+
+use std::fmt;
+
+use super::types::{
+    Assert, Ast, Body, Capture, Entry, InterpolatedString, InterpolatedStringPart, KeyValue,
+    MultipartFormParam, Request, RequestOption, RequestSection, Response, ResponseSection, Span,
+    Spanned, Url,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SyntaxKind {
+    //Nodes
+    Document,
+    Entry,
+    Request,
+    Response,
+    Method,
+    Url,
+    Version,
+    Status,
+    Header,
+    Section,
+    KeyValue,
+    FileParam,
+    Option,
+    Capture,
+    Assert,
+    Key,
+    Value,
+    Query,
+    Filter,
+    Predicate,
+    Template,
+    Body,
+    //Tokens
+    Whitespace,
+    Newline,
+    Comment,
+    Text,
+}
+
+impl SyntaxKind {
+    /// Whether the text between the children of this node is hurl syntax that can
+    /// contain comments. Text inside values (ex: `\#` in a header value) is never a comment.
+    pub(crate) fn is_structural(self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::Document
+                | SyntaxKind::Entry
+                | SyntaxKind::Request
+                | SyntaxKind::Response
+                | SyntaxKind::Header
+                | SyntaxKind::Section
+                | SyntaxKind::KeyValue
+                | SyntaxKind::FileParam
+                | SyntaxKind::Capture
+                | SyntaxKind::Assert
+        )
+    }
+
+    pub fn is_trivia(self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::Whitespace | SyntaxKind::Newline | SyntaxKind::Comment
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SyntaxToken {
+    pub kind: SyntaxKind,
+    pub span: Span,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub span: Span,
+    pub children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+    /// All the tokens of this node in document order
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = vec![];
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken>) {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+
+    /// All the comment tokens of this node in document order
+    pub fn comments(&self) -> Vec<&SyntaxToken> {
+        self.tokens()
+            .into_iter()
+            .filter(|token| token.kind == SyntaxKind::Comment)
+            .collect()
+    }
+
+    /// The child nodes of this node
+    pub fn child_nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// The nodes that contain the offset from the outermost to the innermost, starting with this node
+    pub fn nodes_at(&self, offset: usize) -> Vec<&SyntaxNode> {
+        let mut nodes = vec![];
+        let mut node = self;
+        if offset < node.span.start || offset > node.span.end {
+            return nodes;
+        }
+        loop {
+            nodes.push(node);
+            match node
+                .child_nodes()
+                .find(|child| child.span.start <= offset && offset <= child.span.end)
+            {
+                Some(child) => node = child,
+                None => return nodes,
+            }
+        }
+    }
+
+    /// The text of this node exactly as it appears in the document
+    pub fn text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for token in self.tokens() {
+            write!(f, "{}", token.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds a lossless syntax tree for the document from the spans of its AST.
+///
+/// Text not covered by an AST node is split into whitespace, newline, comment and
+/// text tokens so that the tree covers the whole document.
+pub fn build_cst(document: &str, ast: &Ast) -> SyntaxNode {
+    let builder = CstBuilder { document };
+    let entries = ast
+        .entries
+        .iter()
+        .map(|entry| builder.entry(entry))
+        .collect();
+    builder.node(SyntaxKind::Document, Span::new(0, document.len()), entries)
+}
+
+struct CstBuilder<'a> {
+    document: &'a str,
+}
+
+impl CstBuilder<'_> {
+    fn node(&self, kind: SyntaxKind, span: Span, children: Vec<SyntaxNode>) -> SyntaxNode {
+        let mut elements = vec![];
+        let mut cursor = span.start;
+        for child in children {
+            //Skip children produced by error recovery that overlap their siblings or
+            //fall outside of their parent. Their text is kept as gap tokens instead.
+            if child.span.start < cursor || child.span.end > span.end {
+                continue;
+            }
+            self.gap(kind, Span::new(cursor, child.span.start), &mut elements);
+            cursor = child.span.end;
+            elements.push(SyntaxElement::Node(child));
+        }
+        self.gap(kind, Span::new(cursor, span.end), &mut elements);
+        SyntaxNode {
+            kind,
+            span,
+            children: elements,
+        }
+    }
+
+    fn gap(&self, parent: SyntaxKind, span: Span, elements: &mut Vec<SyntaxElement>) {
+        if span.start >= span.end {
+            return;
+        }
+        if !parent.is_structural() {
+            elements.push(self.token(SyntaxKind::Text, span.start, span.end));
+            return;
+        }
+        let text = &self.document[span.start..span.end];
+        let mut offset = 0;
+        while offset < text.len() {
+            let rest = &text[offset..];
+            let (kind, len) = if rest.starts_with('\n') {
+                (SyntaxKind::Newline, 1)
+            } else if rest.starts_with("\r\n") {
+                (SyntaxKind::Newline, 2)
+            } else if rest.starts_with('#') {
+                (
+                    SyntaxKind::Comment,
+                    rest.find(['\r', '\n']).unwrap_or(rest.len()),
+                )
+            } else if rest.starts_with([' ', '\t']) {
+                (
+                    SyntaxKind::Whitespace,
+                    rest.find(|c| c != ' ' && c != '\t').unwrap_or(rest.len()),
+                )
+            } else {
+                let len = match rest.find([' ', '\t', '\r', '\n', '#']) {
+                    //A lone '\r' that isn't followed by '\n'
+                    Some(0) => 1,
+                    Some(len) => len,
+                    None => rest.len(),
+                };
+                (SyntaxKind::Text, len)
+            };
+            let start = span.start + offset;
+            elements.push(self.token(kind, start, start + len));
+            offset += len;
+        }
+    }
+
+    fn token(&self, kind: SyntaxKind, start: usize, end: usize) -> SyntaxElement {
+        SyntaxElement::Token(SyntaxToken {
+            kind,
+            span: Span::new(start, end),
+            text: self.document[start..end].to_string(),
+        })
+    }
+
+    fn entry(&self, entry: &Entry) -> SyntaxNode {
+        let mut children = vec![self.request(&entry.request)];
+        if let Some(response) = &entry.response {
+            children.push(self.response(response));
+        }
+        self.node(SyntaxKind::Entry, entry.span, children)
+    }
+
+    fn request(&self, request: &Request) -> SyntaxNode {
+        let mut children = vec![
+            self.node(SyntaxKind::Method, request.method.span, vec![]),
+            self.node(SyntaxKind::Url, request.url.1, self.url_templates(request)),
+        ];
+        children.extend(
+            request
+                .headers
+                .iter()
+                .map(|header| self.key_value(SyntaxKind::Header, header)),
+        );
+        let mut sections: Vec<SyntaxNode> = request
+            .request_sections
+            .iter()
+            .map(|section| self.request_section(section))
+            .chain(
+                request
+                    .misplaced_sections
+                    .iter()
+                    .map(|section| self.response_section(section)),
+            )
+            .collect();
+        sections.sort_by_key(|section| section.span.start);
+        children.extend(sections);
+        if let Some(body) = &request.body {
+            children.push(self.body(body));
+        }
+        self.node(SyntaxKind::Request, request.span, children)
+    }
+
+    fn url_templates(&self, request: &Request) -> Vec<SyntaxNode> {
+        match &request.url.0 {
+            Url::Url(url) => self.templates(url),
+            _ => vec![],
+        }
+    }
+
+    fn response(&self, response: &Response) -> SyntaxNode {
+        let mut children = vec![
+            self.node(SyntaxKind::Version, response.version.1, vec![]),
+            self.node(SyntaxKind::Status, response.status.1, vec![]),
+        ];
+        children.extend(
+            response
+                .headers
+                .iter()
+                .map(|header| self.key_value(SyntaxKind::Header, header)),
+        );
+        let mut sections: Vec<SyntaxNode> = response
+            .response_sections
+            .iter()
+            .map(|section| self.response_section(section))
+            .chain(
+                response
+                    .misplaced_sections
+                    .iter()
+                    .map(|section| self.request_section(section)),
+            )
+            .collect();
+        sections.sort_by_key(|section| section.span.start);
+        children.extend(sections);
+        if let Some(body) = &response.body {
+            children.push(self.body(body));
+        }
+        self.node(SyntaxKind::Response, response.span, children)
+    }
+
+    fn request_section(&self, section: &RequestSection) -> SyntaxNode {
+        let (span, children) = match section {
+            RequestSection::BasicAuthSection(section) => {
+                (section.span, self.key_values(&section.key_values))
+            }
+            RequestSection::QueryStringParamsSection(section) => {
+                (section.span, self.key_values(&section.queries))
+            }
+            RequestSection::FormParamsSection(section) => {
+                (section.span, self.key_values(&section.params))
+            }
+            RequestSection::CookiesSection(section) => {
+                (section.span, self.key_values(&section.cookies))
+            }
+            RequestSection::MultipartFormDataSection(section) => (
+                section.span,
+                section
+                    .params
+                    .iter()
+                    .map(|param| match param {
+                        MultipartFormParam::FileParam(file_param) => self.node(
+                            SyntaxKind::FileParam,
+                            file_param.span,
+                            vec![
+                                self.interpolated_string(SyntaxKind::Key, &file_param.key),
+                                self.node(SyntaxKind::Value, file_param.value.span, vec![]),
+                            ],
+                        ),
+                        MultipartFormParam::KeyValueParam(key_value) => {
+                            self.key_value(SyntaxKind::KeyValue, key_value)
+                        }
+                    })
+                    .collect(),
+            ),
+            RequestSection::OptionsSection(section) => (
+                section.span,
+                section
+                    .options
+                    .iter()
+                    .map(|option| self.option(option))
+                    .collect(),
+            ),
+            RequestSection::UnknownSection(section) => (section.span, vec![]),
+        };
+        self.node(SyntaxKind::Section, span, children)
+    }
+
+    fn option(&self, (option, span): &Spanned<RequestOption>) -> SyntaxNode {
+        let children = match option {
+            RequestOption::Variable(variable) => vec![
+                self.node(SyntaxKind::Key, variable.name.1, vec![]),
+                self.node(SyntaxKind::Value, variable.value.1, vec![]),
+            ],
+            _ => vec![],
+        };
+        self.node(SyntaxKind::Option, *span, children)
+    }
+
+    fn response_section(&self, section: &ResponseSection) -> SyntaxNode {
+        match section {
+            ResponseSection::CapturesSection(section) => self.node(
+                SyntaxKind::Section,
+                section.span,
+                section
+                    .captures
+                    .iter()
+                    .map(|capture| self.capture(capture))
+                    .collect(),
+            ),
+            ResponseSection::AssertsSection(section) => self.node(
+                SyntaxKind::Section,
+                section.span,
+                section
+                    .asserts
+                    .iter()
+                    .map(|assert| self.assert(assert))
+                    .collect(),
+            ),
+            ResponseSection::UnknownSection(section) => {
+                self.node(SyntaxKind::Section, section.span, vec![])
+            }
+        }
+    }
+
+    fn capture(&self, capture: &Capture) -> SyntaxNode {
+        let mut children = vec![
+            self.interpolated_string(SyntaxKind::Key, &capture.key),
+            self.node(SyntaxKind::Query, capture.query.1, vec![]),
+        ];
+        children.extend(
+            capture
+                .filters
+                .iter()
+                .map(|(_, span)| self.node(SyntaxKind::Filter, *span, vec![])),
+        );
+        self.node(SyntaxKind::Capture, capture.span, children)
+    }
+
+    fn assert(&self, assert: &Assert) -> SyntaxNode {
+        let mut children = vec![self.node(SyntaxKind::Query, assert.query.1, vec![])];
+        children.extend(
+            assert
+                .filters
+                .iter()
+                .map(|(_, span)| self.node(SyntaxKind::Filter, *span, vec![])),
+        );
+        children.push(self.node(SyntaxKind::Predicate, assert.predicate.span, vec![]));
+        self.node(SyntaxKind::Assert, assert.span, children)
+    }
+
+    fn body(&self, body: &Body) -> SyntaxNode {
+        self.node(SyntaxKind::Body, body.span, vec![])
+    }
+
+    fn key_values(&self, key_values: &[KeyValue]) -> Vec<SyntaxNode> {
+        key_values
+            .iter()
+            .map(|key_value| self.key_value(SyntaxKind::KeyValue, key_value))
+            .collect()
+    }
+
+    fn key_value(&self, kind: SyntaxKind, key_value: &KeyValue) -> SyntaxNode {
+        self.node(
+            kind,
+            key_value.span,
+            vec![
+                self.interpolated_string(SyntaxKind::Key, &key_value.key),
+                self.interpolated_string(SyntaxKind::Value, &key_value.value),
+            ],
+        )
+    }
+
+    fn interpolated_string(&self, kind: SyntaxKind, string: &InterpolatedString) -> SyntaxNode {
+        self.node(kind, string.span, self.templates(string))
+    }
+
+    fn templates(&self, string: &InterpolatedString) -> Vec<SyntaxNode> {
+        string
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                InterpolatedStringPart::Template(template) => {
+                    Some(self.node(SyntaxKind::Template, template.span, vec![]))
+                }
+                InterpolatedStringPart::Str(_) => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod cst_tests {
+    use crate::parser::parser::parse_cst;
+    use insta::assert_debug_snapshot;
+
+    #[test]
+    fn it_round_trips_document_with_comments_and_blank_lines() {
+        let test_str = r#"# Login first
+POST https://example.org/login # trailing comment
+Content-Type: application/json
+[FormParams]
+user: bob\#1 # the \# is part of the value
+
+password: {{ password }}
+
+HTTP 200
+[Captures]
+token: jsonpath "$.token" # capture the token
+[Asserts]
+# check the status
+status == 200
+
+
+GET https://example.org/{{token}}
+"#;
+        let (cst, _, errs) = parse_cst(test_str);
+        assert_eq!(cst.text(), test_str);
+        assert_eq!(errs, vec![]);
+        assert_debug_snapshot!(
+            cst.comments().iter().map(|c| (c.text.as_str(), c.span)).collect::<Vec<_>>(),
+            @r##"
+        [
+            (
+                "# Login first",
+                0..13,
+            ),
+            (
+                "# trailing comment",
+                45..63,
+            ),
+            (
+                "# the \\# is part of the value",
+                121..150,
+            ),
+            (
+                "# capture the token",
+                224..243,
+            ),
+            (
+                "# check the status",
+                254..272,
+            ),
+        ]
+        "##,
+        );
+    }
+
+    #[test]
+    fn it_round_trips_document_with_errors() {
+        let test_str = "GET https://example.org\n*** garbage # not a comment?\r\nPOST {{url}}\n";
+        let (cst, _, _) = parse_cst(test_str);
+        assert_eq!(cst.text(), test_str);
+    }
+
+    #[test]
+    fn it_finds_the_nodes_at_an_offset() {
+        let test_str = "GET https://example.org\nHTTP 200\n[Asserts]\nstatus == 200\n";
+        let (cst, _, _) = parse_cst(test_str);
+        let offset = test_str.find("==").unwrap();
+        assert_debug_snapshot!(
+            cst.nodes_at(offset).iter().map(|node| (node.kind, node.span)).collect::<Vec<_>>(),
+            @r"
+        [
+            (
+                Document,
+                0..57,
+            ),
+            (
+                Entry,
+                0..57,
+            ),
+            (
+                Response,
+                24..57,
+            ),
+            (
+                Section,
+                33..57,
+            ),
+            (
+                Assert,
+                43..56,
+            ),
+            (
+                Predicate,
+                50..56,
+            ),
+        ]
+        ",
+        );
+    }
+
+    #[test]
+    fn it_builds_nodes_for_entries() {
+        let test_str = "GET https://example.org\nHTTP 200\n";
+        let (cst, _, _) = parse_cst(test_str);
+        assert_debug_snapshot!(cst, @r#"
+        SyntaxNode {
+            kind: Document,
+            span: 0..33,
+            children: [
+                Node(
+                    SyntaxNode {
+                        kind: Entry,
+                        span: 0..33,
+                        children: [
+                            Node(
+                                SyntaxNode {
+                                    kind: Request,
+                                    span: 0..24,
+                                    children: [
+                                        Node(
+                                            SyntaxNode {
+                                                kind: Method,
+                                                span: 0..3,
+                                                children: [
+                                                    Token(
+                                                        SyntaxToken {
+                                                            kind: Text,
+                                                            span: 0..3,
+                                                            text: "GET",
+                                                        },
+                                                    ),
+                                                ],
+                                            },
+                                        ),
+                                        Token(
+                                            SyntaxToken {
+                                                kind: Whitespace,
+                                                span: 3..4,
+                                                text: " ",
+                                            },
+                                        ),
+                                        Node(
+                                            SyntaxNode {
+                                                kind: Url,
+                                                span: 4..23,
+                                                children: [
+                                                    Token(
+                                                        SyntaxToken {
+                                                            kind: Text,
+                                                            span: 4..23,
+                                                            text: "https://example.org",
+                                                        },
+                                                    ),
+                                                ],
+                                            },
+                                        ),
+                                        Token(
+                                            SyntaxToken {
+                                                kind: Newline,
+                                                span: 23..24,
+                                                text: "\n",
+                                            },
+                                        ),
+                                    ],
+                                },
+                            ),
+                            Node(
+                                SyntaxNode {
+                                    kind: Response,
+                                    span: 24..33,
+                                    children: [
+                                        Node(
+                                            SyntaxNode {
+                                                kind: Version,
+                                                span: 24..28,
+                                                children: [
+                                                    Token(
+                                                        SyntaxToken {
+                                                            kind: Text,
+                                                            span: 24..28,
+                                                            text: "HTTP",
+                                                        },
+                                                    ),
+                                                ],
+                                            },
+                                        ),
+                                        Token(
+                                            SyntaxToken {
+                                                kind: Whitespace,
+                                                span: 28..29,
+                                                text: " ",
+                                            },
+                                        ),
+                                        Node(
+                                            SyntaxNode {
+                                                kind: Status,
+                                                span: 29..32,
+                                                children: [
+                                                    Token(
+                                                        SyntaxToken {
+                                                            kind: Text,
+                                                            span: 29..32,
+                                                            text: "200",
+                                                        },
+                                                    ),
+                                                ],
+                                            },
+                                        ),
+                                        Token(
+                                            SyntaxToken {
+                                                kind: Newline,
+                                                span: 32..33,
+                                                text: "\n",
+                                            },
+                                        ),
+                                    ],
+                                },
+                            ),
+                        ],
+                    },
+                ),
+            ],
+        }
+        "#);
+    }
+}
@@ -0,0 +1,487 @@
+use std::fmt;
+
+use chumsky::error::{LabelError, RichPattern};
+use chumsky::util::MaybeRef;
+
+use super::types::Span;
+
+/// A secondary location that helps explain an error
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// An error found while parsing a hurl document.
+///
+/// Every variant has a stable error code (see [`ParseError::code`]) so that clients
+/// can filter diagnostics without depending on the wording of the message.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// The parser found something other than what the grammar allows at this position
+    Unexpected {
+        span: Span,
+        found: Option<char>,
+        expected: Vec<String>,
+        labels: Vec<Label>,
+    },
+    /// A single character didn't belong to the expected character class
+    UnexpectedChar {
+        span: Span,
+        found: Option<char>,
+        expected: &'static str,
+    },
+    InvalidMethodCase {
+        span: Span,
+        found: char,
+    },
+    MissingUrl {
+        span: Span,
+    },
+    UnknownHttpVersion {
+        span: Span,
+    },
+    MissingStatus {
+        span: Span,
+    },
+    StatusTooLarge {
+        span: Span,
+    },
+    StatusNotANumber {
+        span: Span,
+    },
+    InvalidUnicodeChar {
+        span: Span,
+    },
+    InvalidFloat {
+        span: Span,
+    },
+    IntegerTooLarge {
+        span: Span,
+    },
+    UnexpectedPredicateValue {
+        span: Span,
+    },
+    BasicAuthMultipleUsers {
+        span: Span,
+    },
+    UnknownMultilineStringType {
+        span: Span,
+        name: String,
+    },
+    MultilineStringTypeAsAttribute {
+        span: Span,
+        name: String,
+    },
+    UnknownMultilineStringAttribute {
+        span: Span,
+        name: String,
+    },
+}
+
+impl ParseError {
+    //The codes returned by `ParseError::code`
+    pub const UNEXPECTED: &'static str = "E0001";
+    pub const UNEXPECTED_CHAR: &'static str = "E0002";
+    pub const INVALID_METHOD_CASE: &'static str = "E0101";
+    pub const MISSING_URL: &'static str = "E0102";
+    pub const UNKNOWN_HTTP_VERSION: &'static str = "E0201";
+    pub const MISSING_STATUS: &'static str = "E0202";
+    pub const STATUS_TOO_LARGE: &'static str = "E0203";
+    pub const STATUS_NOT_A_NUMBER: &'static str = "E0204";
+    pub const INVALID_UNICODE_CHAR: &'static str = "E0301";
+    pub const INVALID_FLOAT: &'static str = "E0302";
+    pub const INTEGER_TOO_LARGE: &'static str = "E0303";
+    pub const UNEXPECTED_PREDICATE_VALUE: &'static str = "E0401";
+    pub const BASIC_AUTH_MULTIPLE_USERS: &'static str = "E0501";
+    pub const UNKNOWN_MULTILINE_STRING_TYPE: &'static str = "E0601";
+    pub const MULTILINE_STRING_TYPE_AS_ATTRIBUTE: &'static str = "E0602";
+    pub const UNKNOWN_MULTILINE_STRING_ATTRIBUTE: &'static str = "E0603";
+
+    /// A stable code identifying the kind of error
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::Unexpected { .. } => ParseError::UNEXPECTED,
+            ParseError::UnexpectedChar { .. } => ParseError::UNEXPECTED_CHAR,
+            ParseError::InvalidMethodCase { .. } => ParseError::INVALID_METHOD_CASE,
+            ParseError::MissingUrl { .. } => ParseError::MISSING_URL,
+            ParseError::UnknownHttpVersion { .. } => ParseError::UNKNOWN_HTTP_VERSION,
+            ParseError::MissingStatus { .. } => ParseError::MISSING_STATUS,
+            ParseError::StatusTooLarge { .. } => ParseError::STATUS_TOO_LARGE,
+            ParseError::StatusNotANumber { .. } => ParseError::STATUS_NOT_A_NUMBER,
+            ParseError::InvalidUnicodeChar { .. } => ParseError::INVALID_UNICODE_CHAR,
+            ParseError::InvalidFloat { .. } => ParseError::INVALID_FLOAT,
+            ParseError::IntegerTooLarge { .. } => ParseError::INTEGER_TOO_LARGE,
+            ParseError::UnexpectedPredicateValue { .. } => ParseError::UNEXPECTED_PREDICATE_VALUE,
+            ParseError::BasicAuthMultipleUsers { .. } => ParseError::BASIC_AUTH_MULTIPLE_USERS,
+            ParseError::UnknownMultilineStringType { .. } => {
+                ParseError::UNKNOWN_MULTILINE_STRING_TYPE
+            }
+            ParseError::MultilineStringTypeAsAttribute { .. } => {
+                ParseError::MULTILINE_STRING_TYPE_AS_ATTRIBUTE
+            }
+            ParseError::UnknownMultilineStringAttribute { .. } => {
+                ParseError::UNKNOWN_MULTILINE_STRING_ATTRIBUTE
+            }
+        }
+    }
+
+    /// The range of the document the error applies to
+    pub fn span(&self) -> Span {
+        match self {
+            ParseError::Unexpected { span, .. }
+            | ParseError::UnexpectedChar { span, .. }
+            | ParseError::InvalidMethodCase { span, .. }
+            | ParseError::MissingUrl { span }
+            | ParseError::UnknownHttpVersion { span }
+            | ParseError::MissingStatus { span }
+            | ParseError::StatusTooLarge { span }
+            | ParseError::StatusNotANumber { span }
+            | ParseError::InvalidUnicodeChar { span }
+            | ParseError::InvalidFloat { span }
+            | ParseError::IntegerTooLarge { span }
+            | ParseError::UnexpectedPredicateValue { span }
+            | ParseError::BasicAuthMultipleUsers { span }
+            | ParseError::UnknownMultilineStringType { span, .. }
+            | ParseError::MultilineStringTypeAsAttribute { span, .. }
+            | ParseError::UnknownMultilineStringAttribute { span, .. } => *span,
+        }
+    }
+
+    /// The human readable message for the error
+    pub fn message(&self) -> String {
+        self.to_string()
+    }
+
+    /// The tokens or grammar rules that would have been valid at the error location
+    pub fn expected(&self) -> Vec<String> {
+        match self {
+            ParseError::Unexpected { expected, .. } => expected.clone(),
+            ParseError::UnexpectedChar { expected, .. } => vec![expected.to_string()],
+            _ => vec![],
+        }
+    }
+
+    /// Secondary locations that help explain the error
+    pub fn labels(&self) -> &[Label] {
+        match self {
+            ParseError::Unexpected { labels, .. } => labels,
+            _ => &[],
+        }
+    }
+}
+
+const MISSING_URL: &str = "missing url";
+const UNKNOWN_HTTP_VERSION: &str = "Unknown http version";
+const MISSING_STATUS: &str = "missing status";
+const STATUS_TOO_LARGE: &str = "invalid status: too large";
+const STATUS_NOT_A_NUMBER: &str = "invalid status: not a number";
+const INVALID_UNICODE_CHAR: &str = "invalid unicode character";
+const INVALID_FLOAT: &str = "invalid float";
+const UNEXPECTED_PREDICATE_VALUE: &str = "Unexpected predicate value";
+const BASIC_AUTH_MULTIPLE_USERS: &str = "Basic Auth can only be defined for one user.";
+
+pub(crate) const ASCII_ALPHABETIC: &str = "an ascii alphabetic char";
+pub(crate) const ASCII_ALPHABETIC_UPPERCASE: &str = "an ascii alphabetic uppercase char";
+pub(crate) const ASCII_ALPHANUMERIC: &str = "an ascii alphanumeric char";
+
+fn integer_too_large_message() -> String {
+    format!(
+        "The integer value is larger than {} and is not valid for 64bit version of hurl",
+        u64::MAX
+    )
+}
+
+/// Formats a character so whitespace is readable in an error message
+fn display_char(c: char) -> String {
+    match c {
+        '\n' => "newline".to_string(),
+        '\r' => "carriage return".to_string(),
+        '\t' => "tab".to_string(),
+        ' ' => "space".to_string(),
+        c => format!("'{}'", c),
+    }
+}
+
+fn display_pattern(pattern: &RichPattern<'_, char>) -> String {
+    match pattern {
+        RichPattern::Token(c) => display_char(**c),
+        RichPattern::Label(label) => label.to_string(),
+        RichPattern::Identifier(identifier) => format!("'{}'", identifier),
+        RichPattern::Any => "any character".to_string(),
+        RichPattern::SomethingElse => "something else".to_string(),
+        RichPattern::EndOfInput => "end of file".to_string(),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Unexpected {
+                found, expected, ..
+            } => {
+                match found {
+                    Some(c) => write!(f, "Unexpected {}", display_char(*c))?,
+                    None => write!(f, "Unexpected end of file")?,
+                }
+                match expected.as_slice() {
+                    [] => Ok(()),
+                    [expected] => write!(f, ", expected {}", expected),
+                    [expected @ .., last] => {
+                        write!(f, ", expected {} or {}", expected.join(", "), last)
+                    }
+                }
+            }
+            ParseError::UnexpectedChar {
+                found, expected, ..
+            } => match found {
+                Some(c) => write!(f, "expected {} but found {}", expected, display_char(*c)),
+                None => write!(f, "expected {} but found end", expected),
+            },
+            ParseError::InvalidMethodCase { found, .. } => write!(
+                f,
+                "Invalid character '{}'. Method must be ascii uppercase.",
+                found
+            ),
+            ParseError::MissingUrl { .. } => write!(f, "{}", MISSING_URL),
+            ParseError::UnknownHttpVersion { .. } => write!(f, "{}", UNKNOWN_HTTP_VERSION),
+            ParseError::MissingStatus { .. } => write!(f, "{}", MISSING_STATUS),
+            ParseError::StatusTooLarge { .. } => write!(f, "{}", STATUS_TOO_LARGE),
+            ParseError::StatusNotANumber { .. } => write!(f, "{}", STATUS_NOT_A_NUMBER),
+            ParseError::InvalidUnicodeChar { .. } => write!(f, "{}", INVALID_UNICODE_CHAR),
+            ParseError::InvalidFloat { .. } => write!(f, "{}", INVALID_FLOAT),
+            ParseError::IntegerTooLarge { .. } => write!(f, "{}", integer_too_large_message()),
+            ParseError::UnexpectedPredicateValue { .. } => {
+                write!(f, "{}", UNEXPECTED_PREDICATE_VALUE)
+            }
+            ParseError::BasicAuthMultipleUsers { .. } => {
+                write!(f, "{}", BASIC_AUTH_MULTIPLE_USERS)
+            }
+            ParseError::UnknownMultilineStringType { name, .. } => {
+                write!(f, "Unknown multiline string type or attribute `{}`.", name)
+            }
+            ParseError::MultilineStringTypeAsAttribute { name, .. } => write!(
+                f,
+                "Found multiline string type `{}` in the multiline string attribute section.",
+                name
+            ),
+            ParseError::UnknownMultilineStringAttribute { name, .. } => {
+                write!(f, "Found unknown multiline string attribute `{}`.", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The displayed patterns, sorted and without duplicates
+fn display_patterns<'a, L: Into<RichPattern<'a, char>>>(
+    patterns: impl IntoIterator<Item = L>,
+) -> Vec<String> {
+    let mut patterns: Vec<String> = patterns
+        .into_iter()
+        .map(|pattern| display_pattern(&pattern.into()))
+        .collect();
+    patterns.sort();
+    patterns.dedup();
+    patterns
+}
+
+/// Errors are merged like chumsky's `Rich` errors: the expected patterns of two unexpected
+/// input errors are combined and any other error wins over an unexpected input.
+impl<'a> chumsky::error::Error<'a, &'a str> for ParseError {
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (
+                ParseError::Unexpected {
+                    span,
+                    found,
+                    mut expected,
+                    labels,
+                },
+                ParseError::Unexpected {
+                    expected: other_expected,
+                    ..
+                },
+            ) => {
+                expected.extend(other_expected);
+                expected.sort();
+                expected.dedup();
+                ParseError::Unexpected {
+                    span,
+                    found,
+                    expected,
+                    labels,
+                }
+            }
+            (ParseError::Unexpected { .. }, other) => other,
+            (err, _) => err,
+        }
+    }
+}
+
+impl<'a, L: Into<RichPattern<'a, char>>> LabelError<'a, &'a str, L> for ParseError {
+    fn expected_found<E: IntoIterator<Item = L>>(
+        expected: E,
+        found: Option<MaybeRef<'a, char>>,
+        span: Span,
+    ) -> Self {
+        ParseError::Unexpected {
+            span,
+            found: found.as_deref().copied(),
+            expected: display_patterns(expected),
+            labels: vec![],
+        }
+    }
+
+    fn merge_expected_found<E: IntoIterator<Item = L>>(
+        self,
+        new_expected: E,
+        new_found: Option<MaybeRef<'a, char>>,
+        _span: Span,
+    ) -> Self {
+        match self {
+            ParseError::Unexpected {
+                span,
+                found,
+                mut expected,
+                labels,
+            } => {
+                expected.extend(display_patterns(new_expected));
+                expected.sort();
+                expected.dedup();
+                ParseError::Unexpected {
+                    span,
+                    found: found.or(new_found.as_deref().copied()),
+                    expected,
+                    labels,
+                }
+            }
+            err => err,
+        }
+    }
+
+    fn replace_expected_found<E: IntoIterator<Item = L>>(
+        self,
+        expected: E,
+        found: Option<MaybeRef<'a, char>>,
+        span: Span,
+    ) -> Self {
+        ParseError::Unexpected {
+            span,
+            found: found.as_deref().copied(),
+            expected: display_patterns(expected),
+            labels: vec![],
+        }
+    }
+
+    fn label_with(&mut self, label: L) {
+        let label = display_pattern(&label.into());
+        match self {
+            ParseError::Unexpected { expected, .. } => *expected = vec![label],
+            err => {
+                *err = ParseError::Unexpected {
+                    span: err.span(),
+                    found: None,
+                    expected: vec![label],
+                    labels: vec![],
+                }
+            }
+        }
+    }
+
+    fn in_context(&mut self, label: L, span: Span) {
+        if let ParseError::Unexpected { labels, .. } = self {
+            let message = format!("while parsing {}", display_pattern(&label.into()));
+            if labels.iter().all(|label| label.message != message) {
+                labels.push(Label { span, message });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+    use crate::parser::parser::parse_ast;
+    use insta::assert_debug_snapshot;
+
+    #[test]
+    fn it_merges_errors_like_rich_errors() {
+        let span = Span::new(0, 1);
+        let unexpected = |expected: &'static str| -> ParseError {
+            LabelError::<&str, _>::expected_found([expected], Some(MaybeRef::Val('F')), span)
+        };
+        assert_eq!(
+            chumsky::error::Error::<&str>::merge(unexpected("template"), unexpected("json-number")),
+            ParseError::Unexpected {
+                span,
+                found: Some('F'),
+                expected: vec!["json-number".to_string(), "template".to_string()],
+                labels: vec![],
+            }
+        );
+        let missing_url = ParseError::MissingUrl { span };
+        assert_eq!(
+            chumsky::error::Error::<&str>::merge(unexpected("template"), missing_url.clone()),
+            missing_url
+        );
+        assert_eq!(
+            chumsky::error::Error::<&str>::merge(missing_url.clone(), unexpected("template")),
+            missing_url
+        );
+    }
+
+    #[test]
+    fn it_reports_unexpected_input() {
+        let (_, errs) = parse_ast("GET https://example.org\n[Foo Bar]\n");
+        let err = &errs[0];
+        assert_eq!(err.code(), "E0001");
+        assert_debug_snapshot!(
+        (err.message(), err.span(), err.expected()),
+            @r#"
+        (
+            "Unexpected 'F', expected '[', ']', 'f', 'n', 't', json-number, json-object, json-string, template or whitespace",
+            25..26,
+            [
+                "'['",
+                "']'",
+                "'f'",
+                "'n'",
+                "'t'",
+                "json-number",
+                "json-object",
+                "json-string",
+                "template",
+                "whitespace",
+            ],
+        )
+        "#);
+    }
+
+    #[test]
+    fn it_names_the_whitespace_found_instead_of_a_char() {
+        let err = ParseError::UnexpectedChar {
+            span: Span::new(3, 4),
+            found: Some('\n'),
+            expected: "a digit",
+        };
+        assert_eq!(err.message(), "expected a digit but found newline");
+    }
+
+    #[test]
+    fn it_reports_custom_errors_with_codes() {
+        let (_, errs) = parse_ast("Get https://example.org\n");
+        assert_debug_snapshot!(
+        errs.iter().map(|err| (err.code(), err.message(), err.span())).collect::<Vec<_>>(),
+            @r#"
+        [
+            (
+                "E0101",
+                "Invalid character 'e'. Method must be ascii uppercase.",
+                0..3,
+            ),
+        ]
+        "#);
+    }
+}
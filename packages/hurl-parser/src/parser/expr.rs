@@ -1,45 +1,58 @@
 use chumsky::prelude::*;
 
-use super::{primitives::{alphabetic_parser, alphanumeric_parser, sp_parser}, regex::regex_parser, types::{Expr, ExprValue, FilterFunction, InterpolatedString}};
-
-
-
-pub fn variable_name_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<Rich<'a, char>>> + Clone {
-
+use super::error::ParseError;
+use super::{
+    primitives::{alphabetic_parser, alphanumeric_parser, sp_parser},
+    regex::regex_parser,
+    types::{Expr, ExprValue, FilterFunction, InterpolatedString, Span, Spanned},
+};
+
+pub fn variable_name_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<ParseError>> + Clone
+{
     let variable_name = alphabetic_parser()
         .labelled("ascii alphabetic char")
         .then(
-            choice((
-                    one_of("_-"),
-                    alphanumeric_parser()
-            ))
-            .labelled("ascii alphanumeric char or underscore or dash")
-            .repeated()
-            .to_slice()
+            choice((one_of("_-"), alphanumeric_parser()))
+                .labelled("ascii alphanumeric char or underscore or dash")
+                .repeated()
+                .to_slice(),
         )
         .to_slice()
         .map(ToString::to_string);
 
-    variable_name
-        .labelled("variable-name")
-        .boxed()
+    variable_name.labelled("variable-name").boxed()
 }
 
+/// The built-in functions that can be called in a template in place of a variable
+pub const FUNCTION_NAMES: [&str; 3] = ["getEnv", "newDate", "newUuid"];
+
+/// Whether the name can be used as a variable name in a template. The names of the built-in
+/// functions are not variable names.
+pub fn is_variable_name(name: &str) -> bool {
+    !FUNCTION_NAMES.contains(&name)
+        && variable_name_parser()
+            .then_ignore(end())
+            .parse(name)
+            .into_result()
+            .is_ok()
+}
 
-pub fn filters_parser<'a, T: Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone + 'a>(
+pub fn filters_parser<
+    'a,
+    T: Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone + 'a,
+>(
     quoted_string: T,
-) -> impl Parser<'a, &'a str, Vec<FilterFunction>, extra::Err<Rich<'a, char>>> + Clone {
-
+) -> impl Parser<'a, &'a str, Vec<Spanned<FilterFunction>>, extra::Err<ParseError>> + Clone {
     let sp = sp_parser();
     let decode_filter_function = just("decode")
         .delimited_by(sp.clone().repeated(), sp.clone().repeated().at_least(1))
         .ignore_then(quoted_string.clone())
-        .map(|s| FilterFunction::Decode { encoding: s});
+        .map(|s| FilterFunction::Decode { encoding: s });
 
     let format_filter_function = just("format")
         .delimited_by(sp.clone().repeated(), sp.clone().repeated().at_least(1))
         .ignore_then(quoted_string.clone())
-        .map(|s| FilterFunction::Format { fmt: s});
+        .map(|s| FilterFunction::Format { fmt: s });
 
     let jsonpath_filter_function = just("jsonpath")
         .delimited_by(sp.clone().repeated(), sp.clone().repeated().at_least(1))
@@ -57,19 +70,23 @@ pub fn filters_parser<'a, T: Parser<'a, &'a str, InterpolatedString, extra::Err<
     let regex_filter_function = just("regex")
         .delimited_by(sp.clone().repeated(), sp.clone().repeated().at_least(1))
         .ignore_then(regex_parser(quoted_string.clone()))
-        .map(|s| FilterFunction::Regex { value: s }).boxed();
+        .map(|s| FilterFunction::Regex { value: s })
+        .boxed();
 
     let split_filter_function = just("split")
         .delimited_by(sp.clone().repeated(), sp.clone().repeated().at_least(1))
         .ignore_then(quoted_string.clone())
         .map(|s| FilterFunction::Split { sep: s });
-        
+
     let replace_filter_function = just("replace")
         .delimited_by(sp.clone().repeated(), sp.clone().repeated().at_least(1))
         .ignore_then(regex_parser(quoted_string.clone()))
         .then_ignore(sp.clone().repeated().at_least(1))
         .then(quoted_string.clone())
-        .map(|(old, new)| FilterFunction::Replace { old_value: old, new_value: new });
+        .map(|(old, new)| FilterFunction::Replace {
+            old_value: old,
+            new_value: new,
+        });
 
     let todate_filter_function = just("toDate")
         .delimited_by(sp.clone().repeated(), sp.clone().repeated().at_least(1))
@@ -81,9 +98,8 @@ pub fn filters_parser<'a, T: Parser<'a, &'a str, InterpolatedString, extra::Err<
         .ignore_then(quoted_string.clone())
         .map(|s| FilterFunction::XPath { expr: s });
 
-    //TODO detect type errors between inputs and outputs of filters
-    let filters = choice((
-        just("count").to(FilterFunction::Count), 
+    choice((
+        just("count").to(FilterFunction::Count),
         just("daysAfterNow").to(FilterFunction::DaysAfterNow),
         just("daysBeforeNow").to(FilterFunction::DaysBeforeNow),
         just("htmlEscape").to(FilterFunction::HtmlEscape),
@@ -101,32 +117,43 @@ pub fn filters_parser<'a, T: Parser<'a, &'a str, InterpolatedString, extra::Err<
         replace_filter_function,
         todate_filter_function,
         xpath_filter_function,
-    )).separated_by(sp.clone().repeated().at_least(1))
-        .collect::<Vec<FilterFunction>>();
-    
-    filters
+    ))
+    .map_with(|filter, e| (filter, e.span()))
+    .separated_by(sp.clone().repeated().at_least(1))
+    .collect::<Vec<Spanned<FilterFunction>>>()
 }
 
-pub fn expr_parser<'a, T: Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone + 'a>(
+pub fn expr_parser<
+    'a,
+    T: Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone + 'a,
+>(
     quoted_string: T,
-) -> impl Parser<'a, &'a str, Expr, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, Expr, extra::Err<ParseError>> + Clone {
+    let expr_function = choice(FUNCTION_NAMES.map(|name| {
+        text::keyword(name).to(ExprValue::FunctionName(name.to_owned()))
+    }))
+    .boxed();
 
-    let expr_function = choice((
-        text::keyword("getEnv").to(ExprValue::FunctionName("getEnv".to_owned())), 
-        text::keyword("newDate").to(ExprValue::FunctionName("newDate".to_owned())),
-        text::keyword("newUuid").to(ExprValue::FunctionName("newUuid".to_owned()))
-    )).boxed();
-
-    let expr_variable = expr_function.or(variable_name_parser().map(ExprValue::VariableName));
+    let expr_variable = expr_function
+        .or(variable_name_parser().map(ExprValue::VariableName))
+        .map_with(|expr_var, e| (expr_var, e.span()));
 
     let expr = expr_variable
-    .padded_by(sp_parser().repeated())
-    .then(filters_parser(quoted_string))
-    .map( |(expr_var, filter_funcs)| Expr {
-        variable: expr_var,
-        filters: filter_funcs
-    })
-    .labelled("expr");
+        .then_ignore(sp_parser().repeated())
+        .then(filters_parser(quoted_string))
+        .map(|(expr_var, filter_funcs)| {
+            //The span is computed from the children so it excludes any trailing spaces
+            let end = filter_funcs
+                .last()
+                .map_or(expr_var.1.end, |(_, span)| span.end);
+            Expr {
+                span: Span::new(expr_var.1.start, end),
+                variable: expr_var,
+                filters: filter_funcs,
+            }
+        })
+        .padded_by(sp_parser().repeated())
+        .labelled("expr");
 
     expr
 }
@@ -153,7 +180,6 @@ mod variable_name_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_dashed_variable() {
         let test_str = "api-key";
@@ -191,31 +217,44 @@ mod variable_name_tests {
         let test_str = "1";
         assert_debug_snapshot!(
         variable_name_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found end of input at 0..1 expected variable-name,
+                Unexpected {
+                    span: 0..1,
+                    found: None,
+                    expected: [
+                        "variable-name",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
-
     #[test]
     fn it_errors_expr_with_dash_starting_variable_name() {
         let test_str = "-";
         assert_debug_snapshot!(
         variable_name_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found end of input at 0..1 expected variable-name,
+                Unexpected {
+                    span: 0..1,
+                    found: None,
+                    expected: [
+                        "variable-name",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -224,17 +263,33 @@ mod variable_name_tests {
         let test_str = "_";
         assert_debug_snapshot!(
         variable_name_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found end of input at 0..1 expected variable-name,
+                Unexpected {
+                    span: 0..1,
+                    found: None,
+                    expected: [
+                        "variable-name",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
+    #[test]
+    fn it_checks_variable_names() {
+        assert!(is_variable_name("api_key-2"));
+        assert!(!is_variable_name("2fa"));
+        assert!(!is_variable_name("api key"));
+        assert!(!is_variable_name(""));
+        assert!(!is_variable_name("newUuid"));
+        assert!(is_variable_name("newUuids"));
+    }
 }
 
 #[cfg(test)]
@@ -255,10 +310,14 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "key",
+                    variable: (
+                        VariableName(
+                            "key",
+                        ),
+                        0..3,
                     ),
                     filters: [],
+                    span: 0..3,
                 },
             ),
             errs: [],
@@ -267,7 +326,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_dashed_variable() {
         let test_str = "api-key";
@@ -278,10 +336,14 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "api-key",
+                    variable: (
+                        VariableName(
+                            "api-key",
+                        ),
+                        0..7,
                     ),
                     filters: [],
+                    span: 0..7,
                 },
             ),
             errs: [],
@@ -300,10 +362,14 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "api_key",
+                    variable: (
+                        VariableName(
+                            "api_key",
+                        ),
+                        0..7,
                     ),
                     filters: [],
+                    span: 0..7,
                 },
             ),
             errs: [],
@@ -318,19 +384,26 @@ mod expr_tests {
         let quoted_string = quoted_string_parser();
         assert_debug_snapshot!(
         expr_parser(quoted_string).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''1'' at 0..1 expected expr,
+                Unexpected {
+                    span: 0..1,
+                    found: Some(
+                        '1',
+                    ),
+                    expected: [
+                        "expr",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
-
-
     #[test]
     fn it_parses_expr_with_decode_filter() {
         let test_str = "api_key decode \"gb2312\"";
@@ -341,20 +414,28 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "api_key",
+                    variable: (
+                        VariableName(
+                            "api_key",
+                        ),
+                        0..7,
                     ),
                     filters: [
-                        Decode {
-                            encoding: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "gb2312",
-                                    ),
-                                ],
+                        (
+                            Decode {
+                                encoding: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "gb2312",
+                                        ),
+                                    ],
+                                    span: 15..23,
+                                },
                             },
-                        },
+                            8..23,
+                        ),
                     ],
+                    span: 0..23,
                 },
             ),
             errs: [],
@@ -363,7 +444,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_format_filter() {
         let test_str = "creation_date format \"%a, %d %b %Y %H:%M:%S\"";
@@ -374,20 +454,28 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "creation_date",
+                    variable: (
+                        VariableName(
+                            "creation_date",
+                        ),
+                        0..13,
                     ),
                     filters: [
-                        Format {
-                            fmt: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "%a, %d %b %Y %H:%M:%S",
-                                    ),
-                                ],
+                        (
+                            Format {
+                                fmt: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "%a, %d %b %Y %H:%M:%S",
+                                        ),
+                                    ],
+                                    span: 21..44,
+                                },
                             },
-                        },
+                            14..44,
+                        ),
                     ],
+                    span: 0..44,
                 },
             ),
             errs: [],
@@ -396,7 +484,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_jsonpath_filter() {
         let test_str = "input_data jsonpath \"$[0].last_name\"";
@@ -407,20 +494,28 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "input_data",
+                    variable: (
+                        VariableName(
+                            "input_data",
+                        ),
+                        0..10,
                     ),
                     filters: [
-                        JsonPath {
-                            expr: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "$[0].last_name",
-                                    ),
-                                ],
+                        (
+                            JsonPath {
+                                expr: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "$[0].last_name",
+                                        ),
+                                    ],
+                                    span: 20..36,
+                                },
                             },
-                        },
+                            11..36,
+                        ),
                     ],
+                    span: 0..36,
                 },
             ),
             errs: [],
@@ -429,7 +524,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_nth_filter() {
         let test_str = "input_data jsonpath \"$[0].names\" nth 2";
@@ -440,23 +534,34 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "input_data",
+                    variable: (
+                        VariableName(
+                            "input_data",
+                        ),
+                        0..10,
                     ),
                     filters: [
-                        JsonPath {
-                            expr: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "$[0].names",
-                                    ),
-                                ],
+                        (
+                            JsonPath {
+                                expr: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "$[0].names",
+                                        ),
+                                    ],
+                                    span: 20..32,
+                                },
+                            },
+                            11..32,
+                        ),
+                        (
+                            Nth {
+                                nth: 2,
                             },
-                        },
-                        Nth {
-                            nth: 2,
-                        },
+                            33..38,
+                        ),
                     ],
+                    span: 0..38,
                 },
             ),
             errs: [],
@@ -465,7 +570,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_regex_quoted_string_filter() {
         let test_str = r#"id regex "\\d{10}""#;
@@ -476,22 +580,30 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "id",
+                    variable: (
+                        VariableName(
+                            "id",
+                        ),
+                        0..2,
                     ),
                     filters: [
-                        Regex {
-                            value: Interpolated(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "\\d{10}",
-                                        ),
-                                    ],
-                                },
-                            ),
-                        },
+                        (
+                            Regex {
+                                value: Interpolated(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "\\d{10}",
+                                            ),
+                                        ],
+                                        span: 9..18,
+                                    },
+                                ),
+                            },
+                            3..18,
+                        ),
                     ],
+                    span: 0..18,
                 },
             ),
             errs: [],
@@ -510,16 +622,23 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "id",
+                    variable: (
+                        VariableName(
+                            "id",
+                        ),
+                        0..2,
                     ),
                     filters: [
-                        Regex {
-                            value: Literal(
-                                "\\d{10}",
-                            ),
-                        },
+                        (
+                            Regex {
+                                value: Literal(
+                                    "\\d{10}",
+                                ),
+                            },
+                            3..17,
+                        ),
                     ],
+                    span: 0..17,
                 },
             ),
             errs: [],
@@ -528,7 +647,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_split_filter() {
         let test_str = r#"names split ", ""#;
@@ -539,20 +657,28 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "names",
+                    variable: (
+                        VariableName(
+                            "names",
+                        ),
+                        0..5,
                     ),
                     filters: [
-                        Split {
-                            sep: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        ", ",
-                                    ),
-                                ],
+                        (
+                            Split {
+                                sep: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            ", ",
+                                        ),
+                                    ],
+                                    span: 12..16,
+                                },
                             },
-                        },
+                            6..16,
+                        ),
                     ],
+                    span: 0..16,
                 },
             ),
             errs: [],
@@ -561,7 +687,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_replace_filter_with_quoted_string_old_value() {
         let test_str = r#"names replace "; " ",""#;
@@ -572,29 +697,38 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "names",
+                    variable: (
+                        VariableName(
+                            "names",
+                        ),
+                        0..5,
                     ),
                     filters: [
-                        Replace {
-                            old_value: Interpolated(
-                                InterpolatedString {
+                        (
+                            Replace {
+                                old_value: Interpolated(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "; ",
+                                            ),
+                                        ],
+                                        span: 14..18,
+                                    },
+                                ),
+                                new_value: InterpolatedString {
                                     parts: [
                                         Str(
-                                            "; ",
+                                            ",",
                                         ),
                                     ],
+                                    span: 19..22,
                                 },
-                            ),
-                            new_value: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        ",",
-                                    ),
-                                ],
                             },
-                        },
+                            6..22,
+                        ),
                     ],
+                    span: 0..22,
                 },
             ),
             errs: [],
@@ -613,23 +747,31 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "names",
+                    variable: (
+                        VariableName(
+                            "names",
+                        ),
+                        0..5,
                     ),
                     filters: [
-                        Replace {
-                            old_value: Literal(
-                                "\\d{10}",
-                            ),
-                            new_value: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "100",
-                                    ),
-                                ],
+                        (
+                            Replace {
+                                old_value: Literal(
+                                    "\\d{10}",
+                                ),
+                                new_value: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "100",
+                                        ),
+                                    ],
+                                    span: 23..28,
+                                },
                             },
-                        },
+                            6..28,
+                        ),
                     ],
+                    span: 0..28,
                 },
             ),
             errs: [],
@@ -638,7 +780,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_to_date_filter() {
         let test_str = "creation_date toDate \"%a, %d %b %Y %H:%M:%S\"";
@@ -649,20 +790,28 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "creation_date",
+                    variable: (
+                        VariableName(
+                            "creation_date",
+                        ),
+                        0..13,
                     ),
                     filters: [
-                        ToDate {
-                            fmt: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "%a, %d %b %Y %H:%M:%S",
-                                    ),
-                                ],
+                        (
+                            ToDate {
+                                fmt: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "%a, %d %b %Y %H:%M:%S",
+                                        ),
+                                    ],
+                                    span: 21..44,
+                                },
                             },
-                        },
+                            14..44,
+                        ),
                     ],
+                    span: 0..44,
                 },
             ),
             errs: [],
@@ -681,20 +830,28 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "document",
+                    variable: (
+                        VariableName(
+                            "document",
+                        ),
+                        0..8,
                     ),
                     filters: [
-                        XPath {
-                            expr: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "string(//div)",
-                                    ),
-                                ],
+                        (
+                            XPath {
+                                expr: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "string(//div)",
+                                        ),
+                                    ],
+                                    span: 15..30,
+                                },
                             },
-                        },
+                            9..30,
+                        ),
                     ],
+                    span: 0..30,
                 },
             ),
             errs: [],
@@ -703,7 +860,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_count_filter() {
         let test_str = r#"response jsonpath "$.names" count"#;
@@ -714,21 +870,32 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "response",
+                    variable: (
+                        VariableName(
+                            "response",
+                        ),
+                        0..8,
                     ),
                     filters: [
-                        JsonPath {
-                            expr: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "$.names",
-                                    ),
-                                ],
+                        (
+                            JsonPath {
+                                expr: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "$.names",
+                                        ),
+                                    ],
+                                    span: 18..27,
+                                },
                             },
-                        },
-                        Count,
+                            9..27,
+                        ),
+                        (
+                            Count,
+                            28..33,
+                        ),
                     ],
+                    span: 0..33,
                 },
             ),
             errs: [],
@@ -743,14 +910,24 @@ mod expr_tests {
         let quoted_string = quoted_string_parser();
         assert_debug_snapshot!(
         expr_parser(quoted_string).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''c'' at 27..28 expected spacing, or end of input,
+                Unexpected {
+                    span: 27..28,
+                    found: Some(
+                        'c',
+                    ),
+                    expected: [
+                        "end of file",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -764,12 +941,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "expiration_date",
+                    variable: (
+                        VariableName(
+                            "expiration_date",
+                        ),
+                        0..15,
                     ),
                     filters: [
-                        DaysAfterNow,
+                        (
+                            DaysAfterNow,
+                            16..28,
+                        ),
                     ],
+                    span: 0..28,
                 },
             ),
             errs: [],
@@ -778,7 +962,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_days_before_now_filter() {
         let test_str = "expiration_date daysBeforeNow";
@@ -789,12 +972,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "expiration_date",
+                    variable: (
+                        VariableName(
+                            "expiration_date",
+                        ),
+                        0..15,
                     ),
                     filters: [
-                        DaysBeforeNow,
+                        (
+                            DaysBeforeNow,
+                            16..29,
+                        ),
                     ],
+                    span: 0..29,
                 },
             ),
             errs: [],
@@ -803,7 +993,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_html_escape_filter() {
         let test_str = "document htmlEscape";
@@ -814,12 +1003,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "document",
+                    variable: (
+                        VariableName(
+                            "document",
+                        ),
+                        0..8,
                     ),
                     filters: [
-                        HtmlEscape,
+                        (
+                            HtmlEscape,
+                            9..19,
+                        ),
                     ],
+                    span: 0..19,
                 },
             ),
             errs: [],
@@ -828,7 +1024,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_html_unescape_filter() {
         let test_str = "escaped_document htmlUnescape";
@@ -839,12 +1034,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "escaped_document",
+                    variable: (
+                        VariableName(
+                            "escaped_document",
+                        ),
+                        0..16,
                     ),
                     filters: [
-                        HtmlUnescape,
+                        (
+                            HtmlUnescape,
+                            17..29,
+                        ),
                     ],
+                    span: 0..29,
                 },
             ),
             errs: [],
@@ -853,7 +1055,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_to_float_filter() {
         let test_str = "inflation_rate toFloat";
@@ -864,12 +1065,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "inflation_rate",
+                    variable: (
+                        VariableName(
+                            "inflation_rate",
+                        ),
+                        0..14,
                     ),
                     filters: [
-                        ToFloat,
+                        (
+                            ToFloat,
+                            15..22,
+                        ),
                     ],
+                    span: 0..22,
                 },
             ),
             errs: [],
@@ -878,7 +1086,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_to_int_filter() {
         let test_str = "id toInt";
@@ -889,12 +1096,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "id",
+                    variable: (
+                        VariableName(
+                            "id",
+                        ),
+                        0..2,
                     ),
                     filters: [
-                        ToInt,
+                        (
+                            ToInt,
+                            3..8,
+                        ),
                     ],
+                    span: 0..8,
                 },
             ),
             errs: [],
@@ -903,7 +1117,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_url_decode_filter() {
         let test_str = "encoded_url urlDecode";
@@ -914,12 +1127,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "encoded_url",
+                    variable: (
+                        VariableName(
+                            "encoded_url",
+                        ),
+                        0..11,
                     ),
                     filters: [
-                        UrlDecode,
+                        (
+                            UrlDecode,
+                            12..21,
+                        ),
                     ],
+                    span: 0..21,
                 },
             ),
             errs: [],
@@ -928,7 +1148,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_url_encode_filter() {
         let test_str = "url urlEncode";
@@ -939,12 +1158,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "url",
+                    variable: (
+                        VariableName(
+                            "url",
+                        ),
+                        0..3,
                     ),
                     filters: [
-                        UrlEncode,
+                        (
+                            UrlEncode,
+                            4..13,
+                        ),
                     ],
+                    span: 0..13,
                 },
             ),
             errs: [],
@@ -953,7 +1179,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_extra_space_between_variable_and_filter() {
         let test_str = "url  urlEncode";
@@ -964,12 +1189,19 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "url",
+                    variable: (
+                        VariableName(
+                            "url",
+                        ),
+                        0..3,
                     ),
                     filters: [
-                        UrlEncode,
+                        (
+                            UrlEncode,
+                            5..14,
+                        ),
                     ],
+                    span: 0..14,
                 },
             ),
             errs: [],
@@ -978,7 +1210,6 @@ mod expr_tests {
         );
     }
 
-
     #[test]
     fn it_parses_expr_with_extra_space_between_filters() {
         let test_str = r#"response jsonpath "$.names"   count"#;
@@ -989,28 +1220,37 @@ mod expr_tests {
         ParseResult {
             output: Some(
                 Expr {
-                    variable: VariableName(
-                        "response",
+                    variable: (
+                        VariableName(
+                            "response",
+                        ),
+                        0..8,
                     ),
                     filters: [
-                        JsonPath {
-                            expr: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "$.names",
-                                    ),
-                                ],
+                        (
+                            JsonPath {
+                                expr: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "$.names",
+                                        ),
+                                    ],
+                                    span: 18..27,
+                                },
                             },
-                        },
-                        Count,
+                            9..27,
+                        ),
+                        (
+                            Count,
+                            30..35,
+                        ),
                     ],
+                    span: 0..35,
                 },
             ),
             errs: [],
         }
         "#,
         );
-
     }
-
 }
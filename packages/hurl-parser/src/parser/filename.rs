@@ -1,10 +1,11 @@
+use super::error::ParseError;
 use super::primitives::escaped_unicode_parser;
 use crate::parser::template::template_parser;
 use crate::parser::types::{InterpolatedString, InterpolatedStringPart};
 use chumsky::prelude::*;
 
 fn filename_escape_char_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     let filename_escape_char = just('\\')
         .ignore_then(choice((
             just('\\').to('\\'),
@@ -39,7 +40,7 @@ fn filename_escape_char_parser<'a>(
 ///
 /// ```
 pub fn filename_parser<'a>(
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let filename_content = choice((none_of("#;{} \n\\"), filename_escape_char_parser()))
         .repeated()
         .at_least(1)
@@ -49,12 +50,15 @@ pub fn filename_parser<'a>(
 
     let filename = choice((
         filename_content,
-        template_parser().map(|t| InterpolatedStringPart::Template(t)),
+        template_parser().map(InterpolatedStringPart::Template),
     ))
     .repeated()
     .at_least(1)
     .collect::<Vec<InterpolatedStringPart>>()
-    .map(|k| InterpolatedString { parts: k })
+    .map_with(|k, e| InterpolatedString {
+        parts: k,
+        span: e.span(),
+    })
     .labelled("filename");
 
     filename.boxed()
@@ -80,6 +84,7 @@ mod expr_tests {
                             "example.txt",
                         ),
                     ],
+                    span: 0..11,
                 },
             ),
             errs: [],
@@ -101,14 +106,20 @@ mod expr_tests {
                         Template(
                             Template {
                                 expr: Expr {
-                                    variable: VariableName(
-                                        "input_file",
+                                    variable: (
+                                        VariableName(
+                                            "input_file",
+                                        ),
+                                        2..12,
                                     ),
                                     filters: [],
+                                    span: 2..12,
                                 },
+                                span: 0..14,
                             },
                         ),
                     ],
+                    span: 0..14,
                 },
             ),
             errs: [],
@@ -133,14 +144,20 @@ mod expr_tests {
                         Template(
                             Template {
                                 expr: Expr {
-                                    variable: VariableName(
-                                        "testFile1",
+                                    variable: (
+                                        VariableName(
+                                            "testFile1",
+                                        ),
+                                        10..19,
                                     ),
                                     filters: [],
+                                    span: 10..19,
                                 },
+                                span: 8..21,
                             },
                         ),
                     ],
+                    span: 0..21,
                 },
             ),
             errs: [],
@@ -163,6 +180,7 @@ mod expr_tests {
                             "./temp/example.txt",
                         ),
                     ],
+                    span: 0..18,
                 },
             ),
             errs: [],
@@ -185,6 +203,7 @@ mod expr_tests {
                             "./temp/../example.txt",
                         ),
                     ],
+                    span: 0..21,
                 },
             ),
             errs: [],
@@ -207,6 +226,7 @@ mod expr_tests {
                             "./temp/../../example.txt",
                         ),
                     ],
+                    span: 0..24,
                 },
             ),
             errs: [],
@@ -229,6 +249,7 @@ mod expr_tests {
                             "../example/example.txt",
                         ),
                     ],
+                    span: 0..22,
                 },
             ),
             errs: [],
@@ -252,6 +273,7 @@ mod expr_tests {
                             "C:/Users/myuser/Documents/projects/hurl-language-server/examples/example.txt",
                         ),
                     ],
+                    span: 0..76,
                 },
             ),
             errs: [],
@@ -274,6 +296,7 @@ mod expr_tests {
                             "C:\\Users\\myuser\\Documents\\projects\\hurl-language-server\\examples\\example.txt",
                         ),
                     ],
+                    span: 0..83,
                 },
             ),
             errs: [],
@@ -287,14 +310,23 @@ mod expr_tests {
         let test_str = r#"#"#;
         assert_debug_snapshot!(
         filename_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''#'' at 0..1 expected filename,
+                Unexpected {
+                    span: 0..1,
+                    found: Some(
+                        '#',
+                    ),
+                    expected: [
+                        "filename",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -306,14 +338,27 @@ mod expr_tests {
         //the rest.
         assert_debug_snapshot!(
         filename_parser().then(end()).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''#'' at 4..5 expected something else, filename-escaped-char, filename-content, template, or end of input,
+                Unexpected {
+                    span: 4..5,
+                    found: Some(
+                        '#',
+                    ),
+                    expected: [
+                        "end of file",
+                        "filename-content",
+                        "filename-escaped-char",
+                        "something else",
+                        "template",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -322,7 +367,7 @@ mod expr_tests {
         let test_strings = vec![r#"#"#, r#";"#, r#"{"#, r#"}"#, r#" "#, "\n", r#"\"#];
         for test_str in test_strings {
             assert!(
-                filename_parser().parse(test_str).into_errors().len() > 0,
+                !filename_parser().parse(test_str).into_errors().is_empty(),
                 r#"The filename parser unexpectedly did not error for the string "{}""#,
                 test_str
             );
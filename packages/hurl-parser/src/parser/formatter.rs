@@ -0,0 +1,548 @@
+//! A canonical pretty-printer for hurl documents.
+//!
+//! The formatter walks the lossless syntax tree so comments stay where the user wrote
+//! them. Spacing between the parts of a line is normalized, indentation and trailing
+//! whitespace are removed, runs of blank lines are collapsed into one and JSON bodies
+//! are re-indented. Values, urls and multiline strings are kept as written.
+//! Formatting an already formatted document doesn't change it.
+
+use std::collections::{HashMap, HashSet};
+
+use super::cst::{build_cst, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+use super::parser::parse_ast;
+use super::types::{Body, Bytes, Json, RequestSection, ResponseSection, Span};
+
+const JSON_INDENT: &str = "  ";
+
+/// Formats the whole document.
+///
+/// Returns `None` if the document has parse errors since the formatter can't tell
+/// which parts of the text belong together.
+pub fn format(document: &str) -> Option<String> {
+    let (out, _) = print(document)?;
+    Some(out)
+}
+
+/// Formats the entries of the document that overlap the range.
+///
+/// Returns the span of the document to replace along with its formatted text or `None`
+/// if the document has parse errors or no entry overlaps the range.
+pub fn format_range(document: &str, range: Span) -> Option<(Span, String)> {
+    let (out, entries) = print(document)?;
+    let overlapping = entries
+        .iter()
+        .filter(|entry| {
+            if range.start == range.end {
+                entry.span.start <= range.start && range.start <= entry.span.end
+            } else {
+                entry.span.start < range.end && range.start < entry.span.end
+            }
+        })
+        .collect::<Vec<_>>();
+    let first = overlapping.first()?;
+    let last = overlapping.last()?;
+    Some((
+        Span::new(first.source.start, last.source.end),
+        out[first.output.start..last.output.end].to_string(),
+    ))
+}
+
+fn print(document: &str) -> Option<(String, Vec<FormattedEntry>)> {
+    let (ast, errs) = parse_ast(document);
+    if !errs.is_empty() {
+        return None;
+    }
+    let ast = ast?;
+    let cst = build_cst(document, &ast);
+    let bodies = ast
+        .entries
+        .iter()
+        .flat_map(|entry| {
+            let response_body = entry.response.as_ref().and_then(|r| r.body.as_ref());
+            entry.request.body.iter().chain(response_body)
+        })
+        .map(|body| (body.span.start, body))
+        .collect();
+    let unknown_sections = ast
+        .entries
+        .iter()
+        .flat_map(|entry| {
+            let request = entry
+                .request_sections()
+                .filter_map(|section| match section {
+                    RequestSection::UnknownSection(section) => Some(section.span.start),
+                    _ => None,
+                });
+            let response = entry
+                .response_sections()
+                .filter_map(|section| match section {
+                    ResponseSection::UnknownSection(section) => Some(section.span.start),
+                    _ => None,
+                });
+            request.chain(response)
+        })
+        .collect();
+    let mut printer = Printer {
+        bodies,
+        unknown_sections,
+        out: String::new(),
+        pending_space: false,
+        pending_newlines: 0,
+        entry: None,
+        entries: vec![],
+    };
+    printer.node(&cst);
+    if !printer.out.is_empty() {
+        printer.out.push('\n');
+    }
+    Some((printer.out, printer.entries))
+}
+
+/// Where an entry was read from and where its formatted text was written to
+struct FormattedEntry {
+    span: Span,
+    source: Span,
+    output: Span,
+}
+
+struct Printer<'a> {
+    bodies: HashMap<usize, &'a Body>,
+    /// The starts of the sections the parser doesn't know, which are kept as written
+    unknown_sections: HashSet<usize>,
+    out: String,
+    pending_space: bool,
+    pending_newlines: usize,
+    entry: Option<(Span, Option<(Span, Span)>)>,
+    entries: Vec<FormattedEntry>,
+}
+
+impl Printer<'_> {
+    /// Writes the text after the spacing or newlines that separate it from the previous text
+    fn text(&mut self, text: &str, span: Span) {
+        if text.is_empty() {
+            return;
+        }
+        if !self.out.is_empty() {
+            if self.pending_newlines > 0 {
+                //Keep at most one blank line
+                let newlines = self.pending_newlines.min(2);
+                self.out.push_str(&"\n".repeat(newlines));
+            } else if self.pending_space {
+                self.out.push(' ');
+            }
+        }
+        self.pending_newlines = 0;
+        self.pending_space = false;
+
+        let start = self.out.len();
+        self.out.push_str(text);
+        if let Some((_, ranges)) = &mut self.entry {
+            let output = Span::new(start, self.out.len());
+            *ranges = match ranges {
+                Some((source, first_output)) => Some((
+                    Span::new(source.start, span.end),
+                    Span::new(first_output.start, output.end),
+                )),
+                None => Some((span, output)),
+            };
+        }
+    }
+
+    fn space(&mut self) {
+        if self.pending_newlines == 0 {
+            self.pending_space = true;
+        }
+    }
+
+    fn newline(&mut self) {
+        self.pending_newlines += 1;
+        self.pending_space = false;
+    }
+
+    fn node(&mut self, node: &SyntaxNode) {
+        match node.kind {
+            SyntaxKind::Entry => {
+                self.entry = Some((node.span, None));
+                self.children(node);
+                if let Some((span, Some((source, output)))) = self.entry.take() {
+                    self.entries.push(FormattedEntry {
+                        span,
+                        source,
+                        output,
+                    });
+                }
+            }
+            SyntaxKind::Section if self.unknown_sections.contains(&node.span.start) => {
+                self.verbatim(node)
+            }
+            kind if kind.is_structural() => self.children(node),
+            SyntaxKind::Query | SyntaxKind::Filter | SyntaxKind::Predicate => {
+                self.text(&collapse_spaces(&node.text()), node.span)
+            }
+            SyntaxKind::Option => self.text(&format_option(&node.text()), node.span),
+            SyntaxKind::Body => self.body(node),
+            SyntaxKind::Url | SyntaxKind::Value => {
+                //The spacing before a trailing comment isn't part of the url or value
+                self.text(node.text().trim_end_matches([' ', '\t']), node.span)
+            }
+            _ => self.text(&node.text(), node.span),
+        }
+    }
+
+    fn children(&mut self, node: &SyntaxNode) {
+        for child in &node.children {
+            match child {
+                SyntaxElement::Node(child) => self.node(child),
+                SyntaxElement::Token(token) => self.token(node.kind, token),
+            }
+        }
+    }
+
+    fn token(&mut self, parent: SyntaxKind, token: &SyntaxToken) {
+        match token.kind {
+            SyntaxKind::Whitespace => self.space(),
+            SyntaxKind::Newline => self.newline(),
+            SyntaxKind::Comment => {
+                self.space();
+                self.text(token.text.trim_end(), token.span);
+            }
+            _ if token.text == ":"
+                && matches!(
+                    parent,
+                    SyntaxKind::Header
+                        | SyntaxKind::KeyValue
+                        | SyntaxKind::FileParam
+                        | SyntaxKind::Capture
+                ) =>
+            {
+                //`key: value` with no space before the colon and one space after it
+                self.pending_space = false;
+                self.text(&token.text, token.span);
+                self.space();
+            }
+            _ => self.text(&token.text, token.span),
+        }
+    }
+
+    /// Writes the text of the node unchanged apart from its trailing whitespace
+    fn verbatim(&mut self, node: &SyntaxNode) {
+        let text = node.text();
+        let content = text.trim_end();
+        self.text(
+            content,
+            Span::new(node.span.start, node.span.start + content.len()),
+        );
+        for _ in text[content.len()..].matches('\n') {
+            self.newline();
+        }
+    }
+
+    fn body(&mut self, node: &SyntaxNode) {
+        let text = node.text();
+        let content = text.trim_start();
+        let leading = &text[..text.len() - content.len()];
+        for _ in leading.matches('\n') {
+            self.newline();
+        }
+        let span = Span::new(node.span.start + leading.len(), node.span.end);
+        match self.bodies.get(&node.span.start).map(|body| &body.bytes) {
+            Some(Bytes::JsonValue(json)) => {
+                let mut formatted = String::new();
+                format_json(content, span.start, json, span, 0, &mut formatted);
+                self.text(&formatted, span);
+            }
+            _ => self.text(content, span),
+        }
+    }
+}
+
+/// Writes the json value with one key or array item per line.
+/// Scalars are copied from the document so numbers and strings keep their exact spelling.
+fn format_json(
+    content: &str,
+    offset: usize,
+    json: &Json,
+    span: Span,
+    depth: usize,
+    out: &mut String,
+) {
+    let source = |span: Span| &content[span.start - offset..span.end - offset];
+    let indent = |depth: usize| JSON_INDENT.repeat(depth);
+    match json {
+        Json::Object(key_values) if key_values.is_empty() => out.push_str("{}"),
+        Json::Object(key_values) => {
+            out.push_str("{\n");
+            for (index, key_value) in key_values.iter().enumerate() {
+                out.push_str(&indent(depth + 1));
+                out.push_str(source(key_value.key.span));
+                out.push_str(": ");
+                let (value, value_span) = &key_value.value;
+                format_json(content, offset, value, *value_span, depth + 1, out);
+                if index + 1 < key_values.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&indent(depth));
+            out.push('}');
+        }
+        Json::Array(values) if values.is_empty() => out.push_str("[]"),
+        Json::Array(values) => {
+            out.push_str("[\n");
+            for (index, (value, value_span)) in values.iter().enumerate() {
+                out.push_str(&indent(depth + 1));
+                format_json(content, offset, value, *value_span, depth + 1, out);
+                if index + 1 < values.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&indent(depth));
+            out.push(']');
+        }
+        _ => out.push_str(source(span)),
+    }
+}
+
+/// Formats an option as `name: value`. Variable options are formatted as `variable: name=value`.
+fn format_option(text: &str) -> String {
+    let Some((name, value)) = text.split_once(':') else {
+        return text.to_string();
+    };
+    let (name, value) = (name.trim(), value.trim());
+    match value.split_once('=') {
+        Some((variable, variable_value)) if name == "variable" => {
+            format!("{}: {}={}", name, variable.trim(), variable_value.trim())
+        }
+        _ => format!("{}: {}", name, value),
+    }
+}
+
+/// Collapses the spacing between the words of a query, filter or predicate into a single
+/// space. Quoted strings, regexes and templates are kept as written.
+fn collapse_spaces(text: &str) -> String {
+    enum State {
+        Words,
+        Quoted(char),
+        Template,
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut state = State::Words;
+    let mut chars = text.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match state {
+            State::Words => match c {
+                ' ' | '\t' => {
+                    if !out.ends_with(' ') {
+                        out.push(' ');
+                    }
+                    continue;
+                }
+                '"' | '`' => state = State::Quoted(c),
+                '/' if out.is_empty() || out.ends_with(' ') => state = State::Quoted(c),
+                '{' if chars.peek() == Some(&'{') => state = State::Template,
+                _ => {}
+            },
+            State::Quoted(delimiter) => {
+                if c == '\\' {
+                    out.push(c);
+                    if let Some(escaped) = chars.next() {
+                        out.push(escaped);
+                    }
+                    continue;
+                }
+                if c == delimiter {
+                    state = State::Words;
+                }
+            }
+            State::Template => {
+                if c == '}' && chars.peek() == Some(&'}') {
+                    out.push(c);
+                    out.extend(chars.next());
+                    state = State::Words;
+                    continue;
+                }
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod formatter_tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    #[test]
+    fn it_formats_spacing_and_keeps_comments() {
+        let test_str = r#"
+
+# Get the users
+  GET    https://example.org/users   # list them
+Accept :application/json
+Authorization: Bearer {{token}}   # trailing comment
+[QueryStringParams]   # filters
+  page:   1
+
+
+   order: {{ order }}
+[Options]
+insecure :  true
+variable:   id =  1
+
+HTTP/1.1    200
+[Captures]
+user_id :   jsonpath   "$.users[0].id"    nth   0
+[Asserts]
+   jsonpath "$.users"   count   ==   2  # two users
+header "Content-Type"    contains   "json   text"
+"#;
+        assert_snapshot!(
+        format(test_str).unwrap(),
+            @r#"
+        # Get the users
+        GET https://example.org/users # list them
+        Accept: application/json
+        Authorization: Bearer {{token}} # trailing comment
+        [QueryStringParams] # filters
+        page: 1
+
+        order: {{ order }}
+        [Options]
+        insecure: true
+        variable: id=1
+
+        HTTP/1.1 200
+        [Captures]
+        user_id: jsonpath "$.users[0].id" nth 0
+        [Asserts]
+        jsonpath "$.users" count == 2 # two users
+        header "Content-Type" contains "json   text"
+        "#);
+    }
+
+    #[test]
+    fn it_formats_json_bodies() {
+        let test_str = r#"POST https://example.org
+{"name":   "bob", "tags": [ 1,2.50 , {{tag}} ], "address": {}, "empty": [],
+  "nested": {"key": "{{value}}"}}
+
+HTTP 200
+[1, "two"]
+"#;
+        assert_snapshot!(
+        format(test_str).unwrap(),
+            @r#"
+        POST https://example.org
+        {
+          "name": "bob",
+          "tags": [
+            1,
+            2.50,
+            {{tag}}
+          ],
+          "address": {},
+          "empty": [],
+          "nested": {
+            "key": "{{value}}"
+          }
+        }
+
+        HTTP 200
+        [
+          1,
+          "two"
+        ]
+        "#);
+    }
+
+    #[test]
+    fn it_keeps_multiline_strings_as_written() {
+        let test_str = r#"POST https://example.org
+```
+  keep    this
+     indentation
+```
+HTTP 200
+"#;
+        assert_snapshot!(
+        format(test_str).unwrap(),
+            @r"
+        POST https://example.org
+        ```
+          keep    this
+             indentation
+        ```
+        HTTP 200
+        ");
+    }
+
+    #[test]
+    fn it_keeps_unknown_sections_as_written() {
+        let test_str =
+            "GET https://example.org\n[Foo]   # unknown\nabc  def\n  ghi:  1\nHTTP 200\n";
+        assert_snapshot!(
+        format(test_str).unwrap(),
+            @r"
+        GET https://example.org
+        [Foo]   # unknown
+        abc  def
+          ghi:  1
+        HTTP 200
+        ");
+    }
+
+    #[test]
+    fn it_is_idempotent() {
+        let test_str = r#"# comment
+  GET    https://example.org/users   # list them
+Accept :application/json
+[Options]
+variable:   id =  1
+{"a":[1,{"b":null}]}
+
+HTTP/1.1    200
+[Asserts]
+   jsonpath "$.users"   count   ==   2  # two users
+
+
+
+GET https://example.org/{{id}}
+"#;
+        let formatted = format(test_str).unwrap();
+        assert_eq!(format(&formatted), Some(formatted));
+    }
+
+    #[test]
+    fn it_does_not_format_documents_with_errors() {
+        let test_str = "get https://example.org\n";
+        assert_eq!(format(test_str), None);
+    }
+
+    #[test]
+    fn it_formats_the_entries_in_a_range() {
+        let test_str = r#"GET    https://example.org/a
+HTTP   200
+
+
+  GET    https://example.org/b
+Accept:   */*
+
+GET    https://example.org/c
+"#;
+        let start = test_str.find("Accept").unwrap();
+        let (span, text) = format_range(test_str, Span::new(start, start + 1)).unwrap();
+        assert_eq!(
+            &test_str[span.start..span.end],
+            "GET    https://example.org/b\nAccept:   */*"
+        );
+        assert_snapshot!(
+        text,
+            @r"
+        GET https://example.org/b
+        Accept: */*
+        ");
+    }
+}
@@ -1,9 +1,9 @@
+use super::error::ParseError;
 use super::primitives::{lt_parser, sp_parser};
-use super::types::{HttpStatus, HttpVersion};
+use super::types::{HttpStatus, HttpVersion, Spanned};
 use chumsky::prelude::*;
 
-fn version_number_parser<'a>(
-) -> impl Parser<'a, &'a str, String, extra::Err<Rich<'a, char>>> + Clone {
+fn version_number_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<ParseError>> + Clone {
     let fraction_part = just(".").then(text::digits(10));
     let version_number = text::digits(10)
         .then(fraction_part.or_not())
@@ -14,13 +14,12 @@ fn version_number_parser<'a>(
     version_number
 }
 
-fn version_parser<'a>() -> impl Parser<'a, &'a str, HttpVersion, extra::Err<Rich<'a, char>>> + Clone
-{
+fn version_parser<'a>() -> impl Parser<'a, &'a str, HttpVersion, extra::Err<ParseError>> + Clone {
     let unknown_version = just("HTTP/")
         .then(version_number_parser())
         .to_slice()
         .validate(|s: &str, e, emitter| {
-            emitter.emit(Rich::custom(e.span(), "Unknown http version"));
+            emitter.emit(ParseError::UnknownHttpVersion { span: e.span() });
             HttpVersion::HttpUknown(s.to_string())
         })
         .boxed();
@@ -39,13 +38,14 @@ fn version_parser<'a>() -> impl Parser<'a, &'a str, HttpVersion, extra::Err<Rich
 }
 
 pub fn http_status_line_parser<'a>(
-) -> impl Parser<'a, &'a str, (HttpVersion, HttpStatus), extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, (Spanned<HttpVersion>, Spanned<HttpStatus>), extra::Err<ParseError>> + Clone
+{
     let status = text::digits(10)
         .to_slice()
         .validate(|number: &str, e, emitter| match number.parse::<u64>() {
             Ok(n) => HttpStatus::Code(n),
             Err(_) => {
-                emitter.emit(Rich::custom(e.span(), "invalid status: too large"));
+                emitter.emit(ParseError::StatusTooLarge { span: e.span() });
                 HttpStatus::Invalid
             }
         })
@@ -56,26 +56,27 @@ pub fn http_status_line_parser<'a>(
             .to_slice()
             .validate(|text: &str, e, emitter| {
                 if text.is_empty() {
-                    emitter.emit(Rich::custom(e.span(), "missing status"));
+                    emitter.emit(ParseError::MissingStatus { span: e.span() });
                     HttpStatus::Missing
                 } else {
-                    emitter.emit(Rich::custom(e.span(), "invalid status: not a number"));
+                    emitter.emit(ParseError::StatusNotANumber { span: e.span() });
                     HttpStatus::Invalid
                 }
             }));
 
     let http_status_line = version_parser()
+        .map_with(|version, e| (version, e.span()))
         .then(
             sp_parser()
                 .repeated()
                 .at_least(1)
-                .ignore_then(status)
+                .ignore_then(status.map_with(|status, e| (status, e.span())))
                 .or_not()
                 .validate(|status, e, emitter| match status {
                     Some(s) => s,
                     None => {
-                        emitter.emit(Rich::custom(e.span(), "missing status"));
-                        HttpStatus::Missing
+                        emitter.emit(ParseError::MissingStatus { span: e.span() });
+                        (HttpStatus::Missing, e.span())
                     }
                 }),
         )
@@ -99,9 +100,15 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http,
-                    Code(
-                        200,
+                    (
+                        Http,
+                        0..4,
+                    ),
+                    (
+                        Code(
+                            200,
+                        ),
+                        5..8,
                     ),
                 ),
             ),
@@ -120,9 +127,15 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http1_0,
-                    Code(
-                        200,
+                    (
+                        Http1_0,
+                        0..8,
+                    ),
+                    (
+                        Code(
+                            200,
+                        ),
+                        9..12,
                     ),
                 ),
             ),
@@ -141,9 +154,15 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http1_1,
-                    Code(
-                        200,
+                    (
+                        Http1_1,
+                        0..8,
+                    ),
+                    (
+                        Code(
+                            200,
+                        ),
+                        9..12,
                     ),
                 ),
             ),
@@ -162,9 +181,15 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http2,
-                    Code(
-                        200,
+                    (
+                        Http2,
+                        0..6,
+                    ),
+                    (
+                        Code(
+                            200,
+                        ),
+                        7..10,
                     ),
                 ),
             ),
@@ -183,9 +208,15 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http3,
-                    Code(
-                        200,
+                    (
+                        Http3,
+                        0..6,
+                    ),
+                    (
+                        Code(
+                            200,
+                        ),
+                        7..10,
                     ),
                 ),
             ),
@@ -204,12 +235,20 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http3,
-                    Missing,
+                    (
+                        Http3,
+                        0..6,
+                    ),
+                    (
+                        Missing,
+                        6..6,
+                    ),
                 ),
             ),
             errs: [
-                missing status at 6..6,
+                MissingStatus {
+                    span: 6..6,
+                },
             ],
         }
         ",
@@ -225,12 +264,20 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http3,
-                    Missing,
+                    (
+                        Http3,
+                        0..6,
+                    ),
+                    (
+                        Missing,
+                        7..7,
+                    ),
                 ),
             ),
             errs: [
-                missing status at 7..7,
+                MissingStatus {
+                    span: 7..7,
+                },
             ],
         }
         ",
@@ -246,16 +293,24 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    HttpUknown(
-                        "HTTP/4.5",
+                    (
+                        HttpUknown(
+                            "HTTP/4.5",
+                        ),
+                        0..8,
                     ),
-                    Code(
-                        200,
+                    (
+                        Code(
+                            200,
+                        ),
+                        9..12,
                     ),
                 ),
             ),
             errs: [
-                Unknown http version at 0..8,
+                UnknownHttpVersion {
+                    span: 0..8,
+                },
             ],
         }
         "#,
@@ -271,12 +326,20 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http3,
-                    Invalid,
+                    (
+                        Http3,
+                        0..6,
+                    ),
+                    (
+                        Invalid,
+                        7..18,
+                    ),
                 ),
             ),
             errs: [
-                invalid status: not a number at 7..18,
+                StatusNotANumber {
+                    span: 7..18,
+                },
             ],
         }
         ",
@@ -292,12 +355,20 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http3,
-                    Invalid,
+                    (
+                        Http3,
+                        0..6,
+                    ),
+                    (
+                        Invalid,
+                        7..27,
+                    ),
                 ),
             ),
             errs: [
-                invalid status: too large at 7..27,
+                StatusTooLarge {
+                    span: 7..27,
+                },
             ],
         }
         ",
@@ -313,8 +384,14 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 (
-                    Http1_1,
-                    Any,
+                    (
+                        Http1_1,
+                        0..8,
+                    ),
+                    (
+                        Any,
+                        9..10,
+                    ),
                 ),
             ),
             errs: [],
@@ -0,0 +1,591 @@
+//! Reparses a document after an edit by only parsing the entries around the edit.
+//!
+//! The spans of the entries of an AST are contiguous and every entry starts with its method
+//! line, so an edit can only change the entries it touches and their neighbours. Those are
+//! reparsed while the entries before them are reused as is and the entries after them are
+//! reused with their spans moved by the change of length of the document.
+
+use chumsky::prelude::*;
+
+use super::error::ParseError;
+use super::parser::{ast_parser, parse_ast};
+use super::types::{
+    Assert, AssertsSection, Ast, BasicAuthSection, Body, BooleanOption, Bytes, Capture,
+    CapturesSection, CookiesSection, Duration, DurationOption, Entry, Expr, FileKeyValue,
+    FileValue, FilterFunction, FormParamsSection, IntegerOption, InterpolatedString,
+    InterpolatedStringPart, Json, JsonKeyValue, KeyValue, Method, MultilineString,
+    MultipartFormDataSection, MultipartFormParam, Predicate, PredicateFunc, PredicateValue, Query,
+    QueryStringParamsSection, Regex, Request, RequestOption, RequestOptionsSection, RequestSection,
+    Response, ResponseSection, Span, Template, UnknownSection, Url, VariableDefinitionOption,
+    VariableValue,
+};
+
+/// A replacement of a byte range of the previous document by a new text
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Edit {
+    pub span: Span,
+    pub text: String,
+}
+
+/// Parses the edited document by reusing the entries of the previous AST that the edit
+/// didn't touch. The result is the same as calling [`parse_ast`] on the edited document.
+///
+/// Only error free trees are reused since the recovery of the parser can move entry
+/// boundaries. The whole document is parsed again when the previous AST had errors or when
+/// the entries around the edit don't parse cleanly.
+pub fn reparse_ast(
+    previous_ast: Option<&Ast>,
+    previous_errors: &[ParseError],
+    edit: &Edit,
+    document: &str,
+) -> (Option<Ast>, Vec<ParseError>) {
+    match previous_ast {
+        Some(previous_ast) if previous_errors.is_empty() => {
+            reparse_entries(previous_ast, edit, document)
+                .map(|ast| (Some(ast), vec![]))
+                .unwrap_or_else(|| parse_ast(document))
+        }
+        _ => parse_ast(document),
+    }
+}
+
+fn reparse_entries(previous_ast: &Ast, edit: &Edit, document: &str) -> Option<Ast> {
+    let entries = &previous_ast.entries;
+    let delta = edit.text.len() as isize - (edit.span.end as isize - edit.span.start as isize);
+
+    //An edit at the boundary of two entries can change both of them
+    let first = entries
+        .iter()
+        .position(|entry| entry.span.end >= edit.span.start)?;
+    //An edit of the comments before the first entry only touches the first entry
+    let last = entries
+        .iter()
+        .rposition(|entry| entry.span.start <= edit.span.end)
+        .unwrap_or(0);
+    if last < first {
+        return None;
+    }
+
+    //The entry before the edit is reparsed in case the edit removed the method line that
+    //separates it from the edited entry
+    let start = match first {
+        0 => 0,
+        first => entries[first - 1].span.start,
+    };
+    //The entry after the edit is reparsed in case the edit added a method line or opened a
+    //body that takes in its lines. It must come back unchanged for the entries after it to
+    //be reused.
+    let next = entries.get(last + 1);
+    let end = match next {
+        Some(next) => shift_offset(next.span.end, delta),
+        None => document.len(),
+    };
+    if start > end || end > document.len() || !document.is_char_boundary(end) {
+        return None;
+    }
+
+    let (region, errors) = ast_parser()
+        .parse(&document[start..end])
+        .into_output_errors();
+    let mut region = region?;
+    if !errors.is_empty() {
+        return None;
+    }
+    region.shift(start as isize);
+    if let Some(next) = next {
+        let mut moved = next.clone();
+        moved.shift(delta);
+        if region.entries.last() != Some(&moved) {
+            return None;
+        }
+    }
+
+    let mut ast = Ast {
+        entries: entries[..first.saturating_sub(1)].to_vec(),
+    };
+    ast.entries.extend(region.entries);
+    ast.entries
+        .extend(entries.iter().skip(last + 2).cloned().map(|mut entry| {
+            entry.shift(delta);
+            entry
+        }));
+    Some(ast)
+}
+
+fn shift_offset(offset: usize, delta: isize) -> usize {
+    offset.saturating_add_signed(delta)
+}
+
+/// Moves every span of a node by the same number of bytes
+trait Shift {
+    fn shift(&mut self, delta: isize);
+}
+
+impl Shift for Span {
+    fn shift(&mut self, delta: isize) {
+        self.start = shift_offset(self.start, delta);
+        self.end = shift_offset(self.end, delta);
+    }
+}
+
+impl<T: Shift> Shift for Vec<T> {
+    fn shift(&mut self, delta: isize) {
+        for node in self {
+            node.shift(delta);
+        }
+    }
+}
+
+impl<T: Shift> Shift for Option<T> {
+    fn shift(&mut self, delta: isize) {
+        if let Some(node) = self {
+            node.shift(delta);
+        }
+    }
+}
+
+impl<T: Shift> Shift for Box<T> {
+    fn shift(&mut self, delta: isize) {
+        self.as_mut().shift(delta);
+    }
+}
+
+impl<T: Shift> Shift for (T, Span) {
+    fn shift(&mut self, delta: isize) {
+        self.0.shift(delta);
+        self.1.shift(delta);
+    }
+}
+
+/// Implements [`Shift`] for nodes without spans
+macro_rules! shift_nothing {
+    ($($node:ty),*) => {
+        $(impl Shift for $node {
+            fn shift(&mut self, _: isize) {}
+        })*
+    };
+}
+
+shift_nothing!(
+    String,
+    super::types::ExprValue,
+    super::types::PredicatePrefixOperator,
+    super::types::HttpVersion,
+    super::types::HttpStatus
+);
+
+/// Implements [`Shift`] for structs by shifting the listed fields
+macro_rules! shift_fields {
+    ($($node:ty { $($field:ident),* }),* $(,)?) => {
+        $(impl Shift for $node {
+            fn shift(&mut self, delta: isize) {
+                $(self.$field.shift(delta);)*
+            }
+        })*
+    };
+}
+
+shift_fields!(
+    Ast { entries },
+    Entry {
+        request,
+        response,
+        span
+    },
+    Request {
+        method,
+        url,
+        headers,
+        request_sections,
+        misplaced_sections,
+        body,
+        span
+    },
+    Response {
+        version,
+        status,
+        headers,
+        response_sections,
+        misplaced_sections,
+        body,
+        span
+    },
+    Method { span },
+    Expr {
+        variable,
+        filters,
+        span
+    },
+    Template { expr, span },
+    InterpolatedString { parts, span },
+    KeyValue { key, value, span },
+    QueryStringParamsSection { queries, span },
+    FormParamsSection { params, span },
+    FileValue { filename, span },
+    FileKeyValue { key, value, span },
+    MultipartFormDataSection { params, span },
+    CookiesSection { cookies, span },
+    Capture {
+        key,
+        query,
+        filters,
+        span
+    },
+    CapturesSection { captures, span },
+    UnknownSection { span },
+    Predicate {
+        prefix,
+        function,
+        span
+    },
+    Assert {
+        query,
+        filters,
+        predicate,
+        span
+    },
+    AssertsSection { asserts, span },
+    BasicAuthSection { key_values, span },
+    Duration { span },
+    VariableDefinitionOption { name, value, span },
+    RequestOptionsSection { options, span },
+    Body { bytes, span },
+    MultilineString { content, span },
+    JsonKeyValue { key, value, span },
+);
+
+impl Shift for Url {
+    fn shift(&mut self, delta: isize) {
+        if let Url::Url(url) = self {
+            url.shift(delta);
+        }
+    }
+}
+
+impl Shift for InterpolatedStringPart {
+    fn shift(&mut self, delta: isize) {
+        if let InterpolatedStringPart::Template(template) = self {
+            template.shift(delta);
+        }
+    }
+}
+
+impl Shift for FilterFunction {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            FilterFunction::Decode { encoding: value }
+            | FilterFunction::Format { fmt: value }
+            | FilterFunction::JsonPath { expr: value }
+            | FilterFunction::Split { sep: value }
+            | FilterFunction::ToDate { fmt: value }
+            | FilterFunction::XPath { expr: value } => value.shift(delta),
+            FilterFunction::Regex { value } => value.shift(delta),
+            FilterFunction::Replace {
+                old_value,
+                new_value,
+            } => {
+                old_value.shift(delta);
+                new_value.shift(delta);
+            }
+            FilterFunction::Count
+            | FilterFunction::DaysAfterNow
+            | FilterFunction::DaysBeforeNow
+            | FilterFunction::HtmlEscape
+            | FilterFunction::HtmlUnescape
+            | FilterFunction::Nth { .. }
+            | FilterFunction::ToFloat
+            | FilterFunction::ToInt
+            | FilterFunction::UrlDecode
+            | FilterFunction::UrlEncode => {}
+        }
+    }
+}
+
+impl Shift for Regex {
+    fn shift(&mut self, delta: isize) {
+        if let Regex::Interpolated(value) = self {
+            value.shift(delta);
+        }
+    }
+}
+
+impl Shift for MultipartFormParam {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            MultipartFormParam::FileParam(param) => param.shift(delta),
+            MultipartFormParam::KeyValueParam(param) => param.shift(delta),
+        }
+    }
+}
+
+impl Shift for Query {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            Query::Header(value)
+            | Query::Cookie(value)
+            | Query::Xpath(value)
+            | Query::JsonPath(value)
+            | Query::Variable(value) => value.shift(delta),
+            Query::Regex(value) => value.shift(delta),
+            Query::Status
+            | Query::Url
+            | Query::Certificate(_)
+            | Query::Body
+            | Query::Duration
+            | Query::Bytes
+            | Query::Sha256
+            | Query::Md5 => {}
+        }
+    }
+}
+
+impl Shift for PredicateValue {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            PredicateValue::OneLineFile(value)
+            | PredicateValue::OneLineString(value)
+            | PredicateValue::QuotedString(value) => value.shift(delta),
+            PredicateValue::MultilineString(value) => value.shift(delta),
+            PredicateValue::Regex(value) => value.shift(delta),
+            PredicateValue::Template(value) => value.shift(delta),
+            PredicateValue::Invalid
+            | PredicateValue::Boolean(_)
+            | PredicateValue::Null
+            | PredicateValue::Integer(_)
+            | PredicateValue::Float(_)
+            | PredicateValue::BigInteger(_)
+            | PredicateValue::OneLineBase64(_)
+            | PredicateValue::OneLineHex(_) => {}
+        }
+    }
+}
+
+impl Shift for PredicateFunc {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            PredicateFunc::Equal { value }
+            | PredicateFunc::NotEqual { value }
+            | PredicateFunc::Greater { value }
+            | PredicateFunc::GreaterOrEqual { value }
+            | PredicateFunc::Less { value }
+            | PredicateFunc::LessOrEqual { value }
+            | PredicateFunc::StartWith { value }
+            | PredicateFunc::EndWith { value }
+            | PredicateFunc::Contain { value }
+            | PredicateFunc::Match { value }
+            | PredicateFunc::Include { value } => value.shift(delta),
+            PredicateFunc::Exists
+            | PredicateFunc::IsEmpty
+            | PredicateFunc::IsInteger
+            | PredicateFunc::IsFloat
+            | PredicateFunc::IsBoolean
+            | PredicateFunc::IsString
+            | PredicateFunc::IsCollection
+            | PredicateFunc::IsDate
+            | PredicateFunc::IsIsoDate
+            | PredicateFunc::Invalid => {}
+        }
+    }
+}
+
+impl Shift for BooleanOption {
+    fn shift(&mut self, delta: isize) {
+        if let BooleanOption::Template(template) = self {
+            template.shift(delta);
+        }
+    }
+}
+
+impl Shift for DurationOption {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            DurationOption::Literal(duration) => duration.shift(delta),
+            DurationOption::Template(template) => template.shift(delta),
+        }
+    }
+}
+
+impl Shift for IntegerOption {
+    fn shift(&mut self, delta: isize) {
+        if let IntegerOption::Template(template) = self {
+            template.shift(delta);
+        }
+    }
+}
+
+impl Shift for VariableValue {
+    fn shift(&mut self, delta: isize) {
+        if let VariableValue::String(value) = self {
+            value.shift(delta);
+        }
+    }
+}
+
+impl Shift for RequestOption {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            RequestOption::Compressed(option)
+            | RequestOption::Location(option)
+            | RequestOption::LocationTrusted(option)
+            | RequestOption::Http10(option)
+            | RequestOption::Http11(option)
+            | RequestOption::Http2(option)
+            | RequestOption::Http3(option)
+            | RequestOption::Insecure(option)
+            | RequestOption::Ipv4(option)
+            | RequestOption::Ipv6(option)
+            | RequestOption::Netrc(option)
+            | RequestOption::NetrcOptional(option)
+            | RequestOption::PathAsIs(option)
+            | RequestOption::Skip(option)
+            | RequestOption::Verbose(option)
+            | RequestOption::VeryVerbose(option) => option.shift(delta),
+            RequestOption::ConnectTimeout(option)
+            | RequestOption::Delay(option)
+            | RequestOption::RetryInterval(option) => option.shift(delta),
+            RequestOption::LimitRate(option)
+            | RequestOption::MaxRedirs(option)
+            | RequestOption::Repeat(option)
+            | RequestOption::Retry(option) => option.shift(delta),
+            RequestOption::Cacert(value)
+            | RequestOption::Key(value)
+            | RequestOption::Output(value)
+            | RequestOption::Cert(value)
+            | RequestOption::AwsSigv4(value)
+            | RequestOption::ConnectTo(value)
+            | RequestOption::NetrcFile(value)
+            | RequestOption::Proxy(value)
+            | RequestOption::Resolve(value)
+            | RequestOption::UnixSocket(value)
+            | RequestOption::User(value) => value.shift(delta),
+            RequestOption::Variable(option) => option.shift(delta),
+        }
+    }
+}
+
+impl Shift for RequestSection {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            RequestSection::BasicAuthSection(section) => section.shift(delta),
+            RequestSection::QueryStringParamsSection(section) => section.shift(delta),
+            RequestSection::FormParamsSection(section) => section.shift(delta),
+            RequestSection::MultipartFormDataSection(section) => section.shift(delta),
+            RequestSection::CookiesSection(section) => section.shift(delta),
+            RequestSection::OptionsSection(section) => section.shift(delta),
+            RequestSection::UnknownSection(section) => section.shift(delta),
+        }
+    }
+}
+
+impl Shift for ResponseSection {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            ResponseSection::CapturesSection(section) => section.shift(delta),
+            ResponseSection::AssertsSection(section) => section.shift(delta),
+            ResponseSection::UnknownSection(section) => section.shift(delta),
+        }
+    }
+}
+
+impl Shift for Bytes {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            Bytes::JsonValue(json) => json.shift(delta),
+            Bytes::MultilineString(value) => value.shift(delta),
+            Bytes::OneLineString(value) | Bytes::OneLineFile(value) => value.shift(delta),
+            Bytes::OneLineBase64(_) | Bytes::OneLineHex(_) => {}
+        }
+    }
+}
+
+impl Shift for Json {
+    fn shift(&mut self, delta: isize) {
+        match self {
+            Json::Object(key_values) => key_values.shift(delta),
+            Json::Array(values) => values.shift(delta),
+            Json::InterpolatedString(value) => value.shift(delta),
+            Json::Template(template) => template.shift(delta),
+            Json::Invalid | Json::Str(_) | Json::Num(_) | Json::Bool(_) | Json::Null => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod incremental_tests {
+    use super::*;
+
+    const TEST_STR: &str = r#"# Log in
+POST https://example.org/login
+[FormParams]
+user: bob
+HTTP 200
+[Captures]
+token: jsonpath "$.token"
+
+GET https://example.org/users/{{token}}
+Authorization: Bearer {{token}}
+HTTP 200
+[Asserts]
+jsonpath "$.users" count == 2
+
+PUT https://example.org/users/1
+{
+  "name": "alice"
+}
+HTTP 204
+
+DELETE https://example.org/users/1
+"#;
+
+    /// Applies the edit and checks the result against a parse of the whole edited document.
+    /// Returns whether the entries around the edit could be reparsed on their own.
+    fn check(document: &str, find: &str, text: &str) -> bool {
+        let start = document.find(find).unwrap();
+        let edit = Edit {
+            span: Span::new(start, start + find.len()),
+            text: text.to_string(),
+        };
+        let mut edited = document.to_string();
+        edited.replace_range(start..start + find.len(), text);
+        let (previous_ast, previous_errors) = parse_ast(document);
+        assert_eq!(
+            reparse_ast(previous_ast.as_ref(), &previous_errors, &edit, &edited),
+            parse_ast(&edited),
+            "replacing {:?} with {:?}",
+            find,
+            text
+        );
+        previous_errors.is_empty()
+            && reparse_entries(previous_ast.as_ref().unwrap(), &edit, &edited).is_some()
+    }
+
+    #[test]
+    fn it_reparses_edits_inside_an_entry() {
+        assert!(check(TEST_STR, "bob", "alice"));
+        assert!(check(TEST_STR, "count == 2", "count == 12"));
+        assert!(check(TEST_STR, "\"alice\"", "\"bob\""));
+        assert!(check(TEST_STR, "Authorization: Bearer {{token}}\n", ""));
+        assert!(check(TEST_STR, "# Log in", "# Log in as {{user}}"));
+    }
+
+    #[test]
+    fn it_reparses_edits_that_change_entry_boundaries() {
+        assert!(check(TEST_STR, "PUT https://example.org/users/1\n", ""));
+        check(
+            TEST_STR,
+            "HTTP 204\n",
+            "HTTP 204\n\nGET https://example.org\n",
+        );
+        check(TEST_STR, "\nDELETE", "DELETE");
+        check(TEST_STR, TEST_STR, "GET https://example.org\n");
+        check(TEST_STR, "DELETE https://example.org/users/1\n", "");
+    }
+
+    #[test]
+    fn it_reparses_edits_that_add_or_fix_errors() {
+        assert!(!check(TEST_STR, "HTTP 204", "HTTP abc"));
+        assert!(!check(TEST_STR, "GET", "get"));
+        let broken = format!("get https://example.org\n{}", TEST_STR);
+        assert!(!check(&broken, "get", "GET"));
+    }
+}
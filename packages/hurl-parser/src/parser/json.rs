@@ -1,13 +1,14 @@
 use chumsky::prelude::*;
 
+use super::error::ParseError;
 use super::{
     method::method_line_parser,
     template::template_parser,
-    types::{InterpolatedString, InterpolatedStringPart, Json, JsonKeyValue},
+    types::{InterpolatedString, InterpolatedStringPart, Json, JsonKeyValue, Spanned},
 };
 
 fn json_string_escaped_char_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     just('\\')
         .ignore_then(choice((
             just('\\'),
@@ -21,7 +22,7 @@ fn json_string_escaped_char_parser<'a>(
             just('u').ignore_then(text::digits(16).exactly(4).to_slice().validate(
                 |digits, e, emitter| {
                     char::from_u32(u32::from_str_radix(digits, 16).unwrap()).unwrap_or_else(|| {
-                        emitter.emit(Rich::custom(e.span(), "invalid unicode character"));
+                        emitter.emit(ParseError::InvalidUnicodeChar { span: e.span() });
                         '\u{FFFD}' // unicode replacement character
                     })
                 },
@@ -32,7 +33,7 @@ fn json_string_escaped_char_parser<'a>(
 }
 
 fn json_string_parser<'a>(
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let json_string_content = choice((
         none_of(r#"\"{"#).labelled("json-string-text"),
         json_string_escaped_char_parser(),
@@ -53,24 +54,26 @@ fn json_string_parser<'a>(
     ))
     .repeated()
     .collect::<Vec<InterpolatedStringPart>>()
-    .map(|v| InterpolatedString { parts: v })
     .delimited_by(just('"'), just('"'))
+    .map_with(|v, e| InterpolatedString {
+        parts: v,
+        span: e.span(),
+    })
     .labelled("json-string")
     .boxed();
 
     json_string
 }
 
-fn json_number_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<Rich<'a, char>>> + Clone
-{
+fn json_number_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<ParseError>> + Clone {
     let digits = text::digits(10).to_slice();
 
-    let frac = just('.').then(digits.clone());
+    let frac = just('.').then(digits);
 
     let exp = just('e')
         .or(just('E'))
         .then(one_of("+-").or_not())
-        .then(digits.clone());
+        .then(digits);
 
     let number = just('-')
         .or_not()
@@ -88,13 +91,17 @@ fn json_number_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<Rich<
     number
 }
 
-fn json_object_parser<'a, T: Parser<'a, &'a str, Json, extra::Err<Rich<'a, char>>> + Clone>(
+fn json_object_parser<'a, T: Parser<'a, &'a str, Spanned<Json>, extra::Err<ParseError>> + Clone>(
     json_value: T,
-) -> impl Parser<'a, &'a str, Vec<JsonKeyValue>, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, Vec<JsonKeyValue>, extra::Err<ParseError>> + Clone {
     let json_key_value = json_string_parser()
         .then_ignore(just(':').padded())
         .then(json_value)
-        .map(|(key, value)| JsonKeyValue { key, value })
+        .map_with(|(key, value), e| JsonKeyValue {
+            key,
+            value,
+            span: e.span(),
+        })
         .labelled("json-key-value");
 
     let json_object = json_key_value
@@ -122,8 +129,8 @@ fn json_object_parser<'a, T: Parser<'a, &'a str, Json, extra::Err<Rich<'a, char>
     json_object
 }
 
-pub fn json_value_parser<'a>() -> impl Parser<'a, &'a str, Json, extra::Err<Rich<'a, char>>> + Clone
-{
+pub fn json_value_parser<'a>(
+) -> impl Parser<'a, &'a str, Spanned<Json>, extra::Err<ParseError>> + Clone {
     let json_value = recursive(|json_value| {
         let array = json_value
             .clone()
@@ -178,7 +185,7 @@ pub fn json_value_parser<'a>() -> impl Parser<'a, &'a str, Json, extra::Err<Rich
                 .or(method_line_parser(true).ignored()),
         ));
 
-        text::whitespace().ignore_then(json_value_unpadded)
+        text::whitespace().ignore_then(json_value_unpadded.map_with(|json, e| (json, e.span())))
     })
     .labelled("json-value")
     .boxed();
@@ -201,15 +208,23 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Template(
-                    Template {
-                        expr: Expr {
-                            variable: VariableName(
-                                "api-key",
-                            ),
-                            filters: [],
+                (
+                    Template(
+                        Template {
+                            expr: Expr {
+                                variable: (
+                                    VariableName(
+                                        "api-key",
+                                    ),
+                                    2..9,
+                                ),
+                                filters: [],
+                                span: 2..9,
+                            },
+                            span: 0..11,
                         },
-                    },
+                    ),
+                    0..11,
                 ),
             ),
             errs: [],
@@ -226,8 +241,11 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Object(
-                    [],
+                (
+                    Object(
+                        [],
+                    ),
+                    0..2,
                 ),
             ),
             errs: [],
@@ -244,8 +262,11 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Object(
-                    [],
+                (
+                    Object(
+                        [],
+                    ),
+                    0..6,
                 ),
             ),
             errs: [],
@@ -265,8 +286,11 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Object(
-                    [],
+                (
+                    Object(
+                        [],
+                    ),
+                    0..11,
                 ),
             ),
             errs: [],
@@ -283,8 +307,11 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Array(
-                    [],
+                (
+                    Array(
+                        [],
+                    ),
+                    0..2,
                 ),
             ),
             errs: [],
@@ -301,8 +328,11 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Array(
-                    [],
+                (
+                    Array(
+                        [],
+                    ),
+                    0..6,
                 ),
             ),
             errs: [],
@@ -322,8 +352,11 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Array(
-                    [],
+                (
+                    Array(
+                        [],
+                    ),
+                    0..19,
                 ),
             ),
             errs: [],
@@ -340,14 +373,18 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                InterpolatedString(
-                    InterpolatedString {
-                        parts: [
-                            Str(
-                                "test",
-                            ),
-                        ],
-                    },
+                (
+                    InterpolatedString(
+                        InterpolatedString {
+                            parts: [
+                                Str(
+                                    "test",
+                                ),
+                            ],
+                            span: 0..6,
+                        },
+                    ),
+                    0..6,
                 ),
             ),
             errs: [],
@@ -362,15 +399,34 @@ mod tests {
         let test_str = r#""{{ }}""#;
         assert_debug_snapshot!(
         json_value_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''}'' at 4..5 expected spacing, or expr,
-                found ''}'' at 5..6 expected end of input,
+                Unexpected {
+                    span: 4..5,
+                    found: Some(
+                        '}',
+                    ),
+                    expected: [
+                        "expr",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
+                Unexpected {
+                    span: 5..6,
+                    found: Some(
+                        '}',
+                    ),
+                    expected: [
+                        "end of file",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -384,41 +440,60 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Object(
-                    [
-                        JsonKeyValue {
-                            key: InterpolatedString {
-                                parts: [
-                                    Template(
-                                        Template {
-                                            expr: Expr {
-                                                variable: VariableName(
-                                                    "key",
-                                                ),
-                                                filters: [],
-                                            },
-                                        },
-                                    ),
-                                ],
-                            },
-                            value: InterpolatedString(
-                                InterpolatedString {
+                (
+                    Object(
+                        [
+                            JsonKeyValue {
+                                key: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "value",
+                                                    variable: (
+                                                        VariableName(
+                                                            "key",
+                                                        ),
+                                                        17..20,
                                                     ),
                                                     filters: [],
+                                                    span: 17..20,
                                                 },
+                                                span: 15..22,
                                             },
                                         ),
                                     ],
+                                    span: 14..23,
                                 },
-                            ),
-                        },
-                    ],
+                                value: (
+                                    InterpolatedString(
+                                        InterpolatedString {
+                                            parts: [
+                                                Template(
+                                                    Template {
+                                                        expr: Expr {
+                                                            variable: (
+                                                                VariableName(
+                                                                    "value",
+                                                                ),
+                                                                28..33,
+                                                            ),
+                                                            filters: [],
+                                                            span: 28..33,
+                                                        },
+                                                        span: 26..35,
+                                                    },
+                                                ),
+                                            ],
+                                            span: 25..36,
+                                        },
+                                    ),
+                                    25..36,
+                                ),
+                                span: 14..36,
+                            },
+                        ],
+                    ),
+                    0..50,
                 ),
             ),
             errs: [],
@@ -437,28 +512,41 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Object(
-                    [
-                        JsonKeyValue {
-                            key: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "pet_count",
-                                    ),
-                                ],
-                            },
-                            value: Template(
-                                Template {
-                                    expr: Expr {
-                                        variable: VariableName(
-                                            "count",
+                (
+                    Object(
+                        [
+                            JsonKeyValue {
+                                key: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "pet_count",
                                         ),
-                                        filters: [],
-                                    },
+                                    ],
+                                    span: 14..25,
                                 },
-                            ),
-                        },
-                    ],
+                                value: (
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "count",
+                                                    ),
+                                                    29..34,
+                                                ),
+                                                filters: [],
+                                                span: 29..34,
+                                            },
+                                            span: 27..36,
+                                        },
+                                    ),
+                                    27..36,
+                                ),
+                                span: 14..36,
+                            },
+                        ],
+                    ),
+                    0..50,
                 ),
             ),
             errs: [],
@@ -475,8 +563,11 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Num(
-                    "55",
+                (
+                    Num(
+                        "55",
+                    ),
+                    0..2,
                 ),
             ),
             errs: [],
@@ -493,8 +584,11 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Num(
-                    "55.05",
+                (
+                    Num(
+                        "55.05",
+                    ),
+                    0..5,
                 ),
             ),
             errs: [],
@@ -508,14 +602,21 @@ mod tests {
         let test_str = r#"55."#;
         assert_debug_snapshot!(
         json_value_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found end of input at 3..3 expected any,
+                Unexpected {
+                    span: 3..3,
+                    found: None,
+                    expected: [
+                        "any character",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -527,8 +628,11 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Num(
-                    "55e+5",
+                (
+                    Num(
+                        "55e+5",
+                    ),
+                    0..5,
                 ),
             ),
             errs: [],
@@ -545,8 +649,11 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Num(
-                    "-55.05e+5",
+                (
+                    Num(
+                        "-55.05e+5",
+                    ),
+                    0..9,
                 ),
             ),
             errs: [],
@@ -563,8 +670,11 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Bool(
-                    true,
+                (
+                    Bool(
+                        true,
+                    ),
+                    0..4,
                 ),
             ),
             errs: [],
@@ -581,8 +691,11 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Bool(
-                    false,
+                (
+                    Bool(
+                        false,
+                    ),
+                    0..5,
                 ),
             ),
             errs: [],
@@ -599,7 +712,10 @@ mod tests {
             @r"
         ParseResult {
             output: Some(
-                Null,
+                (
+                    Null,
+                    0..4,
+                ),
             ),
             errs: [],
         }
@@ -637,212 +753,293 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Object(
-                    [
-                        JsonKeyValue {
-                            key: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "type",
-                                    ),
-                                ],
-                            },
-                            value: Num(
-                                "49",
-                            ),
-                        },
-                        JsonKeyValue {
-                            key: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "id",
-                                    ),
-                                ],
-                            },
-                            value: InterpolatedString(
-                                InterpolatedString {
+                (
+                    Object(
+                        [
+                            JsonKeyValue {
+                                key: InterpolatedString {
                                     parts: [
                                         Str(
-                                            "d89e270c-5f26-4906-b305-c9e3cc2a0a24",
+                                            "type",
                                         ),
                                     ],
+                                    span: 14..20,
                                 },
-                            ),
-                        },
-                        JsonKeyValue {
-                            key: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "pet_types",
+                                value: (
+                                    Num(
+                                        "49",
                                     ),
-                                ],
+                                    22..24,
+                                ),
+                                span: 14..24,
                             },
-                            value: Array(
-                                [
-                                    InterpolatedString(
-                                        InterpolatedString {
-                                            parts: [
-                                                Str(
-                                                    "cat",
-                                                ),
-                                            ],
-                                        },
-                                    ),
-                                    InterpolatedString(
-                                        InterpolatedString {
-                                            parts: [
-                                                Str(
-                                                    "dog",
-                                                ),
-                                            ],
-                                        },
-                                    ),
+                            JsonKeyValue {
+                                key: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "id",
+                                        ),
+                                    ],
+                                    span: 38..42,
+                                },
+                                value: (
                                     InterpolatedString(
                                         InterpolatedString {
                                             parts: [
                                                 Str(
-                                                    "hampster",
+                                                    "d89e270c-5f26-4906-b305-c9e3cc2a0a24",
                                                 ),
                                             ],
+                                            span: 44..82,
                                         },
                                     ),
-                                ],
-                            ),
-                        },
-                        JsonKeyValue {
-                            key: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "pets",
-                                    ),
-                                ],
+                                    44..82,
+                                ),
+                                span: 38..82,
                             },
-                            value: Array(
-                                [
-                                    Object(
+                            JsonKeyValue {
+                                key: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "pet_types",
+                                        ),
+                                    ],
+                                    span: 96..107,
+                                },
+                                value: (
+                                    Array(
                                         [
-                                            JsonKeyValue {
-                                                key: InterpolatedString {
-                                                    parts: [
-                                                        Str(
-                                                            "type",
-                                                        ),
-                                                    ],
-                                                },
-                                                value: InterpolatedString(
+                                            (
+                                                InterpolatedString(
                                                     InterpolatedString {
                                                         parts: [
                                                             Str(
                                                                 "cat",
                                                             ),
                                                         ],
+                                                        span: 127..132,
                                                     },
                                                 ),
-                                            },
-                                            JsonKeyValue {
-                                                key: InterpolatedString {
-                                                    parts: [
-                                                        Str(
-                                                            "mood",
-                                                        ),
-                                                    ],
-                                                },
-                                                value: InterpolatedString(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "annoyed",
-                                                            ),
-                                                        ],
-                                                    },
-                                                ),
-                                            },
-                                        ],
-                                    ),
-                                    Object(
-                                        [
-                                            JsonKeyValue {
-                                                key: InterpolatedString {
-                                                    parts: [
-                                                        Str(
-                                                            "type",
-                                                        ),
-                                                    ],
-                                                },
-                                                value: InterpolatedString(
+                                                127..132,
+                                            ),
+                                            (
+                                                InterpolatedString(
                                                     InterpolatedString {
                                                         parts: [
                                                             Str(
                                                                 "dog",
                                                             ),
                                                         ],
+                                                        span: 150..155,
                                                     },
                                                 ),
-                                            },
-                                            JsonKeyValue {
-                                                key: InterpolatedString {
-                                                    parts: [
-                                                        Str(
-                                                            "mood",
-                                                        ),
-                                                    ],
-                                                },
-                                                value: InterpolatedString(
+                                                150..155,
+                                            ),
+                                            (
+                                                InterpolatedString(
                                                     InterpolatedString {
                                                         parts: [
                                                             Str(
-                                                                "excited",
+                                                                "hampster",
                                                             ),
                                                         ],
+                                                        span: 173..183,
                                                     },
                                                 ),
-                                            },
+                                                173..183,
+                                            ),
                                         ],
                                     ),
-                                    Object(
+                                    109..197,
+                                ),
+                                span: 96..197,
+                            },
+                            JsonKeyValue {
+                                key: InterpolatedString {
+                                    parts: [
+                                        Str(
+                                            "pets",
+                                        ),
+                                    ],
+                                    span: 211..217,
+                                },
+                                value: (
+                                    Array(
                                         [
-                                            JsonKeyValue {
-                                                key: InterpolatedString {
-                                                    parts: [
-                                                        Str(
-                                                            "type",
-                                                        ),
-                                                    ],
-                                                },
-                                                value: InterpolatedString(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "hampster",
+                                            (
+                                                Object(
+                                                    [
+                                                        JsonKeyValue {
+                                                            key: InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "type",
+                                                                    ),
+                                                                ],
+                                                                span: 259..265,
+                                                            },
+                                                            value: (
+                                                                InterpolatedString(
+                                                                    InterpolatedString {
+                                                                        parts: [
+                                                                            Str(
+                                                                                "cat",
+                                                                            ),
+                                                                        ],
+                                                                        span: 267..272,
+                                                                    },
+                                                                ),
+                                                                267..272,
                                                             ),
-                                                        ],
-                                                    },
+                                                            span: 259..272,
+                                                        },
+                                                        JsonKeyValue {
+                                                            key: InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "mood",
+                                                                    ),
+                                                                ],
+                                                                span: 294..300,
+                                                            },
+                                                            value: (
+                                                                InterpolatedString(
+                                                                    InterpolatedString {
+                                                                        parts: [
+                                                                            Str(
+                                                                                "annoyed",
+                                                                            ),
+                                                                        ],
+                                                                        span: 302..311,
+                                                                    },
+                                                                ),
+                                                                302..311,
+                                                            ),
+                                                            span: 294..311,
+                                                        },
+                                                    ],
                                                 ),
-                                            },
-                                            JsonKeyValue {
-                                                key: InterpolatedString {
-                                                    parts: [
-                                                        Str(
-                                                            "mood",
-                                                        ),
+                                                237..329,
+                                            ),
+                                            (
+                                                Object(
+                                                    [
+                                                        JsonKeyValue {
+                                                            key: InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "type",
+                                                                    ),
+                                                                ],
+                                                                span: 369..375,
+                                                            },
+                                                            value: (
+                                                                InterpolatedString(
+                                                                    InterpolatedString {
+                                                                        parts: [
+                                                                            Str(
+                                                                                "dog",
+                                                                            ),
+                                                                        ],
+                                                                        span: 377..382,
+                                                                    },
+                                                                ),
+                                                                377..382,
+                                                            ),
+                                                            span: 369..382,
+                                                        },
+                                                        JsonKeyValue {
+                                                            key: InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "mood",
+                                                                    ),
+                                                                ],
+                                                                span: 404..410,
+                                                            },
+                                                            value: (
+                                                                InterpolatedString(
+                                                                    InterpolatedString {
+                                                                        parts: [
+                                                                            Str(
+                                                                                "excited",
+                                                                            ),
+                                                                        ],
+                                                                        span: 412..421,
+                                                                    },
+                                                                ),
+                                                                412..421,
+                                                            ),
+                                                            span: 404..421,
+                                                        },
                                                     ],
-                                                },
-                                                value: InterpolatedString(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "lazy",
+                                                ),
+                                                347..439,
+                                            ),
+                                            (
+                                                Object(
+                                                    [
+                                                        JsonKeyValue {
+                                                            key: InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "type",
+                                                                    ),
+                                                                ],
+                                                                span: 479..485,
+                                                            },
+                                                            value: (
+                                                                InterpolatedString(
+                                                                    InterpolatedString {
+                                                                        parts: [
+                                                                            Str(
+                                                                                "hampster",
+                                                                            ),
+                                                                        ],
+                                                                        span: 487..497,
+                                                                    },
+                                                                ),
+                                                                487..497,
                                                             ),
-                                                        ],
-                                                    },
+                                                            span: 479..497,
+                                                        },
+                                                        JsonKeyValue {
+                                                            key: InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "mood",
+                                                                    ),
+                                                                ],
+                                                                span: 519..525,
+                                                            },
+                                                            value: (
+                                                                InterpolatedString(
+                                                                    InterpolatedString {
+                                                                        parts: [
+                                                                            Str(
+                                                                                "lazy",
+                                                                            ),
+                                                                        ],
+                                                                        span: 527..533,
+                                                                    },
+                                                                ),
+                                                                527..533,
+                                                            ),
+                                                            span: 519..533,
+                                                        },
+                                                    ],
                                                 ),
-                                            },
+                                                457..551,
+                                            ),
                                         ],
                                     ),
-                                ],
-                            ),
-                        },
-                    ],
+                                    219..565,
+                                ),
+                                span: 211..565,
+                            },
+                        ],
+                    ),
+                    0..575,
                 ),
             ),
             errs: [],
@@ -862,49 +1059,73 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Object(
-                    [
-                        JsonKeyValue {
-                            key: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "type",
-                                    ),
-                                ],
-                            },
-                            value: InterpolatedString(
-                                InterpolatedString {
+                (
+                    Object(
+                        [
+                            JsonKeyValue {
+                                key: InterpolatedString {
                                     parts: [
                                         Str(
-                                            "cat",
+                                            "type",
                                         ),
                                     ],
+                                    span: 14..20,
                                 },
-                            ),
-                        },
-                        JsonKeyValue {
-                            key: InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "mood",
+                                value: (
+                                    InterpolatedString(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "cat",
+                                                ),
+                                            ],
+                                            span: 22..27,
+                                        },
                                     ),
-                                ],
+                                    22..27,
+                                ),
+                                span: 14..27,
                             },
-                            value: InterpolatedString(
-                                InterpolatedString {
+                            JsonKeyValue {
+                                key: InterpolatedString {
                                     parts: [
                                         Str(
-                                            "annoyed",
+                                            "mood",
                                         ),
                                     ],
+                                    span: 41..47,
                                 },
-                            ),
-                        },
-                    ],
+                                value: (
+                                    InterpolatedString(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "annoyed",
+                                                ),
+                                            ],
+                                            span: 49..58,
+                                        },
+                                    ),
+                                    49..58,
+                                ),
+                                span: 41..58,
+                            },
+                        ],
+                    ),
+                    0..69,
                 ),
             ),
             errs: [
-                found ''}'' at 68..69 expected json-key-value,
+                Unexpected {
+                    span: 68..69,
+                    found: Some(
+                        '}',
+                    ),
+                    expected: [
+                        "json-key-value",
+                    ],
+                    labels: [],
+                },
             ],
         }
         "#,
@@ -923,40 +1144,72 @@ mod tests {
             @r#"
         ParseResult {
             output: Some(
-                Array(
-                    [
-                        InterpolatedString(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "cat",
-                                    ),
-                                ],
-                            },
-                        ),
-                        InterpolatedString(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "dog",
-                                    ),
-                                ],
-                            },
-                        ),
-                        InterpolatedString(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "hampster",
-                                    ),
-                                ],
-                            },
-                        ),
-                    ],
+                (
+                    Array(
+                        [
+                            (
+                                InterpolatedString(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "cat",
+                                            ),
+                                        ],
+                                        span: 18..23,
+                                    },
+                                ),
+                                18..23,
+                            ),
+                            (
+                                InterpolatedString(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "dog",
+                                            ),
+                                        ],
+                                        span: 41..46,
+                                    },
+                                ),
+                                41..46,
+                            ),
+                            (
+                                InterpolatedString(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "hampster",
+                                            ),
+                                        ],
+                                        span: 64..74,
+                                    },
+                                ),
+                                64..74,
+                            ),
+                        ],
+                    ),
+                    0..85,
                 ),
             ),
             errs: [
-                found '']'' at 84..85 expected whitespace, template, ''n'', ''t'', ''f'', json-number, json-string, ''['', or json-object,
+                Unexpected {
+                    span: 84..85,
+                    found: Some(
+                        ']',
+                    ),
+                    expected: [
+                        "'['",
+                        "'f'",
+                        "'n'",
+                        "'t'",
+                        "json-number",
+                        "json-object",
+                        "json-string",
+                        "template",
+                        "whitespace",
+                    ],
+                    labels: [],
+                },
             ],
         }
         "#,
@@ -1,10 +1,11 @@
+use super::error::ParseError;
 use super::primitives::escaped_unicode_parser;
-use super::types::{InterpolatedString, InterpolatedStringPart, KeyValue};
+use super::types::{InterpolatedString, InterpolatedStringPart, KeyValue, Span};
 use super::{primitives::sp_parser, template::template_parser};
 use chumsky::prelude::*;
 
 fn key_string_escaped_char_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     let key_string_escaped_char = just('\\')
         .ignore_then(choice((
             just('\\').to('\\'),
@@ -22,7 +23,7 @@ fn key_string_escaped_char_parser<'a>(
 }
 
 pub fn key_parser<'a>(
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let key_string_content = choice((
         any().filter(char::is_ascii_alphanumeric),
         one_of("_-.[]@$"),
@@ -35,21 +36,24 @@ pub fn key_parser<'a>(
     .labelled("key-string-content");
 
     let key_template_part = template_parser()
-        .map(|t| InterpolatedStringPart::Template(t))
+        .map(InterpolatedStringPart::Template)
         .labelled("key-template");
 
     let key_string = choice((key_string_content, key_template_part))
         .repeated()
         .at_least(1)
         .collect::<Vec<InterpolatedStringPart>>()
-        .map(|k| InterpolatedString { parts: k })
+        .map_with(|k, e| InterpolatedString {
+            parts: k,
+            span: e.span(),
+        })
         .labelled("key-string");
 
     key_string.boxed()
 }
 
 fn value_string_escaped_char_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     let value_string_escaped_char = just('\\')
         .ignore_then(choice((
             just('\\').to('\\'),
@@ -66,7 +70,7 @@ fn value_string_escaped_char_parser<'a>(
 }
 
 pub fn value_parser<'a>(
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let value_string_content = choice((
         none_of("#\n\\{"),
         value_string_escaped_char_parser(),
@@ -82,7 +86,7 @@ pub fn value_parser<'a>(
     .labelled("value-string-content");
 
     let value_template_part = template_parser()
-        .map(|t| InterpolatedStringPart::Template(t))
+        .map(InterpolatedStringPart::Template)
         .labelled("value-template");
 
     let value_string = value_template_part
@@ -90,19 +94,26 @@ pub fn value_parser<'a>(
         .repeated()
         .at_least(1)
         .collect::<Vec<InterpolatedStringPart>>()
-        .map(|v| InterpolatedString { parts: v })
+        .map_with(|v, e| InterpolatedString {
+            parts: v,
+            span: e.span(),
+        })
         .labelled("value-string");
 
     value_string.boxed()
 }
 
-pub fn key_value_parser<'a>(
-) -> impl Parser<'a, &'a str, KeyValue, extra::Err<Rich<'a, char>>> + Clone {
+pub fn key_value_parser<'a>() -> impl Parser<'a, &'a str, KeyValue, extra::Err<ParseError>> + Clone
+{
     let key_value = key_parser()
         .padded_by(sp_parser().repeated())
         .then_ignore(just(':').padded_by(sp_parser().repeated()))
         .then(value_parser())
-        .map(|(key, value)| KeyValue { key, value })
+        .map(|(key, value)| KeyValue {
+            span: Span::new(key.span.start, value.span.end),
+            key,
+            value,
+        })
         .labelled("key-value");
 
     key_value.boxed()
@@ -130,14 +141,20 @@ mod value_string_tests {
                         Template(
                             Template {
                                 expr: Expr {
-                                    variable: VariableName(
-                                        "token",
+                                    variable: (
+                                        VariableName(
+                                            "token",
+                                        ),
+                                        9..14,
                                     ),
                                     filters: [],
+                                    span: 9..14,
                                 },
+                                span: 7..16,
                             },
                         ),
                     ],
+                    span: 0..16,
                 },
             ),
             errs: [],
@@ -162,14 +179,20 @@ mod value_string_tests {
                         Template(
                             Template {
                                 expr: Expr {
-                                    variable: VariableName(
-                                        "token",
+                                    variable: (
+                                        VariableName(
+                                            "token",
+                                        ),
+                                        11..16,
                                     ),
                                     filters: [],
+                                    span: 11..16,
                                 },
+                                span: 7..20,
                             },
                         ),
                     ],
+                    span: 0..20,
                 },
             ),
             errs: [],
@@ -196,6 +219,7 @@ mod value_string_tests {
                             "Bearer { {token}}",
                         ),
                     ],
+                    span: 0..17,
                 },
             ),
             errs: [],
@@ -209,14 +233,25 @@ mod value_string_tests {
         let test_str = r#"Bearer {{}}"#;
         assert_debug_snapshot!(
         value_parser().then(end()).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''{'' at 8..9 expected spacing, expr, or something else,
+                Unexpected {
+                    span: 8..9,
+                    found: Some(
+                        '{',
+                    ),
+                    expected: [
+                        "expr",
+                        "something else",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -225,14 +260,24 @@ mod value_string_tests {
         let test_str = r#"Bearer {{  }}"#;
         assert_debug_snapshot!(
         value_parser().then(end()).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''}'' at 11..12 expected spacing, or expr,
+                Unexpected {
+                    span: 11..12,
+                    found: Some(
+                        '}',
+                    ),
+                    expected: [
+                        "expr",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -241,14 +286,34 @@ mod value_string_tests {
         let test_str = r#"Bearer {{token"#;
         assert_debug_snapshot!(
         value_parser().then(end()).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found end of input at 14..14 expected ascii alphanumeric char or underscore or dash, spacing, ''c'', ''d'', ''h'', ''t'', ''u'', ''f'', ''j'', ''n'', ''r'', ''s'', ''x'', or ''}'',
+                Unexpected {
+                    span: 14..14,
+                    found: None,
+                    expected: [
+                        "'c'",
+                        "'d'",
+                        "'f'",
+                        "'h'",
+                        "'j'",
+                        "'n'",
+                        "'r'",
+                        "'s'",
+                        "'t'",
+                        "'u'",
+                        "'x'",
+                        "'}'",
+                        "ascii alphanumeric char or underscore or dash",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -257,14 +322,25 @@ mod value_string_tests {
         let test_str = r#"Bearer {{"#;
         assert_debug_snapshot!(
         value_parser().then(end()).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''{'' at 8..9 expected spacing, expr, or something else,
+                Unexpected {
+                    span: 8..9,
+                    found: Some(
+                        '{',
+                    ),
+                    expected: [
+                        "expr",
+                        "something else",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -282,6 +358,7 @@ mod value_string_tests {
                             "emoji😀",
                         ),
                     ],
+                    span: 0..14,
                 },
             ),
             errs: [],
@@ -1,38 +1,39 @@
 use chumsky::prelude::*;
 
+use super::error::ParseError;
 use super::{
     key_value::value_parser,
     primitives::{ascii_alphabetic_uppercase_parser, lt_parser, sp_parser},
-    types::{Method, Url},
+    types::{Method, Spanned, Url},
 };
 
 fn method_parser<'a>(
     strict: bool,
-) -> impl Parser<'a, &'a str, Method, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, Method, extra::Err<ParseError>> + Clone {
     if strict {
         ascii_alphabetic_uppercase_parser()
             .repeated()
             .at_least(1)
             .collect::<String>()
-            .map(|method| Method { value: method })
+            .map_with(|method, e| Method {
+                value: method,
+                span: e.span(),
+            })
             .boxed()
     } else {
         text::ident()
             .to_slice()
             .validate(|ident: &str, e, emitter| {
-                match ident.find(|c| !char::is_ascii_uppercase(&c)) {
-                    Some(index) => emitter.emit(Rich::custom(
-                        e.span(),
-                        format!(
-                            "Invalid character '{}'. Method must be ascii uppercase.",
-                            ident.chars().nth(index).unwrap() // We know the character is at the index
-                        ),
-                    )),
-                    None => (),
+                if let Some(index) = ident.find(|c| !char::is_ascii_uppercase(&c)) {
+                    emitter.emit(ParseError::InvalidMethodCase {
+                        span: e.span(),
+                        found: ident.chars().nth(index).unwrap(), // We know the character is at the index
+                    })
                 };
 
                 Method {
                     value: ident.to_string(),
+                    span: e.span(),
                 }
             })
             .boxed()
@@ -41,13 +42,16 @@ fn method_parser<'a>(
 
 pub fn method_line_parser<'a>(
     strict: bool,
-) -> impl Parser<'a, &'a str, (Method, Url), extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, (Method, Spanned<Url>), extra::Err<ParseError>> + Clone {
     if strict {
         let method_line = sp_parser()
             .repeated()
             .ignore_then(method_parser(strict))
-            .padded_by(sp_parser().repeated().at_least(1))
-            .then(value_parser().map(Url::Url))
+            .then_ignore(sp_parser().repeated().at_least(1))
+            .then(value_parser().map(|url| {
+                let span = url.span;
+                (Url::Url(url), span)
+            }))
             .then_ignore(lt_parser());
         method_line.boxed()
     } else {
@@ -61,16 +65,13 @@ pub fn method_line_parser<'a>(
                     .ignore_then(value_parser().or_not())
                     .or_not()
                     .validate(|spaces_and_url, e, emitter| match spaces_and_url {
-                        Some(url) => match url {
-                            Some(u) => Url::Url(u),
-                            None => {
-                                emitter.emit(Rich::custom(e.span(), "missing url"));
-                                Url::Missing
-                            }
-                        },
-                        None => {
-                            emitter.emit(Rich::custom(e.span(), "missing url"));
-                            Url::Missing
+                        Some(Some(u)) => {
+                            let span = u.span;
+                            (Url::Url(u), span)
+                        }
+                        Some(None) | None => {
+                            emitter.emit(ParseError::MissingUrl { span: e.span() });
+                            (Url::Missing, e.span())
                         }
                     }),
             )
@@ -98,12 +99,18 @@ mod method_line_tests {
                 (
                     Method {
                         value: "GET",
+                        span: 0..3,
                     },
-                    Missing,
+                    (
+                        Missing,
+                        3..3,
+                    ),
                 ),
             ),
             errs: [
-                missing url at 3..3,
+                MissingUrl {
+                    span: 3..3,
+                },
             ],
         }
         "#,
@@ -121,20 +128,28 @@ mod method_line_tests {
                 (
                     Method {
                         value: "GeT",
+                        span: 0..3,
                     },
-                    Url(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "https://example.org",
-                                ),
-                            ],
-                        },
+                    (
+                        Url(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "https://example.org",
+                                    ),
+                                ],
+                                span: 4..23,
+                            },
+                        ),
+                        4..23,
                     ),
                 ),
             ),
             errs: [
-                Invalid character 'e'. Method must be ascii uppercase. at 0..3,
+                InvalidMethodCase {
+                    span: 0..3,
+                    found: 'e',
+                },
             ],
         }
         "#,
@@ -152,12 +167,18 @@ mod method_line_tests {
                 (
                     Method {
                         value: "GET",
+                        span: 0..3,
                     },
-                    Missing,
+                    (
+                        Missing,
+                        3..4,
+                    ),
                 ),
             ),
             errs: [
-                missing url at 3..4,
+                MissingUrl {
+                    span: 3..4,
+                },
             ],
         }
         "#,
@@ -175,15 +196,20 @@ mod method_line_tests {
                 (
                     Method {
                         value: "GET",
+                        span: 0..3,
                     },
-                    Url(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "https://example.org/",
-                                ),
-                            ],
-                        },
+                    (
+                        Url(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "https://example.org/",
+                                    ),
+                                ],
+                                span: 4..24,
+                            },
+                        ),
+                        4..24,
                     ),
                 ),
             ),
@@ -1,8 +1,12 @@
 mod body;
-mod expr;
+pub mod cst;
+pub mod error;
+pub mod expr;
 mod filename;
+pub mod formatter;
 mod header;
 mod http_status;
+pub mod incremental;
 mod json;
 mod key_value;
 mod method;
@@ -12,6 +16,7 @@ mod oneline_file;
 mod oneline_hex;
 mod oneline_string;
 mod options;
+#[allow(clippy::module_inception)]
 pub mod parser;
 mod predicate;
 mod primitives;
@@ -25,6 +30,7 @@ mod response_section;
 mod template;
 pub mod types;
 mod variable;
+pub mod visit;
 
 #[cfg(test)]
 mod test;
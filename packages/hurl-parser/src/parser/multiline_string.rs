@@ -1,3 +1,4 @@
+use super::error::ParseError;
 use chumsky::prelude::*;
 use text::ascii::ident;
 
@@ -6,11 +7,9 @@ use super::{
     template::template_parser,
     types::{
         InterpolatedString, InterpolatedStringPart, MultilineString, MultilineStringAttribute,
-        MultilineStringType,
+        MultilineStringType, Spanned,
     },
 };
-type Span = SimpleSpan;
-type Spanned<T> = (T, Span);
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 enum TypeOrAttributeToken<'src> {
@@ -42,12 +41,9 @@ impl std::fmt::Display for TypeOrAttributeToken<'_> {
     }
 }
 
-fn multiline_string_header_tokenizor<'src>() -> impl Parser<
-    'src,
-    &'src str,
-    Vec<Spanned<TypeOrAttributeToken<'src>>>,
-    extra::Err<Rich<'src, char, Span>>,
-> {
+fn multiline_string_header_tokenizor<'src>(
+) -> impl Parser<'src, &'src str, Vec<Spanned<TypeOrAttributeToken<'src>>>, extra::Err<ParseError>>
+{
     let multiline_header_token = choice((
         just("base64").to(TypeOrAttributeToken::Base64),
         just("hex").to(TypeOrAttributeToken::Hex),
@@ -56,7 +52,7 @@ fn multiline_string_header_tokenizor<'src>() -> impl Parser<
         just("graphql").to(TypeOrAttributeToken::Graphql),
         just("escape").to(TypeOrAttributeToken::Escape),
         just("novariable").to(TypeOrAttributeToken::NoVariable),
-        ident().map(|s| TypeOrAttributeToken::Ident(s)),
+        ident().map(TypeOrAttributeToken::Ident),
     ))
     .map_with(|tok, e| (tok, e.span()))
     .boxed();
@@ -68,7 +64,7 @@ fn multiline_string_header_tokenizor<'src>() -> impl Parser<
 }
 
 fn multiline_string_escaped_char_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     let multiline_string_escaped_char = just('\\')
         .ignore_then(choice((
             just('\\').to('\\'),
@@ -88,10 +84,10 @@ fn multiline_string_header_parser<'a>() -> impl Parser<
     'a,
     &'a str,
     (Option<MultilineStringType>, Vec<MultilineStringAttribute>),
-    extra::Err<Rich<'a, char>>,
+    extra::Err<ParseError>,
 > + Clone {
-    let multiline_string_header =
-        multiline_string_header_tokenizor().validate(|tokens: Vec<Spanned<TypeOrAttributeToken>>, _, emitter| {
+    let multiline_string_header = multiline_string_header_tokenizor()
+        .validate(|tokens: Vec<Spanned<TypeOrAttributeToken>>, _, emitter| {
             match tokens.first() {
                 Some((token, span)) => {
                     let multiline_string_type = match token {
@@ -103,15 +99,12 @@ fn multiline_string_header_parser<'a>() -> impl Parser<
                         TypeOrAttributeToken::Escape => None,
                         TypeOrAttributeToken::NoVariable => None,
                         TypeOrAttributeToken::Ident(s) => {
-                                emitter.emit(Rich::custom(
-                                    *span,
-                                    format!(
-                                        "Unknown multiline string type or attribute `{}`.",
-                                        token.to_string()
-                                    ),
-                                ));
+                            emitter.emit(ParseError::UnknownMultilineStringType {
+                                span: *span,
+                                name: token.to_string(),
+                            });
                             Some(MultilineStringType::Unknown(s.to_string()))
-                        },
+                        }
                     };
                     let mut token_iterator = tokens.into_iter();
                     if multiline_string_type.is_some() {
@@ -126,35 +119,32 @@ fn multiline_string_header_parser<'a>() -> impl Parser<
                             | TypeOrAttributeToken::Json
                             | TypeOrAttributeToken::Xml
                             | TypeOrAttributeToken::Graphql => {
-                                emitter.emit(Rich::custom(
+                                emitter.emit(ParseError::MultilineStringTypeAsAttribute {
                                     span,
-                                    format!(
-                                        "Found multiline string type `{}` in the multiline string attribute section.",
-                                        token.to_string()
-                                    ),
-                                ));
+                                    name: token.to_string(),
+                                });
                                 MultilineStringAttribute::Unknown(token.to_string())
-                            },
+                            }
 
                             TypeOrAttributeToken::Ident(_) => {
-                                emitter.emit(Rich::custom(
+                                emitter.emit(ParseError::UnknownMultilineStringAttribute {
                                     span,
-                                    format!(
-                                        "Found unknown multiline string attribute `{}`.",
-                                        token.to_string()
-                                    )
-                                ));
+                                    name: token.to_string(),
+                                });
                                 MultilineStringAttribute::Unknown(token.to_string())
                             }
                             TypeOrAttributeToken::Escape => MultilineStringAttribute::Escape,
-                            TypeOrAttributeToken::NoVariable => MultilineStringAttribute::NoVariable,
+                            TypeOrAttributeToken::NoVariable => {
+                                MultilineStringAttribute::NoVariable
+                            }
                         })
                         .collect();
                     (multiline_string_type, attributes)
                 }
                 None => (None, vec![]),
             }
-        }).boxed();
+        })
+        .boxed();
 
     multiline_string_header.then_ignore(text::newline()).boxed() //TODO off-spec official grammer allows
                                                                  //full lt_parser() but hurl only allows
@@ -162,7 +152,7 @@ fn multiline_string_header_parser<'a>() -> impl Parser<
 }
 
 pub fn multiline_string_parser<'a>(
-) -> impl Parser<'a, &'a str, MultilineString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, MultilineString, extra::Err<ParseError>> + Clone {
     let multiline_string_content = choice((
         none_of("\\{`"),
         multiline_string_escaped_char_parser(),
@@ -185,11 +175,14 @@ pub fn multiline_string_parser<'a>(
         .then(
             choice((
                 multiline_string_content,
-                template_parser().map(|t| InterpolatedStringPart::Template(t)),
+                template_parser().map(InterpolatedStringPart::Template),
             ))
             .repeated()
             .collect::<Vec<InterpolatedStringPart>>()
-            .map(|v| InterpolatedString { parts: v }),
+            .map_with(|v, e| InterpolatedString {
+                parts: v,
+                span: e.span(),
+            }),
         )
         //TODO off-spec there is supposed to be an lt_parser() before the closing
         //multistring quote sequence of ```. The official parser however does not require it
@@ -198,10 +191,11 @@ pub fn multiline_string_parser<'a>(
 
     multiline_string
         .delimited_by(just("```"), just("```"))
-        .map(|((string_type, attributes), content)| MultilineString {
+        .map_with(|((string_type, attributes), content), e| MultilineString {
             r#type: string_type,
             attributes,
             content,
+            span: e.span(),
         })
         .boxed()
 }
@@ -396,7 +390,10 @@ mod multiline_string_header_tests {
                 ),
             ),
             errs: [
-                Unknown multiline string type or attribute `unknown_attribute`. at 0..17,
+                UnknownMultilineStringType {
+                    span: 0..17,
+                    name: "unknown_attribute",
+                },
             ],
         }
         "#,
@@ -457,14 +454,24 @@ mod multiline_string_header_tests {
         let test_str = "json,novariable,escape #this is a comment\n";
         assert_debug_snapshot!(
         multiline_string_header_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''#'' at 23..24 expected spacing, or '','',
+                Unexpected {
+                    span: 23..24,
+                    found: Some(
+                        '#',
+                    ),
+                    expected: [
+                        "','",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -562,8 +569,14 @@ mod multiline_string_header_tests {
                 ),
             ),
             errs: [
-                Unknown multiline string type or attribute `unknown_type`. at 0..12,
-                Found unknown multiline string attribute `unknown`. at 26..33,
+                UnknownMultilineStringType {
+                    span: 0..12,
+                    name: "unknown_type",
+                },
+                UnknownMultilineStringAttribute {
+                    span: 26..33,
+                    name: "unknown",
+                },
             ],
         }
         "#,
@@ -575,14 +588,24 @@ mod multiline_string_header_tests {
         let test_str = "json,novariable, *ignored,escape\n";
         assert_debug_snapshot!(
         multiline_string_header_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''*'' at 17..18 expected identifier, or newline,
+                Unexpected {
+                    span: 17..18,
+                    found: Some(
+                        '*',
+                    ),
+                    expected: [
+                        "identifier",
+                        "newline",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 }
@@ -598,14 +621,24 @@ mod multiline_string_tests {
         let test_str = r#"``````"#;
         assert_debug_snapshot!(
         multiline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''`'' at 3..4 expected identifier, or newline,
+                Unexpected {
+                    span: 3..4,
+                    found: Some(
+                        '`',
+                    ),
+                    expected: [
+                        "identifier",
+                        "newline",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -628,7 +661,9 @@ mod multiline_string_tests {
                                 "\n",
                             ),
                         ],
+                        span: 4..5,
                     },
+                    span: 0..8,
                 },
             ),
             errs: [],
@@ -658,7 +693,9 @@ mod multiline_string_tests {
                                 "    this is some text\n    another line\n    hello world\n                ",
                             ),
                         ],
+                        span: 4..75,
                     },
+                    span: 0..78,
                 },
             ),
             errs: [],
@@ -689,7 +726,9 @@ mod multiline_string_tests {
                                 "5 # test\n# test\n# testing ",
                             ),
                         ],
+                        span: 8..34,
                     },
+                    span: 0..37,
                 },
             ),
             errs: [],
@@ -717,7 +756,9 @@ this `text` has some funny ``quotes``
                                 "this `text` has some funny ``quotes``\n            ",
                             ),
                         ],
+                        span: 4..54,
                     },
+                    span: 0..57,
                 },
             ),
             errs: [],
@@ -751,11 +792,16 @@ this `text` has some funny ``quotes``
                             Template(
                                 Template {
                                     expr: Expr {
-                                        variable: VariableName(
-                                            "key",
+                                        variable: (
+                                            VariableName(
+                                                "key",
+                                            ),
+                                            24..27,
                                         ),
                                         filters: [],
+                                        span: 24..27,
                                     },
+                                    span: 22..29,
                                 },
                             ),
                             Str(
@@ -764,18 +810,25 @@ this `text` has some funny ``quotes``
                             Template(
                                 Template {
                                     expr: Expr {
-                                        variable: VariableName(
-                                            "value",
+                                        variable: (
+                                            VariableName(
+                                                "value",
+                                            ),
+                                            33..38,
                                         ),
                                         filters: [],
+                                        span: 33..38,
                                     },
+                                    span: 31..40,
                                 },
                             ),
                             Str(
                                 "\n    }\n            ",
                             ),
                         ],
+                        span: 8..59,
                     },
+                    span: 0..62,
                 },
             ),
             errs: [],
@@ -791,14 +844,25 @@ this `text` has some funny ``quotes``
 ```"#;
         assert_debug_snapshot!(
         multiline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''{'' at 14..15 expected spacing, expr, or something else,
+                Unexpected {
+                    span: 14..15,
+                    found: Some(
+                        '{',
+                    ),
+                    expected: [
+                        "expr",
+                        "something else",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -809,14 +873,30 @@ this `text` has some funny ``quotes``
 ```"#;
         assert_debug_snapshot!(
         multiline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''{'' at 14..15 expected ''\\'', ''b'', ''f'', ''n'', ''r'', ''t'', ''`'', or ''u'',
+                Unexpected {
+                    span: 14..15,
+                    found: Some(
+                        '{',
+                    ),
+                    expected: [
+                        "'\\'",
+                        "'`'",
+                        "'b'",
+                        "'f'",
+                        "'n'",
+                        "'r'",
+                        "'t'",
+                        "'u'",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -839,7 +919,9 @@ this `text` has some funny ``quotes``
                                 "            escapedchars(`, \\, \u{8}, \u{c}, \r\n, \t)\n            ",
                             ),
                         ],
+                        span: 4..67,
                     },
+                    span: 0..70,
                 },
             ),
             errs: [],
@@ -867,7 +949,9 @@ this `text` has some funny ``quotes``
                                 "            escapedemoji(\u{1}\u{f600})\n            ",
                             ),
                         ],
+                        span: 4..59,
                     },
+                    span: 0..62,
                 },
             ),
             errs: [],
@@ -884,14 +968,24 @@ this `text` has some funny ``quotes``
     ```"#;
         assert_debug_snapshot!(
         multiline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''H'' at 35..36 expected digit, or ''}'',
+                Unexpected {
+                    span: 35..36,
+                    found: Some(
+                        'H',
+                    ),
+                    expected: [
+                        "'}'",
+                        "digit",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -903,14 +997,30 @@ this `text` has some funny ``quotes``
             ```"#;
         assert_debug_snapshot!(
         multiline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''g'' at 35..36 expected ''\\'', ''b'', ''f'', ''n'', ''r'', ''t'', ''`'', or ''u'',
+                Unexpected {
+                    span: 35..36,
+                    found: Some(
+                        'g',
+                    ),
+                    expected: [
+                        "'\\'",
+                        "'`'",
+                        "'b'",
+                        "'f'",
+                        "'n'",
+                        "'r'",
+                        "'t'",
+                        "'u'",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 }
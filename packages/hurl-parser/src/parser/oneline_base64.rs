@@ -1,9 +1,10 @@
 use chumsky::prelude::*;
 
+use super::error::ParseError;
 use super::primitives::{alphanumeric_parser, sp_parser};
 
 pub fn oneline_base64_parser<'a>(
-) -> impl Parser<'a, &'a str, String, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, String, extra::Err<ParseError>> + Clone {
     just("base64,")
         .padded_by(sp_parser().repeated())
         .ignore_then(
@@ -1,3 +1,4 @@
+use super::error::ParseError;
 use super::filename::filename_parser;
 use super::primitives::sp_parser;
 use super::types::InterpolatedString;
@@ -6,7 +7,7 @@ use chumsky::prelude::*;
 //TODO official spec should reuse the "file, filename ;" in file-value since
 //it is also the syntax as oneline-filename
 pub fn oneline_file_parser<'a>(
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     just("file,")
         .padded_by(sp_parser().repeated())
         .ignore_then(filename_parser())
@@ -34,6 +35,7 @@ mod oneline_file_tests {
                             "example.txt",
                         ),
                     ],
+                    span: 5..16,
                 },
             ),
             errs: [],
@@ -56,6 +58,7 @@ mod oneline_file_tests {
                             "example.txt",
                         ),
                     ],
+                    span: 7..18,
                 },
             ),
             errs: [],
@@ -1,8 +1,9 @@
+use super::error::ParseError;
 use super::primitives::sp_parser;
 use chumsky::prelude::*;
 
-pub fn oneline_hex_parser<'a>(
-) -> impl Parser<'a, &'a str, String, extra::Err<Rich<'a, char>>> + Clone {
+pub fn oneline_hex_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<ParseError>> + Clone
+{
     just("hex,")
         .ignore_then(
             text::digits(16)
@@ -1,5 +1,6 @@
 use chumsky::prelude::*;
 
+use super::error::ParseError;
 use super::{
     primitives::escaped_unicode_parser,
     template::template_parser,
@@ -7,7 +8,7 @@ use super::{
 };
 
 fn oneline_string_escaped_char_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     let oneline_string_escaped_char = just('\\')
         .ignore_then(choice((
             just('\\').to('\\'),
@@ -26,7 +27,7 @@ fn oneline_string_escaped_char_parser<'a>(
 }
 
 pub fn oneline_string_parser<'a>(
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let oneline_string_content = choice((
         none_of("#\n\\{`"),
         oneline_string_escaped_char_parser(),
@@ -42,16 +43,19 @@ pub fn oneline_string_parser<'a>(
     .labelled("oneline-string-content");
 
     let oneline_template_part = template_parser()
-        .map(|t| InterpolatedStringPart::Template(t))
+        .map(InterpolatedStringPart::Template)
         .labelled("oneline-string-template");
 
     let oneline_string_parts = choice((oneline_template_part, oneline_string_content))
         .repeated()
-        .collect::<Vec<InterpolatedStringPart>>()
-        .map(|v| InterpolatedString { parts: v });
+        .collect::<Vec<InterpolatedStringPart>>();
 
     oneline_string_parts
         .delimited_by(just("`"), just("`"))
+        .map_with(|v, e| InterpolatedString {
+            parts: v,
+            span: e.span(),
+        })
         .labelled("oneline-string")
         .boxed()
 }
@@ -76,6 +80,7 @@ mod oneline_string_tests {
                             "test",
                         ),
                     ],
+                    span: 0..6,
                 },
             ),
             errs: [],
@@ -94,6 +99,7 @@ mod oneline_string_tests {
             output: Some(
                 InterpolatedString {
                     parts: [],
+                    span: 0..2,
                 },
             ),
             errs: [],
@@ -107,14 +113,23 @@ mod oneline_string_tests {
         let test_str = "`";
         assert_debug_snapshot!(
         oneline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found end of input at 1..1 expected oneline-string-template, oneline-string-content, or ''`'',
+                Unexpected {
+                    span: 1..1,
+                    found: None,
+                    expected: [
+                        "'`'",
+                        "oneline-string-content",
+                        "oneline-string-template",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -123,14 +138,26 @@ mod oneline_string_tests {
         let test_str = "`hello";
         assert_debug_snapshot!(
         oneline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found end of input at 6..6 expected something else, oneline-string-escaped-char, ''{'', oneline-string-template, oneline-string-content, or ''`'',
+                Unexpected {
+                    span: 6..6,
+                    found: None,
+                    expected: [
+                        "'`'",
+                        "'{'",
+                        "oneline-string-content",
+                        "oneline-string-escaped-char",
+                        "oneline-string-template",
+                        "something else",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -148,6 +175,7 @@ mod oneline_string_tests {
                             "`I'm in backtick quotes`",
                         ),
                     ],
+                    span: 0..28,
                 },
             ),
             errs: [],
@@ -169,14 +197,20 @@ mod oneline_string_tests {
                         Template(
                             Template {
                                 expr: Expr {
-                                    variable: VariableName(
-                                        "seperator",
+                                    variable: (
+                                        VariableName(
+                                            "seperator",
+                                        ),
+                                        3..12,
                                     ),
                                     filters: [],
+                                    span: 3..12,
                                 },
+                                span: 1..14,
                             },
                         ),
                     ],
+                    span: 0..15,
                 },
             ),
             errs: [],
@@ -199,6 +233,7 @@ mod oneline_string_tests {
                             "escapedchars(`, #, \\, \u{8}, \u{c}, \r\n, \t)",
                         ),
                     ],
+                    span: 0..44,
                 },
             ),
             errs: [],
@@ -221,6 +256,7 @@ mod oneline_string_tests {
                             "escapedemoji(\u{1}\u{f600})",
                         ),
                     ],
+                    span: 0..32,
                 },
             ),
             errs: [],
@@ -235,14 +271,24 @@ mod oneline_string_tests {
         let test_str = r#"`escapedemoji(\u{FFFH})`"#;
         assert_debug_snapshot!(
         oneline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''H'' at 20..21 expected digit, or ''}'',
+                Unexpected {
+                    span: 20..21,
+                    found: Some(
+                        'H',
+                    ),
+                    expected: [
+                        "'}'",
+                        "digit",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -252,14 +298,31 @@ mod oneline_string_tests {
         let test_str = r#"`invalidescapechar:\g`"#;
         assert_debug_snapshot!(
         oneline_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''g'' at 20..21 expected ''\\'', ''#'', ''b'', ''f'', ''`'', ''n'', ''r'', ''t'', or ''u'',
+                Unexpected {
+                    span: 20..21,
+                    found: Some(
+                        'g',
+                    ),
+                    expected: [
+                        "'#'",
+                        "'\\'",
+                        "'`'",
+                        "'b'",
+                        "'f'",
+                        "'n'",
+                        "'r'",
+                        "'t'",
+                        "'u'",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 }
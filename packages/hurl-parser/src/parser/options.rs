@@ -1,3 +1,4 @@
+use super::error::ParseError;
 use crate::parser::expr::variable_name_parser;
 use crate::parser::primitives::escaped_unicode_parser;
 
@@ -7,63 +8,55 @@ use super::primitives::{lt_parser, sp_parser};
 use super::template::template_parser;
 use super::types::{
     BooleanOption, Duration, DurationOption, DurationUnit, IntegerOption, InterpolatedString,
-    InterpolatedStringPart, RequestOption, VariableDefinitionOption,
+    InterpolatedStringPart, RequestOption, Spanned, VariableDefinitionOption,
 };
 use super::variable::variable_value_parser;
 use chumsky::prelude::*;
 
 fn integer_option_parser<'a>(
     option_identifier: &'a str,
-) -> impl Parser<'a, &'a str, IntegerOption, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, IntegerOption, extra::Err<ParseError>> + Clone {
     let integer_option = choice((
-        text::int(10).to_slice().validate(|v: &str, e, emitter| {
-            match v.parse::<u64>() {
+        text::int(10)
+            .to_slice()
+            .validate(|v: &str, e, emitter| match v.parse::<u64>() {
                 Ok(n) => IntegerOption::Literal(n),
                 Err(_) => {
-                    emitter.emit(Rich::custom(
-                        e.span(),
-                        format!(
-                            "The integer value is larger than {} and is not valid for 64bit version of hurl",
-                            u64::MAX
-                        ),
-                    ));
+                    emitter.emit(ParseError::IntegerTooLarge { span: e.span() });
                     IntegerOption::BigInteger(v.to_string())
                 }
-            }
-        }),
-        template_parser().map(IntegerOption::Template)
+            }),
+        template_parser().map(IntegerOption::Template),
     ));
 
     let option = just(option_identifier)
         .padded_by(sp_parser().repeated())
         .then_ignore(just(":").padded_by(sp_parser().repeated()))
         .then(integer_option)
-        .then_ignore(lt_parser())
         .map(|(_, o)| o);
     option.boxed()
 }
 
 fn boolean_option_parser<'a>(
     option_identifier: &'a str,
-) -> impl Parser<'a, &'a str, BooleanOption, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, BooleanOption, extra::Err<ParseError>> + Clone {
     let boolean_option = choice((
         just("false").to(BooleanOption::Literal(false)),
         just("true").to(BooleanOption::Literal(true)),
-        template_parser().map(|t| BooleanOption::Template(t)),
+        template_parser().map(BooleanOption::Template),
     ));
 
     let option = just(option_identifier)
         .padded_by(sp_parser().repeated())
         .then_ignore(just(":").padded_by(sp_parser().repeated()))
         .then(boolean_option)
-        .then_ignore(lt_parser())
         .map(|(_, o)| o);
     option.boxed()
 }
 
 fn duration_option_parser<'a>(
     option_identifier: &'a str,
-) -> impl Parser<'a, &'a str, DurationOption, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, DurationOption, extra::Err<ParseError>> + Clone {
     let duration_literal = text::int(10)
         .to_slice()
         .then(
@@ -74,11 +67,12 @@ fn duration_option_parser<'a>(
             ))
             .or_not(),
         )
-        .map(|(duration, unit): (&str, _)| {
+        .map_with(|(duration, unit): (&str, _), e| {
             DurationOption::Literal(Duration {
                 //TODO handle parsing errors
                 duration: duration.parse::<u64>().unwrap(),
                 unit,
+                span: e.span(),
             })
         });
 
@@ -91,19 +85,17 @@ fn duration_option_parser<'a>(
         .padded_by(sp_parser().repeated())
         .then_ignore(just(":").padded_by(sp_parser().repeated()))
         .then(duration_option)
-        .then_ignore(lt_parser())
         .map(|(_, o)| o);
     option.boxed()
 }
 
 fn value_string_option_parser<'a>(
     option_identifier: &'a str,
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let option = just(option_identifier)
         .padded_by(sp_parser().repeated())
         .then_ignore(just(":").padded_by(sp_parser().repeated()))
         .then(value_parser())
-        .then_ignore(lt_parser())
         .map(|(_, o)| o);
 
     option.boxed()
@@ -111,19 +103,18 @@ fn value_string_option_parser<'a>(
 
 fn filename_option_parser<'a>(
     option_identifier: &'a str,
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let option = just(option_identifier)
         .padded_by(sp_parser().repeated())
         .then_ignore(just(":").padded_by(sp_parser().repeated()))
         .then(filename_parser())
-        .then_ignore(lt_parser())
         .map(|(_, o)| o);
 
     option.boxed()
 }
 
 fn filename_password_string_escaped_char_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     let filename_password_string_escaped_char = just('\\')
         .ignore_then(choice((
             just('\\').to('\\'),
@@ -145,7 +136,7 @@ fn filename_password_string_escaped_char_parser<'a>(
 
 fn filename_password_option_parser<'a>(
     option_identifier: &'a str,
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let filename_password_str_part = choice((
         none_of("#;{} \n\\"),
         filename_password_string_escaped_char_parser(),
@@ -157,45 +148,51 @@ fn filename_password_option_parser<'a>(
     .labelled("filename_password_str");
 
     let filename_password_template_part = template_parser()
-        .map(|t| InterpolatedStringPart::Template(t))
+        .map(InterpolatedStringPart::Template)
         .labelled("filename_password_template");
 
     let filename_password = choice((filename_password_str_part, filename_password_template_part))
         .repeated()
         .at_least(1)
         .collect::<Vec<InterpolatedStringPart>>()
-        .map(|k| InterpolatedString { parts: k })
+        .map_with(|k, e| InterpolatedString {
+            parts: k,
+            span: e.span(),
+        })
         .labelled("filename_password");
 
     let option = just(option_identifier)
         .padded_by(sp_parser().repeated())
         .then_ignore(just(":").padded_by(sp_parser().repeated()))
         .then(filename_password)
-        .then_ignore(lt_parser())
         .map(|(_, o)| o);
 
     option.boxed()
 }
 
 fn variable_option_parser<'a>(
-) -> impl Parser<'a, &'a str, VariableDefinitionOption, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, VariableDefinitionOption, extra::Err<ParseError>> + Clone {
     let variable_definition = variable_name_parser()
+        .map_with(|name, e| (name, e.span()))
         .then_ignore(just("=").padded_by(sp_parser().repeated()))
-        .then(variable_value_parser())
-        .map(|(name, value)| VariableDefinitionOption { name, value });
+        .then(variable_value_parser().map_with(|value, e| (value, e.span())))
+        .map_with(|(name, value), e| VariableDefinitionOption {
+            name,
+            value,
+            span: e.span(),
+        });
 
     let option = just("variable")
         .padded_by(sp_parser().repeated())
         .then_ignore(just(":").padded_by(sp_parser().repeated()))
         .then(variable_definition)
-        .then_ignore(lt_parser())
         .map(|(_, o)| o);
 
     option.boxed()
 }
 
 pub fn option_parser<'a>(
-) -> impl Parser<'a, &'a str, RequestOption, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, Spanned<RequestOption>, extra::Err<ParseError>> + Clone {
     //TODO a tokenizer would likely make this parsing more efficient
     let boolean_request_option = choice((
         boolean_option_parser("compressed").map(RequestOption::Compressed),
@@ -252,23 +249,29 @@ pub fn option_parser<'a>(
 
     let variable_request_option = variable_option_parser().map(RequestOption::Variable);
 
-    let option = choice((
-        boolean_request_option,
-        duration_request_option,
-        integer_request_option,
-        value_string_request_option,
-        filename_request_option,
-        filename_password_request_option,
-        variable_request_option,
-    ))
-    .labelled("option");
+    let option = sp_parser().repeated().ignore_then(
+        choice((
+            boolean_request_option,
+            duration_request_option,
+            integer_request_option,
+            value_string_request_option,
+            filename_request_option,
+            filename_password_request_option,
+            variable_request_option,
+        ))
+        .map_with(|option, e| (option, e.span()))
+        .then_ignore(lt_parser())
+        .labelled("option"),
+    );
 
     option.boxed()
 }
 
 pub fn options_parser<'a>(
-) -> impl Parser<'a, &'a str, Vec<RequestOption>, extra::Err<Rich<'a, char>>> + Clone {
-    let options = option_parser().repeated().collect::<Vec<RequestOption>>();
+) -> impl Parser<'a, &'a str, Vec<Spanned<RequestOption>>, extra::Err<ParseError>> + Clone {
+    let options = option_parser()
+        .repeated()
+        .collect::<Vec<Spanned<RequestOption>>>();
     options.boxed()
 }
 
@@ -287,27 +290,48 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    Variable(
-                        VariableDefinitionOption {
-                            name: "host",
-                            value: String(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "example.net",
-                                        ),
-                                    ],
-                                },
-                            ),
-                        },
-                    ),
-                    Variable(
-                        VariableDefinitionOption {
-                            name: "id",
-                            value: Integer(
-                                1234,
-                            ),
-                        },
+                    (
+                        Variable(
+                            VariableDefinitionOption {
+                                name: (
+                                    "host",
+                                    10..14,
+                                ),
+                                value: (
+                                    String(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "example.net",
+                                                ),
+                                            ],
+                                            span: 15..26,
+                                        },
+                                    ),
+                                    15..26,
+                                ),
+                                span: 10..26,
+                            },
+                        ),
+                        0..26,
+                    ),
+                    (
+                        Variable(
+                            VariableDefinitionOption {
+                                name: (
+                                    "id",
+                                    37..39,
+                                ),
+                                value: (
+                                    Integer(
+                                        1234,
+                                    ),
+                                    40..44,
+                                ),
+                                span: 37..44,
+                            },
+                        ),
+                        27..44,
                     ),
                 ],
             ),
@@ -325,19 +349,30 @@ mod option_tests {
             @r#"
         ParseResult {
             output: Some(
-                Variable(
-                    VariableDefinitionOption {
-                        name: "host",
-                        value: String(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "example.net",
-                                    ),
-                                ],
-                            },
-                        ),
-                    },
+                (
+                    Variable(
+                        VariableDefinitionOption {
+                            name: (
+                                "host",
+                                14..18,
+                            ),
+                            value: (
+                                String(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "example.net",
+                                            ),
+                                        ],
+                                        span: 23..34,
+                                    },
+                                ),
+                                23..34,
+                            ),
+                            span: 14..34,
+                        },
+                    ),
+                    1..34,
                 ),
             ),
             errs: [],
@@ -355,85 +390,133 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    Compressed(
-                        Literal(
-                            true,
+                    (
+                        Compressed(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        0..16,
                     ),
-                    Location(
-                        Literal(
-                            true,
+                    (
+                        Location(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        17..31,
                     ),
-                    LocationTrusted(
-                        Literal(
-                            true,
+                    (
+                        LocationTrusted(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        32..54,
                     ),
-                    Http10(
-                        Literal(
-                            false,
+                    (
+                        Http10(
+                            Literal(
+                                false,
+                            ),
                         ),
+                        55..69,
                     ),
-                    Http11(
-                        Literal(
-                            false,
+                    (
+                        Http11(
+                            Literal(
+                                false,
+                            ),
                         ),
+                        70..84,
                     ),
-                    Http2(
-                        Literal(
-                            false,
+                    (
+                        Http2(
+                            Literal(
+                                false,
+                            ),
                         ),
+                        85..97,
                     ),
-                    Http3(
-                        Literal(
-                            true,
+                    (
+                        Http3(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        98..109,
                     ),
-                    Insecure(
-                        Literal(
-                            false,
+                    (
+                        Insecure(
+                            Literal(
+                                false,
+                            ),
                         ),
+                        110..125,
                     ),
-                    Ipv4(
-                        Literal(
-                            false,
+                    (
+                        Ipv4(
+                            Literal(
+                                false,
+                            ),
                         ),
+                        126..137,
                     ),
-                    Ipv6(
-                        Literal(
-                            true,
+                    (
+                        Ipv6(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        138..148,
                     ),
-                    Netrc(
-                        Literal(
-                            true,
+                    (
+                        Netrc(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        149..160,
                     ),
-                    NetrcOptional(
-                        Literal(
-                            true,
+                    (
+                        NetrcOptional(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        161..181,
                     ),
-                    PathAsIs(
-                        Literal(
-                            true,
+                    (
+                        PathAsIs(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        182..198,
                     ),
-                    Skip(
-                        Literal(
-                            false,
+                    (
+                        Skip(
+                            Literal(
+                                false,
+                            ),
                         ),
+                        199..210,
                     ),
-                    Verbose(
-                        Literal(
-                            true,
+                    (
+                        Verbose(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        211..224,
                     ),
-                    VeryVerbose(
-                        Literal(
-                            true,
+                    (
+                        VeryVerbose(
+                            Literal(
+                                true,
+                            ),
                         ),
+                        225..243,
                     ),
                 ],
             ),
@@ -453,41 +536,65 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    ConnectTimeout(
-                        Template(
-                            Template {
-                                expr: Expr {
-                                    variable: VariableName(
-                                        "connectTimeout",
-                                    ),
-                                    filters: [],
+                    (
+                        ConnectTimeout(
+                            Template(
+                                Template {
+                                    expr: Expr {
+                                        variable: (
+                                            VariableName(
+                                                "connectTimeout",
+                                            ),
+                                            19..33,
+                                        ),
+                                        filters: [],
+                                        span: 19..33,
+                                    },
+                                    span: 17..35,
                                 },
-                            },
+                            ),
                         ),
-                    ),
-                    Delay(
-                        Template(
-                            Template {
-                                expr: Expr {
-                                    variable: VariableName(
-                                        "delay",
-                                    ),
-                                    filters: [],
+                        0..35,
+                    ),
+                    (
+                        Delay(
+                            Template(
+                                Template {
+                                    expr: Expr {
+                                        variable: (
+                                            VariableName(
+                                                "delay",
+                                            ),
+                                            45..50,
+                                        ),
+                                        filters: [],
+                                        span: 45..50,
+                                    },
+                                    span: 43..52,
                                 },
-                            },
+                            ),
                         ),
-                    ),
-                    RetryInterval(
-                        Template(
-                            Template {
-                                expr: Expr {
-                                    variable: VariableName(
-                                        "retryInterval",
-                                    ),
-                                    filters: [],
+                        36..52,
+                    ),
+                    (
+                        RetryInterval(
+                            Template(
+                                Template {
+                                    expr: Expr {
+                                        variable: (
+                                            VariableName(
+                                                "retryInterval",
+                                            ),
+                                            71..84,
+                                        ),
+                                        filters: [],
+                                        span: 71..84,
+                                    },
+                                    span: 69..86,
                                 },
-                            },
+                            ),
                         ),
+                        53..86,
                     ),
                 ],
             ),
@@ -506,29 +613,41 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    ConnectTimeout(
-                        Literal(
-                            Duration {
-                                duration: 5,
-                                unit: None,
-                            },
+                    (
+                        ConnectTimeout(
+                            Literal(
+                                Duration {
+                                    duration: 5,
+                                    unit: None,
+                                    span: 17..18,
+                                },
+                            ),
                         ),
-                    ),
-                    Delay(
-                        Literal(
-                            Duration {
-                                duration: 4,
-                                unit: None,
-                            },
+                        0..18,
+                    ),
+                    (
+                        Delay(
+                            Literal(
+                                Duration {
+                                    duration: 4,
+                                    unit: None,
+                                    span: 26..27,
+                                },
+                            ),
                         ),
-                    ),
-                    RetryInterval(
-                        Literal(
-                            Duration {
-                                duration: 500,
-                                unit: None,
-                            },
+                        19..27,
+                    ),
+                    (
+                        RetryInterval(
+                            Literal(
+                                Duration {
+                                    duration: 500,
+                                    unit: None,
+                                    span: 44..47,
+                                },
+                            ),
                         ),
+                        28..47,
                     ),
                 ],
             ),
@@ -547,35 +666,47 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    ConnectTimeout(
-                        Literal(
-                            Duration {
-                                duration: 5,
-                                unit: Some(
-                                    Second,
-                                ),
-                            },
+                    (
+                        ConnectTimeout(
+                            Literal(
+                                Duration {
+                                    duration: 5,
+                                    unit: Some(
+                                        Second,
+                                    ),
+                                    span: 17..19,
+                                },
+                            ),
                         ),
-                    ),
-                    Delay(
-                        Literal(
-                            Duration {
-                                duration: 4,
-                                unit: Some(
-                                    Second,
-                                ),
-                            },
+                        0..19,
+                    ),
+                    (
+                        Delay(
+                            Literal(
+                                Duration {
+                                    duration: 4,
+                                    unit: Some(
+                                        Second,
+                                    ),
+                                    span: 27..29,
+                                },
+                            ),
                         ),
-                    ),
-                    RetryInterval(
-                        Literal(
-                            Duration {
-                                duration: 500,
-                                unit: Some(
-                                    Second,
-                                ),
-                            },
+                        20..29,
+                    ),
+                    (
+                        RetryInterval(
+                            Literal(
+                                Duration {
+                                    duration: 500,
+                                    unit: Some(
+                                        Second,
+                                    ),
+                                    span: 46..50,
+                                },
+                            ),
                         ),
+                        30..50,
                     ),
                 ],
             ),
@@ -594,35 +725,47 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    ConnectTimeout(
-                        Literal(
-                            Duration {
-                                duration: 5,
-                                unit: Some(
-                                    Millisecond,
-                                ),
-                            },
+                    (
+                        ConnectTimeout(
+                            Literal(
+                                Duration {
+                                    duration: 5,
+                                    unit: Some(
+                                        Millisecond,
+                                    ),
+                                    span: 17..20,
+                                },
+                            ),
                         ),
-                    ),
-                    Delay(
-                        Literal(
-                            Duration {
-                                duration: 4,
-                                unit: Some(
-                                    Millisecond,
-                                ),
-                            },
+                        0..20,
+                    ),
+                    (
+                        Delay(
+                            Literal(
+                                Duration {
+                                    duration: 4,
+                                    unit: Some(
+                                        Millisecond,
+                                    ),
+                                    span: 28..31,
+                                },
+                            ),
                         ),
-                    ),
-                    RetryInterval(
-                        Literal(
-                            Duration {
-                                duration: 500,
-                                unit: Some(
-                                    Millisecond,
-                                ),
-                            },
+                        21..31,
+                    ),
+                    (
+                        RetryInterval(
+                            Literal(
+                                Duration {
+                                    duration: 500,
+                                    unit: Some(
+                                        Millisecond,
+                                    ),
+                                    span: 48..53,
+                                },
+                            ),
                         ),
+                        32..53,
                     ),
                 ],
             ),
@@ -638,14 +781,26 @@ mod option_tests {
         let test_str = "connect-timeout: 5 ms";
         assert_debug_snapshot!(
         option_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''m'' at 19..20 expected spacing, comment, newline, or end of input,
+                Unexpected {
+                    span: 19..20,
+                    found: Some(
+                        'm',
+                    ),
+                    expected: [
+                        "comment",
+                        "end of file",
+                        "newline",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -658,35 +813,47 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    ConnectTimeout(
-                        Literal(
-                            Duration {
-                                duration: 5,
-                                unit: Some(
-                                    Minute,
-                                ),
-                            },
+                    (
+                        ConnectTimeout(
+                            Literal(
+                                Duration {
+                                    duration: 5,
+                                    unit: Some(
+                                        Minute,
+                                    ),
+                                    span: 17..19,
+                                },
+                            ),
                         ),
-                    ),
-                    Delay(
-                        Literal(
-                            Duration {
-                                duration: 4,
-                                unit: Some(
-                                    Minute,
-                                ),
-                            },
+                        0..19,
+                    ),
+                    (
+                        Delay(
+                            Literal(
+                                Duration {
+                                    duration: 4,
+                                    unit: Some(
+                                        Minute,
+                                    ),
+                                    span: 27..29,
+                                },
+                            ),
                         ),
-                    ),
-                    RetryInterval(
-                        Literal(
-                            Duration {
-                                duration: 500,
-                                unit: Some(
-                                    Minute,
-                                ),
-                            },
+                        20..29,
+                    ),
+                    (
+                        RetryInterval(
+                            Literal(
+                                Duration {
+                                    duration: 500,
+                                    unit: Some(
+                                        Minute,
+                                    ),
+                                    span: 46..50,
+                                },
+                            ),
                         ),
+                        30..50,
                     ),
                 ],
             ),
@@ -704,13 +871,17 @@ mod option_tests {
             @r"
         ParseResult {
             output: Some(
-                RetryInterval(
-                    Literal(
-                        Duration {
-                            duration: 500,
-                            unit: None,
-                        },
+                (
+                    RetryInterval(
+                        Literal(
+                            Duration {
+                                duration: 500,
+                                unit: None,
+                                span: 16..19,
+                            },
+                        ),
                     ),
+                    0..19,
                 ),
             ),
             errs: [],
@@ -727,13 +898,17 @@ mod option_tests {
             @r"
         ParseResult {
             output: Some(
-                Delay(
-                    Literal(
-                        Duration {
-                            duration: 4,
-                            unit: None,
-                        },
+                (
+                    Delay(
+                        Literal(
+                            Duration {
+                                duration: 4,
+                                unit: None,
+                                span: 7..8,
+                            },
+                        ),
                     ),
+                    0..8,
                 ),
             ),
             errs: [],
@@ -751,25 +926,37 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    LimitRate(
-                        Literal(
-                            59,
+                    (
+                        LimitRate(
+                            Literal(
+                                59,
+                            ),
                         ),
+                        0..14,
                     ),
-                    MaxRedirs(
-                        Literal(
-                            109,
+                    (
+                        MaxRedirs(
+                            Literal(
+                                109,
+                            ),
                         ),
+                        15..30,
                     ),
-                    Repeat(
-                        Literal(
-                            10,
+                    (
+                        Repeat(
+                            Literal(
+                                10,
+                            ),
                         ),
+                        31..41,
                     ),
-                    Retry(
-                        Literal(
-                            5,
+                    (
+                        Retry(
+                            Literal(
+                                5,
+                            ),
                         ),
+                        42..50,
                     ),
                 ],
             ),
@@ -788,53 +975,85 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    LimitRate(
-                        Template(
-                            Template {
-                                expr: Expr {
-                                    variable: VariableName(
-                                        "limit_retry",
-                                    ),
-                                    filters: [],
+                    (
+                        LimitRate(
+                            Template(
+                                Template {
+                                    expr: Expr {
+                                        variable: (
+                                            VariableName(
+                                                "limit_retry",
+                                            ),
+                                            14..25,
+                                        ),
+                                        filters: [],
+                                        span: 14..25,
+                                    },
+                                    span: 12..27,
                                 },
-                            },
+                            ),
                         ),
-                    ),
-                    MaxRedirs(
-                        Template(
-                            Template {
-                                expr: Expr {
-                                    variable: VariableName(
-                                        "max_retries",
-                                    ),
-                                    filters: [],
+                        0..27,
+                    ),
+                    (
+                        MaxRedirs(
+                            Template(
+                                Template {
+                                    expr: Expr {
+                                        variable: (
+                                            VariableName(
+                                                "max_retries",
+                                            ),
+                                            42..53,
+                                        ),
+                                        filters: [],
+                                        span: 42..53,
+                                    },
+                                    span: 40..55,
                                 },
-                            },
+                            ),
                         ),
-                    ),
-                    Repeat(
-                        Template(
-                            Template {
-                                expr: Expr {
-                                    variable: VariableName(
-                                        "repeat",
-                                    ),
-                                    filters: [],
+                        28..55,
+                    ),
+                    (
+                        Repeat(
+                            Template(
+                                Template {
+                                    expr: Expr {
+                                        variable: (
+                                            VariableName(
+                                                "repeat",
+                                            ),
+                                            66..72,
+                                        ),
+                                        filters: [],
+                                        span: 66..72,
+                                    },
+                                    span: 64..74,
                                 },
-                            },
+                            ),
                         ),
-                    ),
-                    Retry(
-                        Template(
-                            Template {
-                                expr: Expr {
-                                    variable: VariableName(
-                                        "retry",
-                                    ),
-                                    filters: [],
+                        56..74,
+                    ),
+                    (
+                        Retry(
+                            Template(
+                                Template {
+                                    expr: Expr {
+                                        variable: (
+                                            VariableName(
+                                                "retry",
+                                            ),
+                                            84..89,
+                                        ),
+                                        filters: [],
+                                        span: 84..89,
+                                    },
+                                    span: 82..91,
                                 },
-                            },
+                            ),
                         ),
+                        75..91,
                     ),
                 ],
             ),
@@ -850,15 +1069,25 @@ mod option_tests {
         //cannot be a mix of both
         let test_str = "limit-rate: 5{{magnitude}}";
         assert_debug_snapshot!(
-        option_parser().then_ignore(end()).parse(&test_str),
-            @r"
+        option_parser().then_ignore(end()).parse(test_str),
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''{'' at 13..14 expected digit, or line terminator,
+                Unexpected {
+                    span: 13..14,
+                    found: Some(
+                        '{',
+                    ),
+                    expected: [
+                        "digit",
+                        "line terminator",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -870,10 +1099,13 @@ mod option_tests {
             @r"
         ParseResult {
             output: Some(
-                LimitRate(
-                    Literal(
-                        18446744073709551615,
+                (
+                    LimitRate(
+                        Literal(
+                            18446744073709551615,
+                        ),
                     ),
+                    0..32,
                 ),
             ),
             errs: [],
@@ -891,14 +1123,19 @@ mod option_tests {
             @r#"
         ParseResult {
             output: Some(
-                LimitRate(
-                    BigInteger(
-                        "18446744073709551616",
+                (
+                    LimitRate(
+                        BigInteger(
+                            "18446744073709551616",
+                        ),
                     ),
+                    0..32,
                 ),
             ),
             errs: [
-                The integer value is larger than 18446744073709551615 and is not valid for 64bit version of hurl at 12..32,
+                IntegerTooLarge {
+                    span: 12..32,
+                },
             ],
         }
         "#,
@@ -913,10 +1150,13 @@ mod option_tests {
             @r"
         ParseResult {
             output: Some(
-                LimitRate(
-                    Literal(
-                        4294967295,
+                (
+                    LimitRate(
+                        Literal(
+                            4294967295,
+                        ),
                     ),
+                    0..22,
                 ),
             ),
             errs: [],
@@ -934,68 +1174,96 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    AwsSigv4(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "aws:amz:eu-central-1:sts",
-                                ),
-                            ],
-                        },
+                    (
+                        AwsSigv4(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "aws:amz:eu-central-1:sts",
+                                    ),
+                                ],
+                                span: 11..35,
+                            },
+                        ),
+                        0..35,
                     ),
-                    ConnectTo(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "example.com:8000:127.0.0.1:8080",
-                                ),
-                            ],
-                        },
+                    (
+                        ConnectTo(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "example.com:8000:127.0.0.1:8080",
+                                    ),
+                                ],
+                                span: 48..79,
+                            },
+                        ),
+                        36..79,
                     ),
-                    NetrcFile(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "~/.netrc",
-                                ),
-                            ],
-                        },
+                    (
+                        NetrcFile(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "~/.netrc",
+                                    ),
+                                ],
+                                span: 92..100,
+                            },
+                        ),
+                        80..100,
                     ),
-                    Proxy(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "example.proxy:8050",
-                                ),
-                            ],
-                        },
+                    (
+                        Proxy(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "example.proxy:8050",
+                                    ),
+                                ],
+                                span: 108..126,
+                            },
+                        ),
+                        101..126,
                     ),
-                    Resolve(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "example.com:8000:127.0.0.1",
-                                ),
-                            ],
-                        },
+                    (
+                        Resolve(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "example.com:8000:127.0.0.1",
+                                    ),
+                                ],
+                                span: 136..162,
+                            },
+                        ),
+                        127..162,
                     ),
-                    UnixSocket(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "sock",
-                                ),
-                            ],
-                        },
+                    (
+                        UnixSocket(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "sock",
+                                    ),
+                                ],
+                                span: 176..180,
+                            },
+                        ),
+                        163..180,
                     ),
-                    User(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "joe=secret",
-                                ),
-                            ],
-                        },
+                    (
+                        User(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "joe=secret",
+                                    ),
+                                ],
+                                span: 187..197,
+                            },
+                        ),
+                        181..197,
                     ),
                 ],
             ),
@@ -1014,165 +1282,243 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    AwsSigv4(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "aws",
-                                            ),
-                                            filters: [],
+                    (
+                        AwsSigv4(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "aws",
+                                                    ),
+                                                    13..16,
+                                                ),
+                                                filters: [],
+                                                span: 13..16,
+                                            },
+                                            span: 11..18,
                                         },
-                                    },
-                                ),
-                            ],
-                        },
+                                    ),
+                                ],
+                                span: 11..18,
+                            },
+                        ),
+                        0..18,
                     ),
-                    ConnectTo(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "host",
-                                            ),
-                                            filters: [],
+                    (
+                        ConnectTo(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "host",
+                                                    ),
+                                                    33..37,
+                                                ),
+                                                filters: [],
+                                                span: 33..37,
+                                            },
+                                            span: 31..39,
                                         },
-                                    },
-                                ),
-                                Str(
-                                    ":",
-                                ),
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "port",
-                                            ),
-                                            filters: [],
+                                    ),
+                                    Str(
+                                        ":",
+                                    ),
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "port",
+                                                    ),
+                                                    42..46,
+                                                ),
+                                                filters: [],
+                                                span: 42..46,
+                                            },
+                                            span: 40..48,
                                         },
-                                    },
-                                ),
-                                Str(
-                                    ":127.0.0.1:8080",
-                                ),
-                            ],
-                        },
+                                    ),
+                                    Str(
+                                        ":127.0.0.1:8080",
+                                    ),
+                                ],
+                                span: 31..63,
+                            },
+                        ),
+                        19..63,
                     ),
-                    NetrcFile(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "filepath",
-                                            ),
-                                            filters: [],
+                    (
+                        NetrcFile(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "filepath",
+                                                    ),
+                                                    78..86,
+                                                ),
+                                                filters: [],
+                                                span: 78..86,
+                                            },
+                                            span: 76..88,
                                         },
-                                    },
-                                ),
-                            ],
-                        },
+                                    ),
+                                ],
+                                span: 76..88,
+                            },
+                        ),
+                        64..88,
                     ),
-                    Proxy(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "proxyhost",
-                                            ),
-                                            filters: [],
+                    (
+                        Proxy(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "proxyhost",
+                                                    ),
+                                                    98..107,
+                                                ),
+                                                filters: [],
+                                                span: 98..107,
+                                            },
+                                            span: 96..109,
                                         },
-                                    },
-                                ),
-                                Str(
-                                    ":8050",
-                                ),
-                            ],
-                        },
+                                    ),
+                                    Str(
+                                        ":8050",
+                                    ),
+                                ],
+                                span: 96..114,
+                            },
+                        ),
+                        89..114,
                     ),
-                    Resolve(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "host",
-                                            ),
-                                            filters: [],
+                    (
+                        Resolve(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "host",
+                                                    ),
+                                                    126..130,
+                                                ),
+                                                filters: [],
+                                                span: 126..130,
+                                            },
+                                            span: 124..132,
                                         },
-                                    },
-                                ),
-                                Str(
-                                    ":",
-                                ),
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "port",
-                                            ),
-                                            filters: [],
+                                    ),
+                                    Str(
+                                        ":",
+                                    ),
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "port",
+                                                    ),
+                                                    135..139,
+                                                ),
+                                                filters: [],
+                                                span: 135..139,
+                                            },
+                                            span: 133..141,
                                         },
-                                    },
-                                ),
-                                Str(
-                                    ":127.0.0.1",
-                                ),
-                            ],
-                        },
+                                    ),
+                                    Str(
+                                        ":127.0.0.1",
+                                    ),
+                                ],
+                                span: 124..151,
+                            },
+                        ),
+                        115..151,
                     ),
-                    UnixSocket(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "socket",
-                                            ),
-                                            filters: [],
+                    (
+                        UnixSocket(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "socket",
+                                                    ),
+                                                    167..173,
+                                                ),
+                                                filters: [],
+                                                span: 167..173,
+                                            },
+                                            span: 165..175,
                                         },
-                                    },
-                                ),
-                            ],
-                        },
+                                    ),
+                                ],
+                                span: 165..175,
+                            },
+                        ),
+                        152..175,
                     ),
-                    User(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "user",
-                                            ),
-                                            filters: [],
+                    (
+                        User(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "user",
+                                                    ),
+                                                    184..188,
+                                                ),
+                                                filters: [],
+                                                span: 184..188,
+                                            },
+                                            span: 182..190,
                                         },
-                                    },
-                                ),
-                                Str(
-                                    "=",
-                                ),
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "password",
-                                            ),
-                                            filters: [],
+                                    ),
+                                    Str(
+                                        "=",
+                                    ),
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "password",
+                                                    ),
+                                                    193..201,
+                                                ),
+                                                filters: [],
+                                                span: 193..201,
+                                            },
+                                            span: 191..203,
                                         },
-                                    },
-                                ),
-                            ],
-                        },
+                                    ),
+                                ],
+                                span: 182..203,
+                            },
+                        ),
+                        176..203,
                     ),
                 ],
             ),
@@ -1191,32 +1537,44 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    Cacert(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "/etc/cert.pem",
-                                ),
-                            ],
-                        },
+                    (
+                        Cacert(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "/etc/cert.pem",
+                                    ),
+                                ],
+                                span: 8..21,
+                            },
+                        ),
+                        0..21,
                     ),
-                    Key(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    ".ssh/id_rsa.pub",
-                                ),
-                            ],
-                        },
+                    (
+                        Key(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        ".ssh/id_rsa.pub",
+                                    ),
+                                ],
+                                span: 27..42,
+                            },
+                        ),
+                        22..42,
                     ),
-                    Output(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "./myreport",
-                                ),
-                            ],
-                        },
+                    (
+                        Output(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "./myreport",
+                                    ),
+                                ],
+                                span: 51..61,
+                            },
+                        ),
+                        43..61,
                     ),
                 ],
             ),
@@ -1235,53 +1593,80 @@ mod option_tests {
         ParseResult {
             output: Some(
                 [
-                    Cacert(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "certfilepath",
-                                            ),
-                                            filters: [],
+                    (
+                        Cacert(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "certfilepath",
+                                                    ),
+                                                    10..22,
+                                                ),
+                                                filters: [],
+                                                span: 10..22,
+                                            },
+                                            span: 8..24,
                                         },
-                                    },
-                                ),
-                            ],
-                        },
+                                    ),
+                                ],
+                                span: 8..24,
+                            },
+                        ),
+                        0..24,
                     ),
-                    Key(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "keyfilepath",
-                                            ),
-                                            filters: [],
+                    (
+                        Key(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "keyfilepath",
+                                                    ),
+                                                    32..43,
+                                                ),
+                                                filters: [],
+                                                span: 32..43,
+                                            },
+                                            span: 30..45,
                                         },
-                                    },
-                                ),
-                            ],
-                        },
+                                    ),
+                                ],
+                                span: 30..45,
+                            },
+                        ),
+                        25..45,
                     ),
-                    Output(
-                        InterpolatedString {
-                            parts: [
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "reportfilepath",
-                                            ),
-                                            filters: [],
+                    (
+                        Output(
+                            InterpolatedString {
+                                parts: [
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "reportfilepath",
+                                                    ),
+                                                    56..70,
+                                                ),
+                                                filters: [],
+                                                span: 56..70,
+                                            },
+                                            span: 54..72,
                                         },
-                                    },
-                                ),
-                            ],
-                        },
+                                    ),
+                                ],
+                                span: 54..72,
+                            },
+                        ),
+                        46..72,
                     ),
                 ],
             ),
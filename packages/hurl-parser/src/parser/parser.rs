@@ -1,18 +1,19 @@
+use super::cst::{build_cst, SyntaxNode};
+use super::error::ParseError;
 use super::primitives::lt_parser;
 use super::request::request_parser;
 use super::response::response_parser;
-use super::types::{Ast, Entry};
+use super::response_section::assert_start_parser;
+use super::types::{AssertStart, Ast, Entry};
 use chumsky::prelude::*;
 
-pub fn ast_parser<'a>() -> impl Parser<'a, &'a str, Ast, extra::Err<Rich<'a, char>>> + Clone {
+pub fn ast_parser<'a>() -> impl Parser<'a, &'a str, Ast, extra::Err<ParseError>> + Clone {
     let entry = request_parser()
         .then(response_parser().or_not())
-        .map(|(request_value, response_value)| Entry {
+        .map_with(|(request_value, response_value), e| Entry {
             request: Box::new(request_value),
-            response: match response_value {
-                Some(r) => Some(Box::new(r)),
-                None => None,
-            },
+            response: response_value.map(Box::new),
+            span: e.span(),
         })
         .labelled("entry")
         .boxed();
@@ -35,9 +36,25 @@ pub fn ast_parser<'a>() -> impl Parser<'a, &'a str, Ast, extra::Err<Rich<'a, cha
         .boxed()
 }
 
-pub fn parse_ast<'a>(document: &'a str) -> (Option<Ast>, Vec<Rich<'a, char>>) {
-    let (ast, errs) = ast_parser().parse(document).into_output_errors();
-    (ast, errs)
+pub fn parse_ast(document: &str) -> (Option<Ast>, Vec<ParseError>) {
+    ast_parser().parse(document).into_output_errors()
+}
+
+/// Parses the start of an assert or capture line up to its predicate, as completion sees
+/// it while the rest of the line is still being written.
+pub fn parse_assert_start(line: &str) -> Option<AssertStart> {
+    assert_start_parser().parse(line).into_output()
+}
+
+/// Parses the document into both the typed AST and a lossless syntax tree that keeps
+/// comments and whitespace. The syntax tree is always built even if the AST could not be.
+pub fn parse_cst(document: &str) -> (SyntaxNode, Option<Ast>, Vec<ParseError>) {
+    let (ast, errs) = parse_ast(document);
+    let cst = match &ast {
+        Some(ast) => build_cst(document, ast),
+        None => build_cst(document, &Ast { entries: vec![] }),
+    };
+    (cst, ast, errs)
 }
 
 #[cfg(test)]
@@ -65,115 +82,257 @@ mod ast_tests {
                             request: Request {
                                 method: Method {
                                     value: "GeT",
+                                    span: 6..9,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 10..29,
+                                        },
+                                    ),
+                                    10..29,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 2..30,
                             },
                             response: None,
+                            span: 2..30,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 34..37,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 38..57,
+                                        },
+                                    ),
+                                    38..57,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 30..58,
                             },
                             response: None,
+                            span: 30..58,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "post",
+                                    span: 62..66,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 67..86,
+                                        },
+                                    ),
+                                    67..86,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 58..87,
                             },
                             response: None,
+                            span: 58..87,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "pOst",
+                                    span: 91..95,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 96..115,
+                                        },
+                                    ),
+                                    96..115,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 87..116,
                             },
                             response: None,
+                            span: 87..116,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 120..124,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 125..144,
+                                        },
+                                    ),
+                                    125..144,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 116..157,
                             },
                             response: None,
+                            span: 116..157,
                         },
                     ],
                 },
             ),
             [
-                Invalid character 'e'. Method must be ascii uppercase. at 6..9,
-                Invalid character 'p'. Method must be ascii uppercase. at 62..66,
-                Invalid character 'p'. Method must be ascii uppercase. at 91..95,
+                InvalidMethodCase {
+                    span: 6..9,
+                    found: 'e',
+                },
+                InvalidMethodCase {
+                    span: 62..66,
+                    found: 'p',
+                },
+                InvalidMethodCase {
+                    span: 91..95,
+                    found: 'p',
+                },
             ],
         )
         "#,
         );
     }
 
+    #[test]
+    fn it_parses_response_after_request_without_body() {
+        let test_str = "GET https://example.org\nHTTP 200\n\n\nGET https://example.org\n";
+        assert_debug_snapshot!(
+        parse_ast(test_str),
+            @r#"
+        (
+            Some(
+                Ast {
+                    entries: [
+                        Entry {
+                            request: Request {
+                                method: Method {
+                                    value: "GET",
+                                    span: 0..3,
+                                },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
+                                ),
+                                headers: [],
+                                request_sections: [],
+                                misplaced_sections: [],
+                                body: None,
+                                span: 0..24,
+                            },
+                            response: Some(
+                                Response {
+                                    version: (
+                                        Http,
+                                        24..28,
+                                    ),
+                                    status: (
+                                        Code(
+                                            200,
+                                        ),
+                                        29..32,
+                                    ),
+                                    headers: [],
+                                    response_sections: [],
+                                    misplaced_sections: [],
+                                    body: None,
+                                    span: 24..35,
+                                },
+                            ),
+                            span: 0..35,
+                        },
+                        Entry {
+                            request: Request {
+                                method: Method {
+                                    value: "GET",
+                                    span: 35..38,
+                                },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 39..58,
+                                        },
+                                    ),
+                                    39..58,
+                                ),
+                                headers: [],
+                                request_sections: [],
+                                misplaced_sections: [],
+                                body: None,
+                                span: 35..59,
+                            },
+                            response: None,
+                            span: 35..59,
+                        },
+                    ],
+                },
+            ),
+            [],
+        )
+        "#,
+        );
+    }
+
     #[test]
     fn it_recovers_from_invalid_syntax_in_entries() {
         //This should parse the 5 valid entries and skip invalid ones until the next valid entry
@@ -199,108 +358,162 @@ mod ast_tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 6..9,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 10..29,
+                                        },
+                                    ),
+                                    10..29,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 2..30,
                             },
                             response: None,
+                            span: 2..30,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 60..63,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 64..83,
+                                        },
+                                    ),
+                                    64..83,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 56..84,
                             },
                             response: None,
+                            span: 56..84,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 88..92,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 93..112,
+                                        },
+                                    ),
+                                    93..112,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 84..113,
                             },
                             response: None,
+                            span: 84..113,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 172..176,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 177..196,
+                                        },
+                                    ),
+                                    177..196,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 168..197,
                             },
                             response: None,
+                            span: 168..197,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 201..205,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 206..225,
+                                        },
+                                    ),
+                                    206..225,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 197..238,
                             },
                             response: None,
+                            span: 197..238,
                         },
                     ],
                 },
             ),
             [
-                found end of input at 34..35 expected something else,
-                found end of input at 117..118 expected something else,
+                Unexpected {
+                    span: 34..35,
+                    found: None,
+                    expected: [
+                        "something else",
+                    ],
+                    labels: [],
+                },
+                Unexpected {
+                    span: 117..118,
+                    found: None,
+                    expected: [
+                        "something else",
+                    ],
+                    labels: [],
+                },
             ],
         )
         "#,
@@ -340,18 +553,24 @@ mod ast_tests {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 6..10,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 11..30,
+                                        },
+                                    ),
+                                    11..30,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: Some(
                                     Body {
                                         bytes: JsonValue(
@@ -364,16 +583,22 @@ mod ast_tests {
                                                                     "id",
                                                                 ),
                                                             ],
+                                                            span: 52..56,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "d89e270c-5f26-4906-b305-c9e3cc2a0a24",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "d89e270c-5f26-4906-b305-c9e3cc2a0a24",
+                                                                        ),
+                                                                    ],
+                                                                    span: 58..96,
+                                                                },
+                                                            ),
+                                                            58..96,
                                                         ),
+                                                        span: 52..96,
                                                     },
                                                     JsonKeyValue {
                                                         key: InterpolatedString {
@@ -382,63 +607,89 @@ mod ast_tests {
                                                                     "pets",
                                                                 ),
                                                             ],
+                                                            span: 106..112,
                                                         },
-                                                        value: Array(
-                                                            [
-                                                                InterpolatedString(
-                                                                    InterpolatedString {
-                                                                        parts: [
-                                                                            Str(
-                                                                                "cat",
-                                                                            ),
-                                                                        ],
-                                                                    },
-                                                                ),
-                                                                InterpolatedString(
-                                                                    InterpolatedString {
-                                                                        parts: [
-                                                                            Str(
-                                                                                "dog",
-                                                                            ),
-                                                                        ],
-                                                                    },
-                                                                ),
-                                                                InterpolatedString(
-                                                                    InterpolatedString {
-                                                                        parts: [
-                                                                            Str(
-                                                                                "hampster",
-                                                                            ),
-                                                                        ],
-                                                                    },
-                                                                ),
-                                                            ],
+                                                        value: (
+                                                            Array(
+                                                                [
+                                                                    (
+                                                                        InterpolatedString(
+                                                                            InterpolatedString {
+                                                                                parts: [
+                                                                                    Str(
+                                                                                        "cat",
+                                                                                    ),
+                                                                                ],
+                                                                                span: 128..133,
+                                                                            },
+                                                                        ),
+                                                                        128..133,
+                                                                    ),
+                                                                    (
+                                                                        InterpolatedString(
+                                                                            InterpolatedString {
+                                                                                parts: [
+                                                                                    Str(
+                                                                                        "dog",
+                                                                                    ),
+                                                                                ],
+                                                                                span: 147..152,
+                                                                            },
+                                                                        ),
+                                                                        147..152,
+                                                                    ),
+                                                                    (
+                                                                        InterpolatedString(
+                                                                            InterpolatedString {
+                                                                                parts: [
+                                                                                    Str(
+                                                                                        "hampster",
+                                                                                    ),
+                                                                                ],
+                                                                                span: 166..176,
+                                                                            },
+                                                                        ),
+                                                                        166..176,
+                                                                    ),
+                                                                ],
+                                                            ),
+                                                            114..186,
                                                         ),
+                                                        span: 106..186,
                                                     },
                                                 ],
                                             ),
                                         ),
+                                        span: 31..192,
                                     },
                                 ),
+                                span: 2..194,
                             },
                             response: None,
+                            span: 2..194,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 198..202,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 203..222,
+                                        },
+                                    ),
+                                    203..222,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: Some(
                                     Body {
                                         bytes: JsonValue(
@@ -451,16 +702,22 @@ mod ast_tests {
                                                                     "id",
                                                                 ),
                                                             ],
+                                                            span: 244..248,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "bde6c63f-eebe-4cae-b955-d128b5d2444d",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "bde6c63f-eebe-4cae-b955-d128b5d2444d",
+                                                                        ),
+                                                                    ],
+                                                                    span: 250..288,
+                                                                },
+                                                            ),
+                                                            250..288,
                                                         ),
+                                                        span: 244..288,
                                                     },
                                                     JsonKeyValue {
                                                         key: InterpolatedString {
@@ -469,37 +726,53 @@ mod ast_tests {
                                                                     "pets",
                                                                 ),
                                                             ],
+                                                            span: 298..304,
                                                         },
-                                                        value: Array(
-                                                            [
-                                                                InterpolatedString(
-                                                                    InterpolatedString {
-                                                                        parts: [
-                                                                            Str(
-                                                                                "cat",
-                                                                            ),
-                                                                        ],
-                                                                    },
-                                                                ),
-                                                                InterpolatedString(
-                                                                    InterpolatedString {
-                                                                        parts: [
-                                                                            Str(
-                                                                                "dog",
-                                                                            ),
-                                                                        ],
-                                                                    },
-                                                                ),
-                                                            ],
+                                                        value: (
+                                                            Array(
+                                                                [
+                                                                    (
+                                                                        InterpolatedString(
+                                                                            InterpolatedString {
+                                                                                parts: [
+                                                                                    Str(
+                                                                                        "cat",
+                                                                                    ),
+                                                                                ],
+                                                                                span: 320..325,
+                                                                            },
+                                                                        ),
+                                                                        320..325,
+                                                                    ),
+                                                                    (
+                                                                        InterpolatedString(
+                                                                            InterpolatedString {
+                                                                                parts: [
+                                                                                    Str(
+                                                                                        "dog",
+                                                                                    ),
+                                                                                ],
+                                                                                span: 339..344,
+                                                                            },
+                                                                        ),
+                                                                        339..344,
+                                                                    ),
+                                                                ],
+                                                            ),
+                                                            306..354,
                                                         ),
+                                                        span: 298..354,
                                                     },
                                                 ],
                                             ),
                                         ),
+                                        span: 223..360,
                                     },
                                 ),
+                                span: 194..373,
                             },
                             response: None,
+                            span: 194..373,
                         },
                     ],
                 },
@@ -543,18 +816,24 @@ mod ast_tests {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 6..10,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 11..30,
+                                        },
+                                    ),
+                                    11..30,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: Some(
                                     Body {
                                         bytes: JsonValue(
@@ -567,16 +846,22 @@ mod ast_tests {
                                                                     "id",
                                                                 ),
                                                             ],
+                                                            span: 52..56,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "d89e270c-5f26-4906-b305-c9e3cc2a0a24",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "d89e270c-5f26-4906-b305-c9e3cc2a0a24",
+                                                                        ),
+                                                                    ],
+                                                                    span: 58..96,
+                                                                },
+                                                            ),
+                                                            58..96,
                                                         ),
+                                                        span: 52..96,
                                                     },
                                                     JsonKeyValue {
                                                         key: InterpolatedString {
@@ -585,41 +870,56 @@ mod ast_tests {
                                                                     "pets",
                                                                 ),
                                                             ],
+                                                            span: 106..112,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "cat",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "cat",
+                                                                        ),
+                                                                    ],
+                                                                    span: 128..133,
+                                                                },
+                                                            ),
+                                                            114..133,
                                                         ),
+                                                        span: 106..133,
                                                     },
                                                 ],
                                             ),
                                         ),
+                                        span: 31..191,
                                     },
                                 ),
+                                span: 2..193,
                             },
                             response: None,
+                            span: 2..193,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 197..201,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 202..221,
+                                        },
+                                    ),
+                                    202..221,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: Some(
                                     Body {
                                         bytes: JsonValue(
@@ -632,16 +932,22 @@ mod ast_tests {
                                                                     "id",
                                                                 ),
                                                             ],
+                                                            span: 243..247,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "bde6c63f-eebe-4cae-b955-d128b5d2444d",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "bde6c63f-eebe-4cae-b955-d128b5d2444d",
+                                                                        ),
+                                                                    ],
+                                                                    span: 249..287,
+                                                                },
+                                                            ),
+                                                            249..287,
                                                         ),
+                                                        span: 243..287,
                                                     },
                                                     JsonKeyValue {
                                                         key: InterpolatedString {
@@ -650,44 +956,79 @@ mod ast_tests {
                                                                     "pets",
                                                                 ),
                                                             ],
+                                                            span: 297..303,
                                                         },
-                                                        value: Array(
-                                                            [
-                                                                InterpolatedString(
-                                                                    InterpolatedString {
-                                                                        parts: [
-                                                                            Str(
-                                                                                "cat",
-                                                                            ),
-                                                                        ],
-                                                                    },
-                                                                ),
-                                                                InterpolatedString(
-                                                                    InterpolatedString {
-                                                                        parts: [
-                                                                            Str(
-                                                                                "dog",
-                                                                            ),
-                                                                        ],
-                                                                    },
-                                                                ),
-                                                            ],
+                                                        value: (
+                                                            Array(
+                                                                [
+                                                                    (
+                                                                        InterpolatedString(
+                                                                            InterpolatedString {
+                                                                                parts: [
+                                                                                    Str(
+                                                                                        "cat",
+                                                                                    ),
+                                                                                ],
+                                                                                span: 319..324,
+                                                                            },
+                                                                        ),
+                                                                        319..324,
+                                                                    ),
+                                                                    (
+                                                                        InterpolatedString(
+                                                                            InterpolatedString {
+                                                                                parts: [
+                                                                                    Str(
+                                                                                        "dog",
+                                                                                    ),
+                                                                                ],
+                                                                                span: 338..343,
+                                                                            },
+                                                                        ),
+                                                                        338..343,
+                                                                    ),
+                                                                ],
+                                                            ),
+                                                            305..353,
                                                         ),
+                                                        span: 297..353,
                                                     },
                                                 ],
                                             ),
                                         ),
+                                        span: 222..359,
                                     },
                                 ),
+                                span: 193..372,
                             },
                             response: None,
+                            span: 193..372,
                         },
                     ],
                 },
             ),
             [
-                found ''}'' at 190..191 expected '','', or '']'',
-                found '','' at 152..153 expected '':'',
+                Unexpected {
+                    span: 190..191,
+                    found: Some(
+                        '}',
+                    ),
+                    expected: [
+                        "','",
+                        "']'",
+                    ],
+                    labels: [],
+                },
+                Unexpected {
+                    span: 152..153,
+                    found: Some(
+                        ',',
+                    ),
+                    expected: [
+                        "':'",
+                    ],
+                    labels: [],
+                },
             ],
         )
         "#,
@@ -721,18 +1062,24 @@ mod ast_tests {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 6..10,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 11..30,
+                                        },
+                                    ),
+                                    11..30,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: Some(
                                     Body {
                                         bytes: JsonValue(
@@ -745,16 +1092,22 @@ mod ast_tests {
                                                                     "id",
                                                                 ),
                                                             ],
+                                                            span: 52..56,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "d89e270c-5f26-4906-b305-c9e3cc2a0a24",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "d89e270c-5f26-4906-b305-c9e3cc2a0a24",
+                                                                        ),
+                                                                    ],
+                                                                    span: 58..96,
+                                                                },
+                                                            ),
+                                                            58..96,
                                                         ),
+                                                        span: 52..96,
                                                     },
                                                     JsonKeyValue {
                                                         key: InterpolatedString {
@@ -763,41 +1116,56 @@ mod ast_tests {
                                                                     "pet",
                                                                 ),
                                                             ],
+                                                            span: 106..111,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "cat",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "cat",
+                                                                        ),
+                                                                    ],
+                                                                    span: 113..118,
+                                                                },
+                                                            ),
+                                                            113..118,
                                                         ),
+                                                        span: 106..118,
                                                     },
                                                 ],
                                             ),
                                         ),
+                                        span: 31..125,
                                     },
                                 ),
+                                span: 2..127,
                             },
                             response: None,
+                            span: 2..127,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 131..135,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 136..155,
+                                        },
+                                    ),
+                                    136..155,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: Some(
                                     Body {
                                         bytes: JsonValue(
@@ -810,16 +1178,22 @@ mod ast_tests {
                                                                     "id",
                                                                 ),
                                                             ],
+                                                            span: 177..181,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "55b42346-02be-4cf3-824c-b2dcdf5f7512",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "55b42346-02be-4cf3-824c-b2dcdf5f7512",
+                                                                        ),
+                                                                    ],
+                                                                    span: 183..221,
+                                                                },
+                                                            ),
+                                                            183..221,
                                                         ),
+                                                        span: 177..221,
                                                     },
                                                     JsonKeyValue {
                                                         key: InterpolatedString {
@@ -828,30 +1202,49 @@ mod ast_tests {
                                                                     "pet",
                                                                 ),
                                                             ],
+                                                            span: 231..236,
                                                         },
-                                                        value: InterpolatedString(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "dog",
-                                                                    ),
-                                                                ],
-                                                            },
+                                                        value: (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "dog",
+                                                                        ),
+                                                                    ],
+                                                                    span: 238..243,
+                                                                },
+                                                            ),
+                                                            238..243,
                                                         ),
+                                                        span: 231..243,
                                                     },
                                                 ],
                                             ),
                                         ),
+                                        span: 156..249,
                                     },
                                 ),
+                                span: 127..255,
                             },
                             response: None,
+                            span: 127..255,
                         },
                     ],
                 },
             ),
             [
-                found ''#'' at 123..124 expected '','', or ''}'',
+                Unexpected {
+                    span: 123..124,
+                    found: Some(
+                        '#',
+                    ),
+                    expected: [
+                        "','",
+                        "'}'",
+                    ],
+                    labels: [],
+                },
             ],
         )
         "#,
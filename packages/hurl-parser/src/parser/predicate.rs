@@ -1,3 +1,4 @@
+use super::error::ParseError;
 use super::{
     multiline_string::multiline_string_parser,
     oneline_base64::oneline_base64_parser,
@@ -7,13 +8,13 @@ use super::{
     primitives::sp_parser,
     quoted_string::quoted_string_parser,
     template::template_parser,
-    types::{Predicate, PredicateFunc, PredicatePrefixOperator, PredicateValue},
+    types::{Predicate, PredicateFunc, PredicatePrefixOperator, PredicateValue, Spanned},
 };
 use chumsky::prelude::*;
 use ordered_float::OrderedFloat;
 
-pub fn predicate_parser<'a>(
-) -> impl Parser<'a, &'a str, Predicate, extra::Err<Rich<'a, char>>> + Clone {
+pub fn predicate_parser<'a>() -> impl Parser<'a, &'a str, Predicate, extra::Err<ParseError>> + Clone
+{
     let fraction = just('.').then(text::digits(10));
     let number = just("-")
         .or_not()
@@ -25,7 +26,7 @@ pub fn predicate_parser<'a>(
                 match number.parse::<f64>() {
                     Ok(n) => PredicateValue::Float(OrderedFloat::<f64>::from(n)),
                     Err(_) => {
-                        emitter.emit(Rich::custom(e.span(), "invalid float"));
+                        emitter.emit(ParseError::InvalidFloat { span: e.span() });
                         PredicateValue::Invalid
                     }
                 }
@@ -49,7 +50,8 @@ pub fn predicate_parser<'a>(
         oneline_base64_parser().map(PredicateValue::OneLineBase64),
         oneline_string_parser().map(PredicateValue::OneLineString),
         multiline_string_parser().map(PredicateValue::MultilineString),
-    ));
+    ))
+    .map_with(|value, e| (value, e.span()));
 
     let predicate_function = choice((
         just("==")
@@ -63,7 +65,8 @@ pub fn predicate_parser<'a>(
         just(">")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
-            .validate(|(_, value): (_, PredicateValue), e, emitter| match value {
+            .validate(
+                |(_, value): (_, Spanned<PredicateValue>), e, emitter| match &value.0 {
                 PredicateValue::Integer(_)
                 | PredicateValue::Float(_)
                 | PredicateValue::BigInteger(_)
@@ -73,14 +76,16 @@ pub fn predicate_parser<'a>(
                 | PredicateValue::QuotedString(_) => PredicateFunc::Greater { value },
 
                 _ => {
-                    emitter.emit(Rich::custom(e.span(), "Unexpected predicate value"));
+                    emitter.emit(ParseError::UnexpectedPredicateValue { span: e.span() });
                     PredicateFunc::Greater { value }
                 }
-            }),
+            },
+            ),
         just(">=")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
-            .validate(|(_, value): (_, PredicateValue), e, emitter| match value {
+            .validate(
+                |(_, value): (_, Spanned<PredicateValue>), e, emitter| match &value.0 {
                 PredicateValue::Integer(_)
                 | PredicateValue::Float(_)
                 | PredicateValue::BigInteger(_)
@@ -89,14 +94,16 @@ pub fn predicate_parser<'a>(
                 | PredicateValue::OneLineString(_)//Off spec but is in official parser
                 | PredicateValue::QuotedString(_) => PredicateFunc::GreaterOrEqual { value },
                 _ => {
-                    emitter.emit(Rich::custom(e.span(), "Unexpected predicate value"));
+                    emitter.emit(ParseError::UnexpectedPredicateValue { span: e.span() });
                     PredicateFunc::GreaterOrEqual { value }
                 }
-            }),
+            },
+            ),
         just("<")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
-            .validate(|(_, value): (_, PredicateValue), e, emitter| match value {
+            .validate(
+                |(_, value): (_, Spanned<PredicateValue>), e, emitter| match &value.0 {
                 PredicateValue::Integer(_)
                 | PredicateValue::Float(_)
                 | PredicateValue::BigInteger(_)
@@ -105,14 +112,16 @@ pub fn predicate_parser<'a>(
                 | PredicateValue::OneLineString(_)//Off spec but is in official parser
                 | PredicateValue::QuotedString(_) => PredicateFunc::Less { value },
                 _ => {
-                    emitter.emit(Rich::custom(e.span(), "Unexpected predicate value"));
+                    emitter.emit(ParseError::UnexpectedPredicateValue { span: e.span() });
                     PredicateFunc::Less { value }
                 }
-            }),
+            },
+            ),
         just("<=")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
-            .validate(|(_, value): (_, PredicateValue), e, emitter| match value {
+            .validate(
+                |(_, value): (_, Spanned<PredicateValue>), e, emitter| match &value.0 {
                 PredicateValue::Integer(_)
                 | PredicateValue::Float(_)
                 | PredicateValue::BigInteger(_)
@@ -121,40 +130,46 @@ pub fn predicate_parser<'a>(
                 | PredicateValue::OneLineString(_)//Off spec but is in official parser
                 | PredicateValue::QuotedString(_) => PredicateFunc::LessOrEqual { value },
                 _ => {
-                    emitter.emit(Rich::custom(e.span(), "Unexpected predicate value"));
+                    emitter.emit(ParseError::UnexpectedPredicateValue { span: e.span() });
                     PredicateFunc::LessOrEqual { value }
                 }
-            }),
+            },
+            ),
         just("startsWith")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
-            .validate(|(_, value): (_, PredicateValue), e, emitter| match value {
+            .validate(
+                |(_, value): (_, Spanned<PredicateValue>), e, emitter| match &value.0 {
                 PredicateValue::OneLineString(_)//Off spec but is in official parser
                 | PredicateValue::QuotedString(_)
                 | PredicateValue::OneLineHex(_)
                 | PredicateValue::OneLineBase64(_) => PredicateFunc::StartWith { value },
                 _ => {
-                    emitter.emit(Rich::custom(e.span(), "Unexpected predicate value"));
+                    emitter.emit(ParseError::UnexpectedPredicateValue { span: e.span() });
                     PredicateFunc::StartWith { value }
                 }
-            }),
+            },
+            ),
         just("endsWith")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
-            .validate(|(_, value): (_, PredicateValue), e, emitter| match value {
+            .validate(
+                |(_, value): (_, Spanned<PredicateValue>), e, emitter| match &value.0 {
                 PredicateValue::OneLineString(_)//Off spec but is in official parser
                 | PredicateValue::QuotedString(_)
                 | PredicateValue::OneLineHex(_)
                 | PredicateValue::OneLineBase64(_) => PredicateFunc::EndWith { value },
                 _ => {
-                    emitter.emit(Rich::custom(e.span(), "Unexpected predicate value"));
+                    emitter.emit(ParseError::UnexpectedPredicateValue { span: e.span() });
                     PredicateFunc::EndWith { value }
                 }
-            }),
+            },
+            ),
         just("contains")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
-            .validate(|(_, value): (_, PredicateValue), e, emitter| match value {
+            .validate(
+                |(_, value): (_, Spanned<PredicateValue>), e, emitter| match &value.0 {
                 //TODO the spec says on quoted strings are allowed but I don't see where in the 
                 //code it restricts the types for this predicate function's value
                 PredicateValue::OneLineString(_)//Off spec but but determined testing showed that
@@ -164,10 +179,11 @@ pub fn predicate_parser<'a>(
                 | PredicateValue::OneLineBase64(_) // Offspec but testing shows this works
                 => PredicateFunc::Contain { value },
                 _ => {
-                    emitter.emit(Rich::custom(e.span(), "Unexpected predicate value"));
+                    emitter.emit(ParseError::UnexpectedPredicateValue { span: e.span() });
                     PredicateFunc::Contain { value }
                 }
-            }),
+            },
+            ),
         just("includes")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
@@ -175,17 +191,19 @@ pub fn predicate_parser<'a>(
         just("matches")
             .then_ignore(sp_parser().repeated().at_least(1))
             .then(predicate_value.clone())
-            .validate(|(_, value): (_, PredicateValue), e, emitter| match value {
+            .validate(
+                |(_, value): (_, Spanned<PredicateValue>), e, emitter| match &value.0 {
                 PredicateValue::OneLineString(_)//Off spec but but determined testing showed that
                                                 //this workds
                 |PredicateValue::QuotedString(_) | PredicateValue::Regex(_) => {
                     PredicateFunc::Match { value }
                 }
                 _ => {
-                    emitter.emit(Rich::custom(e.span(), "Unexpected predicate value"));
+                    emitter.emit(ParseError::UnexpectedPredicateValue { span: e.span() });
                     PredicateFunc::Match { value }
                 }
-            }),
+            },
+            ),
         just("exists").to(PredicateFunc::Exists),
         just("isEmpty").to(PredicateFunc::IsEmpty),
         just("isInteger").to(PredicateFunc::IsInteger),
@@ -195,16 +213,22 @@ pub fn predicate_parser<'a>(
         just("isCollection").to(PredicateFunc::IsCollection),
         just("isDate").to(PredicateFunc::IsDate),
         just("isIsoDate").to(PredicateFunc::IsIsoDate),
-    ));
+    ))
+    .map_with(|function, e| (function, e.span()));
     let not_keyword = just("not")
         .to(PredicatePrefixOperator::Not)
+        .map_with(|prefix, e| (prefix, e.span()))
         .then_ignore(sp_parser().repeated().at_least(1))
         .boxed();
-    let predicate = not_keyword
+
+    not_keyword
         .or_not()
         .then(predicate_function)
-        .map(|(prefix, function)| Predicate { prefix, function });
-    predicate
+        .map_with(|(prefix, function), e| Predicate {
+            prefix,
+            function,
+            span: e.span(),
+        })
 }
 
 #[cfg(test)]
@@ -222,11 +246,18 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: Equal {
-                        value: Boolean(
-                            true,
-                        ),
-                    },
+                    function: (
+                        Equal {
+                            value: (
+                                Boolean(
+                                    true,
+                                ),
+                                3..7,
+                            ),
+                        },
+                        0..7,
+                    ),
+                    span: 0..7,
                 },
             ),
             errs: [],
@@ -245,17 +276,25 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: NotEqual {
-                        value: QuotedString(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "dog",
-                                    ),
-                                ],
-                            },
-                        ),
-                    },
+                    function: (
+                        NotEqual {
+                            value: (
+                                QuotedString(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "dog",
+                                            ),
+                                        ],
+                                        span: 3..8,
+                                    },
+                                ),
+                                3..8,
+                            ),
+                        },
+                        0..8,
+                    ),
+                    span: 0..8,
                 },
             ),
             errs: [],
@@ -274,11 +313,18 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: Greater {
-                        value: Float(
-                            5.05,
-                        ),
-                    },
+                    function: (
+                        Greater {
+                            value: (
+                                Float(
+                                    5.05,
+                                ),
+                                2..6,
+                            ),
+                        },
+                        0..6,
+                    ),
+                    span: 0..6,
                 },
             ),
             errs: [],
@@ -297,11 +343,18 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: GreaterOrEqual {
-                        value: Integer(
-                            0,
-                        ),
-                    },
+                    function: (
+                        GreaterOrEqual {
+                            value: (
+                                Integer(
+                                    0,
+                                ),
+                                3..4,
+                            ),
+                        },
+                        0..4,
+                    ),
+                    span: 0..4,
                 },
             ),
             errs: [],
@@ -320,11 +373,18 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: Less {
-                        value: Integer(
-                            9,
-                        ),
-                    },
+                    function: (
+                        Less {
+                            value: (
+                                Integer(
+                                    9,
+                                ),
+                                2..3,
+                            ),
+                        },
+                        0..3,
+                    ),
+                    span: 0..3,
                 },
             ),
             errs: [],
@@ -343,11 +403,18 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: LessOrEqual {
-                        value: Integer(
-                            20,
-                        ),
-                    },
+                    function: (
+                        LessOrEqual {
+                            value: (
+                                Integer(
+                                    20,
+                                ),
+                                3..5,
+                            ),
+                        },
+                        0..5,
+                    ),
+                    span: 0..5,
                 },
             ),
             errs: [],
@@ -366,17 +433,25 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: StartWith {
-                        value: QuotedString(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "abc",
-                                    ),
-                                ],
-                            },
-                        ),
-                    },
+                    function: (
+                        StartWith {
+                            value: (
+                                QuotedString(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "abc",
+                                            ),
+                                        ],
+                                        span: 11..16,
+                                    },
+                                ),
+                                11..16,
+                            ),
+                        },
+                        0..16,
+                    ),
+                    span: 0..16,
                 },
             ),
             errs: [],
@@ -395,17 +470,25 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: EndWith {
-                        value: QuotedString(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "xyz",
-                                    ),
-                                ],
-                            },
-                        ),
-                    },
+                    function: (
+                        EndWith {
+                            value: (
+                                QuotedString(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "xyz",
+                                            ),
+                                        ],
+                                        span: 9..14,
+                                    },
+                                ),
+                                9..14,
+                            ),
+                        },
+                        0..14,
+                    ),
+                    span: 0..14,
                 },
             ),
             errs: [],
@@ -424,17 +507,25 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: Contain {
-                        value: QuotedString(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "bird",
-                                    ),
-                                ],
-                            },
-                        ),
-                    },
+                    function: (
+                        Contain {
+                            value: (
+                                QuotedString(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "bird",
+                                            ),
+                                        ],
+                                        span: 9..15,
+                                    },
+                                ),
+                                9..15,
+                            ),
+                        },
+                        0..15,
+                    ),
+                    span: 0..15,
                 },
             ),
             errs: [],
@@ -453,17 +544,25 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: Match {
-                        value: QuotedString(
-                            InterpolatedString {
-                                parts: [
-                                    Str(
-                                        "cat",
-                                    ),
-                                ],
-                            },
-                        ),
-                    },
+                    function: (
+                        Match {
+                            value: (
+                                QuotedString(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "cat",
+                                            ),
+                                        ],
+                                        span: 8..13,
+                                    },
+                                ),
+                                8..13,
+                            ),
+                        },
+                        0..13,
+                    ),
+                    span: 0..13,
                 },
             ),
             errs: [],
@@ -482,11 +581,18 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: Include {
-                        value: Integer(
-                            5,
-                        ),
-                    },
+                    function: (
+                        Include {
+                            value: (
+                                Integer(
+                                    5,
+                                ),
+                                9..10,
+                            ),
+                        },
+                        0..10,
+                    ),
+                    span: 0..10,
                 },
             ),
             errs: [],
@@ -505,7 +611,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: Exists,
+                    function: (
+                        Exists,
+                        0..6,
+                    ),
+                    span: 0..6,
                 },
             ),
             errs: [],
@@ -524,9 +634,16 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: Some(
-                        Not,
+                        (
+                            Not,
+                            0..3,
+                        ),
+                    ),
+                    function: (
+                        Exists,
+                        5..11,
                     ),
-                    function: Exists,
+                    span: 0..11,
                 },
             ),
             errs: [],
@@ -545,7 +662,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: IsEmpty,
+                    function: (
+                        IsEmpty,
+                        0..7,
+                    ),
+                    span: 0..7,
                 },
             ),
             errs: [],
@@ -564,7 +685,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: IsInteger,
+                    function: (
+                        IsInteger,
+                        0..9,
+                    ),
+                    span: 0..9,
                 },
             ),
             errs: [],
@@ -583,7 +708,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: IsFloat,
+                    function: (
+                        IsFloat,
+                        0..7,
+                    ),
+                    span: 0..7,
                 },
             ),
             errs: [],
@@ -602,7 +731,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: IsBoolean,
+                    function: (
+                        IsBoolean,
+                        0..9,
+                    ),
+                    span: 0..9,
                 },
             ),
             errs: [],
@@ -621,7 +754,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: IsString,
+                    function: (
+                        IsString,
+                        0..8,
+                    ),
+                    span: 0..8,
                 },
             ),
             errs: [],
@@ -640,7 +777,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: IsCollection,
+                    function: (
+                        IsCollection,
+                        0..12,
+                    ),
+                    span: 0..12,
                 },
             ),
             errs: [],
@@ -659,7 +800,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: IsDate,
+                    function: (
+                        IsDate,
+                        0..6,
+                    ),
+                    span: 0..6,
                 },
             ),
             errs: [],
@@ -678,7 +823,11 @@ mod predicate_tests {
             output: Some(
                 Predicate {
                     prefix: None,
-                    function: IsIsoDate,
+                    function: (
+                        IsIsoDate,
+                        0..9,
+                    ),
+                    span: 0..9,
                 },
             ),
             errs: [],
@@ -1,18 +1,18 @@
 use chumsky::prelude::*;
 
+use super::error::{ParseError, ASCII_ALPHABETIC, ASCII_ALPHABETIC_UPPERCASE, ASCII_ALPHANUMERIC};
 use super::types::Lt;
 
 /// A parser that never matches. Used as a placeholder for parsers that
 /// I haven't yet implemented but plan to. Since I only use this when in the middle
 /// of implementing something I added the allow dead code attribute.
 #[allow(dead_code)]
-pub fn todo_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+pub fn todo_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     any().filter(|_| false).boxed()
 }
 
-pub fn alphabetic_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone
-{
-    let ascii_alphabetic_char = custom::<'a, _, &'a str, char, extra::Err<Rich<'a, char>>>(|inp| {
+pub fn alphabetic_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
+    let ascii_alphabetic_char = custom::<'a, _, &'a str, char, extra::Err<ParseError>>(|inp| {
         let before = inp.cursor();
         let c = inp.next();
         let span = inp.span_since(&before);
@@ -21,16 +21,18 @@ pub fn alphabetic_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<Rich
                 if c.is_ascii_alphabetic() {
                     Ok(c)
                 } else {
-                    Err(Rich::custom(
+                    Err(ParseError::UnexpectedChar {
                         span,
-                        format!("expected an ascii alphabetic char but found {}", c),
-                    ))
+                        found: Some(c),
+                        expected: ASCII_ALPHABETIC,
+                    })
                 }
             }
-            None => Err(Rich::custom(
+            None => Err(ParseError::UnexpectedChar {
                 span,
-                format!("expected an ascii alphabetic char but found end"),
-            )),
+                found: None,
+                expected: ASCII_ALPHABETIC,
+            }),
         }
     });
     ascii_alphabetic_char
@@ -39,8 +41,8 @@ pub fn alphabetic_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<Rich
 }
 
 pub fn ascii_alphabetic_uppercase_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
-    let ascii_alphabetic_char = custom::<'a, _, &'a str, char, extra::Err<Rich<'a, char>>>(|inp| {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
+    let ascii_alphabetic_char = custom::<'a, _, &'a str, char, extra::Err<ParseError>>(|inp| {
         let before = inp.cursor();
         let c = inp.next();
         let span = inp.span_since(&before);
@@ -49,19 +51,18 @@ pub fn ascii_alphabetic_uppercase_parser<'a>(
                 if c.is_ascii_uppercase() {
                     Ok(c)
                 } else {
-                    Err(Rich::custom(
+                    Err(ParseError::UnexpectedChar {
                         span,
-                        format!(
-                            "expected an ascii alphabetic uppercase char but found {}",
-                            c
-                        ),
-                    ))
+                        found: Some(c),
+                        expected: ASCII_ALPHABETIC_UPPERCASE,
+                    })
                 }
             }
-            None => Err(Rich::custom(
+            None => Err(ParseError::UnexpectedChar {
                 span,
-                format!("expected an ascii alphabetic uppercase char but found end"),
-            )),
+                found: None,
+                expected: ASCII_ALPHABETIC_UPPERCASE,
+            }),
         }
     });
     ascii_alphabetic_char
@@ -69,49 +70,48 @@ pub fn ascii_alphabetic_uppercase_parser<'a>(
         .boxed()
 }
 
-pub fn alphanumeric_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
-    let ascii_alphanumeric_char =
-        custom::<'a, _, &'a str, char, extra::Err<Rich<'a, char>>>(|inp| {
-            let before = inp.cursor();
-            let c = inp.next();
-            let span = inp.span_since(&before);
+pub fn alphanumeric_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
+    let ascii_alphanumeric_char = custom::<'a, _, &'a str, char, extra::Err<ParseError>>(|inp| {
+        let before = inp.cursor();
+        let c = inp.next();
+        let span = inp.span_since(&before);
 
-            match c {
-                Some(c) => {
-                    if c.is_ascii_alphanumeric() {
-                        Ok(c)
-                    } else {
-                        Err(Rich::custom(
-                            span,
-                            format!("expected an ascii alphanumeric char but found {}", c),
-                        ))
-                    }
+        match c {
+            Some(c) => {
+                if c.is_ascii_alphanumeric() {
+                    Ok(c)
+                } else {
+                    Err(ParseError::UnexpectedChar {
+                        span,
+                        found: Some(c),
+                        expected: ASCII_ALPHANUMERIC,
+                    })
                 }
-                None => Err(Rich::custom(
-                    span,
-                    format!("expected an ascii alphanumeric char but found end"),
-                )),
             }
-        });
+            None => Err(ParseError::UnexpectedChar {
+                span,
+                found: None,
+                expected: ASCII_ALPHANUMERIC,
+            }),
+        }
+    });
     ascii_alphanumeric_char
         .labelled("ascii-alphanumeric-char")
         .boxed()
 }
 
-pub fn sp_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+pub fn sp_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     one_of("\t ").labelled("spacing").boxed()
 }
 
-pub fn comment_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<Rich<'a, char>>> + Clone
-{
+pub fn comment_parser<'a>() -> impl Parser<'a, &'a str, String, extra::Err<ParseError>> + Clone {
     let comment = just('#')
         .ignore_then(none_of('\n').repeated().collect::<String>())
         .labelled("comment");
     comment.boxed()
 }
 
-pub fn lt_not_end_parser<'a>() -> impl Parser<'a, &'a str, Lt, extra::Err<Rich<'a, char>>> + Clone {
+pub fn lt_not_end_parser<'a>() -> impl Parser<'a, &'a str, Lt, extra::Err<ParseError>> + Clone {
     sp_parser()
         .repeated()
         .ignore_then(comment_parser().or_not())
@@ -121,7 +121,7 @@ pub fn lt_not_end_parser<'a>() -> impl Parser<'a, &'a str, Lt, extra::Err<Rich<'
         .boxed()
 }
 
-pub fn lt_at_end_parser<'a>() -> impl Parser<'a, &'a str, Lt, extra::Err<Rich<'a, char>>> + Clone {
+pub fn lt_at_end_parser<'a>() -> impl Parser<'a, &'a str, Lt, extra::Err<ParseError>> + Clone {
     sp_parser()
         .repeated()
         .ignore_then(comment_parser().or_not())
@@ -131,7 +131,7 @@ pub fn lt_at_end_parser<'a>() -> impl Parser<'a, &'a str, Lt, extra::Err<Rich<'a
         .boxed()
 }
 
-pub fn lt_parser<'a>() -> impl Parser<'a, &'a str, Vec<Lt>, extra::Err<Rich<'a, char>>> + Clone {
+pub fn lt_parser<'a>() -> impl Parser<'a, &'a str, Vec<Lt>, extra::Err<ParseError>> + Clone {
     //TODO this looks really stupid but since the old lt consumes the end() token doing
     //lt.repeated() would cause a crash. I want to refactor this so it doesn't look so stupid
     choice((
@@ -141,9 +141,8 @@ pub fn lt_parser<'a>() -> impl Parser<'a, &'a str, Vec<Lt>, extra::Err<Rich<'a,
             .collect::<Vec<_>>()
             .then(lt_at_end_parser().or_not())
             .map(|(mut lts, optional_lt)| {
-                match optional_lt {
-                    Some(l) => lts.push(l),
-                    None => (),
+                if let Some(l) = optional_lt {
+                    lts.push(l)
                 };
                 lts
             }),
@@ -152,13 +151,13 @@ pub fn lt_parser<'a>() -> impl Parser<'a, &'a str, Vec<Lt>, extra::Err<Rich<'a,
     .boxed()
 }
 
-pub fn escaped_unicode_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+pub fn escaped_unicode_parser<'a>() -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone
+{
     text::digits(16)
         .to_slice()
         .validate(|digits, e, emitter| {
             char::from_u32(u32::from_str_radix(digits, 16).unwrap()).unwrap_or_else(|| {
-                emitter.emit(Rich::custom(e.span(), "invalid unicode character"));
+                emitter.emit(ParseError::InvalidUnicodeChar { span: e.span() });
                 '\u{FFFD}' // unicode replacement character
             })
         })
@@ -209,14 +208,23 @@ mod sp_tests {
         let test_str = "\n";
         assert_debug_snapshot!(
         sp_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''\n'' at 0..1 expected spacing,
+                Unexpected {
+                    span: 0..1,
+                    found: Some(
+                        '\n',
+                    ),
+                    expected: [
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 }
@@ -319,14 +327,23 @@ mod lt_tests {
         let test_str = "not a line ending";
         assert_debug_snapshot!(
         lt_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''n'' at 0..1 expected line terminator,
+                Unexpected {
+                    span: 0..1,
+                    found: Some(
+                        'n',
+                    ),
+                    expected: [
+                        "line terminator",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -412,7 +429,9 @@ mod unicode_parser_tests {
                 '�',
             ),
             errs: [
-                invalid unicode character at 3..9,
+                InvalidUnicodeChar {
+                    span: 3..9,
+                },
             ],
         }
         ",
@@ -424,14 +443,23 @@ mod unicode_parser_tests {
         let test_str = r#"\u1F6000"#;
         assert_debug_snapshot!(
         escaped_unicode_parser().then_ignore(end()).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''1'' at 2..3 expected ''{'',
+                Unexpected {
+                    span: 2..3,
+                    found: Some(
+                        '1',
+                    ),
+                    expected: [
+                        "'{'",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -456,14 +484,24 @@ mod unicode_parser_tests {
         let test_str = r#"\u{FFFH}"#;
         assert_debug_snapshot!(
         escaped_unicode_parser().then_ignore(end()).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''H'' at 6..7 expected digit, or ''}'',
+                Unexpected {
+                    span: 6..7,
+                    found: Some(
+                        'H',
+                    ),
+                    expected: [
+                        "'}'",
+                        "digit",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 }
@@ -1,3 +1,4 @@
+use super::error::ParseError;
 use super::{
     primitives::sp_parser,
     quoted_string::quoted_string_parser,
@@ -6,7 +7,7 @@ use super::{
 };
 use chumsky::prelude::*;
 
-pub fn query_parser<'a>() -> impl Parser<'a, &'a str, Query, extra::Err<Rich<'a, char>>> + Clone {
+pub fn query_parser<'a>() -> impl Parser<'a, &'a str, Query, extra::Err<ParseError>> + Clone {
     let query = choice((
         just("status").to(Query::Status),
         just("url").to(Query::Url),
@@ -109,6 +110,7 @@ mod query_tests {
                                 "Location",
                             ),
                         ],
+                        span: 7..17,
                     },
                 ),
             ),
@@ -205,6 +207,7 @@ mod query_tests {
                                 "LSID",
                             ),
                         ],
+                        span: 7..13,
                     },
                 ),
             ),
@@ -229,6 +232,7 @@ mod query_tests {
                                 "LSID[Value]",
                             ),
                         ],
+                        span: 7..20,
                     },
                 ),
             ),
@@ -253,6 +257,7 @@ mod query_tests {
                                 "LSID[Expires]",
                             ),
                         ],
+                        span: 7..22,
                     },
                 ),
             ),
@@ -277,6 +282,7 @@ mod query_tests {
                                 "LSID[Max-Age]",
                             ),
                         ],
+                        span: 7..22,
                     },
                 ),
             ),
@@ -301,6 +307,7 @@ mod query_tests {
                                 "LSID[Domain]",
                             ),
                         ],
+                        span: 7..21,
                     },
                 ),
             ),
@@ -325,6 +332,7 @@ mod query_tests {
                                 "LSID[Path]",
                             ),
                         ],
+                        span: 7..19,
                     },
                 ),
             ),
@@ -349,6 +357,7 @@ mod query_tests {
                                 "LSID[Secure]",
                             ),
                         ],
+                        span: 7..21,
                     },
                 ),
             ),
@@ -373,6 +382,7 @@ mod query_tests {
                                 "LSID[HttpOnly]",
                             ),
                         ],
+                        span: 7..23,
                     },
                 ),
             ),
@@ -397,6 +407,7 @@ mod query_tests {
                                 "LSID[SameSite]",
                             ),
                         ],
+                        span: 7..23,
                     },
                 ),
             ),
@@ -437,6 +448,7 @@ mod query_tests {
                                 "normalize-space(//div[@id='pet2'])",
                             ),
                         ],
+                        span: 6..42,
                     },
                 ),
             ),
@@ -461,6 +473,7 @@ mod query_tests {
                                 "$['type']",
                             ),
                         ],
+                        span: 9..20,
                     },
                 ),
             ),
@@ -486,6 +499,7 @@ mod query_tests {
                                     "[0-9]+",
                                 ),
                             ],
+                            span: 6..14,
                         },
                     ),
                 ),
@@ -511,6 +525,7 @@ mod query_tests {
                                 "api_key",
                             ),
                         ],
+                        span: 9..18,
                     },
                 ),
             ),
@@ -1,10 +1,11 @@
 use crate::parser::types::{InterpolatedString, InterpolatedStringPart};
 use chumsky::prelude::*;
 
+use super::error::ParseError;
 use super::{primitives::escaped_unicode_parser, template::template_parser, types::Template};
 
 fn quoted_string_escaped_char_parser<'a>(
-) -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, char, extra::Err<ParseError>> + Clone {
     let quoted_string_escaped_char = just('\\')
         .ignore_then(choice((
             just('\\').to('\\'),
@@ -21,7 +22,7 @@ fn quoted_string_escaped_char_parser<'a>(
 }
 
 fn quoted_str_part_parser<'a>(
-) -> impl Parser<'a, &'a str, InterpolatedStringPart, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedStringPart, extra::Err<ParseError>> + Clone {
     let quoted_str_part = choice((
         quoted_string_escaped_char_parser(),
         //opening curly brackes are valid as long as they are not followed by a
@@ -50,26 +51,27 @@ fn quoted_str_part_parser<'a>(
 /// ```
 pub fn generic_quoted_string_parser<
     'a,
-    T: Parser<'a, &'a str, Template, extra::Err<Rich<'a, char>>> + Clone,
+    T: Parser<'a, &'a str, Template, extra::Err<ParseError>> + Clone,
 >(
     template: T,
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let template_part = template.map(InterpolatedStringPart::Template);
 
     let parts = choice((quoted_str_part_parser(), template_part))
         .repeated()
-        .collect::<Vec<InterpolatedStringPart>>()
-        .map(|v| InterpolatedString { parts: v });
+        .collect::<Vec<InterpolatedStringPart>>();
 
-    let quoted_string = parts
+    parts
         .delimited_by(just("\""), just("\""))
-        .labelled("quoted_string");
-
-    quoted_string
+        .map_with(|v, e| InterpolatedString {
+            parts: v,
+            span: e.span(),
+        })
+        .labelled("quoted_string")
 }
 
 pub fn quoted_string_parser<'a>(
-) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone {
     let template_parser = template_parser();
     generic_quoted_string_parser(template_parser).boxed()
 }
@@ -94,6 +96,7 @@ mod quoted_string_tests {
                             "gb2312",
                         ),
                     ],
+                    span: 0..8,
                 },
             ),
             errs: [],
@@ -112,6 +115,7 @@ mod quoted_string_tests {
             output: Some(
                 InterpolatedString {
                     parts: [],
+                    span: 0..2,
                 },
             ),
             errs: [],
@@ -129,7 +133,18 @@ mod quoted_string_tests {
         ParseResult {
             output: None,
             errs: [
-                found end of input at 1..1 expected quoted_string_escaped_char, ''{'', something else, template, or ''"'',
+                Unexpected {
+                    span: 1..1,
+                    found: None,
+                    expected: [
+                        "'\"'",
+                        "'{'",
+                        "quoted_string_escaped_char",
+                        "something else",
+                        "template",
+                    ],
+                    labels: [],
+                },
             ],
         }
         "#,
@@ -145,7 +160,18 @@ mod quoted_string_tests {
         ParseResult {
             output: None,
             errs: [
-                found end of input at 6..6 expected quoted_string_escaped_char, ''{'', something else, template, or ''"'',
+                Unexpected {
+                    span: 6..6,
+                    found: None,
+                    expected: [
+                        "'\"'",
+                        "'{'",
+                        "quoted_string_escaped_char",
+                        "something else",
+                        "template",
+                    ],
+                    labels: [],
+                },
             ],
         }
         "#,
@@ -166,6 +192,7 @@ mod quoted_string_tests {
                             "\"I'm in quotes\"",
                         ),
                     ],
+                    span: 0..19,
                 },
             ),
             errs: [],
@@ -187,14 +214,20 @@ mod quoted_string_tests {
                         Template(
                             Template {
                                 expr: Expr {
-                                    variable: VariableName(
-                                        "seperator",
+                                    variable: (
+                                        VariableName(
+                                            "seperator",
+                                        ),
+                                        3..12,
                                     ),
                                     filters: [],
+                                    span: 3..12,
                                 },
+                                span: 1..14,
                             },
                         ),
                     ],
+                    span: 0..15,
                 },
             ),
             errs: [],
@@ -219,17 +252,23 @@ mod quoted_string_tests {
                         Template(
                             Template {
                                 expr: Expr {
-                                    variable: VariableName(
-                                        "count",
+                                    variable: (
+                                        VariableName(
+                                            "count",
+                                        ),
+                                        5..10,
                                     ),
                                     filters: [],
+                                    span: 5..10,
                                 },
+                                span: 3..12,
                             },
                         ),
                         Str(
                             " }",
                         ),
                     ],
+                    span: 0..15,
                 },
             ),
             errs: [],
@@ -243,14 +282,25 @@ mod quoted_string_tests {
         let test_str = "\"{{{count}}}\"";
         assert_debug_snapshot!(
         quoted_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''{'' at 2..3 expected spacing, expr, or something else,
+                Unexpected {
+                    span: 2..3,
+                    found: Some(
+                        '{',
+                    ),
+                    expected: [
+                        "expr",
+                        "something else",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -268,6 +318,7 @@ mod quoted_string_tests {
                             "escapedchars(\", \\, \u{8}, \u{c}, \r\n, \t)",
                         ),
                     ],
+                    span: 0..40,
                 },
             ),
             errs: [],
@@ -290,6 +341,7 @@ mod quoted_string_tests {
                             "escapedemoji(\u{1}\u{f600})",
                         ),
                     ],
+                    span: 0..32,
                 },
             ),
             errs: [],
@@ -304,14 +356,24 @@ mod quoted_string_tests {
         let test_str = "\"escapedemoji(\\u{FFFH})\"";
         assert_debug_snapshot!(
         quoted_string_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''H'' at 20..21 expected digit, or ''}'',
+                Unexpected {
+                    span: 20..21,
+                    found: Some(
+                        'H',
+                    ),
+                    expected: [
+                        "'}'",
+                        "digit",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -325,7 +387,23 @@ mod quoted_string_tests {
         ParseResult {
             output: None,
             errs: [
-                found ''g'' at 20..21 expected ''\\'', ''"'', ''b'', ''f'', ''n'', ''r'', ''t'', or ''u'',
+                Unexpected {
+                    span: 20..21,
+                    found: Some(
+                        'g',
+                    ),
+                    expected: [
+                        "'\"'",
+                        "'\\'",
+                        "'b'",
+                        "'f'",
+                        "'n'",
+                        "'r'",
+                        "'t'",
+                        "'u'",
+                    ],
+                    labels: [],
+                },
             ],
         }
         "#,
@@ -1,9 +1,10 @@
 use chumsky::prelude::*;
 
+use super::error::ParseError;
 use super::types::{InterpolatedString, Regex};
 
-pub fn regex_literal_parser<'a>(
-) -> impl Parser<'a, &'a str, Regex, extra::Err<Rich<'a, char>>> + Clone {
+pub fn regex_literal_parser<'a>() -> impl Parser<'a, &'a str, Regex, extra::Err<ParseError>> + Clone
+{
     let regex_escaped_char = just('\\')
         .ignore_then(choice((just('/').to('/'), none_of('\n').rewind().to('\\'))))
         .labelled("regex_escaped_char")
@@ -23,10 +24,10 @@ pub fn regex_literal_parser<'a>(
 
 pub fn regex_parser<
     'a,
-    T: Parser<'a, &'a str, InterpolatedString, extra::Err<Rich<'a, char>>> + Clone + 'a,
+    T: Parser<'a, &'a str, InterpolatedString, extra::Err<ParseError>> + Clone + 'a,
 >(
     quoted_string: T,
-) -> impl Parser<'a, &'a str, Regex, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, Regex, extra::Err<ParseError>> + Clone {
     choice((
         regex_literal_parser(),
         quoted_string.map(Regex::Interpolated),
@@ -73,6 +74,7 @@ mod regex_tests {
                 Interpolated(
                     InterpolatedString {
                         parts: [],
+                        span: 0..2,
                     },
                 ),
             ),
@@ -117,6 +119,7 @@ mod regex_tests {
                                 "\\d{10}",
                             ),
                         ],
+                        span: 0..9,
                     },
                 ),
             ),
@@ -144,17 +147,23 @@ mod regex_tests {
                             Template(
                                 Template {
                                     expr: Expr {
-                                        variable: VariableName(
-                                            "count",
+                                        variable: (
+                                            VariableName(
+                                                "count",
+                                            ),
+                                            8..13,
                                         ),
                                         filters: [],
+                                        span: 8..13,
                                     },
+                                    span: 6..15,
                                 },
                             ),
                             Str(
                                 " }",
                             ),
                         ],
+                        span: 0..18,
                     },
                 ),
             ),
@@ -170,14 +179,25 @@ mod regex_tests {
         let quoted_string = quoted_string_parser();
         assert_debug_snapshot!(
         regex_parser(quoted_string).parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''{'' at 5..6 expected spacing, expr, or something else,
+                Unexpected {
+                    span: 5..6,
+                    found: Some(
+                        '{',
+                    ),
+                    expected: [
+                        "expr",
+                        "something else",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 }
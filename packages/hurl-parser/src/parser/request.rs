@@ -1,23 +1,28 @@
 use super::body::body_parser;
+use super::error::ParseError;
 use super::header::headers_parser;
 use super::method::method_line_parser;
 use super::request_section::request_sections_parser;
 use super::types::Request;
 use chumsky::prelude::*;
 
-pub fn request_parser<'a>() -> impl Parser<'a, &'a str, Request, extra::Err<Rich<'a, char>>> + Clone
-{
+pub fn request_parser<'a>() -> impl Parser<'a, &'a str, Request, extra::Err<ParseError>> + Clone {
     let request = method_line_parser(false)
         .then(headers_parser())
         .then(request_sections_parser())
         .then(body_parser().or_not())
-        .map(
-            |((((method_value, url_value_string), headers), request_sections), body)| Request {
-                method: method_value,
-                url: url_value_string,
-                headers,
-                request_sections,
-                body,
+        .map_with(
+            |((((method_value, url_value_string), headers), sections), body), e| {
+                let (request_sections, misplaced_sections) = sections;
+                Request {
+                    method: method_value,
+                    url: url_value_string,
+                    headers,
+                    request_sections,
+                    misplaced_sections,
+                    body,
+                    span: e.span(),
+                }
             },
         )
         .labelled("request");
@@ -52,15 +57,23 @@ mod request_parser_tests {
                 Request {
                     method: Method {
                         value: "GET",
+                        span: 0..3,
                     },
-                    url: Missing,
+                    url: (
+                        Missing,
+                        3..3,
+                    ),
                     headers: [],
                     request_sections: [],
+                    misplaced_sections: [],
                     body: None,
+                    span: 0..3,
                 },
             ),
             errs: [
-                missing url at 3..3,
+                MissingUrl {
+                    span: 3..3,
+                },
             ],
         }
         "#,
@@ -78,15 +91,23 @@ mod request_parser_tests {
                 Request {
                     method: Method {
                         value: "GET",
+                        span: 0..3,
                     },
-                    url: Missing,
+                    url: (
+                        Missing,
+                        3..4,
+                    ),
                     headers: [],
                     request_sections: [],
+                    misplaced_sections: [],
                     body: None,
+                    span: 0..4,
                 },
             ),
             errs: [
-                missing url at 3..4,
+                MissingUrl {
+                    span: 3..4,
+                },
             ],
         }
         "#,
@@ -104,19 +125,26 @@ mod request_parser_tests {
                 Request {
                     method: Method {
                         value: "GET",
+                        span: 0..3,
                     },
-                    url: Url(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "https://example.org/",
-                                ),
-                            ],
-                        },
+                    url: (
+                        Url(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "https://example.org/",
+                                    ),
+                                ],
+                                span: 4..24,
+                            },
+                        ),
+                        4..24,
                     ),
                     headers: [],
                     request_sections: [],
+                    misplaced_sections: [],
                     body: None,
+                    span: 0..24,
                 },
             ),
             errs: [],
@@ -136,23 +164,33 @@ mod request_parser_tests {
                 Request {
                     method: Method {
                         value: "GeT",
+                        span: 0..3,
                     },
-                    url: Url(
-                        InterpolatedString {
-                            parts: [
-                                Str(
-                                    "https://example.org",
-                                ),
-                            ],
-                        },
+                    url: (
+                        Url(
+                            InterpolatedString {
+                                parts: [
+                                    Str(
+                                        "https://example.org",
+                                    ),
+                                ],
+                                span: 4..23,
+                            },
+                        ),
+                        4..23,
                     ),
                     headers: [],
                     request_sections: [],
+                    misplaced_sections: [],
                     body: None,
+                    span: 0..23,
                 },
             ),
             errs: [
-                Invalid character 'e'. Method must be ascii uppercase. at 0..3,
+                InvalidMethodCase {
+                    span: 0..3,
+                    found: 'e',
+                },
             ],
         }
         "#,
@@ -1,16 +1,18 @@
+use super::error::ParseError;
 use super::key_value::{key_parser, key_value_parser};
 use super::oneline_file::oneline_file_parser;
 use super::options::options_parser;
 use super::primitives::{lt_parser, sp_parser};
+use super::response_section::response_section_parser;
 use super::types::{
     BasicAuthSection, CookiesSection, FileKeyValue, FileValue, FormParamsSection, KeyValue,
     MultipartFormDataSection, MultipartFormParam, QueryStringParamsSection, RequestOptionsSection,
-    RequestSection,
+    RequestSection, ResponseSection, Span, UnknownSection,
 };
 use chumsky::prelude::*;
 
-fn file_param_parser<'a>(
-) -> impl Parser<'a, &'a str, FileKeyValue, extra::Err<Rich<'a, char>>> + Clone {
+fn file_param_parser<'a>() -> impl Parser<'a, &'a str, FileKeyValue, extra::Err<ParseError>> + Clone
+{
     let file_content_type = any()
         .filter(|c: &char| c.is_ascii_alphanumeric() || c == &'/' || c == &'+' || c == &'-')
         .repeated()
@@ -22,23 +24,28 @@ fn file_param_parser<'a>(
     let file_value = oneline_file_parser()
         .padded_by(sp_parser().repeated())
         .then(file_content_type.padded_by(sp_parser().repeated()).or_not())
-        .map(|(filename, content_type)| FileValue {
+        .map_with(|(filename, content_type), e| FileValue {
             filename,
             content_type,
+            span: e.span(),
         });
 
     let file_param = key_parser()
         .padded_by(sp_parser().repeated())
         .then_ignore(just(':').padded_by(sp_parser().repeated()))
         .then(file_value)
-        .map(|(key, value)| FileKeyValue { key, value })
+        .map(|(key, value)| FileKeyValue {
+            span: Span::new(key.span.start, value.span.end),
+            key,
+            value,
+        })
         .labelled("file_key_value");
 
     file_param.boxed()
 }
 
 pub fn request_section_parser<'a>(
-) -> impl Parser<'a, &'a str, RequestSection, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, RequestSection, extra::Err<ParseError>> + Clone {
     let key_values = key_value_parser()
         .then_ignore(lt_parser())
         .repeated()
@@ -49,16 +56,14 @@ pub fn request_section_parser<'a>(
         .then_ignore(lt_parser())
         .then(key_values.clone().validate(|key_values, e, emitter| {
             if key_values.len() > 1 {
-                emitter.emit(Rich::custom(
-                    e.span(),
-                    "Basic Auth can only be defined for one user.",
-                ));
+                emitter.emit(ParseError::BasicAuthMultipleUsers { span: e.span() });
             }
             key_values
         }))
-        .map(|(_, auth_key_values)| {
+        .map_with(|(_, auth_key_values), e| {
             RequestSection::BasicAuthSection(BasicAuthSection {
                 key_values: auth_key_values,
+                span: e.span(),
             })
         });
 
@@ -66,9 +71,10 @@ pub fn request_section_parser<'a>(
         .padded_by(sp_parser().repeated())
         .then_ignore(lt_parser())
         .then(key_values.clone())
-        .map(|(_, query_key_values)| {
+        .map_with(|(_, query_key_values), e| {
             RequestSection::QueryStringParamsSection(QueryStringParamsSection {
                 queries: query_key_values,
+                span: e.span(),
             })
         });
 
@@ -76,9 +82,10 @@ pub fn request_section_parser<'a>(
         .padded_by(sp_parser().repeated())
         .then_ignore(lt_parser())
         .then(key_values.clone())
-        .map(|(_, form_params)| {
+        .map_with(|(_, form_params), e| {
             RequestSection::FormParamsSection(FormParamsSection {
                 params: form_params,
+                span: e.span(),
             })
         });
 
@@ -95,9 +102,10 @@ pub fn request_section_parser<'a>(
                 .repeated()
                 .collect::<Vec<MultipartFormParam>>(),
         )
-        .map(|(_, file_params)| {
+        .map_with(|(_, file_params), e| {
             RequestSection::MultipartFormDataSection(MultipartFormDataSection {
                 params: file_params,
+                span: e.span(),
             })
         });
 
@@ -105,9 +113,10 @@ pub fn request_section_parser<'a>(
         .padded_by(sp_parser().repeated())
         .then_ignore(lt_parser())
         .then(key_values.clone())
-        .map(|(_, cookies_key_value)| {
+        .map_with(|(_, cookies_key_value), e| {
             RequestSection::CookiesSection(CookiesSection {
                 cookies: cookies_key_value,
+                span: e.span(),
             })
         });
 
@@ -115,7 +124,12 @@ pub fn request_section_parser<'a>(
         .padded_by(sp_parser().repeated())
         .then_ignore(lt_parser())
         .then(options_parser())
-        .map(|(_, options)| RequestSection::OptionsSection(RequestOptionsSection { options }));
+        .map_with(|(_, options), e| {
+            RequestSection::OptionsSection(RequestOptionsSection {
+                options,
+                span: e.span(),
+            })
+        });
 
     let request_section = basic_auth_section
         .or(query_string_params_section)
@@ -123,22 +137,96 @@ pub fn request_section_parser<'a>(
         .or(multipart_form_data_section)
         .or(cookies_section)
         .or(options_section);
-    // TODO and an unknown section for error handling
-    // .or(unknown_section);
 
     request_section.boxed()
 }
 
-pub fn request_sections_parser<'a>(
-) -> impl Parser<'a, &'a str, Vec<RequestSection>, extra::Err<Rich<'a, char>>> + Clone {
-    //TODO add tests
-    request_section_parser()
+/// Whether a line can belong to an unknown section. The section ends at the first line that
+/// starts something else: another section, a body, a status line or the next entry.
+fn is_unknown_section_line(line: &str) -> bool {
+    let line = line.trim_start_matches([' ', '\t']);
+    let word = line.split([' ', '\t']).next().unwrap_or_default();
+    let method_or_status = word.starts_with("HTTP")
+        || (word.len() < line.len() && word.chars().all(|c| c.is_ascii_uppercase()));
+    !(line.is_empty()
+        || method_or_status
+        || line.starts_with(['[', '{', '"', '`', '<', '#'])
+        || ["base64,", "hex,", "file,"]
+            .iter()
+            .any(|prefix| line.starts_with(prefix)))
+}
+
+/// A section with an unknown name like `[Capture]`. Its lines are kept as written so that
+/// the section can be reported and fixed rather than failing the whole entry.
+pub fn unknown_section_parser<'a>(
+) -> impl Parser<'a, &'a str, UnknownSection, extra::Err<ParseError>> + Clone {
+    let section_name = any()
+        .filter(|c: &char| c.is_ascii_alphanumeric())
         .repeated()
-        .collect::<Vec<RequestSection>>()
+        .at_least(1)
+        .to_slice()
+        //A json array like [true] is a body rather than a section
+        .filter(|name: &&str| {
+            name.starts_with(|c: char| c.is_ascii_alphabetic())
+                && !["true", "false", "null"].contains(name)
+        })
+        .delimited_by(just('['), just(']'));
+
+    let line = any()
+        .and_is(text::newline().not())
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .filter(|line: &&str| is_unknown_section_line(line))
+        .map(|line: &str| line.to_string())
+        .then_ignore(lt_parser());
+
+    section_name
+        .padded_by(sp_parser().repeated())
+        .then_ignore(lt_parser())
+        .then(line.repeated().collect::<Vec<String>>())
+        .map_with(|(section_name, lines), e| UnknownSection {
+            section_name: section_name.to_string(),
+            lines,
+            span: e.span(),
+        })
+        .labelled("unknown section")
         .boxed()
+}
 
-    //TODO sections can only be defined once per entry's request section. So you can't have [BasicAuth] defined
-    //twice and so should be a diagnostic error.
+/// The sections of one part of an entry. The sections that belong to the other part of the
+/// entry are accepted and kept apart so that they can be reported by the language server.
+pub fn sections_parser<'a, S, M>(
+    section: impl Parser<'a, &'a str, S, extra::Err<ParseError>> + Clone,
+    misplaced_section: impl Parser<'a, &'a str, M, extra::Err<ParseError>> + Clone,
+    unknown_section: fn(UnknownSection) -> S,
+) -> impl Parser<'a, &'a str, (Vec<S>, Vec<M>), extra::Err<ParseError>> + Clone {
+    choice((
+        section.map(|section| (Some(section), None)),
+        misplaced_section.map(|section| (None, Some(section))),
+        unknown_section_parser().map(move |section| (Some(unknown_section(section)), None)),
+    ))
+    .repeated()
+    .collect::<Vec<_>>()
+    .map(|sections| {
+        let (sections, misplaced_sections): (Vec<_>, Vec<_>) = sections.into_iter().unzip();
+        (
+            sections.into_iter().flatten().collect(),
+            misplaced_sections.into_iter().flatten().collect(),
+        )
+    })
+}
+
+pub fn request_sections_parser<'a>(
+) -> impl Parser<'a, &'a str, (Vec<RequestSection>, Vec<ResponseSection>), extra::Err<ParseError>> + Clone
+{
+    //TODO add tests
+    sections_parser(
+        request_section_parser(),
+        response_section_parser(),
+        RequestSection::UnknownSection,
+    )
+    .boxed()
 }
 
 #[cfg(test)]
@@ -165,6 +253,7 @@ mod request_section_tests {
                                             "joe",
                                         ),
                                     ],
+                                    span: 12..15,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -172,9 +261,12 @@ mod request_section_tests {
                                             "secret",
                                         ),
                                     ],
+                                    span: 17..23,
                                 },
+                                span: 12..23,
                             },
                         ],
+                        span: 0..23,
                     },
                 ),
             ),
@@ -203,6 +295,7 @@ mod request_section_tests {
                                             "joe",
                                         ),
                                     ],
+                                    span: 12..15,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -210,7 +303,9 @@ mod request_section_tests {
                                             "secret",
                                         ),
                                     ],
+                                    span: 17..23,
                                 },
+                                span: 12..23,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -219,6 +314,7 @@ mod request_section_tests {
                                             "alice",
                                         ),
                                     ],
+                                    span: 24..29,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -226,14 +322,19 @@ mod request_section_tests {
                                             "secret",
                                         ),
                                     ],
+                                    span: 30..36,
                                 },
+                                span: 24..36,
                             },
                         ],
+                        span: 0..36,
                     },
                 ),
             ),
             errs: [
-                Basic Auth can only be defined for one user. at 12..36,
+                BasicAuthMultipleUsers {
+                    span: 12..36,
+                },
             ],
         }
         "#,
@@ -258,6 +359,7 @@ mod request_section_tests {
                                             "joe",
                                         ),
                                     ],
+                                    span: 32..35,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -265,9 +367,12 @@ mod request_section_tests {
                                             "secret",
                                         ),
                                     ],
+                                    span: 37..43,
                                 },
+                                span: 32..43,
                             },
                         ],
+                        span: 0..43,
                     },
                 ),
             ),
@@ -295,6 +400,7 @@ mod request_section_tests {
                                             "joe",
                                         ),
                                     ],
+                                    span: 16..19,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -302,9 +408,12 @@ mod request_section_tests {
                                             "secret",
                                         ),
                                     ],
+                                    span: 21..27,
                                 },
+                                span: 16..27,
                             },
                         ],
+                        span: 0..27,
                     },
                 ),
             ),
@@ -332,21 +441,29 @@ mod request_section_tests {
                                             "search",
                                         ),
                                     ],
+                                    span: 20..26,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "my-search",
+                                                    variable: (
+                                                        VariableName(
+                                                            "my-search",
+                                                        ),
+                                                        30..39,
                                                     ),
                                                     filters: [],
+                                                    span: 30..39,
                                                 },
+                                                span: 28..41,
                                             },
                                         ),
                                     ],
+                                    span: 28..41,
                                 },
+                                span: 20..41,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -355,6 +472,7 @@ mod request_section_tests {
                                             "order",
                                         ),
                                     ],
+                                    span: 42..47,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -362,7 +480,9 @@ mod request_section_tests {
                                             "desc",
                                         ),
                                     ],
+                                    span: 49..53,
                                 },
+                                span: 42..53,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -371,6 +491,7 @@ mod request_section_tests {
                                             "count",
                                         ),
                                     ],
+                                    span: 54..59,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -378,9 +499,12 @@ mod request_section_tests {
                                             "420",
                                         ),
                                     ],
+                                    span: 61..64,
                                 },
+                                span: 54..64,
                             },
                         ],
+                        span: 0..64,
                     },
                 ),
             ),
@@ -408,21 +532,29 @@ mod request_section_tests {
                                             "search",
                                         ),
                                     ],
+                                    span: 8..14,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "my-search",
+                                                    variable: (
+                                                        VariableName(
+                                                            "my-search",
+                                                        ),
+                                                        18..27,
                                                     ),
                                                     filters: [],
+                                                    span: 18..27,
                                                 },
+                                                span: 16..29,
                                             },
                                         ),
                                     ],
+                                    span: 16..29,
                                 },
+                                span: 8..29,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -431,6 +563,7 @@ mod request_section_tests {
                                             "order",
                                         ),
                                     ],
+                                    span: 30..35,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -438,7 +571,9 @@ mod request_section_tests {
                                             "desc",
                                         ),
                                     ],
+                                    span: 37..41,
                                 },
+                                span: 30..41,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -447,6 +582,7 @@ mod request_section_tests {
                                             "count",
                                         ),
                                     ],
+                                    span: 42..47,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -454,9 +590,12 @@ mod request_section_tests {
                                             "420",
                                         ),
                                     ],
+                                    span: 49..52,
                                 },
+                                span: 42..52,
                             },
                         ],
+                        span: 0..52,
                     },
                 ),
             ),
@@ -484,21 +623,29 @@ mod request_section_tests {
                                             "search",
                                         ),
                                     ],
+                                    span: 24..30,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "my-search",
+                                                    variable: (
+                                                        VariableName(
+                                                            "my-search",
+                                                        ),
+                                                        34..43,
                                                     ),
                                                     filters: [],
+                                                    span: 34..43,
                                                 },
+                                                span: 32..45,
                                             },
                                         ),
                                     ],
+                                    span: 32..45,
                                 },
+                                span: 24..45,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -507,6 +654,7 @@ mod request_section_tests {
                                             "order",
                                         ),
                                     ],
+                                    span: 67..72,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -514,7 +662,9 @@ mod request_section_tests {
                                             "desc   ",
                                         ),
                                     ],
+                                    span: 74..81,
                                 },
+                                span: 67..81,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -523,6 +673,7 @@ mod request_section_tests {
                                             "count",
                                         ),
                                     ],
+                                    span: 94..99,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -530,9 +681,12 @@ mod request_section_tests {
                                             "420",
                                         ),
                                     ],
+                                    span: 101..104,
                                 },
+                                span: 94..104,
                             },
                         ],
+                        span: 0..104,
                     },
                 ),
             ),
@@ -561,21 +715,29 @@ mod request_section_tests {
                                             "search",
                                         ),
                                     ],
+                                    span: 24..30,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "my-search",
+                                                    variable: (
+                                                        VariableName(
+                                                            "my-search",
+                                                        ),
+                                                        34..43,
                                                     ),
                                                     filters: [],
+                                                    span: 34..43,
                                                 },
+                                                span: 32..45,
                                             },
                                         ),
                                     ],
+                                    span: 32..45,
                                 },
+                                span: 24..45,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -584,6 +746,7 @@ mod request_section_tests {
                                             "order",
                                         ),
                                     ],
+                                    span: 50..55,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -591,7 +754,9 @@ mod request_section_tests {
                                             "desc   ",
                                         ),
                                     ],
+                                    span: 57..64,
                                 },
+                                span: 50..64,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -600,6 +765,7 @@ mod request_section_tests {
                                             "count",
                                         ),
                                     ],
+                                    span: 66..71,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -607,9 +773,12 @@ mod request_section_tests {
                                             "420",
                                         ),
                                     ],
+                                    span: 73..76,
                                 },
+                                span: 66..76,
                             },
                         ],
+                        span: 0..76,
                     },
                 ),
             ),
@@ -637,21 +806,29 @@ mod request_section_tests {
                                             "search",
                                         ),
                                     ],
+                                    span: 20..26,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "my-search",
+                                                    variable: (
+                                                        VariableName(
+                                                            "my-search",
+                                                        ),
+                                                        30..39,
                                                     ),
                                                     filters: [],
+                                                    span: 30..39,
                                                 },
+                                                span: 28..41,
                                             },
                                         ),
                                     ],
+                                    span: 28..41,
                                 },
+                                span: 20..41,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -660,6 +837,7 @@ mod request_section_tests {
                                             "order",
                                         ),
                                     ],
+                                    span: 60..65,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -667,7 +845,9 @@ mod request_section_tests {
                                             "desc",
                                         ),
                                     ],
+                                    span: 67..71,
                                 },
+                                span: 60..71,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -676,6 +856,7 @@ mod request_section_tests {
                                             "count",
                                         ),
                                     ],
+                                    span: 83..88,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -683,9 +864,12 @@ mod request_section_tests {
                                             "420",
                                         ),
                                     ],
+                                    span: 90..93,
                                 },
+                                span: 83..93,
                             },
                         ],
+                        span: 0..93,
                     },
                 ),
             ),
@@ -713,21 +897,29 @@ mod request_section_tests {
                                             "token",
                                         ),
                                     ],
+                                    span: 13..18,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "token",
+                                                    variable: (
+                                                        VariableName(
+                                                            "token",
+                                                        ),
+                                                        22..27,
                                                     ),
                                                     filters: [],
+                                                    span: 22..27,
                                                 },
+                                                span: 20..29,
                                             },
                                         ),
                                     ],
+                                    span: 20..29,
                                 },
+                                span: 13..29,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -736,6 +928,7 @@ mod request_section_tests {
                                             "email",
                                         ),
                                     ],
+                                    span: 30..35,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -743,7 +936,9 @@ mod request_section_tests {
                                             "john.smith@example.com",
                                         ),
                                     ],
+                                    span: 37..59,
                                 },
+                                span: 30..59,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -752,21 +947,29 @@ mod request_section_tests {
                                             "accountNumber",
                                         ),
                                     ],
+                                    span: 60..73,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "accountNumber",
+                                                    variable: (
+                                                        VariableName(
+                                                            "accountNumber",
+                                                        ),
+                                                        77..90,
                                                     ),
                                                     filters: [],
+                                                    span: 77..90,
                                                 },
+                                                span: 75..92,
                                             },
                                         ),
                                     ],
+                                    span: 75..92,
                                 },
+                                span: 60..92,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -775,6 +978,7 @@ mod request_section_tests {
                                             "enabledEmailNotifications",
                                         ),
                                     ],
+                                    span: 93..118,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -782,9 +986,12 @@ mod request_section_tests {
                                             "true",
                                         ),
                                     ],
+                                    span: 120..124,
                                 },
+                                span: 93..124,
                             },
                         ],
+                        span: 0..124,
                     },
                 ),
             ),
@@ -812,21 +1019,29 @@ mod request_section_tests {
                                             "token",
                                         ),
                                     ],
+                                    span: 7..12,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "token",
+                                                    variable: (
+                                                        VariableName(
+                                                            "token",
+                                                        ),
+                                                        16..21,
                                                     ),
                                                     filters: [],
+                                                    span: 16..21,
                                                 },
+                                                span: 14..23,
                                             },
                                         ),
                                     ],
+                                    span: 14..23,
                                 },
+                                span: 7..23,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -835,6 +1050,7 @@ mod request_section_tests {
                                             "email",
                                         ),
                                     ],
+                                    span: 24..29,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -842,7 +1058,9 @@ mod request_section_tests {
                                             "john.smith@example.com",
                                         ),
                                     ],
+                                    span: 31..53,
                                 },
+                                span: 24..53,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -851,21 +1069,29 @@ mod request_section_tests {
                                             "accountNumber",
                                         ),
                                     ],
+                                    span: 54..67,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "accountNumber",
+                                                    variable: (
+                                                        VariableName(
+                                                            "accountNumber",
+                                                        ),
+                                                        71..84,
                                                     ),
                                                     filters: [],
+                                                    span: 71..84,
                                                 },
+                                                span: 69..86,
                                             },
                                         ),
                                     ],
+                                    span: 69..86,
                                 },
+                                span: 54..86,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -874,6 +1100,7 @@ mod request_section_tests {
                                             "enabledEmailNotifications",
                                         ),
                                     ],
+                                    span: 87..112,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -881,9 +1108,12 @@ mod request_section_tests {
                                             "true",
                                         ),
                                     ],
+                                    span: 114..118,
                                 },
+                                span: 87..118,
                             },
                         ],
+                        span: 0..118,
                     },
                 ),
             ),
@@ -912,6 +1142,7 @@ mod request_section_tests {
                                                 "field1",
                                             ),
                                         ],
+                                        span: 20..26,
                                     },
                                     value: InterpolatedString {
                                         parts: [
@@ -919,7 +1150,9 @@ mod request_section_tests {
                                                 "value1",
                                             ),
                                         ],
+                                        span: 28..34,
                                     },
+                                    span: 20..34,
                                 },
                             ),
                             FileParam(
@@ -930,6 +1163,7 @@ mod request_section_tests {
                                                 "field2",
                                             ),
                                         ],
+                                        span: 35..41,
                                     },
                                     value: FileValue {
                                         filename: InterpolatedString {
@@ -938,9 +1172,12 @@ mod request_section_tests {
                                                     "example.txt",
                                                 ),
                                             ],
+                                            span: 48..59,
                                         },
                                         content_type: None,
+                                        span: 43..60,
                                     },
+                                    span: 35..60,
                                 },
                             ),
                             FileParam(
@@ -951,6 +1188,7 @@ mod request_section_tests {
                                                 "field3",
                                             ),
                                         ],
+                                        span: 61..67,
                                     },
                                     value: FileValue {
                                         filename: InterpolatedString {
@@ -959,14 +1197,18 @@ mod request_section_tests {
                                                     "example.zip",
                                                 ),
                                             ],
+                                            span: 74..85,
                                         },
                                         content_type: Some(
                                             "application/zip",
                                         ),
+                                        span: 69..102,
                                     },
+                                    span: 61..102,
                                 },
                             ),
                         ],
+                        span: 0..102,
                     },
                 ),
             ),
@@ -995,6 +1237,7 @@ mod request_section_tests {
                                                 "field1",
                                             ),
                                         ],
+                                        span: 12..18,
                                     },
                                     value: InterpolatedString {
                                         parts: [
@@ -1002,7 +1245,9 @@ mod request_section_tests {
                                                 "value1",
                                             ),
                                         ],
+                                        span: 20..26,
                                     },
+                                    span: 12..26,
                                 },
                             ),
                             FileParam(
@@ -1013,6 +1258,7 @@ mod request_section_tests {
                                                 "field2",
                                             ),
                                         ],
+                                        span: 27..33,
                                     },
                                     value: FileValue {
                                         filename: InterpolatedString {
@@ -1021,9 +1267,12 @@ mod request_section_tests {
                                                     "example.txt",
                                                 ),
                                             ],
+                                            span: 40..51,
                                         },
                                         content_type: None,
+                                        span: 35..52,
                                     },
+                                    span: 27..52,
                                 },
                             ),
                             FileParam(
@@ -1034,6 +1283,7 @@ mod request_section_tests {
                                                 "field3",
                                             ),
                                         ],
+                                        span: 53..59,
                                     },
                                     value: FileValue {
                                         filename: InterpolatedString {
@@ -1042,14 +1292,18 @@ mod request_section_tests {
                                                     "example.zip",
                                                 ),
                                             ],
+                                            span: 66..77,
                                         },
                                         content_type: Some(
                                             "application/zip",
                                         ),
+                                        span: 61..94,
                                     },
+                                    span: 53..94,
                                 },
                             ),
                         ],
+                        span: 0..94,
                     },
                 ),
             ),
@@ -1074,6 +1328,7 @@ mod request_section_tests {
                                 "field2",
                             ),
                         ],
+                        span: 0..6,
                     },
                     value: FileValue {
                         filename: InterpolatedString {
@@ -1082,9 +1337,12 @@ mod request_section_tests {
                                     "example.txt",
                                 ),
                             ],
+                            span: 13..24,
                         },
                         content_type: None,
+                        span: 8..25,
                     },
+                    span: 0..25,
                 },
             ),
             errs: [],
@@ -1108,6 +1366,7 @@ mod request_section_tests {
                                 "field3",
                             ),
                         ],
+                        span: 0..6,
                     },
                     value: FileValue {
                         filename: InterpolatedString {
@@ -1116,11 +1375,14 @@ mod request_section_tests {
                                     "example.zip",
                                 ),
                             ],
+                            span: 13..24,
                         },
                         content_type: Some(
                             "application/zip",
                         ),
+                        span: 8..41,
                     },
+                    span: 0..41,
                 },
             ),
             errs: [],
@@ -1144,6 +1406,7 @@ mod request_section_tests {
                                 "field3",
                             ),
                         ],
+                        span: 0..6,
                     },
                     value: FileValue {
                         filename: InterpolatedString {
@@ -1152,11 +1415,14 @@ mod request_section_tests {
                                     "example.zip",
                                 ),
                             ],
+                            span: 19..30,
                         },
                         content_type: Some(
                             "application/zip",
                         ),
+                        span: 11..51,
                     },
+                    span: 0..51,
                 },
             ),
             errs: [],
@@ -1183,6 +1449,7 @@ mod request_section_tests {
                                             "theme",
                                         ),
                                     ],
+                                    span: 10..15,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -1190,7 +1457,9 @@ mod request_section_tests {
                                             "dark",
                                         ),
                                     ],
+                                    span: 17..21,
                                 },
+                                span: 10..21,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -1199,23 +1468,32 @@ mod request_section_tests {
                                             "sessionToken",
                                         ),
                                     ],
+                                    span: 22..34,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "token",
+                                                    variable: (
+                                                        VariableName(
+                                                            "token",
+                                                        ),
+                                                        38..43,
                                                     ),
                                                     filters: [],
+                                                    span: 38..43,
                                                 },
+                                                span: 36..45,
                                             },
                                         ),
                                     ],
+                                    span: 36..45,
                                 },
+                                span: 22..45,
                             },
                         ],
+                        span: 0..45,
                     },
                 ),
             ),
@@ -1244,6 +1522,7 @@ mod request_section_tests {
                                             "theme",
                                         ),
                                     ],
+                                    span: 33..38,
                                 },
                                 value: InterpolatedString {
                                     parts: [
@@ -1251,7 +1530,9 @@ mod request_section_tests {
                                             "dark",
                                         ),
                                     ],
+                                    span: 40..44,
                                 },
+                                span: 33..44,
                             },
                             KeyValue {
                                 key: InterpolatedString {
@@ -1260,23 +1541,32 @@ mod request_section_tests {
                                             "sessionToken",
                                         ),
                                     ],
+                                    span: 49..61,
                                 },
                                 value: InterpolatedString {
                                     parts: [
                                         Template(
                                             Template {
                                                 expr: Expr {
-                                                    variable: VariableName(
-                                                        "token",
+                                                    variable: (
+                                                        VariableName(
+                                                            "token",
+                                                        ),
+                                                        65..70,
                                                     ),
                                                     filters: [],
+                                                    span: 65..70,
                                                 },
+                                                span: 63..72,
                                             },
                                         ),
                                     ],
+                                    span: 63..72,
                                 },
+                                span: 49..72,
                             },
                         ],
+                        span: 0..72,
                     },
                 ),
             ),
@@ -1297,29 +1587,51 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            Variable(
-                                VariableDefinitionOption {
-                                    name: "host",
-                                    value: String(
-                                        InterpolatedString {
-                                            parts: [
-                                                Str(
-                                                    "example.net",
-                                                ),
-                                            ],
-                                        },
-                                    ),
-                                },
-                            ),
-                            Variable(
-                                VariableDefinitionOption {
-                                    name: "id",
-                                    value: Integer(
-                                        1234,
-                                    ),
-                                },
+                            (
+                                Variable(
+                                    VariableDefinitionOption {
+                                        name: (
+                                            "host",
+                                            20..24,
+                                        ),
+                                        value: (
+                                            String(
+                                                InterpolatedString {
+                                                    parts: [
+                                                        Str(
+                                                            "example.net",
+                                                        ),
+                                                    ],
+                                                    span: 25..36,
+                                                },
+                                            ),
+                                            25..36,
+                                        ),
+                                        span: 20..36,
+                                    },
+                                ),
+                                10..36,
+                            ),
+                            (
+                                Variable(
+                                    VariableDefinitionOption {
+                                        name: (
+                                            "id",
+                                            47..49,
+                                        ),
+                                        value: (
+                                            Integer(
+                                                1234,
+                                            ),
+                                            50..54,
+                                        ),
+                                        span: 47..54,
+                                    },
+                                ),
+                                37..54,
                             ),
                         ],
+                        span: 0..54,
                     },
                 ),
             ),
@@ -1340,87 +1652,136 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            Compressed(
-                                Literal(
-                                    true,
+                            (
+                                Compressed(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                10..26,
                             ),
-                            Location(
-                                Literal(
-                                    true,
+                            (
+                                Location(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                27..41,
                             ),
-                            LocationTrusted(
-                                Literal(
-                                    true,
+                            (
+                                LocationTrusted(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                42..64,
                             ),
-                            Http10(
-                                Literal(
-                                    false,
+                            (
+                                Http10(
+                                    Literal(
+                                        false,
+                                    ),
                                 ),
+                                65..79,
                             ),
-                            Http11(
-                                Literal(
-                                    false,
+                            (
+                                Http11(
+                                    Literal(
+                                        false,
+                                    ),
                                 ),
+                                80..94,
                             ),
-                            Http2(
-                                Literal(
-                                    false,
+                            (
+                                Http2(
+                                    Literal(
+                                        false,
+                                    ),
                                 ),
+                                95..107,
                             ),
-                            Http3(
-                                Literal(
-                                    true,
+                            (
+                                Http3(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                108..119,
                             ),
-                            Insecure(
-                                Literal(
-                                    false,
+                            (
+                                Insecure(
+                                    Literal(
+                                        false,
+                                    ),
                                 ),
+                                120..135,
                             ),
-                            Ipv4(
-                                Literal(
-                                    false,
+                            (
+                                Ipv4(
+                                    Literal(
+                                        false,
+                                    ),
                                 ),
+                                136..147,
                             ),
-                            Ipv6(
-                                Literal(
-                                    true,
+                            (
+                                Ipv6(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                148..158,
                             ),
-                            Netrc(
-                                Literal(
-                                    true,
+                            (
+                                Netrc(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                159..170,
                             ),
-                            NetrcOptional(
-                                Literal(
-                                    true,
+                            (
+                                NetrcOptional(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                171..191,
                             ),
-                            PathAsIs(
-                                Literal(
-                                    true,
+                            (
+                                PathAsIs(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                192..208,
                             ),
-                            Skip(
-                                Literal(
-                                    false,
+                            (
+                                Skip(
+                                    Literal(
+                                        false,
+                                    ),
                                 ),
+                                209..220,
                             ),
-                            Verbose(
-                                Literal(
-                                    true,
+                            (
+                                Verbose(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                221..234,
                             ),
-                            VeryVerbose(
-                                Literal(
-                                    true,
+                            (
+                                VeryVerbose(
+                                    Literal(
+                                        true,
+                                    ),
                                 ),
+                                235..253,
                             ),
                         ],
+                        span: 0..253,
                     },
                 ),
             ),
@@ -1442,43 +1803,68 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            ConnectTimeout(
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "connectTimeout",
-                                            ),
-                                            filters: [],
+                            (
+                                ConnectTimeout(
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "connectTimeout",
+                                                    ),
+                                                    29..43,
+                                                ),
+                                                filters: [],
+                                                span: 29..43,
+                                            },
+                                            span: 27..45,
                                         },
-                                    },
+                                    ),
                                 ),
-                            ),
-                            Delay(
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "delay",
-                                            ),
-                                            filters: [],
+                                10..45,
+                            ),
+                            (
+                                Delay(
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "delay",
+                                                    ),
+                                                    55..60,
+                                                ),
+                                                filters: [],
+                                                span: 55..60,
+                                            },
+                                            span: 53..62,
                                         },
-                                    },
+                                    ),
                                 ),
-                            ),
-                            RetryInterval(
-                                Template(
-                                    Template {
-                                        expr: Expr {
-                                            variable: VariableName(
-                                                "retryInterval",
-                                            ),
-                                            filters: [],
+                                46..62,
+                            ),
+                            (
+                                RetryInterval(
+                                    Template(
+                                        Template {
+                                            expr: Expr {
+                                                variable: (
+                                                    VariableName(
+                                                        "retryInterval",
+                                                    ),
+                                                    81..94,
+                                                ),
+                                                filters: [],
+                                                span: 81..94,
+                                            },
+                                            span: 79..96,
                                         },
-                                    },
+                                    ),
                                 ),
+                                63..96,
                             ),
                         ],
+                        span: 0..96,
                     },
                 ),
             ),
@@ -1499,31 +1885,44 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            ConnectTimeout(
-                                Literal(
-                                    Duration {
-                                        duration: 5,
-                                        unit: None,
-                                    },
+                            (
+                                ConnectTimeout(
+                                    Literal(
+                                        Duration {
+                                            duration: 5,
+                                            unit: None,
+                                            span: 27..28,
+                                        },
+                                    ),
                                 ),
-                            ),
-                            Delay(
-                                Literal(
-                                    Duration {
-                                        duration: 4,
-                                        unit: None,
-                                    },
+                                10..28,
+                            ),
+                            (
+                                Delay(
+                                    Literal(
+                                        Duration {
+                                            duration: 4,
+                                            unit: None,
+                                            span: 36..37,
+                                        },
+                                    ),
                                 ),
-                            ),
-                            RetryInterval(
-                                Literal(
-                                    Duration {
-                                        duration: 500,
-                                        unit: None,
-                                    },
+                                29..37,
+                            ),
+                            (
+                                RetryInterval(
+                                    Literal(
+                                        Duration {
+                                            duration: 500,
+                                            unit: None,
+                                            span: 54..57,
+                                        },
+                                    ),
                                 ),
+                                38..57,
                             ),
                         ],
+                        span: 0..57,
                     },
                 ),
             ),
@@ -1544,37 +1943,50 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            ConnectTimeout(
-                                Literal(
-                                    Duration {
-                                        duration: 5,
-                                        unit: Some(
-                                            Second,
-                                        ),
-                                    },
+                            (
+                                ConnectTimeout(
+                                    Literal(
+                                        Duration {
+                                            duration: 5,
+                                            unit: Some(
+                                                Second,
+                                            ),
+                                            span: 27..29,
+                                        },
+                                    ),
                                 ),
-                            ),
-                            Delay(
-                                Literal(
-                                    Duration {
-                                        duration: 4,
-                                        unit: Some(
-                                            Second,
-                                        ),
-                                    },
+                                10..29,
+                            ),
+                            (
+                                Delay(
+                                    Literal(
+                                        Duration {
+                                            duration: 4,
+                                            unit: Some(
+                                                Second,
+                                            ),
+                                            span: 37..39,
+                                        },
+                                    ),
                                 ),
-                            ),
-                            RetryInterval(
-                                Literal(
-                                    Duration {
-                                        duration: 500,
-                                        unit: Some(
-                                            Second,
-                                        ),
-                                    },
+                                30..39,
+                            ),
+                            (
+                                RetryInterval(
+                                    Literal(
+                                        Duration {
+                                            duration: 500,
+                                            unit: Some(
+                                                Second,
+                                            ),
+                                            span: 56..60,
+                                        },
+                                    ),
                                 ),
+                                40..60,
                             ),
                         ],
+                        span: 0..60,
                     },
                 ),
             ),
@@ -1595,37 +2007,50 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            ConnectTimeout(
-                                Literal(
-                                    Duration {
-                                        duration: 5,
-                                        unit: Some(
-                                            Millisecond,
-                                        ),
-                                    },
+                            (
+                                ConnectTimeout(
+                                    Literal(
+                                        Duration {
+                                            duration: 5,
+                                            unit: Some(
+                                                Millisecond,
+                                            ),
+                                            span: 27..30,
+                                        },
+                                    ),
                                 ),
-                            ),
-                            Delay(
-                                Literal(
-                                    Duration {
-                                        duration: 4,
-                                        unit: Some(
-                                            Millisecond,
-                                        ),
-                                    },
+                                10..30,
+                            ),
+                            (
+                                Delay(
+                                    Literal(
+                                        Duration {
+                                            duration: 4,
+                                            unit: Some(
+                                                Millisecond,
+                                            ),
+                                            span: 38..41,
+                                        },
+                                    ),
                                 ),
-                            ),
-                            RetryInterval(
-                                Literal(
-                                    Duration {
-                                        duration: 500,
-                                        unit: Some(
-                                            Millisecond,
-                                        ),
-                                    },
+                                31..41,
+                            ),
+                            (
+                                RetryInterval(
+                                    Literal(
+                                        Duration {
+                                            duration: 500,
+                                            unit: Some(
+                                                Millisecond,
+                                            ),
+                                            span: 58..63,
+                                        },
+                                    ),
                                 ),
+                                42..63,
                             ),
                         ],
+                        span: 0..63,
                     },
                 ),
             ),
@@ -1646,37 +2071,50 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            ConnectTimeout(
-                                Literal(
-                                    Duration {
-                                        duration: 5,
-                                        unit: Some(
-                                            Minute,
-                                        ),
-                                    },
+                            (
+                                ConnectTimeout(
+                                    Literal(
+                                        Duration {
+                                            duration: 5,
+                                            unit: Some(
+                                                Minute,
+                                            ),
+                                            span: 27..29,
+                                        },
+                                    ),
                                 ),
-                            ),
-                            Delay(
-                                Literal(
-                                    Duration {
-                                        duration: 4,
-                                        unit: Some(
-                                            Minute,
-                                        ),
-                                    },
+                                10..29,
+                            ),
+                            (
+                                Delay(
+                                    Literal(
+                                        Duration {
+                                            duration: 4,
+                                            unit: Some(
+                                                Minute,
+                                            ),
+                                            span: 37..39,
+                                        },
+                                    ),
                                 ),
-                            ),
-                            RetryInterval(
-                                Literal(
-                                    Duration {
-                                        duration: 500,
-                                        unit: Some(
-                                            Minute,
-                                        ),
-                                    },
+                                30..39,
+                            ),
+                            (
+                                RetryInterval(
+                                    Literal(
+                                        Duration {
+                                            duration: 500,
+                                            unit: Some(
+                                                Minute,
+                                            ),
+                                            span: 56..60,
+                                        },
+                                    ),
                                 ),
+                                40..60,
                             ),
                         ],
+                        span: 0..60,
                     },
                 ),
             ),
@@ -1697,15 +2135,20 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            RetryInterval(
-                                Literal(
-                                    Duration {
-                                        duration: 500,
-                                        unit: None,
-                                    },
+                            (
+                                RetryInterval(
+                                    Literal(
+                                        Duration {
+                                            duration: 500,
+                                            unit: None,
+                                            span: 26..29,
+                                        },
+                                    ),
                                 ),
+                                10..29,
                             ),
                         ],
+                        span: 0..29,
                     },
                 ),
             ),
@@ -1726,15 +2169,20 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            Delay(
-                                Literal(
-                                    Duration {
-                                        duration: 4,
-                                        unit: None,
-                                    },
+                            (
+                                Delay(
+                                    Literal(
+                                        Duration {
+                                            duration: 4,
+                                            unit: None,
+                                            span: 17..18,
+                                        },
+                                    ),
                                 ),
+                                10..18,
                             ),
                         ],
+                        span: 0..18,
                     },
                 ),
             ),
@@ -1755,27 +2203,40 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            LimitRate(
-                                Literal(
-                                    59,
+                            (
+                                LimitRate(
+                                    Literal(
+                                        59,
+                                    ),
                                 ),
+                                10..24,
                             ),
-                            MaxRedirs(
-                                Literal(
-                                    109,
+                            (
+                                MaxRedirs(
+                                    Literal(
+                                        109,
+                                    ),
                                 ),
+                                25..40,
                             ),
-                            Repeat(
-                                Literal(
-                                    10,
+                            (
+                                Repeat(
+                                    Literal(
+                                        10,
+                                    ),
                                 ),
+                                41..51,
                             ),
-                            Retry(
-                                Literal(
-                                    5,
+                            (
+                                Retry(
+                                    Literal(
+                                        5,
+                                    ),
                                 ),
+                                52..60,
                             ),
                         ],
+                        span: 0..60,
                     },
                 ),
             ),
@@ -1796,12 +2257,16 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            LimitRate(
-                                Literal(
-                                    18446744073709551615,
+                            (
+                                LimitRate(
+                                    Literal(
+                                        18446744073709551615,
+                                    ),
                                 ),
+                                10..42,
                             ),
                         ],
+                        span: 0..42,
                     },
                 ),
             ),
@@ -1823,17 +2288,23 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            LimitRate(
-                                BigInteger(
-                                    "18446744073709551616",
+                            (
+                                LimitRate(
+                                    BigInteger(
+                                        "18446744073709551616",
+                                    ),
                                 ),
+                                10..42,
                             ),
                         ],
+                        span: 0..42,
                     },
                 ),
             ),
             errs: [
-                The integer value is larger than 18446744073709551615 and is not valid for 64bit version of hurl at 22..42,
+                IntegerTooLarge {
+                    span: 22..42,
+                },
             ],
         }
         "#,
@@ -1851,12 +2322,16 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            LimitRate(
-                                Literal(
-                                    4294967295,
+                            (
+                                LimitRate(
+                                    Literal(
+                                        4294967295,
+                                    ),
                                 ),
+                                10..32,
                             ),
                         ],
+                        span: 0..32,
                     },
                 ),
             ),
@@ -1877,70 +2352,99 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            AwsSigv4(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "aws:amz:eu-central-1:sts",
-                                        ),
-                                    ],
-                                },
+                            (
+                                AwsSigv4(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "aws:amz:eu-central-1:sts",
+                                            ),
+                                        ],
+                                        span: 21..45,
+                                    },
+                                ),
+                                10..45,
                             ),
-                            ConnectTo(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "example.com:8000:127.0.0.1:8080",
-                                        ),
-                                    ],
-                                },
+                            (
+                                ConnectTo(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "example.com:8000:127.0.0.1:8080",
+                                            ),
+                                        ],
+                                        span: 58..89,
+                                    },
+                                ),
+                                46..89,
                             ),
-                            NetrcFile(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "~/.netrc",
-                                        ),
-                                    ],
-                                },
+                            (
+                                NetrcFile(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "~/.netrc",
+                                            ),
+                                        ],
+                                        span: 102..110,
+                                    },
+                                ),
+                                90..110,
                             ),
-                            Proxy(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "example.proxy:8050",
-                                        ),
-                                    ],
-                                },
+                            (
+                                Proxy(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "example.proxy:8050",
+                                            ),
+                                        ],
+                                        span: 118..136,
+                                    },
+                                ),
+                                111..136,
                             ),
-                            Resolve(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "example.com:8000:127.0.0.1",
-                                        ),
-                                    ],
-                                },
+                            (
+                                Resolve(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "example.com:8000:127.0.0.1",
+                                            ),
+                                        ],
+                                        span: 146..172,
+                                    },
+                                ),
+                                137..172,
                             ),
-                            UnixSocket(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "sock",
-                                        ),
-                                    ],
-                                },
+                            (
+                                UnixSocket(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "sock",
+                                            ),
+                                        ],
+                                        span: 186..190,
+                                    },
+                                ),
+                                173..190,
                             ),
-                            User(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "joe=secret",
-                                        ),
-                                    ],
-                                },
+                            (
+                                User(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "joe=secret",
+                                            ),
+                                        ],
+                                        span: 197..207,
+                                    },
+                                ),
+                                191..207,
                             ),
                         ],
+                        span: 0..207,
                     },
                 ),
             ),
@@ -1961,167 +2465,246 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            AwsSigv4(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "aws",
-                                                    ),
-                                                    filters: [],
+                            (
+                                AwsSigv4(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "aws",
+                                                            ),
+                                                            23..26,
+                                                        ),
+                                                        filters: [],
+                                                        span: 23..26,
+                                                    },
+                                                    span: 21..28,
                                                 },
-                                            },
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                        ],
+                                        span: 21..28,
+                                    },
+                                ),
+                                10..28,
                             ),
-                            ConnectTo(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "host",
-                                                    ),
-                                                    filters: [],
+                            (
+                                ConnectTo(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "host",
+                                                            ),
+                                                            43..47,
+                                                        ),
+                                                        filters: [],
+                                                        span: 43..47,
+                                                    },
+                                                    span: 41..49,
                                                 },
-                                            },
-                                        ),
-                                        Str(
-                                            ":",
-                                        ),
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "port",
-                                                    ),
-                                                    filters: [],
+                                            ),
+                                            Str(
+                                                ":",
+                                            ),
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "port",
+                                                            ),
+                                                            52..56,
+                                                        ),
+                                                        filters: [],
+                                                        span: 52..56,
+                                                    },
+                                                    span: 50..58,
                                                 },
-                                            },
-                                        ),
-                                        Str(
-                                            ":127.0.0.1:8080",
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                            Str(
+                                                ":127.0.0.1:8080",
+                                            ),
+                                        ],
+                                        span: 41..73,
+                                    },
+                                ),
+                                29..73,
                             ),
-                            NetrcFile(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "filepath",
-                                                    ),
-                                                    filters: [],
+                            (
+                                NetrcFile(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "filepath",
+                                                            ),
+                                                            88..96,
+                                                        ),
+                                                        filters: [],
+                                                        span: 88..96,
+                                                    },
+                                                    span: 86..98,
                                                 },
-                                            },
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                        ],
+                                        span: 86..98,
+                                    },
+                                ),
+                                74..98,
                             ),
-                            Proxy(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "proxyhost",
-                                                    ),
-                                                    filters: [],
+                            (
+                                Proxy(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "proxyhost",
+                                                            ),
+                                                            108..117,
+                                                        ),
+                                                        filters: [],
+                                                        span: 108..117,
+                                                    },
+                                                    span: 106..119,
                                                 },
-                                            },
-                                        ),
-                                        Str(
-                                            ":8050",
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                            Str(
+                                                ":8050",
+                                            ),
+                                        ],
+                                        span: 106..124,
+                                    },
+                                ),
+                                99..124,
                             ),
-                            Resolve(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "host",
-                                                    ),
-                                                    filters: [],
+                            (
+                                Resolve(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "host",
+                                                            ),
+                                                            136..140,
+                                                        ),
+                                                        filters: [],
+                                                        span: 136..140,
+                                                    },
+                                                    span: 134..142,
                                                 },
-                                            },
-                                        ),
-                                        Str(
-                                            ":",
-                                        ),
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "port",
-                                                    ),
-                                                    filters: [],
+                                            ),
+                                            Str(
+                                                ":",
+                                            ),
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "port",
+                                                            ),
+                                                            145..149,
+                                                        ),
+                                                        filters: [],
+                                                        span: 145..149,
+                                                    },
+                                                    span: 143..151,
                                                 },
-                                            },
-                                        ),
-                                        Str(
-                                            ":127.0.0.1",
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                            Str(
+                                                ":127.0.0.1",
+                                            ),
+                                        ],
+                                        span: 134..161,
+                                    },
+                                ),
+                                125..161,
                             ),
-                            UnixSocket(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "socket",
-                                                    ),
-                                                    filters: [],
+                            (
+                                UnixSocket(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "socket",
+                                                            ),
+                                                            177..183,
+                                                        ),
+                                                        filters: [],
+                                                        span: 177..183,
+                                                    },
+                                                    span: 175..185,
                                                 },
-                                            },
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                        ],
+                                        span: 175..185,
+                                    },
+                                ),
+                                162..185,
                             ),
-                            User(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "user",
-                                                    ),
-                                                    filters: [],
+                            (
+                                User(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "user",
+                                                            ),
+                                                            194..198,
+                                                        ),
+                                                        filters: [],
+                                                        span: 194..198,
+                                                    },
+                                                    span: 192..200,
                                                 },
-                                            },
-                                        ),
-                                        Str(
-                                            "=",
-                                        ),
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "password",
-                                                    ),
-                                                    filters: [],
+                                            ),
+                                            Str(
+                                                "=",
+                                            ),
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "password",
+                                                            ),
+                                                            203..211,
+                                                        ),
+                                                        filters: [],
+                                                        span: 203..211,
+                                                    },
+                                                    span: 201..213,
                                                 },
-                                            },
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                        ],
+                                        span: 192..213,
+                                    },
+                                ),
+                                186..213,
                             ),
                         ],
+                        span: 0..213,
                     },
                 ),
             ),
@@ -2142,34 +2725,47 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            Cacert(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "/etc/cert.pem",
-                                        ),
-                                    ],
-                                },
+                            (
+                                Cacert(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "/etc/cert.pem",
+                                            ),
+                                        ],
+                                        span: 18..31,
+                                    },
+                                ),
+                                10..31,
                             ),
-                            Key(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            ".ssh/id_rsa.pub",
-                                        ),
-                                    ],
-                                },
+                            (
+                                Key(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                ".ssh/id_rsa.pub",
+                                            ),
+                                        ],
+                                        span: 37..52,
+                                    },
+                                ),
+                                32..52,
                             ),
-                            Output(
-                                InterpolatedString {
-                                    parts: [
-                                        Str(
-                                            "./myreport",
-                                        ),
-                                    ],
-                                },
+                            (
+                                Output(
+                                    InterpolatedString {
+                                        parts: [
+                                            Str(
+                                                "./myreport",
+                                            ),
+                                        ],
+                                        span: 61..71,
+                                    },
+                                ),
+                                53..71,
                             ),
                         ],
+                        span: 0..71,
                     },
                 ),
             ),
@@ -2191,55 +2787,83 @@ mod request_section_tests {
                 OptionsSection(
                     RequestOptionsSection {
                         options: [
-                            Cacert(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "certfilepath",
-                                                    ),
-                                                    filters: [],
+                            (
+                                Cacert(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "certfilepath",
+                                                            ),
+                                                            20..32,
+                                                        ),
+                                                        filters: [],
+                                                        span: 20..32,
+                                                    },
+                                                    span: 18..34,
                                                 },
-                                            },
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                        ],
+                                        span: 18..34,
+                                    },
+                                ),
+                                10..34,
                             ),
-                            Key(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "keyfilepath",
-                                                    ),
-                                                    filters: [],
+                            (
+                                Key(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "keyfilepath",
+                                                            ),
+                                                            42..53,
+                                                        ),
+                                                        filters: [],
+                                                        span: 42..53,
+                                                    },
+                                                    span: 40..55,
                                                 },
-                                            },
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                        ],
+                                        span: 40..55,
+                                    },
+                                ),
+                                35..55,
                             ),
-                            Output(
-                                InterpolatedString {
-                                    parts: [
-                                        Template(
-                                            Template {
-                                                expr: Expr {
-                                                    variable: VariableName(
-                                                        "reportfilepath",
-                                                    ),
-                                                    filters: [],
+                            (
+                                Output(
+                                    InterpolatedString {
+                                        parts: [
+                                            Template(
+                                                Template {
+                                                    expr: Expr {
+                                                        variable: (
+                                                            VariableName(
+                                                                "reportfilepath",
+                                                            ),
+                                                            66..80,
+                                                        ),
+                                                        filters: [],
+                                                        span: 66..80,
+                                                    },
+                                                    span: 64..82,
                                                 },
-                                            },
-                                        ),
-                                    ],
-                                },
+                                            ),
+                                        ],
+                                        span: 64..82,
+                                    },
+                                ),
+                                56..82,
                             ),
                         ],
+                        span: 0..82,
                     },
                 ),
             ),
@@ -2248,4 +2872,34 @@ mod request_section_tests {
         "#,
         );
     }
+
+    #[test]
+    fn it_parses_unknown_section() {
+        let test_str = "[Capture]\nid: jsonpath \"$.id\"\n# comment\nnext: header \"Location\"\n";
+        assert_debug_snapshot!(
+        request_sections_parser().parse(test_str),
+            @r#"
+        ParseResult {
+            output: Some(
+                (
+                    [
+                        UnknownSection(
+                            UnknownSection {
+                                section_name: "Capture",
+                                lines: [
+                                    "id: jsonpath \"$.id\"",
+                                    "next: header \"Location\"",
+                                ],
+                                span: 0..64,
+                            },
+                        ),
+                    ],
+                    [],
+                ),
+            ),
+            errs: [],
+        }
+        "#,
+        );
+    }
 }
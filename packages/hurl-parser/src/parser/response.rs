@@ -1,25 +1,28 @@
 use super::body::body_parser;
+use super::error::ParseError;
 use super::header::headers_parser;
 use super::http_status::http_status_line_parser;
 use super::response_section::response_sections_parser;
 use super::types::Response;
 use chumsky::prelude::*;
 
-pub fn response_parser<'a>(
-) -> impl Parser<'a, &'a str, Response, extra::Err<Rich<'a, char>>> + Clone {
+pub fn response_parser<'a>() -> impl Parser<'a, &'a str, Response, extra::Err<ParseError>> + Clone {
     http_status_line_parser()
         .then(headers_parser())
         .then(response_sections_parser())
         .then(body_parser().or_not())
-        .map(
-            |((((version, status), headers), response_sections), body)| Response {
+        .map_with(|((((version, status), headers), sections), body), e| {
+            let (response_sections, misplaced_sections) = sections;
+            Response {
                 version,
                 status,
                 headers,
                 response_sections,
+                misplaced_sections,
                 body,
-            },
-        )
+                span: e.span(),
+            }
+        })
         .labelled("response")
         .boxed()
 }
@@ -38,13 +41,21 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 Response {
-                    version: Http,
-                    status: Code(
-                        200,
+                    version: (
+                        Http,
+                        0..4,
+                    ),
+                    status: (
+                        Code(
+                            200,
+                        ),
+                        5..8,
                     ),
                     headers: [],
                     response_sections: [],
+                    misplaced_sections: [],
                     body: None,
+                    span: 0..8,
                 },
             ),
             errs: [],
@@ -66,12 +77,19 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 Response {
-                    version: Http3,
-                    status: Code(
-                        200,
+                    version: (
+                        Http3,
+                        0..6,
+                    ),
+                    status: (
+                        Code(
+                            200,
+                        ),
+                        7..10,
                     ),
                     headers: [],
                     response_sections: [],
+                    misplaced_sections: [],
                     body: Some(
                         Body {
                             bytes: JsonValue(
@@ -84,35 +102,50 @@ mod http_status_line_tests {
                                                         "pets",
                                                     ),
                                                 ],
+                                                span: 33..39,
                                             },
-                                            value: Array(
-                                                [
-                                                    InterpolatedString(
-                                                        InterpolatedString {
-                                                            parts: [
-                                                                Str(
-                                                                    "cat",
-                                                                ),
-                                                            ],
-                                                        },
-                                                    ),
-                                                    InterpolatedString(
-                                                        InterpolatedString {
-                                                            parts: [
-                                                                Str(
-                                                                    "dog",
-                                                                ),
-                                                            ],
-                                                        },
-                                                    ),
-                                                ],
+                                            value: (
+                                                Array(
+                                                    [
+                                                        (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "cat",
+                                                                        ),
+                                                                    ],
+                                                                    span: 42..47,
+                                                                },
+                                                            ),
+                                                            42..47,
+                                                        ),
+                                                        (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "dog",
+                                                                        ),
+                                                                    ],
+                                                                    span: 49..54,
+                                                                },
+                                                            ),
+                                                            49..54,
+                                                        ),
+                                                    ],
+                                                ),
+                                                41..55,
                                             ),
+                                            span: 33..55,
                                         },
                                     ],
                                 ),
                             ),
+                            span: 11..65,
                         },
                     ),
+                    span: 0..78,
                 },
             ),
             errs: [],
@@ -140,9 +173,15 @@ mod http_status_line_tests {
         ParseResult {
             output: Some(
                 Response {
-                    version: Http3,
-                    status: Code(
-                        200,
+                    version: (
+                        Http3,
+                        0..6,
+                    ),
+                    status: (
+                        Code(
+                            200,
+                        ),
+                        7..10,
                     ),
                     headers: [
                         KeyValue {
@@ -152,6 +191,7 @@ mod http_status_line_tests {
                                         "Access-Control-Allow-Origin",
                                     ),
                                 ],
+                                span: 19..46,
                             },
                             value: InterpolatedString {
                                 parts: [
@@ -159,7 +199,9 @@ mod http_status_line_tests {
                                         "*",
                                     ),
                                 ],
+                                span: 48..49,
                             },
+                            span: 19..49,
                         },
                         KeyValue {
                             key: InterpolatedString {
@@ -168,6 +210,7 @@ mod http_status_line_tests {
                                         "Connection",
                                     ),
                                 ],
+                                span: 58..68,
                             },
                             value: InterpolatedString {
                                 parts: [
@@ -175,7 +218,9 @@ mod http_status_line_tests {
                                         "Keep-Alive",
                                     ),
                                 ],
+                                span: 70..80,
                             },
+                            span: 58..80,
                         },
                         KeyValue {
                             key: InterpolatedString {
@@ -184,6 +229,7 @@ mod http_status_line_tests {
                                         "Content-Encoding",
                                     ),
                                 ],
+                                span: 89..105,
                             },
                             value: InterpolatedString {
                                 parts: [
@@ -191,7 +237,9 @@ mod http_status_line_tests {
                                         "gzip",
                                     ),
                                 ],
+                                span: 107..111,
                             },
+                            span: 89..111,
                         },
                         KeyValue {
                             key: InterpolatedString {
@@ -200,6 +248,7 @@ mod http_status_line_tests {
                                         "Content-Type",
                                     ),
                                 ],
+                                span: 120..132,
                             },
                             value: InterpolatedString {
                                 parts: [
@@ -207,7 +256,9 @@ mod http_status_line_tests {
                                         "text/html; charset=utf-8",
                                     ),
                                 ],
+                                span: 134..158,
                             },
+                            span: 120..158,
                         },
                         KeyValue {
                             key: InterpolatedString {
@@ -216,6 +267,7 @@ mod http_status_line_tests {
                                         "Server",
                                     ),
                                 ],
+                                span: 167..173,
                             },
                             value: InterpolatedString {
                                 parts: [
@@ -223,7 +275,9 @@ mod http_status_line_tests {
                                         "Apache",
                                     ),
                                 ],
+                                span: 175..181,
                             },
+                            span: 167..181,
                         },
                         KeyValue {
                             key: InterpolatedString {
@@ -232,6 +286,7 @@ mod http_status_line_tests {
                                         "Transfer-Encoding",
                                     ),
                                 ],
+                                span: 190..207,
                             },
                             value: InterpolatedString {
                                 parts: [
@@ -239,10 +294,13 @@ mod http_status_line_tests {
                                         "chunked",
                                     ),
                                 ],
+                                span: 209..216,
                             },
+                            span: 190..216,
                         },
                     ],
                     response_sections: [],
+                    misplaced_sections: [],
                     body: Some(
                         Body {
                             bytes: JsonValue(
@@ -255,35 +313,50 @@ mod http_status_line_tests {
                                                         "pets",
                                                     ),
                                                 ],
+                                                span: 239..245,
                                             },
-                                            value: Array(
-                                                [
-                                                    InterpolatedString(
-                                                        InterpolatedString {
-                                                            parts: [
-                                                                Str(
-                                                                    "cat",
-                                                                ),
-                                                            ],
-                                                        },
-                                                    ),
-                                                    InterpolatedString(
-                                                        InterpolatedString {
-                                                            parts: [
-                                                                Str(
-                                                                    "dog",
-                                                                ),
-                                                            ],
-                                                        },
-                                                    ),
-                                                ],
+                                            value: (
+                                                Array(
+                                                    [
+                                                        (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "cat",
+                                                                        ),
+                                                                    ],
+                                                                    span: 248..253,
+                                                                },
+                                                            ),
+                                                            248..253,
+                                                        ),
+                                                        (
+                                                            InterpolatedString(
+                                                                InterpolatedString {
+                                                                    parts: [
+                                                                        Str(
+                                                                            "dog",
+                                                                        ),
+                                                                    ],
+                                                                    span: 255..260,
+                                                                },
+                                                            ),
+                                                            255..260,
+                                                        ),
+                                                    ],
+                                                ),
+                                                247..261,
                                             ),
+                                            span: 239..261,
                                         },
                                     ],
                                 ),
                             ),
+                            span: 217..271,
                         },
                     ),
+                    span: 0..284,
                 },
             ),
             errs: [],
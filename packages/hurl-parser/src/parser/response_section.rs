@@ -1,3 +1,4 @@
+use super::error::ParseError;
 use super::{
     expr::filters_parser,
     key_value::key_parser,
@@ -5,60 +6,115 @@ use super::{
     primitives::{lt_parser, sp_parser},
     query::query_parser,
     quoted_string::quoted_string_parser,
-    types::{Assert, AssertsSection, Capture, CapturesSection, ResponseSection},
+    request_section::{request_section_parser, sections_parser},
+    types::{
+        Assert, AssertStart, AssertsSection, Capture, CapturesSection, RequestSection,
+        ResponseSection, Span,
+    },
 };
 use chumsky::prelude::*;
 
-pub fn response_sections_parser<'a>(
-) -> impl Parser<'a, &'a str, Vec<ResponseSection>, extra::Err<Rich<'a, char>>> + Clone {
+pub fn response_section_parser<'a>(
+) -> impl Parser<'a, &'a str, ResponseSection, extra::Err<ParseError>> + Clone {
     let capture_line = key_parser()
         .padded_by(sp_parser().repeated())
         .then_ignore(just(':').padded_by(sp_parser().repeated()))
-        .then(query_parser())
+        .then(query_parser().map_with(|query, e| (query, e.span())))
         .then_ignore(sp_parser().repeated())
         .then(filters_parser(quoted_string_parser()))
-        .then_ignore(lt_parser())
-        .map(|((key, query), filters)| Capture {
-            key,
-            query,
-            filters,
-        });
+        .map(|((key, query), filters)| {
+            let end = filters.last().map_or(query.1.end, |(_, span)| span.end);
+            Capture {
+                span: Span::new(key.span.start, end),
+                key,
+                query,
+                filters,
+            }
+        })
+        .then_ignore(lt_parser());
 
     let captures = capture_line.repeated().collect::<Vec<Capture>>();
 
     let assert_line = query_parser()
+        .map_with(|query, e| (query, e.span()))
         .delimited_by(sp_parser().repeated(), sp_parser().repeated().at_least(1))
         .then(filters_parser(quoted_string_parser()))
         .then_ignore(sp_parser().repeated())
         .then(predicate_parser())
-        .then_ignore(lt_parser())
         .map(|((query, filters), predicate)| Assert {
+            span: Span::new(query.1.start, predicate.span.end),
             query,
             filters,
             predicate,
-        });
+        })
+        .then_ignore(lt_parser());
     let asserts = assert_line.repeated().collect::<Vec<Assert>>();
 
     let captures_section = just("[Captures]")
         .padded_by(sp_parser().repeated())
         .then_ignore(lt_parser())
         .then(captures)
-        .map(|(_, captures)| ResponseSection::CapturesSection(CapturesSection { captures }));
+        .map_with(|(_, captures), e| {
+            ResponseSection::CapturesSection(CapturesSection {
+                captures,
+                span: e.span(),
+            })
+        });
 
     let asserts_section = just("[Asserts]")
         .padded_by(sp_parser().repeated())
         .then_ignore(lt_parser())
         .then(asserts)
-        .map(|(_, asserts)| ResponseSection::AssertsSection(AssertsSection { asserts }));
+        .map_with(|(_, asserts), e| {
+            ResponseSection::AssertsSection(AssertsSection {
+                asserts,
+                span: e.span(),
+            })
+        });
+
+    choice((captures_section, asserts_section)).boxed()
+}
+
+/// Parses the start of a line of a `[Asserts]` or `[Captures]` section, with nothing after
+/// its last query, filter or `not` word but spacing.
+pub fn assert_start_parser<'a>(
+) -> impl Parser<'a, &'a str, AssertStart, extra::Err<ParseError>> + Clone {
+    let filters = sp_parser()
+        .repeated()
+        .at_least(1)
+        .ignore_then(filters_parser(quoted_string_parser()))
+        .filter(|filters| !filters.is_empty())
+        .or_not();
+    let not = sp_parser()
+        .repeated()
+        .at_least(1)
+        .ignore_then(just("not"))
+        .or_not();
 
-    let response_sections = choice((captures_section, asserts_section))
+    sp_parser()
         .repeated()
-        .collect::<Vec<ResponseSection>>()
-        .boxed();
+        .ignore_then(query_parser().map_with(|query, e| (query, e.span())))
+        .then(filters)
+        .then(not)
+        .then_ignore(sp_parser().repeated())
+        .then_ignore(end())
+        .map(|((query, filters), not)| AssertStart {
+            query,
+            filters: filters.unwrap_or_default(),
+            not: not.is_some(),
+        })
+        .boxed()
+}
 
-    //TODO sections can only be defined once per entry's response section. So you can't have [Asserts] defined
-    //twice and so should be a diagnostic error.
-    response_sections
+pub fn response_sections_parser<'a>(
+) -> impl Parser<'a, &'a str, (Vec<ResponseSection>, Vec<RequestSection>), extra::Err<ParseError>> + Clone
+{
+    sections_parser(
+        response_section_parser(),
+        request_section_parser(),
+        ResponseSection::UnknownSection,
+    )
+    .boxed()
 }
 
 #[cfg(test)]
@@ -77,52 +133,68 @@ mod response_section_tests {
             @r#"
         ParseResult {
             output: Some(
-                [
-                    CapturesSection(
-                        CapturesSection {
-                            captures: [
-                                Capture {
-                                    key: InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "csrf_token",
-                                            ),
-                                        ],
-                                    },
-                                    query: Xpath(
-                                        InterpolatedString {
+                (
+                    [
+                        CapturesSection(
+                            CapturesSection {
+                                captures: [
+                                    Capture {
+                                        key: InterpolatedString {
                                             parts: [
                                                 Str(
-                                                    "string(//meta[@name='_csrf_token']/@content)",
+                                                    "csrf_token",
                                                 ),
                                             ],
+                                            span: 19..29,
                                         },
-                                    ),
-                                    filters: [],
-                                },
-                                Capture {
-                                    key: InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "next_url",
+                                        query: (
+                                            Xpath(
+                                                InterpolatedString {
+                                                    parts: [
+                                                        Str(
+                                                            "string(//meta[@name='_csrf_token']/@content)",
+                                                        ),
+                                                    ],
+                                                    span: 37..83,
+                                                },
                                             ),
-                                        ],
+                                            31..83,
+                                        ),
+                                        filters: [],
+                                        span: 19..83,
                                     },
-                                    query: Header(
-                                        InterpolatedString {
+                                    Capture {
+                                        key: InterpolatedString {
                                             parts: [
                                                 Str(
-                                                    "Location",
+                                                    "next_url",
                                                 ),
                                             ],
+                                            span: 92..100,
                                         },
-                                    ),
-                                    filters: [],
-                                },
-                            ],
-                        },
-                    ),
-                ],
+                                        query: (
+                                            Header(
+                                                InterpolatedString {
+                                                    parts: [
+                                                        Str(
+                                                            "Location",
+                                                        ),
+                                                    ],
+                                                    span: 109..119,
+                                                },
+                                            ),
+                                            102..119,
+                                        ),
+                                        filters: [],
+                                        span: 92..119,
+                                    },
+                                ],
+                                span: 0..128,
+                            },
+                        ),
+                    ],
+                    [],
+                ),
             ),
             errs: [],
         }
@@ -144,70 +216,110 @@ mod response_section_tests {
             @r#"
         ParseResult {
             output: Some(
-                [
-                    AssertsSection(
-                        AssertsSection {
-                            asserts: [
-                                Assert {
-                                    query: Header(
-                                        InterpolatedString {
-                                            parts: [
-                                                Str(
-                                                    "Content-Type",
-                                                ),
-                                            ],
-                                        },
-                                    ),
-                                    filters: [],
-                                    predicate: Predicate {
-                                        prefix: None,
-                                        function: Equal {
-                                            value: QuotedString(
+                (
+                    [
+                        AssertsSection(
+                            AssertsSection {
+                                asserts: [
+                                    Assert {
+                                        query: (
+                                            Header(
                                                 InterpolatedString {
                                                     parts: [
                                                         Str(
-                                                            "text/html; charset=utf8",
+                                                            "Content-Type",
                                                         ),
                                                     ],
+                                                    span: 29..43,
+                                                },
+                                            ),
+                                            22..43,
+                                        ),
+                                        filters: [],
+                                        predicate: Predicate {
+                                            prefix: None,
+                                            function: (
+                                                Equal {
+                                                    value: (
+                                                        QuotedString(
+                                                            InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "text/html; charset=utf8",
+                                                                    ),
+                                                                ],
+                                                                span: 47..72,
+                                                            },
+                                                        ),
+                                                        47..72,
+                                                    ),
                                                 },
+                                                44..72,
                                             ),
+                                            span: 44..72,
                                         },
+                                        span: 22..72,
                                     },
-                                },
-                                Assert {
-                                    query: Bytes,
-                                    filters: [],
-                                    predicate: Predicate {
-                                        prefix: None,
-                                        function: Contain {
-                                            value: OneLineHex(
-                                                "5468697320697320616E206578616D706C65",
+                                    Assert {
+                                        query: (
+                                            Bytes,
+                                            125..130,
+                                        ),
+                                        filters: [],
+                                        predicate: Predicate {
+                                            prefix: None,
+                                            function: (
+                                                Contain {
+                                                    value: (
+                                                        OneLineHex(
+                                                            "5468697320697320616E206578616D706C65",
+                                                        ),
+                                                        140..181,
+                                                    ),
+                                                },
+                                                131..181,
                                             ),
+                                            span: 131..181,
                                         },
+                                        span: 125..181,
                                     },
-                                },
-                                Assert {
-                                    query: Body,
-                                    filters: [],
-                                    predicate: Predicate {
-                                        prefix: None,
-                                        function: Contain {
-                                            value: QuotedString(
-                                                InterpolatedString {
-                                                    parts: [
-                                                        Str(
-                                                            "This is an example",
+                                    Assert {
+                                        query: (
+                                            Body,
+                                            234..238,
+                                        ),
+                                        filters: [],
+                                        predicate: Predicate {
+                                            prefix: None,
+                                            function: (
+                                                Contain {
+                                                    value: (
+                                                        QuotedString(
+                                                            InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "This is an example",
+                                                                    ),
+                                                                ],
+                                                                span: 248..268,
+                                                            },
                                                         ),
-                                                    ],
+                                                        248..268,
+                                                    ),
                                                 },
+                                                239..268,
                                             ),
+                                            span: 239..268,
                                         },
+                                        span: 234..268,
                                     },
-                                },
-                            ],
-                        },
-                    ),
-                ],
+                                ],
+                                span: 0..285,
+                            },
+                        ),
+                    ],
+                    [],
+                ),
             ),
             errs: [],
         }
@@ -232,118 +344,227 @@ mod response_section_tests {
             @r#"
         ParseResult {
             output: Some(
-                [
-                    CapturesSection(
-                        CapturesSection {
-                            captures: [
-                                Capture {
-                                    key: InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "csrf_token",
-                                            ),
-                                        ],
-                                    },
-                                    query: Xpath(
-                                        InterpolatedString {
+                (
+                    [
+                        CapturesSection(
+                            CapturesSection {
+                                captures: [
+                                    Capture {
+                                        key: InterpolatedString {
                                             parts: [
                                                 Str(
-                                                    "string(//meta[@name='_csrf_token']/@content)",
+                                                    "csrf_token",
                                                 ),
                                             ],
+                                            span: 23..33,
                                         },
-                                    ),
-                                    filters: [],
-                                },
-                                Capture {
-                                    key: InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "next_url",
+                                        query: (
+                                            Xpath(
+                                                InterpolatedString {
+                                                    parts: [
+                                                        Str(
+                                                            "string(//meta[@name='_csrf_token']/@content)",
+                                                        ),
+                                                    ],
+                                                    span: 41..87,
+                                                },
                                             ),
-                                        ],
+                                            35..87,
+                                        ),
+                                        filters: [],
+                                        span: 23..87,
                                     },
-                                    query: Header(
-                                        InterpolatedString {
-                                            parts: [
-                                                Str(
-                                                    "Location",
-                                                ),
-                                            ],
-                                        },
-                                    ),
-                                    filters: [],
-                                },
-                            ],
-                        },
-                    ),
-                    AssertsSection(
-                        AssertsSection {
-                            asserts: [
-                                Assert {
-                                    query: Header(
-                                        InterpolatedString {
+                                    Capture {
+                                        key: InterpolatedString {
                                             parts: [
                                                 Str(
-                                                    "Content-Type",
+                                                    "next_url",
                                                 ),
                                             ],
+                                            span: 100..108,
                                         },
-                                    ),
-                                    filters: [],
-                                    predicate: Predicate {
-                                        prefix: None,
-                                        function: Equal {
-                                            value: QuotedString(
+                                        query: (
+                                            Header(
                                                 InterpolatedString {
                                                     parts: [
                                                         Str(
-                                                            "text/html; charset=utf8",
+                                                            "Location",
                                                         ),
                                                     ],
+                                                    span: 117..127,
                                                 },
                                             ),
-                                        },
-                                    },
-                                },
-                                Assert {
-                                    query: Bytes,
-                                    filters: [],
-                                    predicate: Predicate {
-                                        prefix: None,
-                                        function: Contain {
-                                            value: OneLineHex(
-                                                "5468697320697320616E206578616D706C65",
-                                            ),
-                                        },
+                                            110..127,
+                                        ),
+                                        filters: [],
+                                        span: 100..127,
                                     },
-                                },
-                                Assert {
-                                    query: Body,
-                                    filters: [],
-                                    predicate: Predicate {
-                                        prefix: None,
-                                        function: Contain {
-                                            value: QuotedString(
+                                ],
+                                span: 0..128,
+                            },
+                        ),
+                        AssertsSection(
+                            AssertsSection {
+                                asserts: [
+                                    Assert {
+                                        query: (
+                                            Header(
                                                 InterpolatedString {
                                                     parts: [
                                                         Str(
-                                                            "This is an example",
+                                                            "Content-Type",
                                                         ),
                                                     ],
+                                                    span: 169..183,
                                                 },
                                             ),
+                                            162..183,
+                                        ),
+                                        filters: [],
+                                        predicate: Predicate {
+                                            prefix: None,
+                                            function: (
+                                                Equal {
+                                                    value: (
+                                                        QuotedString(
+                                                            InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "text/html; charset=utf8",
+                                                                    ),
+                                                                ],
+                                                                span: 187..212,
+                                                            },
+                                                        ),
+                                                        187..212,
+                                                    ),
+                                                },
+                                                184..212,
+                                            ),
+                                            span: 184..212,
                                         },
+                                        span: 162..212,
                                     },
-                                },
-                            ],
-                        },
-                    ),
-                ],
+                                    Assert {
+                                        query: (
+                                            Bytes,
+                                            265..270,
+                                        ),
+                                        filters: [],
+                                        predicate: Predicate {
+                                            prefix: None,
+                                            function: (
+                                                Contain {
+                                                    value: (
+                                                        OneLineHex(
+                                                            "5468697320697320616E206578616D706C65",
+                                                        ),
+                                                        280..321,
+                                                    ),
+                                                },
+                                                271..321,
+                                            ),
+                                            span: 271..321,
+                                        },
+                                        span: 265..321,
+                                    },
+                                    Assert {
+                                        query: (
+                                            Body,
+                                            374..378,
+                                        ),
+                                        filters: [],
+                                        predicate: Predicate {
+                                            prefix: None,
+                                            function: (
+                                                Contain {
+                                                    value: (
+                                                        QuotedString(
+                                                            InterpolatedString {
+                                                                parts: [
+                                                                    Str(
+                                                                        "This is an example",
+                                                                    ),
+                                                                ],
+                                                                span: 388..408,
+                                                            },
+                                                        ),
+                                                        388..408,
+                                                    ),
+                                                },
+                                                379..408,
+                                            ),
+                                            span: 379..408,
+                                        },
+                                        span: 374..408,
+                                    },
+                                ],
+                                span: 128..417,
+                            },
+                        ),
+                    ],
+                    [],
+                ),
             ),
             errs: [],
         }
         "#,
         );
     }
+
+    #[test]
+    fn it_parses_request_section_after_status_line() {
+        let test_str = "[Options]\nverbose: true\n";
+        assert_debug_snapshot!(
+        response_sections_parser().parse(test_str),
+            @r"
+        ParseResult {
+            output: Some(
+                (
+                    [],
+                    [
+                        OptionsSection(
+                            RequestOptionsSection {
+                                options: [
+                                    (
+                                        Verbose(
+                                            Literal(
+                                                true,
+                                            ),
+                                        ),
+                                        10..23,
+                                    ),
+                                ],
+                                span: 0..24,
+                            },
+                        ),
+                    ],
+                ),
+            ),
+            errs: [],
+        }
+        ",
+        );
+    }
+
+    #[test]
+    fn it_parses_the_start_of_an_assert_line() {
+        let start = assert_start_parser()
+            .parse(r#"  jsonpath "$.a" nth 0 not "#)
+            .into_output()
+            .unwrap();
+        assert_eq!(start.query.1, Span::new(2, 16));
+        assert_eq!(start.filters.len(), 1);
+        assert!(start.not);
+
+        let start = assert_start_parser()
+            .parse("status ")
+            .into_output()
+            .unwrap();
+        assert!(start.filters.is_empty());
+        assert!(!start.not);
+
+        assert!(assert_start_parser().parse("status ==").has_errors());
+        assert!(assert_start_parser().parse("statusnot").has_errors());
+    }
 }
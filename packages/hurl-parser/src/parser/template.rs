@@ -1,18 +1,19 @@
+use super::error::ParseError;
 use super::{
     expr::expr_parser, primitives::sp_parser, quoted_string::generic_quoted_string_parser,
 };
 use crate::parser::types::Template;
 use chumsky::prelude::*;
 
-pub fn template_parser<'a>(
-) -> impl Parser<'a, &'a str, Template, extra::Err<Rich<'a, char>>> + Clone {
+pub fn template_parser<'a>() -> impl Parser<'a, &'a str, Template, extra::Err<ParseError>> + Clone {
     let template = recursive(|template| {
         let quoted_string = generic_quoted_string_parser(template);
         let expr = expr_parser(quoted_string);
         expr.padded_by(sp_parser().repeated())
             .delimited_by(just("{{"), just("}}"))
-            .map(|captured_expr| Template {
+            .map_with(|captured_expr, e| Template {
                 expr: captured_expr,
+                span: e.span(),
             })
     })
     .labelled("template");
@@ -36,11 +37,16 @@ mod template_tests {
             output: Some(
                 Template {
                     expr: Expr {
-                        variable: VariableName(
-                            "key",
+                        variable: (
+                            VariableName(
+                                "key",
+                            ),
+                            2..5,
                         ),
                         filters: [],
+                        span: 2..5,
                     },
+                    span: 0..7,
                 },
             ),
             errs: [],
@@ -59,11 +65,16 @@ mod template_tests {
             output: Some(
                 Template {
                     expr: Expr {
-                        variable: VariableName(
-                            "key",
+                        variable: (
+                            VariableName(
+                                "key",
+                            ),
+                            5..8,
                         ),
                         filters: [],
+                        span: 5..8,
                     },
+                    span: 0..12,
                 },
             ),
             errs: [],
@@ -82,11 +93,16 @@ mod template_tests {
             output: Some(
                 Template {
                     expr: Expr {
-                        variable: VariableName(
-                            "api-key",
+                        variable: (
+                            VariableName(
+                                "api-key",
+                            ),
+                            2..9,
                         ),
                         filters: [],
+                        span: 2..9,
                     },
+                    span: 0..11,
                 },
             ),
             errs: [],
@@ -105,11 +121,16 @@ mod template_tests {
             output: Some(
                 Template {
                     expr: Expr {
-                        variable: VariableName(
-                            "api_key",
+                        variable: (
+                            VariableName(
+                                "api_key",
+                            ),
+                            2..9,
                         ),
                         filters: [],
+                        span: 2..9,
                     },
+                    span: 0..11,
                 },
             ),
             errs: [],
@@ -123,14 +144,24 @@ mod template_tests {
         let test_str = "{{1}}";
         assert_debug_snapshot!(
         template_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''1'' at 2..3 expected spacing, or expr,
+                Unexpected {
+                    span: 2..3,
+                    found: Some(
+                        '1',
+                    ),
+                    expected: [
+                        "expr",
+                        "spacing",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 }
@@ -18,21 +18,29 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..23,
                             },
                             response: None,
+                            span: 0..23,
                         },
                     ],
                 },
@@ -57,21 +65,29 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..24,
                             },
                             response: None,
+                            span: 0..24,
                         },
                     ],
                 },
@@ -96,8 +112,12 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Missing,
+                                url: (
+                                    Missing,
+                                    3..3,
+                                ),
                                 headers: [
                                     KeyValue {
                                         key: InterpolatedString {
@@ -106,6 +126,7 @@ mod tests {
                                                     "https",
                                                 ),
                                             ],
+                                            span: 4..9,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -113,19 +134,26 @@ mod tests {
                                                     "//example.org",
                                                 ),
                                             ],
+                                            span: 10..23,
                                         },
+                                        span: 4..23,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..23,
                             },
                             response: None,
+                            span: 0..23,
                         },
                     ],
                 },
             ),
             errs: [
-                missing url at 3..3,
+                MissingUrl {
+                    span: 3..3,
+                },
             ],
         }
         "#,
@@ -146,21 +174,29 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "POST",
+                                    span: 0..4,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 5..24,
+                                        },
+                                    ),
+                                    5..24,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..24,
                             },
                             response: None,
+                            span: 0..24,
                         },
                     ],
                 },
@@ -182,21 +218,29 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "FOO",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..23,
                             },
                             response: None,
+                            span: 0..23,
                         },
                     ],
                 },
@@ -221,15 +265,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org/protected",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org/protected",
+                                                ),
+                                            ],
+                                            span: 4..33,
+                                        },
+                                    ),
+                                    4..33,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -239,6 +288,7 @@ mod tests {
                                                     "Authorization",
                                                 ),
                                             ],
+                                            span: 34..47,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -246,13 +296,18 @@ mod tests {
                                                     "Basic Ym9iOnNlY3JldA==",
                                                 ),
                                             ],
+                                            span: 49..71,
                                         },
+                                        span: 34..71,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..71,
                             },
                             response: None,
+                            span: 0..71,
                         },
                     ],
                 },
@@ -277,21 +332,29 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 4..7,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 8..27,
+                                        },
+                                    ),
+                                    8..27,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..27,
                             },
                             response: None,
+                            span: 0..27,
                         },
                     ],
                 },
@@ -316,35 +379,48 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 4..7,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 8..27,
+                                        },
+                                    ),
+                                    8..27,
                                 ),
                                 headers: [],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..28,
                             },
                             response: None,
+                            span: 0..28,
                         },
                         Entry {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 28..31,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org/protected",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org/protected",
+                                                ),
+                                            ],
+                                            span: 32..61,
+                                        },
+                                    ),
+                                    32..61,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -354,6 +430,7 @@ mod tests {
                                                     "Authorization",
                                                 ),
                                             ],
+                                            span: 62..75,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -361,13 +438,18 @@ mod tests {
                                                     "Basic Ym9iOnNlY3JldA==",
                                                 ),
                                             ],
+                                            span: 77..99,
                                         },
+                                        span: 62..99,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 28..99,
                             },
                             response: None,
+                            span: 28..99,
                         },
                     ],
                 },
@@ -392,15 +474,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -410,6 +497,7 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 24..27,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -417,13 +505,18 @@ mod tests {
                                                     "this:value:has:colons",
                                                 ),
                                             ],
+                                            span: 29..50,
                                         },
+                                        span: 24..50,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..50,
                             },
                             response: None,
+                            span: 0..50,
                         },
                     ],
                 },
@@ -449,15 +542,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -467,6 +565,7 @@ mod tests {
                                                     "key:has:escaped:colons",
                                                 ),
                                             ],
+                                            span: 24..49,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -474,13 +573,18 @@ mod tests {
                                                     "thekeyhadescapedcolons",
                                                 ),
                                             ],
+                                            span: 51..73,
                                         },
+                                        span: 24..73,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..73,
                             },
                             response: None,
+                            span: 0..73,
                         },
                     ],
                 },
@@ -505,15 +609,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -523,6 +632,7 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 24..27,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -530,13 +640,18 @@ mod tests {
                                                     "thekeyhasescaped\\backslash",
                                                 ),
                                             ],
+                                            span: 29..56,
                                         },
+                                        span: 24..56,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..56,
                             },
                             response: None,
+                            span: 0..56,
                         },
                     ],
                 },
@@ -552,14 +667,30 @@ mod tests {
         let test_str = "GET https://example.org\nkey: this\\valuehasanunescapedbackslash";
         assert_debug_snapshot!(
         ast_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''v'' at 34..35 expected ''\\'', ''#'', ''b'', ''f'', ''n'', ''r'', ''t'', or ''u'',
+                Unexpected {
+                    span: 34..35,
+                    found: Some(
+                        'v',
+                    ),
+                    expected: [
+                        "'#'",
+                        "'\\'",
+                        "'b'",
+                        "'f'",
+                        "'n'",
+                        "'r'",
+                        "'t'",
+                        "'u'",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -570,14 +701,23 @@ mod tests {
         let test_str = "GET https://example.org\nkey-{{ }}: dummyvalue";
         assert_debug_snapshot!(
         ast_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''}'' at 32..33 expected line terminator,
+                Unexpected {
+                    span: 32..33,
+                    found: Some(
+                        '}',
+                    ),
+                    expected: [
+                        "line terminator",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -595,15 +735,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -615,14 +760,20 @@ mod tests {
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "env",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "env",
+                                                                ),
+                                                                30..33,
                                                             ),
                                                             filters: [],
+                                                            span: 30..33,
                                                         },
+                                                        span: 28..35,
                                                     },
                                                 ),
                                             ],
+                                            span: 24..35,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -630,13 +781,18 @@ mod tests {
                                                     "dummyvalue",
                                                 ),
                                             ],
+                                            span: 37..47,
                                         },
+                                        span: 24..47,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..47,
                             },
                             response: None,
+                            span: 0..47,
                         },
                     ],
                 },
@@ -661,15 +817,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -679,6 +840,7 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 24..27,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -686,13 +848,18 @@ mod tests {
                                                     "valuewithemoji😀",
                                                 ),
                                             ],
+                                            span: 29..47,
                                         },
+                                        span: 24..47,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..47,
                             },
                             response: None,
+                            span: 0..47,
                         },
                     ],
                 },
@@ -717,15 +884,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -735,6 +907,7 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 24..27,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -742,13 +915,18 @@ mod tests {
                                                     "dummy{v}alue",
                                                 ),
                                             ],
+                                            span: 29..41,
                                         },
+                                        span: 24..41,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..41,
                             },
                             response: None,
+                            span: 0..41,
                         },
                     ],
                 },
@@ -774,15 +952,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org/cn",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org/cn",
+                                                ),
+                                            ],
+                                            span: 4..26,
+                                        },
+                                    ),
+                                    4..26,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -792,37 +975,52 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 27..30,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "apikey",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "apikey",
+                                                                ),
+                                                                34..40,
                                                             ),
                                                             filters: [
-                                                                Decode {
-                                                                    encoding: InterpolatedString {
-                                                                        parts: [
-                                                                            Str(
-                                                                                "gb2312",
-                                                                            ),
-                                                                        ],
+                                                                (
+                                                                    Decode {
+                                                                        encoding: InterpolatedString {
+                                                                            parts: [
+                                                                                Str(
+                                                                                    "gb2312",
+                                                                                ),
+                                                                            ],
+                                                                            span: 48..56,
+                                                                        },
                                                                     },
-                                                                },
+                                                                    41..56,
+                                                                ),
                                                             ],
+                                                            span: 34..56,
                                                         },
+                                                        span: 32..58,
                                                     },
                                                 ),
                                             ],
+                                            span: 32..58,
                                         },
+                                        span: 27..58,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..58,
                             },
                             response: None,
+                            span: 0..58,
                         },
                     ],
                 },
@@ -848,15 +1046,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org/cn",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org/cn",
+                                                ),
+                                            ],
+                                            span: 4..26,
+                                        },
+                                    ),
+                                    4..26,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -866,27 +1069,38 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 27..30,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "apikey",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "apikey",
+                                                                ),
+                                                                34..40,
                                                             ),
                                                             filters: [],
+                                                            span: 34..40,
                                                         },
+                                                        span: 32..42,
                                                     },
                                                 ),
                                             ],
+                                            span: 32..42,
                                         },
+                                        span: 27..42,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..42,
                             },
                             response: None,
+                            span: 0..42,
                         },
                     ],
                 },
@@ -903,14 +1117,23 @@ mod tests {
 
         assert_debug_snapshot!(
         ast_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found end of input at 30..31 expected something else, spacing, or value-string,
+                Unexpected {
+                    span: 30..31,
+                    found: None,
+                    expected: [
+                        "something else",
+                        "spacing",
+                        "value-string",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -929,15 +1152,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org/cn",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org/cn",
+                                                ),
+                                            ],
+                                            span: 4..26,
+                                        },
+                                    ),
+                                    4..26,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -947,27 +1175,38 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 27..30,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "apikey",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "apikey",
+                                                                ),
+                                                                33..39,
                                                             ),
                                                             filters: [],
+                                                            span: 33..39,
                                                         },
+                                                        span: 31..41,
                                                     },
                                                 ),
                                             ],
+                                            span: 31..41,
                                         },
+                                        span: 27..41,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..41,
                             },
                             response: None,
+                            span: 0..41,
                         },
                     ],
                 },
@@ -993,15 +1232,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org/cn",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org/cn",
+                                                ),
+                                            ],
+                                            span: 4..26,
+                                        },
+                                    ),
+                                    4..26,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -1011,29 +1255,43 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 27..30,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "apikey",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "apikey",
+                                                                ),
+                                                                34..40,
                                                             ),
                                                             filters: [
-                                                                UrlDecode,
+                                                                (
+                                                                    UrlDecode,
+                                                                    41..50,
+                                                                ),
                                                             ],
+                                                            span: 34..50,
                                                         },
+                                                        span: 32..52,
                                                     },
                                                 ),
                                             ],
+                                            span: 32..52,
                                         },
+                                        span: 27..52,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..52,
                             },
                             response: None,
+                            span: 0..52,
                         },
                     ],
                 },
@@ -1060,15 +1318,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org/cn",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org/cn",
+                                                ),
+                                            ],
+                                            span: 4..26,
+                                        },
+                                    ),
+                                    4..26,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -1078,45 +1341,68 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 27..30,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "apikey",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "apikey",
+                                                                ),
+                                                                34..40,
                                                             ),
                                                             filters: [
-                                                                UrlDecode,
-                                                                Split {
-                                                                    sep: InterpolatedString {
-                                                                        parts: [
-                                                                            Template(
-                                                                                Template {
-                                                                                    expr: Expr {
-                                                                                        variable: VariableName(
-                                                                                            "seperator",
-                                                                                        ),
-                                                                                        filters: [],
+                                                                (
+                                                                    UrlDecode,
+                                                                    41..50,
+                                                                ),
+                                                                (
+                                                                    Split {
+                                                                        sep: InterpolatedString {
+                                                                            parts: [
+                                                                                Template(
+                                                                                    Template {
+                                                                                        expr: Expr {
+                                                                                            variable: (
+                                                                                                VariableName(
+                                                                                                    "seperator",
+                                                                                                ),
+                                                                                                60..69,
+                                                                                            ),
+                                                                                            filters: [],
+                                                                                            span: 60..69,
+                                                                                        },
+                                                                                        span: 58..71,
                                                                                     },
-                                                                                },
-                                                                            ),
-                                                                        ],
+                                                                                ),
+                                                                            ],
+                                                                            span: 57..72,
+                                                                        },
                                                                     },
-                                                                },
+                                                                    51..72,
+                                                                ),
                                                             ],
+                                                            span: 34..72,
                                                         },
+                                                        span: 32..74,
                                                     },
                                                 ),
                                             ],
+                                            span: 32..74,
                                         },
+                                        span: 27..74,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..74,
                             },
                             response: None,
+                            span: 0..74,
                         },
                     ],
                 },
@@ -1141,15 +1427,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -1159,6 +1450,7 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 24..27,
                                         },
                                         value: InterpolatedString {
                                             parts: [
@@ -1168,21 +1460,31 @@ mod tests {
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "apikey",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "apikey",
+                                                                ),
+                                                                42..48,
                                                             ),
                                                             filters: [],
+                                                            span: 42..48,
                                                         },
+                                                        span: 40..50,
                                                     },
                                                 ),
                                             ],
+                                            span: 29..50,
                                         },
+                                        span: 24..50,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..50,
                             },
                             response: None,
+                            span: 0..50,
                         },
                     ],
                 },
@@ -1207,15 +1509,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -1225,27 +1532,38 @@ mod tests {
                                                     "message",
                                                 ),
                                             ],
+                                            span: 24..31,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: FunctionName(
-                                                                "newUuid",
+                                                            variable: (
+                                                                FunctionName(
+                                                                    "newUuid",
+                                                                ),
+                                                                35..42,
                                                             ),
                                                             filters: [],
+                                                            span: 35..42,
                                                         },
+                                                        span: 33..44,
                                                     },
                                                 ),
                                             ],
+                                            span: 33..44,
                                         },
+                                        span: 24..44,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..44,
                             },
                             response: None,
+                            span: 0..44,
                         },
                     ],
                 },
@@ -1270,15 +1588,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -1288,21 +1611,29 @@ mod tests {
                                                     "message",
                                                 ),
                                             ],
+                                            span: 24..31,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: FunctionName(
-                                                                "newUuid",
+                                                            variable: (
+                                                                FunctionName(
+                                                                    "newUuid",
+                                                                ),
+                                                                35..42,
                                                             ),
                                                             filters: [],
+                                                            span: 35..42,
                                                         },
+                                                        span: 33..44,
                                                     },
                                                 ),
                                             ],
+                                            span: 33..44,
                                         },
+                                        span: 24..44,
                                     },
                                     KeyValue {
                                         key: InterpolatedString {
@@ -1311,27 +1642,38 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 45..48,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "apikey",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "apikey",
+                                                                ),
+                                                                52..58,
                                                             ),
                                                             filters: [],
+                                                            span: 52..58,
                                                         },
+                                                        span: 50..60,
                                                     },
                                                 ),
                                             ],
+                                            span: 50..60,
                                         },
+                                        span: 45..60,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..60,
                             },
                             response: None,
+                            span: 0..60,
                         },
                     ],
                 },
@@ -1357,15 +1699,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.org",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.org",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [
                                     KeyValue {
@@ -1375,27 +1722,38 @@ mod tests {
                                                     "key",
                                                 ),
                                             ],
+                                            span: 24..27,
                                         },
                                         value: InterpolatedString {
                                             parts: [
                                                 Template(
                                                     Template {
                                                         expr: Expr {
-                                                            variable: VariableName(
-                                                                "api-key",
+                                                            variable: (
+                                                                VariableName(
+                                                                    "api-key",
+                                                                ),
+                                                                31..38,
                                                             ),
                                                             filters: [],
+                                                            span: 31..38,
                                                         },
+                                                        span: 29..40,
                                                     },
                                                 ),
                                             ],
+                                            span: 29..40,
                                         },
+                                        span: 24..40,
                                     },
                                 ],
                                 request_sections: [],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..40,
                             },
                             response: None,
+                            span: 0..40,
                         },
                     ],
                 },
@@ -1411,14 +1769,23 @@ mod tests {
         let test_str = "GET https://example.org\nkey\\uFFFT: thisshoulderror";
         assert_debug_snapshot!(
         ast_parser().parse(test_str),
-            @r"
+            @r#"
         ParseResult {
             output: None,
             errs: [
-                found ''F'' at 29..30 expected ''{'',
+                Unexpected {
+                    span: 29..30,
+                    found: Some(
+                        'F',
+                    ),
+                    expected: [
+                        "'{'",
+                    ],
+                    labels: [],
+                },
             ],
         }
-        ",
+        "#,
         );
     }
 
@@ -1436,76 +1803,116 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://",
-                                            ),
-                                            Template(
-                                                Template {
-                                                    expr: Expr {
-                                                        variable: VariableName(
-                                                            "host",
-                                                        ),
-                                                        filters: [],
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://",
+                                                ),
+                                                Template(
+                                                    Template {
+                                                        expr: Expr {
+                                                            variable: (
+                                                                VariableName(
+                                                                    "host",
+                                                                ),
+                                                                14..18,
+                                                            ),
+                                                            filters: [],
+                                                            span: 14..18,
+                                                        },
+                                                        span: 12..20,
                                                     },
-                                                },
-                                            ),
-                                            Str(
-                                                "/",
-                                            ),
-                                            Template(
-                                                Template {
-                                                    expr: Expr {
-                                                        variable: VariableName(
-                                                            "id",
-                                                        ),
-                                                        filters: [],
+                                                ),
+                                                Str(
+                                                    "/",
+                                                ),
+                                                Template(
+                                                    Template {
+                                                        expr: Expr {
+                                                            variable: (
+                                                                VariableName(
+                                                                    "id",
+                                                                ),
+                                                                23..25,
+                                                            ),
+                                                            filters: [],
+                                                            span: 23..25,
+                                                        },
+                                                        span: 21..27,
                                                     },
-                                                },
-                                            ),
-                                            Str(
-                                                "/status",
-                                            ),
-                                        ],
-                                    },
+                                                ),
+                                                Str(
+                                                    "/status",
+                                                ),
+                                            ],
+                                            span: 4..34,
+                                        },
+                                    ),
+                                    4..34,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                Variable(
-                                                    VariableDefinitionOption {
-                                                        name: "host",
-                                                        value: String(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "example.net",
-                                                                    ),
-                                                                ],
-                                                            },
-                                                        ),
-                                                    },
+                                                (
+                                                    Variable(
+                                                        VariableDefinitionOption {
+                                                            name: (
+                                                                "host",
+                                                                55..59,
+                                                            ),
+                                                            value: (
+                                                                String(
+                                                                    InterpolatedString {
+                                                                        parts: [
+                                                                            Str(
+                                                                                "example.net",
+                                                                            ),
+                                                                        ],
+                                                                        span: 60..71,
+                                                                    },
+                                                                ),
+                                                                60..71,
+                                                            ),
+                                                            span: 55..71,
+                                                        },
+                                                    ),
+                                                    45..71,
                                                 ),
-                                                Variable(
-                                                    VariableDefinitionOption {
-                                                        name: "id",
-                                                        value: Integer(
-                                                            1234,
-                                                        ),
-                                                    },
+                                                (
+                                                    Variable(
+                                                        VariableDefinitionOption {
+                                                            name: (
+                                                                "id",
+                                                                82..84,
+                                                            ),
+                                                            value: (
+                                                                Integer(
+                                                                    1234,
+                                                                ),
+                                                                85..89,
+                                                            ),
+                                                            span: 82..89,
+                                                        },
+                                                    ),
+                                                    72..89,
                                                 ),
                                             ],
+                                            span: 35..89,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..89,
                             },
                             response: None,
+                            span: 0..89,
                         },
                     ],
                 },
@@ -1531,108 +1938,165 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                Compressed(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    Compressed(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    34..50,
                                                 ),
-                                                Location(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    Location(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    51..65,
                                                 ),
-                                                LocationTrusted(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    LocationTrusted(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    66..88,
                                                 ),
-                                                Http10(
-                                                    Literal(
-                                                        false,
+                                                (
+                                                    Http10(
+                                                        Literal(
+                                                            false,
+                                                        ),
                                                     ),
+                                                    89..103,
                                                 ),
-                                                Http11(
-                                                    Literal(
-                                                        false,
+                                                (
+                                                    Http11(
+                                                        Literal(
+                                                            false,
+                                                        ),
                                                     ),
+                                                    104..118,
                                                 ),
-                                                Http2(
-                                                    Literal(
-                                                        false,
+                                                (
+                                                    Http2(
+                                                        Literal(
+                                                            false,
+                                                        ),
                                                     ),
+                                                    119..131,
                                                 ),
-                                                Http3(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    Http3(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    132..143,
                                                 ),
-                                                Insecure(
-                                                    Literal(
-                                                        false,
+                                                (
+                                                    Insecure(
+                                                        Literal(
+                                                            false,
+                                                        ),
                                                     ),
+                                                    144..159,
                                                 ),
-                                                Ipv4(
-                                                    Literal(
-                                                        false,
+                                                (
+                                                    Ipv4(
+                                                        Literal(
+                                                            false,
+                                                        ),
                                                     ),
+                                                    160..171,
                                                 ),
-                                                Ipv6(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    Ipv6(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    172..182,
                                                 ),
-                                                Netrc(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    Netrc(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    183..194,
                                                 ),
-                                                NetrcOptional(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    NetrcOptional(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    195..215,
                                                 ),
-                                                PathAsIs(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    PathAsIs(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    216..232,
                                                 ),
-                                                Skip(
-                                                    Literal(
-                                                        false,
+                                                (
+                                                    Skip(
+                                                        Literal(
+                                                            false,
+                                                        ),
                                                     ),
+                                                    233..244,
                                                 ),
-                                                Verbose(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    Verbose(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    245..258,
                                                 ),
-                                                VeryVerbose(
-                                                    Literal(
-                                                        true,
+                                                (
+                                                    VeryVerbose(
+                                                        Literal(
+                                                            true,
+                                                        ),
                                                     ),
+                                                    259..277,
                                                 ),
                                             ],
+                                            span: 24..277,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..277,
                             },
                             response: None,
+                            span: 0..277,
                         },
                     ],
                 },
@@ -1659,64 +2123,97 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                ConnectTimeout(
-                                                    Template(
-                                                        Template {
-                                                            expr: Expr {
-                                                                variable: VariableName(
-                                                                    "connectTimeout",
-                                                                ),
-                                                                filters: [],
+                                                (
+                                                    ConnectTimeout(
+                                                        Template(
+                                                            Template {
+                                                                expr: Expr {
+                                                                    variable: (
+                                                                        VariableName(
+                                                                            "connectTimeout",
+                                                                        ),
+                                                                        53..67,
+                                                                    ),
+                                                                    filters: [],
+                                                                    span: 53..67,
+                                                                },
+                                                                span: 51..69,
                                                             },
-                                                        },
+                                                        ),
                                                     ),
+                                                    34..69,
                                                 ),
-                                                Delay(
-                                                    Template(
-                                                        Template {
-                                                            expr: Expr {
-                                                                variable: VariableName(
-                                                                    "delay",
-                                                                ),
-                                                                filters: [],
+                                                (
+                                                    Delay(
+                                                        Template(
+                                                            Template {
+                                                                expr: Expr {
+                                                                    variable: (
+                                                                        VariableName(
+                                                                            "delay",
+                                                                        ),
+                                                                        79..84,
+                                                                    ),
+                                                                    filters: [],
+                                                                    span: 79..84,
+                                                                },
+                                                                span: 77..86,
                                                             },
-                                                        },
+                                                        ),
                                                     ),
+                                                    70..86,
                                                 ),
-                                                RetryInterval(
-                                                    Template(
-                                                        Template {
-                                                            expr: Expr {
-                                                                variable: VariableName(
-                                                                    "retryInterval",
-                                                                ),
-                                                                filters: [],
+                                                (
+                                                    RetryInterval(
+                                                        Template(
+                                                            Template {
+                                                                expr: Expr {
+                                                                    variable: (
+                                                                        VariableName(
+                                                                            "retryInterval",
+                                                                        ),
+                                                                        105..118,
+                                                                    ),
+                                                                    filters: [],
+                                                                    span: 105..118,
+                                                                },
+                                                                span: 103..120,
                                                             },
-                                                        },
+                                                        ),
                                                     ),
+                                                    87..120,
                                                 ),
                                             ],
+                                            span: 24..120,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..120,
                             },
                             response: None,
+                            span: 0..120,
                         },
                     ],
                 },
@@ -1743,52 +2240,73 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                ConnectTimeout(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 5,
-                                                            unit: None,
-                                                        },
+                                                (
+                                                    ConnectTimeout(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 5,
+                                                                unit: None,
+                                                                span: 51..52,
+                                                            },
+                                                        ),
                                                     ),
+                                                    34..52,
                                                 ),
-                                                Delay(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 4,
-                                                            unit: None,
-                                                        },
+                                                (
+                                                    Delay(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 4,
+                                                                unit: None,
+                                                                span: 60..61,
+                                                            },
+                                                        ),
                                                     ),
+                                                    53..61,
                                                 ),
-                                                RetryInterval(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 500,
-                                                            unit: None,
-                                                        },
+                                                (
+                                                    RetryInterval(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 500,
+                                                                unit: None,
+                                                                span: 78..81,
+                                                            },
+                                                        ),
                                                     ),
+                                                    62..81,
                                                 ),
                                             ],
+                                            span: 24..81,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..81,
                             },
                             response: None,
+                            span: 0..81,
                         },
                     ],
                 },
@@ -1815,58 +2333,79 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                ConnectTimeout(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 5,
-                                                            unit: Some(
-                                                                Second,
-                                                            ),
-                                                        },
+                                                (
+                                                    ConnectTimeout(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 5,
+                                                                unit: Some(
+                                                                    Second,
+                                                                ),
+                                                                span: 51..53,
+                                                            },
+                                                        ),
                                                     ),
+                                                    34..53,
                                                 ),
-                                                Delay(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 4,
-                                                            unit: Some(
-                                                                Second,
-                                                            ),
-                                                        },
+                                                (
+                                                    Delay(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 4,
+                                                                unit: Some(
+                                                                    Second,
+                                                                ),
+                                                                span: 61..63,
+                                                            },
+                                                        ),
                                                     ),
+                                                    54..63,
                                                 ),
-                                                RetryInterval(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 500,
-                                                            unit: Some(
-                                                                Second,
-                                                            ),
-                                                        },
+                                                (
+                                                    RetryInterval(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 500,
+                                                                unit: Some(
+                                                                    Second,
+                                                                ),
+                                                                span: 80..84,
+                                                            },
+                                                        ),
                                                     ),
+                                                    64..84,
                                                 ),
                                             ],
+                                            span: 24..84,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..84,
                             },
                             response: None,
+                            span: 0..84,
                         },
                     ],
                 },
@@ -1893,58 +2432,79 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                ConnectTimeout(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 5,
-                                                            unit: Some(
-                                                                Millisecond,
-                                                            ),
-                                                        },
+                                                (
+                                                    ConnectTimeout(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 5,
+                                                                unit: Some(
+                                                                    Millisecond,
+                                                                ),
+                                                                span: 51..54,
+                                                            },
+                                                        ),
                                                     ),
+                                                    34..54,
                                                 ),
-                                                Delay(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 4,
-                                                            unit: Some(
-                                                                Millisecond,
-                                                            ),
-                                                        },
+                                                (
+                                                    Delay(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 4,
+                                                                unit: Some(
+                                                                    Millisecond,
+                                                                ),
+                                                                span: 62..65,
+                                                            },
+                                                        ),
                                                     ),
+                                                    55..65,
                                                 ),
-                                                RetryInterval(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 500,
-                                                            unit: Some(
-                                                                Millisecond,
-                                                            ),
-                                                        },
+                                                (
+                                                    RetryInterval(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 500,
+                                                                unit: Some(
+                                                                    Millisecond,
+                                                                ),
+                                                                span: 82..87,
+                                                            },
+                                                        ),
                                                     ),
+                                                    66..87,
                                                 ),
                                             ],
+                                            span: 24..87,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..87,
                             },
                             response: None,
+                            span: 0..87,
                         },
                     ],
                 },
@@ -1971,58 +2531,79 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                ConnectTimeout(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 5,
-                                                            unit: Some(
-                                                                Minute,
-                                                            ),
-                                                        },
+                                                (
+                                                    ConnectTimeout(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 5,
+                                                                unit: Some(
+                                                                    Minute,
+                                                                ),
+                                                                span: 51..53,
+                                                            },
+                                                        ),
                                                     ),
+                                                    34..53,
                                                 ),
-                                                Delay(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 4,
-                                                            unit: Some(
-                                                                Minute,
-                                                            ),
-                                                        },
+                                                (
+                                                    Delay(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 4,
+                                                                unit: Some(
+                                                                    Minute,
+                                                                ),
+                                                                span: 61..63,
+                                                            },
+                                                        ),
                                                     ),
+                                                    54..63,
                                                 ),
-                                                RetryInterval(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 500,
-                                                            unit: Some(
-                                                                Minute,
-                                                            ),
-                                                        },
+                                                (
+                                                    RetryInterval(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 500,
+                                                                unit: Some(
+                                                                    Minute,
+                                                                ),
+                                                                span: 80..84,
+                                                            },
+                                                        ),
                                                     ),
+                                                    64..84,
                                                 ),
                                             ],
+                                            span: 24..84,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..84,
                             },
                             response: None,
+                            span: 0..84,
                         },
                     ],
                 },
@@ -2048,36 +2629,49 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                RetryInterval(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 500,
-                                                            unit: None,
-                                                        },
+                                                (
+                                                    RetryInterval(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 500,
+                                                                unit: None,
+                                                                span: 50..53,
+                                                            },
+                                                        ),
                                                     ),
+                                                    34..53,
                                                 ),
                                             ],
+                                            span: 24..53,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..53,
                             },
                             response: None,
+                            span: 0..53,
                         },
                     ],
                 },
@@ -2103,36 +2697,49 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                Delay(
-                                                    Literal(
-                                                        Duration {
-                                                            duration: 4,
-                                                            unit: None,
-                                                        },
+                                                (
+                                                    Delay(
+                                                        Literal(
+                                                            Duration {
+                                                                duration: 4,
+                                                                unit: None,
+                                                                span: 41..42,
+                                                            },
+                                                        ),
                                                     ),
+                                                    34..42,
                                                 ),
                                             ],
+                                            span: 24..42,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..42,
                             },
                             response: None,
+                            span: 0..42,
                         },
                     ],
                 },
@@ -2159,48 +2766,69 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                LimitRate(
-                                                    Literal(
-                                                        59,
+                                                (
+                                                    LimitRate(
+                                                        Literal(
+                                                            59,
+                                                        ),
                                                     ),
+                                                    34..48,
                                                 ),
-                                                MaxRedirs(
-                                                    Literal(
-                                                        109,
+                                                (
+                                                    MaxRedirs(
+                                                        Literal(
+                                                            109,
+                                                        ),
                                                     ),
+                                                    49..64,
                                                 ),
-                                                Repeat(
-                                                    Literal(
-                                                        10,
+                                                (
+                                                    Repeat(
+                                                        Literal(
+                                                            10,
+                                                        ),
                                                     ),
+                                                    65..75,
                                                 ),
-                                                Retry(
-                                                    Literal(
-                                                        5,
+                                                (
+                                                    Retry(
+                                                        Literal(
+                                                            5,
+                                                        ),
                                                     ),
+                                                    76..84,
                                                 ),
                                             ],
+                                            span: 24..84,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..84,
                             },
                             response: None,
+                            span: 0..84,
                         },
                     ],
                 },
@@ -2229,33 +2857,45 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                LimitRate(
-                                                    Literal(
-                                                        18446744073709551615,
+                                                (
+                                                    LimitRate(
+                                                        Literal(
+                                                            18446744073709551615,
+                                                        ),
                                                     ),
+                                                    34..66,
                                                 ),
                                             ],
+                                            span: 24..66,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..66,
                             },
                             response: None,
+                            span: 0..66,
                         },
                     ],
                 },
@@ -2282,39 +2922,53 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                LimitRate(
-                                                    BigInteger(
-                                                        "18446744073709551616",
+                                                (
+                                                    LimitRate(
+                                                        BigInteger(
+                                                            "18446744073709551616",
+                                                        ),
                                                     ),
+                                                    34..66,
                                                 ),
                                             ],
+                                            span: 24..66,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..66,
                             },
                             response: None,
+                            span: 0..66,
                         },
                     ],
                 },
             ),
             errs: [
-                The integer value is larger than 18446744073709551615 and is not valid for 64bit version of hurl at 46..66,
+                IntegerTooLarge {
+                    span: 46..66,
+                },
             ],
         }
         "#,
@@ -2338,33 +2992,45 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                LimitRate(
-                                                    Literal(
-                                                        4294967295,
+                                                (
+                                                    LimitRate(
+                                                        Literal(
+                                                            4294967295,
+                                                        ),
                                                     ),
+                                                    34..56,
                                                 ),
                                             ],
+                                            span: 24..56,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..56,
                             },
                             response: None,
+                            span: 0..56,
                         },
                     ],
                 },
@@ -2390,91 +3056,128 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                AwsSigv4(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "aws:amz:eu-central-1:sts",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    AwsSigv4(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "aws:amz:eu-central-1:sts",
+                                                                ),
+                                                            ],
+                                                            span: 45..69,
+                                                        },
+                                                    ),
+                                                    34..69,
                                                 ),
-                                                ConnectTo(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "example.com:8000:127.0.0.1:8080",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    ConnectTo(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "example.com:8000:127.0.0.1:8080",
+                                                                ),
+                                                            ],
+                                                            span: 82..113,
+                                                        },
+                                                    ),
+                                                    70..113,
                                                 ),
-                                                NetrcFile(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "~/.netrc",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    NetrcFile(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "~/.netrc",
+                                                                ),
+                                                            ],
+                                                            span: 126..134,
+                                                        },
+                                                    ),
+                                                    114..134,
                                                 ),
-                                                Proxy(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "example.proxy:8050",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    Proxy(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "example.proxy:8050",
+                                                                ),
+                                                            ],
+                                                            span: 142..160,
+                                                        },
+                                                    ),
+                                                    135..160,
                                                 ),
-                                                Resolve(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "example.com:8000:127.0.0.1",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    Resolve(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "example.com:8000:127.0.0.1",
+                                                                ),
+                                                            ],
+                                                            span: 170..196,
+                                                        },
+                                                    ),
+                                                    161..196,
                                                 ),
-                                                UnixSocket(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "sock",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    UnixSocket(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "sock",
+                                                                ),
+                                                            ],
+                                                            span: 210..214,
+                                                        },
+                                                    ),
+                                                    197..214,
                                                 ),
-                                                User(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "joe=secret",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    User(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "joe=secret",
+                                                                ),
+                                                            ],
+                                                            span: 221..231,
+                                                        },
+                                                    ),
+                                                    215..231,
                                                 ),
                                             ],
+                                            span: 24..231,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..231,
                             },
                             response: None,
+                            span: 0..231,
                         },
                     ],
                 },
@@ -2500,211 +3203,308 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://",
-                                            ),
-                                            Template(
-                                                Template {
-                                                    expr: Expr {
-                                                        variable: VariableName(
-                                                            "host",
-                                                        ),
-                                                        filters: [],
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://",
+                                                ),
+                                                Template(
+                                                    Template {
+                                                        expr: Expr {
+                                                            variable: (
+                                                                VariableName(
+                                                                    "host",
+                                                                ),
+                                                                14..18,
+                                                            ),
+                                                            filters: [],
+                                                            span: 14..18,
+                                                        },
+                                                        span: 12..20,
                                                     },
-                                                },
-                                            ),
-                                            Str(
-                                                ":",
-                                            ),
-                                            Template(
-                                                Template {
-                                                    expr: Expr {
-                                                        variable: VariableName(
-                                                            "port",
-                                                        ),
-                                                        filters: [],
+                                                ),
+                                                Str(
+                                                    ":",
+                                                ),
+                                                Template(
+                                                    Template {
+                                                        expr: Expr {
+                                                            variable: (
+                                                                VariableName(
+                                                                    "port",
+                                                                ),
+                                                                23..27,
+                                                            ),
+                                                            filters: [],
+                                                            span: 23..27,
+                                                        },
+                                                        span: 21..29,
                                                     },
-                                                },
-                                            ),
-                                        ],
-                                    },
+                                                ),
+                                            ],
+                                            span: 4..29,
+                                        },
+                                    ),
+                                    4..29,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                AwsSigv4(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "aws",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    AwsSigv4(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "aws",
+                                                                                ),
+                                                                                53..56,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 53..56,
+                                                                        },
+                                                                        span: 51..58,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 51..58,
+                                                        },
+                                                    ),
+                                                    40..58,
                                                 ),
-                                                ConnectTo(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "host",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    ConnectTo(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "host",
+                                                                                ),
+                                                                                73..77,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 73..77,
+                                                                        },
+                                                                        span: 71..79,
                                                                     },
-                                                                },
-                                                            ),
-                                                            Str(
-                                                                ":",
-                                                            ),
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "port",
-                                                                        ),
-                                                                        filters: [],
+                                                                ),
+                                                                Str(
+                                                                    ":",
+                                                                ),
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "port",
+                                                                                ),
+                                                                                82..86,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 82..86,
+                                                                        },
+                                                                        span: 80..88,
                                                                     },
-                                                                },
-                                                            ),
-                                                            Str(
-                                                                ":127.0.0.1:8080",
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                                Str(
+                                                                    ":127.0.0.1:8080",
+                                                                ),
+                                                            ],
+                                                            span: 71..103,
+                                                        },
+                                                    ),
+                                                    59..103,
                                                 ),
-                                                NetrcFile(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "filepath",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    NetrcFile(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "filepath",
+                                                                                ),
+                                                                                118..126,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 118..126,
+                                                                        },
+                                                                        span: 116..128,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 116..128,
+                                                        },
+                                                    ),
+                                                    104..128,
                                                 ),
-                                                Proxy(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "proxyhost",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    Proxy(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "proxyhost",
+                                                                                ),
+                                                                                138..147,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 138..147,
+                                                                        },
+                                                                        span: 136..149,
                                                                     },
-                                                                },
-                                                            ),
-                                                            Str(
-                                                                ":8050",
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                                Str(
+                                                                    ":8050",
+                                                                ),
+                                                            ],
+                                                            span: 136..154,
+                                                        },
+                                                    ),
+                                                    129..154,
                                                 ),
-                                                Resolve(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "host",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    Resolve(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "host",
+                                                                                ),
+                                                                                166..170,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 166..170,
+                                                                        },
+                                                                        span: 164..172,
                                                                     },
-                                                                },
-                                                            ),
-                                                            Str(
-                                                                ":",
-                                                            ),
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "port",
-                                                                        ),
-                                                                        filters: [],
+                                                                ),
+                                                                Str(
+                                                                    ":",
+                                                                ),
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "port",
+                                                                                ),
+                                                                                175..179,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 175..179,
+                                                                        },
+                                                                        span: 173..181,
                                                                     },
-                                                                },
-                                                            ),
-                                                            Str(
-                                                                ":127.0.0.1",
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                                Str(
+                                                                    ":127.0.0.1",
+                                                                ),
+                                                            ],
+                                                            span: 164..191,
+                                                        },
+                                                    ),
+                                                    155..191,
                                                 ),
-                                                UnixSocket(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "socket",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    UnixSocket(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "socket",
+                                                                                ),
+                                                                                207..213,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 207..213,
+                                                                        },
+                                                                        span: 205..215,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 205..215,
+                                                        },
+                                                    ),
+                                                    192..215,
                                                 ),
-                                                User(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "user",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    User(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "user",
+                                                                                ),
+                                                                                224..228,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 224..228,
+                                                                        },
+                                                                        span: 222..230,
                                                                     },
-                                                                },
-                                                            ),
-                                                            Str(
-                                                                "=",
-                                                            ),
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "password",
-                                                                        ),
-                                                                        filters: [],
+                                                                ),
+                                                                Str(
+                                                                    "=",
+                                                                ),
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "password",
+                                                                                ),
+                                                                                233..241,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 233..241,
+                                                                        },
+                                                                        span: 231..243,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 222..243,
+                                                        },
+                                                    ),
+                                                    216..243,
                                                 ),
                                             ],
+                                            span: 30..243,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..243,
                             },
                             response: None,
+                            span: 0..243,
                         },
                     ],
                 },
@@ -2730,55 +3530,76 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                Cacert(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "/etc/cert.pem",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    Cacert(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "/etc/cert.pem",
+                                                                ),
+                                                            ],
+                                                            span: 42..55,
+                                                        },
+                                                    ),
+                                                    34..55,
                                                 ),
-                                                Key(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                ".ssh/id_rsa.pub",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    Key(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    ".ssh/id_rsa.pub",
+                                                                ),
+                                                            ],
+                                                            span: 61..76,
+                                                        },
+                                                    ),
+                                                    56..76,
                                                 ),
-                                                Output(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Str(
-                                                                "./myreport",
-                                                            ),
-                                                        ],
-                                                    },
+                                                (
+                                                    Output(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Str(
+                                                                    "./myreport",
+                                                                ),
+                                                            ],
+                                                            span: 85..95,
+                                                        },
+                                                    ),
+                                                    77..95,
                                                 ),
                                             ],
+                                            span: 24..95,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..95,
                             },
                             response: None,
+                            span: 0..95,
                         },
                     ],
                 },
@@ -2804,76 +3625,112 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 4..23,
+                                        },
+                                    ),
+                                    4..23,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                Cacert(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "certfilepath",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    Cacert(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "certfilepath",
+                                                                                ),
+                                                                                44..56,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 44..56,
+                                                                        },
+                                                                        span: 42..58,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 42..58,
+                                                        },
+                                                    ),
+                                                    34..58,
                                                 ),
-                                                Key(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "keyfilepath",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    Key(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "keyfilepath",
+                                                                                ),
+                                                                                66..77,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 66..77,
+                                                                        },
+                                                                        span: 64..79,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 64..79,
+                                                        },
+                                                    ),
+                                                    59..79,
                                                 ),
-                                                Output(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "reportfilepath",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    Output(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "reportfilepath",
+                                                                                ),
+                                                                                90..104,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 90..104,
+                                                                        },
+                                                                        span: 88..106,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 88..106,
+                                                        },
+                                                    ),
+                                                    80..106,
                                                 ),
                                             ],
+                                            span: 24..106,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..106,
                             },
                             response: None,
+                            span: 0..106,
                         },
                     ],
                 },
@@ -2899,15 +3756,20 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 0..3,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "http://localhost:3000/api/search",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "http://localhost:3000/api/search",
+                                                ),
+                                            ],
+                                            span: 4..36,
+                                        },
+                                    ),
+                                    4..36,
                                 ),
                                 headers: [],
                                 request_sections: [
@@ -2921,6 +3783,7 @@ mod tests {
                                                                 "q",
                                                             ),
                                                         ],
+                                                        span: 57..58,
                                                     },
                                                     value: InterpolatedString {
                                                         parts: [
@@ -2928,7 +3791,9 @@ mod tests {
                                                                 "1982",
                                                             ),
                                                         ],
+                                                        span: 60..64,
                                                     },
+                                                    span: 57..64,
                                                 },
                                                 KeyValue {
                                                     key: InterpolatedString {
@@ -2937,6 +3802,7 @@ mod tests {
                                                                 "sort",
                                                             ),
                                                         ],
+                                                        span: 65..69,
                                                     },
                                                     value: InterpolatedString {
                                                         parts: [
@@ -2944,15 +3810,21 @@ mod tests {
                                                                 "name",
                                                             ),
                                                         ],
+                                                        span: 71..75,
                                                     },
+                                                    span: 65..75,
                                                 },
                                             ],
+                                            span: 37..75,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 0..75,
                             },
                             response: None,
+                            span: 0..75,
                         },
                     ],
                 },
@@ -2987,130 +3859,201 @@ mod tests {
                             request: Request {
                                 method: Method {
                                     value: "GET",
+                                    span: 13..16,
                                 },
-                                url: Url(
-                                    InterpolatedString {
-                                        parts: [
-                                            Str(
-                                                "https://example.com",
-                                            ),
-                                        ],
-                                    },
+                                url: (
+                                    Url(
+                                        InterpolatedString {
+                                            parts: [
+                                                Str(
+                                                    "https://example.com",
+                                                ),
+                                            ],
+                                            span: 17..36,
+                                        },
+                                    ),
+                                    17..36,
                                 ),
                                 headers: [],
                                 request_sections: [
                                     OptionsSection(
                                         RequestOptionsSection {
                                             options: [
-                                                Cacert(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "certfilepath",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    Cacert(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "certfilepath",
+                                                                                ),
+                                                                                81..93,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 81..93,
+                                                                        },
+                                                                        span: 79..95,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 79..95,
+                                                        },
+                                                    ),
+                                                    71..95,
                                                 ),
-                                                Key(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "keyfilepath",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    Key(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "keyfilepath",
+                                                                                ),
+                                                                                115..126,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 115..126,
+                                                                        },
+                                                                        span: 113..128,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 113..128,
+                                                        },
+                                                    ),
+                                                    108..128,
                                                 ),
-                                                Output(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "reportfilepath",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    Output(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "reportfilepath",
+                                                                                ),
+                                                                                151..165,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 151..165,
+                                                                        },
+                                                                        span: 149..167,
                                                                     },
-                                                                },
-                                                            ),
-                                                        ],
-                                                    },
+                                                                ),
+                                                            ],
+                                                            span: 149..167,
+                                                        },
+                                                    ),
+                                                    141..167,
                                                 ),
-                                                ConnectTo(
-                                                    InterpolatedString {
-                                                        parts: [
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "host",
-                                                                        ),
-                                                                        filters: [],
+                                                (
+                                                    ConnectTo(
+                                                        InterpolatedString {
+                                                            parts: [
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "host",
+                                                                                ),
+                                                                                194..198,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 194..198,
+                                                                        },
+                                                                        span: 192..200,
                                                                     },
-                                                                },
-                                                            ),
-                                                            Str(
-                                                                ":",
-                                                            ),
-                                                            Template(
-                                                                Template {
-                                                                    expr: Expr {
-                                                                        variable: VariableName(
-                                                                            "port",
-                                                                        ),
-                                                                        filters: [],
+                                                                ),
+                                                                Str(
+                                                                    ":",
+                                                                ),
+                                                                Template(
+                                                                    Template {
+                                                                        expr: Expr {
+                                                                            variable: (
+                                                                                VariableName(
+                                                                                    "port",
+                                                                                ),
+                                                                                203..207,
+                                                                            ),
+                                                                            filters: [],
+                                                                            span: 203..207,
+                                                                        },
+                                                                        span: 201..209,
                                                                     },
-                                                                },
+                                                                ),
+                                                                Str(
+                                                                    ":127.0.0.1:8080",
+                                                                ),
+                                                            ],
+                                                            span: 192..224,
+                                                        },
+                                                    ),
+                                                    180..224,
+                                                ),
+                                                (
+                                                    Variable(
+                                                        VariableDefinitionOption {
+                                                            name: (
+                                                                "host",
+                                                                247..251,
                                                             ),
-                                                            Str(
-                                                                ":127.0.0.1:8080",
+                                                            value: (
+                                                                String(
+                                                                    InterpolatedString {
+                                                                        parts: [
+                                                                            Str(
+                                                                                "example.net",
+                                                                            ),
+                                                                        ],
+                                                                        span: 252..263,
+                                                                    },
+                                                                ),
+                                                                252..263,
                                                             ),
-                                                        ],
-                                                    },
-                                                ),
-                                                Variable(
-                                                    VariableDefinitionOption {
-                                                        name: "host",
-                                                        value: String(
-                                                            InterpolatedString {
-                                                                parts: [
-                                                                    Str(
-                                                                        "example.net",
-                                                                    ),
-                                                                ],
-                                                            },
-                                                        ),
-                                                    },
+                                                            span: 247..263,
+                                                        },
+                                                    ),
+                                                    237..263,
                                                 ),
-                                                Variable(
-                                                    VariableDefinitionOption {
-                                                        name: "id",
-                                                        value: Integer(
-                                                            1234,
-                                                        ),
-                                                    },
+                                                (
+                                                    Variable(
+                                                        VariableDefinitionOption {
+                                                            name: (
+                                                                "id",
+                                                                286..288,
+                                                            ),
+                                                            value: (
+                                                                Integer(
+                                                                    1234,
+                                                                ),
+                                                                291..295,
+                                                            ),
+                                                            span: 286..295,
+                                                        },
+                                                    ),
+                                                    276..295,
                                                 ),
                                             ],
+                                            span: 37..304,
                                         },
                                     ),
                                 ],
+                                misplaced_sections: [],
                                 body: None,
+                                span: 1..304,
                             },
                             response: None,
+                            span: 1..304,
                         },
                     ],
                 },
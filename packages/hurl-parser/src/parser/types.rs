@@ -1,9 +1,17 @@
 use ordered_float::OrderedFloat;
 use std::hash::Hash;
 
+/// The byte range of a node within the parsed document.
+pub type Span = chumsky::span::SimpleSpan;
+
+/// A node paired with the byte range it was parsed from. Used for enum nodes
+/// that can't carry a span field of their own.
+pub type Spanned<T> = (T, Span);
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Method {
     pub value: String,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -65,13 +73,15 @@ pub enum FilterFunction {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Expr {
-    pub variable: ExprValue,
-    pub filters: Vec<FilterFunction>,
+    pub variable: Spanned<ExprValue>,
+    pub filters: Vec<Spanned<FilterFunction>>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Template {
     pub expr: Expr,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -91,33 +101,39 @@ pub enum InterpolatedStringPart {
 //the official grammer
 pub struct InterpolatedString {
     pub parts: Vec<InterpolatedStringPart>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct KeyValue {
     pub key: InterpolatedString,
     pub value: InterpolatedString,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct QueryStringParamsSection {
     pub queries: Vec<KeyValue>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FormParamsSection {
     pub params: Vec<KeyValue>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FileValue {
     pub filename: InterpolatedString,
     pub content_type: Option<String>,
+    pub span: Span,
 }
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FileKeyValue {
     pub key: InterpolatedString,
     pub value: FileValue,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -129,11 +145,13 @@ pub enum MultipartFormParam {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct MultipartFormDataSection {
     pub params: Vec<MultipartFormParam>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct CookiesSection {
     pub cookies: Vec<KeyValue>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -166,13 +184,15 @@ pub enum Query {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Capture {
     pub key: InterpolatedString,
-    pub query: Query,
-    pub filters: Vec<FilterFunction>,
+    pub query: Spanned<Query>,
+    pub filters: Vec<Spanned<FilterFunction>>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct CapturesSection {
     pub captures: Vec<Capture>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -207,19 +227,19 @@ pub enum PredicateValue {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum PredicateFunc {
-    Equal { value: PredicateValue },
-    NotEqual { value: PredicateValue },
-    Greater { value: PredicateValue },
-    GreaterOrEqual { value: PredicateValue },
-    Less { value: PredicateValue },
-    LessOrEqual { value: PredicateValue },
-    StartWith { value: PredicateValue },
-    EndWith { value: PredicateValue },
-    Contain { value: PredicateValue },
-    Match { value: PredicateValue },
+    Equal { value: Spanned<PredicateValue> },
+    NotEqual { value: Spanned<PredicateValue> },
+    Greater { value: Spanned<PredicateValue> },
+    GreaterOrEqual { value: Spanned<PredicateValue> },
+    Less { value: Spanned<PredicateValue> },
+    LessOrEqual { value: Spanned<PredicateValue> },
+    StartWith { value: Spanned<PredicateValue> },
+    EndWith { value: Spanned<PredicateValue> },
+    Contain { value: Spanned<PredicateValue> },
+    Match { value: Spanned<PredicateValue> },
     Exists,
     IsEmpty,
-    Include { value: PredicateValue },
+    Include { value: Spanned<PredicateValue> },
     IsInteger,
     IsFloat,
     IsBoolean,
@@ -232,25 +252,38 @@ pub enum PredicateFunc {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Predicate {
-    pub prefix: Option<PredicatePrefixOperator>,
-    pub function: PredicateFunc,
+    pub prefix: Option<Spanned<PredicatePrefixOperator>>,
+    pub function: Spanned<PredicateFunc>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Assert {
-    pub query: Query,
-    pub filters: Vec<FilterFunction>,
+    pub query: Spanned<Query>,
+    pub filters: Vec<Spanned<FilterFunction>>,
     pub predicate: Predicate,
+    pub span: Span,
+}
+
+/// The query and filters written at the start of an assert or capture line, followed
+/// by the `not` of a predicate when there is one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AssertStart {
+    pub query: Spanned<Query>,
+    pub filters: Vec<Spanned<FilterFunction>>,
+    pub not: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct AssertsSection {
     pub asserts: Vec<Assert>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct BasicAuthSection {
     pub key_values: Vec<KeyValue>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -270,6 +303,7 @@ pub enum DurationUnit {
 pub struct Duration {
     pub duration: u64,
     pub unit: Option<DurationUnit>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -299,8 +333,9 @@ pub enum VariableValue {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct VariableDefinitionOption {
-    pub name: String,
-    pub value: VariableValue,
+    pub name: Spanned<String>,
+    pub value: Spanned<VariableValue>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -351,13 +386,15 @@ pub enum RequestOption {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct RequestOptionsSection {
-    pub options: Vec<RequestOption>,
+    pub options: Vec<Spanned<RequestOption>>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct UnknownSection {
     pub section_name: String,
     pub lines: Vec<String>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -368,15 +405,34 @@ pub enum RequestSection {
     MultipartFormDataSection(MultipartFormDataSection),
     CookiesSection(CookiesSection),
     OptionsSection(RequestOptionsSection),
+    UnknownSection(UnknownSection),
+}
+
+impl RequestSection {
+    pub fn span(&self) -> Span {
+        match self {
+            RequestSection::BasicAuthSection(section) => section.span,
+            RequestSection::QueryStringParamsSection(section) => section.span,
+            RequestSection::FormParamsSection(section) => section.span,
+            RequestSection::MultipartFormDataSection(section) => section.span,
+            RequestSection::CookiesSection(section) => section.span,
+            RequestSection::OptionsSection(section) => section.span,
+            RequestSection::UnknownSection(section) => section.span,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Request {
     pub method: Method,
-    pub url: Url,
+    pub url: Spanned<Url>,
     pub headers: Vec<KeyValue>, //TODO rename to headers
     pub request_sections: Vec<RequestSection>,
+    //Response sections like [Asserts] written before the status line. They are parsed so
+    //that they can be reported instead of failing the whole entry
+    pub misplaced_sections: Vec<ResponseSection>,
     pub body: Option<Body>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -414,21 +470,57 @@ pub enum HttpStatus {
 pub enum ResponseSection {
     CapturesSection(CapturesSection),
     AssertsSection(AssertsSection),
+    UnknownSection(UnknownSection),
+}
+
+impl ResponseSection {
+    pub fn span(&self) -> Span {
+        match self {
+            ResponseSection::CapturesSection(section) => section.span,
+            ResponseSection::AssertsSection(section) => section.span,
+            ResponseSection::UnknownSection(section) => section.span,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Response {
-    pub version: HttpVersion,
-    pub status: HttpStatus,
+    pub version: Spanned<HttpVersion>,
+    pub status: Spanned<HttpStatus>,
     pub headers: Vec<KeyValue>,
     pub response_sections: Vec<ResponseSection>,
+    //Request sections like [Options] written after the status line
+    pub misplaced_sections: Vec<RequestSection>,
     pub body: Option<Body>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Entry {
     pub request: Box<Request>,
     pub response: Option<Box<Response>>,
+    pub span: Span,
+}
+
+impl Entry {
+    /// The request sections of the entry, including the ones misplaced after the status line
+    pub fn request_sections(&self) -> impl Iterator<Item = &RequestSection> {
+        self.request.request_sections.iter().chain(
+            self.response
+                .iter()
+                .flat_map(|response| &response.misplaced_sections),
+        )
+    }
+
+    /// The response sections of the entry, including the ones misplaced before the status
+    /// line
+    pub fn response_sections(&self) -> impl Iterator<Item = &ResponseSection> {
+        self.request.misplaced_sections.iter().chain(
+            self.response
+                .iter()
+                .flat_map(|response| &response.response_sections),
+        )
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -455,6 +547,7 @@ pub enum Bytes {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Body {
     pub bytes: Bytes,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -480,19 +573,21 @@ pub struct MultilineString {
     pub r#type: Option<MultilineStringType>,
     pub attributes: Vec<MultilineStringAttribute>,
     pub content: InterpolatedString,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct JsonKeyValue {
     pub key: InterpolatedString,
-    pub value: Json,
+    pub value: Spanned<Json>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Json {
     Invalid,
     Object(Vec<JsonKeyValue>),
-    Array(Vec<Json>),
+    Array(Vec<Spanned<Json>>),
     Str(String),
     InterpolatedString(InterpolatedString),
     Num(String),
@@ -506,6 +601,3 @@ pub enum Regex {
     Interpolated(InterpolatedString),
     Literal(String),
 }
-
-//TODO replace with custom error type
-pub use chumsky::error::Rich;
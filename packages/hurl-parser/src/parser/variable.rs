@@ -1,9 +1,10 @@
+use super::error::ParseError;
 use super::{key_value::key_parser, quoted_string::quoted_string_parser, types::VariableValue};
 use chumsky::prelude::*;
 use ordered_float::OrderedFloat;
 
 pub fn variable_value_parser<'a>(
-) -> impl Parser<'a, &'a str, VariableValue, extra::Err<Rich<'a, char>>> + Clone {
+) -> impl Parser<'a, &'a str, VariableValue, extra::Err<ParseError>> + Clone {
     let fraction = just('.').then(text::digits(10));
     let number = just("-")
         .or_not()
@@ -15,7 +16,7 @@ pub fn variable_value_parser<'a>(
                 match number.parse::<f64>() {
                     Ok(n) => VariableValue::Float(OrderedFloat::<f64>::from(n)),
                     Err(_) => {
-                        emitter.emit(Rich::custom(e.span(), "invalid float"));
+                        emitter.emit(ParseError::InvalidFloat { span: e.span() });
                         VariableValue::Invalid
                     }
                 }
@@ -40,7 +41,6 @@ pub fn variable_value_parser<'a>(
     variable_value.boxed()
 }
 
-//TODO add code action to wrap an invalid unquoted string in quotes
 //TODO add code action to convert the invalid variable definition
 // variable: bad_float=6.{{fraction}}
 //
@@ -48,8 +48,6 @@ pub fn variable_value_parser<'a>(
 //
 // variable: good_float_string="6.{{fraction}}"
 // variable: float_string={{good_float_string toFloat}}
-//
-// TODO add a lazy type evaluation to variables to validate if filters and templates are valid
 
 #[cfg(test)]
 mod option_tests {
@@ -124,6 +122,7 @@ mod option_tests {
                                 "example.net",
                             ),
                         ],
+                        span: 0..11,
                     },
                 ),
             ),
@@ -148,6 +147,7 @@ mod option_tests {
                                 "example.net",
                             ),
                         ],
+                        span: 0..13,
                     },
                 ),
             ),
@@ -0,0 +1,410 @@
+//! Walks the AST to find the templates used anywhere in a document.
+
+use super::types::{
+    Ast, Body, Bytes, FilterFunction, InterpolatedString, InterpolatedStringPart, Json, KeyValue,
+    MultilineString, MultipartFormParam, PredicateFunc, PredicateValue, Query, Regex, Request,
+    RequestOption, RequestSection, Response, ResponseSection, Spanned, Template, Url,
+    VariableValue,
+};
+use super::types::{BooleanOption, DurationOption, IntegerOption};
+
+/// All the templates of the AST in document order, including templates nested in the
+/// filters of other templates.
+pub fn templates(ast: &Ast) -> Vec<&Template> {
+    let mut walker = TemplateWalker { templates: vec![] };
+    for entry in &ast.entries {
+        walker.request(&entry.request);
+        if let Some(response) = &entry.response {
+            walker.response(response);
+        }
+    }
+    walker.templates.sort_by_key(|template| template.span.start);
+    walker.templates
+}
+
+struct TemplateWalker<'a> {
+    templates: Vec<&'a Template>,
+}
+
+impl<'a> TemplateWalker<'a> {
+    fn request(&mut self, request: &'a Request) {
+        if let Url::Url(url) = &request.url.0 {
+            self.interpolated_string(url);
+        }
+        self.key_values(&request.headers);
+        for section in &request.request_sections {
+            self.request_section(section);
+        }
+        for section in &request.misplaced_sections {
+            self.response_section(section);
+        }
+        if let Some(body) = &request.body {
+            self.body(body);
+        }
+    }
+
+    fn response(&mut self, response: &'a Response) {
+        self.key_values(&response.headers);
+        for section in &response.response_sections {
+            self.response_section(section);
+        }
+        for section in &response.misplaced_sections {
+            self.request_section(section);
+        }
+        if let Some(body) = &response.body {
+            self.body(body);
+        }
+    }
+
+    fn request_section(&mut self, section: &'a RequestSection) {
+        match section {
+            RequestSection::BasicAuthSection(section) => self.key_values(&section.key_values),
+            RequestSection::QueryStringParamsSection(section) => self.key_values(&section.queries),
+            RequestSection::FormParamsSection(section) => self.key_values(&section.params),
+            RequestSection::CookiesSection(section) => self.key_values(&section.cookies),
+            RequestSection::MultipartFormDataSection(section) => {
+                for param in &section.params {
+                    match param {
+                        MultipartFormParam::FileParam(file_param) => {
+                            self.interpolated_string(&file_param.key);
+                            self.interpolated_string(&file_param.value.filename);
+                        }
+                        MultipartFormParam::KeyValueParam(key_value) => self.key_value(key_value),
+                    }
+                }
+            }
+            RequestSection::OptionsSection(section) => {
+                for (option, _) in &section.options {
+                    self.option(option);
+                }
+            }
+            RequestSection::UnknownSection(_) => {}
+        }
+    }
+
+    fn response_section(&mut self, section: &'a ResponseSection) {
+        match section {
+            ResponseSection::CapturesSection(section) => {
+                for capture in &section.captures {
+                    self.interpolated_string(&capture.key);
+                    self.query(&capture.query.0);
+                    self.filters(&capture.filters);
+                }
+            }
+            ResponseSection::AssertsSection(section) => {
+                for assert in &section.asserts {
+                    self.query(&assert.query.0);
+                    self.filters(&assert.filters);
+                    self.predicate_func(&assert.predicate.function.0);
+                }
+            }
+            ResponseSection::UnknownSection(_) => {}
+        }
+    }
+
+    fn option(&mut self, option: &'a RequestOption) {
+        match option {
+            RequestOption::Compressed(option)
+            | RequestOption::Location(option)
+            | RequestOption::LocationTrusted(option)
+            | RequestOption::Http10(option)
+            | RequestOption::Http11(option)
+            | RequestOption::Http2(option)
+            | RequestOption::Http3(option)
+            | RequestOption::Insecure(option)
+            | RequestOption::Ipv4(option)
+            | RequestOption::Ipv6(option)
+            | RequestOption::Netrc(option)
+            | RequestOption::NetrcOptional(option)
+            | RequestOption::PathAsIs(option)
+            | RequestOption::Skip(option)
+            | RequestOption::Verbose(option)
+            | RequestOption::VeryVerbose(option) => {
+                if let BooleanOption::Template(template) = option {
+                    self.template(template);
+                }
+            }
+            RequestOption::ConnectTimeout(option)
+            | RequestOption::Delay(option)
+            | RequestOption::RetryInterval(option) => {
+                if let DurationOption::Template(template) = option {
+                    self.template(template);
+                }
+            }
+            RequestOption::LimitRate(option)
+            | RequestOption::MaxRedirs(option)
+            | RequestOption::Repeat(option)
+            | RequestOption::Retry(option) => {
+                if let IntegerOption::Template(template) = option {
+                    self.template(template);
+                }
+            }
+            RequestOption::Cacert(value)
+            | RequestOption::Key(value)
+            | RequestOption::Output(value)
+            | RequestOption::Cert(value)
+            | RequestOption::AwsSigv4(value)
+            | RequestOption::ConnectTo(value)
+            | RequestOption::NetrcFile(value)
+            | RequestOption::Proxy(value)
+            | RequestOption::Resolve(value)
+            | RequestOption::UnixSocket(value)
+            | RequestOption::User(value) => self.interpolated_string(value),
+            RequestOption::Variable(variable) => {
+                if let VariableValue::String(value) = &variable.value.0 {
+                    self.interpolated_string(value);
+                }
+            }
+        }
+    }
+
+    fn query(&mut self, query: &'a Query) {
+        match query {
+            Query::Header(value)
+            | Query::Cookie(value)
+            | Query::Xpath(value)
+            | Query::JsonPath(value)
+            | Query::Variable(value) => self.interpolated_string(value),
+            Query::Regex(regex) => self.regex(regex),
+            Query::Status
+            | Query::Url
+            | Query::Certificate(_)
+            | Query::Body
+            | Query::Duration
+            | Query::Bytes
+            | Query::Sha256
+            | Query::Md5 => {}
+        }
+    }
+
+    fn filters(&mut self, filters: &'a [Spanned<FilterFunction>]) {
+        for (filter, _) in filters {
+            match filter {
+                FilterFunction::Decode { encoding: value }
+                | FilterFunction::Format { fmt: value }
+                | FilterFunction::JsonPath { expr: value }
+                | FilterFunction::Split { sep: value }
+                | FilterFunction::ToDate { fmt: value }
+                | FilterFunction::XPath { expr: value } => self.interpolated_string(value),
+                FilterFunction::Regex { value } => self.regex(value),
+                FilterFunction::Replace {
+                    old_value,
+                    new_value,
+                } => {
+                    self.regex(old_value);
+                    self.interpolated_string(new_value);
+                }
+                FilterFunction::Count
+                | FilterFunction::DaysAfterNow
+                | FilterFunction::DaysBeforeNow
+                | FilterFunction::HtmlEscape
+                | FilterFunction::HtmlUnescape
+                | FilterFunction::Nth { .. }
+                | FilterFunction::ToFloat
+                | FilterFunction::ToInt
+                | FilterFunction::UrlDecode
+                | FilterFunction::UrlEncode => {}
+            }
+        }
+    }
+
+    fn predicate_func(&mut self, function: &'a PredicateFunc) {
+        match function {
+            PredicateFunc::Equal { value }
+            | PredicateFunc::NotEqual { value }
+            | PredicateFunc::Greater { value }
+            | PredicateFunc::GreaterOrEqual { value }
+            | PredicateFunc::Less { value }
+            | PredicateFunc::LessOrEqual { value }
+            | PredicateFunc::StartWith { value }
+            | PredicateFunc::EndWith { value }
+            | PredicateFunc::Contain { value }
+            | PredicateFunc::Match { value }
+            | PredicateFunc::Include { value } => self.predicate_value(&value.0),
+            PredicateFunc::Exists
+            | PredicateFunc::IsEmpty
+            | PredicateFunc::IsInteger
+            | PredicateFunc::IsFloat
+            | PredicateFunc::IsBoolean
+            | PredicateFunc::IsString
+            | PredicateFunc::IsCollection
+            | PredicateFunc::IsDate
+            | PredicateFunc::IsIsoDate
+            | PredicateFunc::Invalid => {}
+        }
+    }
+
+    fn predicate_value(&mut self, value: &'a PredicateValue) {
+        match value {
+            PredicateValue::OneLineFile(value)
+            | PredicateValue::OneLineString(value)
+            | PredicateValue::QuotedString(value) => self.interpolated_string(value),
+            PredicateValue::MultilineString(value) => self.multiline_string(value),
+            PredicateValue::Regex(regex) => self.regex(regex),
+            PredicateValue::Template(template) => self.template(template),
+            PredicateValue::Invalid
+            | PredicateValue::Boolean(_)
+            | PredicateValue::Null
+            | PredicateValue::Integer(_)
+            | PredicateValue::Float(_)
+            | PredicateValue::BigInteger(_)
+            | PredicateValue::OneLineBase64(_)
+            | PredicateValue::OneLineHex(_) => {}
+        }
+    }
+
+    fn body(&mut self, body: &'a Body) {
+        match &body.bytes {
+            Bytes::JsonValue(json) => self.json(json),
+            Bytes::MultilineString(value) => self.multiline_string(value),
+            Bytes::OneLineString(value) | Bytes::OneLineFile(value) => {
+                self.interpolated_string(value)
+            }
+            Bytes::OneLineBase64(_) | Bytes::OneLineHex(_) => {}
+        }
+    }
+
+    fn json(&mut self, json: &'a Json) {
+        match json {
+            Json::Object(key_values) => {
+                for key_value in key_values {
+                    self.interpolated_string(&key_value.key);
+                    self.json(&key_value.value.0);
+                }
+            }
+            Json::Array(values) => {
+                for (value, _) in values {
+                    self.json(value);
+                }
+            }
+            Json::InterpolatedString(value) => self.interpolated_string(value),
+            Json::Template(template) => self.template(template),
+            Json::Invalid | Json::Str(_) | Json::Num(_) | Json::Bool(_) | Json::Null => {}
+        }
+    }
+
+    fn multiline_string(&mut self, value: &'a MultilineString) {
+        self.interpolated_string(&value.content);
+    }
+
+    fn regex(&mut self, regex: &'a Regex) {
+        if let Regex::Interpolated(value) = regex {
+            self.interpolated_string(value);
+        }
+    }
+
+    fn key_values(&mut self, key_values: &'a [KeyValue]) {
+        for key_value in key_values {
+            self.key_value(key_value);
+        }
+    }
+
+    fn key_value(&mut self, key_value: &'a KeyValue) {
+        self.interpolated_string(&key_value.key);
+        self.interpolated_string(&key_value.value);
+    }
+
+    fn interpolated_string(&mut self, value: &'a InterpolatedString) {
+        for part in &value.parts {
+            if let InterpolatedStringPart::Template(template) = part {
+                self.template(template);
+            }
+        }
+    }
+
+    fn template(&mut self, template: &'a Template) {
+        self.templates.push(template);
+        self.filters(&template.expr.filters);
+    }
+}
+
+#[cfg(test)]
+mod visit_tests {
+    use super::*;
+    use crate::parser::parser::parse_ast;
+    use crate::parser::types::ExprValue;
+    use insta::assert_debug_snapshot;
+
+    #[test]
+    fn it_finds_templates_everywhere() {
+        let test_str = r#"POST https://{{host}}/users
+Authorization: Bearer {{token}}
+[Query]
+page: {{page}}
+[Options]
+retry: {{retries}}
+variable: name={{prefix}}-bob
+{"name": "{{name}}", "age": {{age}}}
+HTTP 200
+[Captures]
+id: jsonpath "{{path}}" split "{{sep}}"
+[Asserts]
+header "{{header}}" == {{expected}}
+body contains "{{text}}"
+"#;
+        let (ast, errs) = parse_ast(test_str);
+        assert_eq!(errs, vec![]);
+        let ast = ast.unwrap();
+        assert_debug_snapshot!(
+            templates(&ast)
+                .iter()
+                .map(|template| match &template.expr.variable {
+                    (ExprValue::VariableName(name), span) => (name.as_str(), *span),
+                    (ExprValue::FunctionName(name), span) => (name.as_str(), *span),
+                })
+                .collect::<Vec<_>>(),
+            @r#"
+        [
+            (
+                "host",
+                15..19,
+            ),
+            (
+                "token",
+                52..57,
+            ),
+            (
+                "page",
+                76..80,
+            ),
+            (
+                "retries",
+                102..109,
+            ),
+            (
+                "prefix",
+                129..135,
+            ),
+            (
+                "name",
+                154..158,
+            ),
+            (
+                "age",
+                172..175,
+            ),
+            (
+                "path",
+                215..219,
+            ),
+            (
+                "sep",
+                232..235,
+            ),
+            (
+                "header",
+                259..265,
+            ),
+            (
+                "expected",
+                274..282,
+            ),
+            (
+                "text",
+                302..306,
+            ),
+        ]
+        "#,
+        );
+    }
+}